@@ -0,0 +1,142 @@
+//! Lints SNS configuration files (the `sns_init.yaml` format) with the same
+//! validators the token creation flow uses.
+//!
+//! Usage: `sns-validate [--json] <config.yaml | config.json | ->`
+//!
+//! Exits with 0 if the configuration is valid, 1 if it has defects and 2 if it
+//! could not be read.
+
+use std::{io::Read, path::Path, process::ExitCode};
+
+use sns_validation::{
+    config::{ConfigDefect, SnsConfigurationFile},
+    SnsValidationErrorCode,
+};
+
+const USAGE: &str = "Usage: sns-validate [--json] <config.yaml | config.json | ->";
+
+#[derive(serde::Serialize)]
+struct Report<'a> {
+    file: &'a str,
+    valid: bool,
+    errors: &'a [ReportError],
+}
+
+#[derive(serde::Serialize)]
+struct ReportError {
+    /// `parse` or a [sns_validation::config::ValidationStage]
+    stage: String,
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<SnsValidationErrorCode>,
+    message: String,
+}
+
+impl From<ConfigDefect> for ReportError {
+    fn from(defect: ConfigDefect) -> Self {
+        let stage = serde_json::to_value(defect.stage)
+            .ok()
+            .and_then(|stage| stage.as_str().map(str::to_string))
+            .unwrap_or_default();
+        Self {
+            stage,
+            path: defect.path,
+            code: defect.code,
+            message: defect.message,
+        }
+    }
+}
+
+fn read_input(file: &str) -> std::io::Result<String> {
+    if file == "-" {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input)?;
+        return Ok(input);
+    }
+    std::fs::read_to_string(file)
+}
+
+fn parse_config(file: &str, input: &str) -> Result<SnsConfigurationFile, String> {
+    let is_json = Path::new(file)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    if is_json {
+        serde_json::from_str(input)
+            .map_err(|err| format!("Unable to parse configuration file: {}", err))
+    } else {
+        // YAML is a superset of JSON, so this also covers JSON piped through stdin.
+        SnsConfigurationFile::from_yaml(input)
+    }
+}
+
+fn main() -> ExitCode {
+    let mut json = false;
+    let mut file = None;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--json" => json = true,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            _ if file.is_none() => file = Some(arg),
+            _ => {
+                eprintln!("{USAGE}");
+                return ExitCode::from(2);
+            }
+        }
+    }
+    let Some(file) = file else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+
+    let input = match read_input(&file) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Unable to read {file}: {err}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let errors: Vec<ReportError> = match parse_config(&file, &input) {
+        Ok(config) => config.validate().into_iter().map(Into::into).collect(),
+        Err(message) => vec![ReportError {
+            stage: "parse".to_string(),
+            path: String::new(),
+            code: None,
+            message,
+        }],
+    };
+
+    if json {
+        let report = Report {
+            file: &file,
+            valid: errors.is_empty(),
+            errors: &errors,
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("report must serialize")
+        );
+    } else if errors.is_empty() {
+        println!("{file}: OK");
+    } else {
+        for error in &errors {
+            let path = if error.path.is_empty() {
+                "<config>"
+            } else {
+                &error.path
+            };
+            // Validator messages may span several lines, keep them under their field.
+            let message = error.message.replace('\n', "\n    ");
+            println!("{file}: [{}] {path}: {message}", error.stage);
+        }
+    }
+
+    if errors.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -2,13 +2,15 @@ use candid::Principal;
 use std::{fmt::Debug, str::FromStr};
 
 use crate::{
+    consts::ONE_DAY_SECONDS,
     humanize,
     pbs::{
         gov_pb::CreateServiceNervousSystem,
         nns_pb::{self, GlobalTimeOfDay, Image},
-        sns_pb::SnsInitPayload,
+        sns_pb::{self, SnsInitPayload},
         ExecutedCreateServiceNervousSystemProposal,
     },
+    validation::sns_init::{SnsValidationError, SnsValidationErrorCode},
 };
 use web_time::{SystemTime, UNIX_EPOCH};
 
@@ -307,6 +309,33 @@ impl SnsConfigurationFile {
     pub fn try_convert_to_create_service_nervous_system(
         &self,
     ) -> Result<CreateServiceNervousSystem, String> {
+        let result = self
+            .convert_to_create_service_nervous_system_unvalidated()
+            .map_err(|defects| {
+                format!(
+                    "Unable to convert configuration file to proposal for the following \
+                     reason(s):\n  -{}",
+                    defects
+                        .into_iter()
+                        .map(|defect| defect.message)
+                        .collect::<Vec<_>>()
+                        .join("\n  -"),
+                )
+            })?;
+
+        if let Err(err) = SnsInitPayload::try_from(result.clone()) {
+            return Err(format!(
+                "Unable to convert configuration file to proposal: {}",
+                err,
+            ));
+        }
+
+        Ok(result)
+    }
+
+    fn convert_to_create_service_nervous_system_unvalidated(
+        &self,
+    ) -> Result<CreateServiceNervousSystem, Vec<ConfigDefect>> {
         // Step 1: Unpack.
         let SnsConfigurationFile {
             name,
@@ -344,12 +373,19 @@ impl SnsConfigurationFile {
                 "fallback_controller_principals",
                 fallback_controller_principals,
             )
-            .map_err(|inner_defects| defects.extend(inner_defects))
+            .map_err(|inner_defects| {
+                defects.extend(ConfigDefect::conversion(
+                    "fallback_controller_principals",
+                    inner_defects,
+                ))
+            })
             .unwrap_or_default();
 
         let dapp_canisters = alias_to_principal_id
             .unalias("dapp_canisters", dapp_canisters)
-            .map_err(|inner_defects| defects.extend(inner_defects))
+            .map_err(|inner_defects| {
+                defects.extend(ConfigDefect::conversion("dapp_canisters", inner_defects))
+            })
             .unwrap_or_default();
 
         // Wrap in Canister.
@@ -365,7 +401,9 @@ impl SnsConfigurationFile {
         let initial_token_distribution = Some(
             distribution
                 .try_convert_to_initial_token_distribution()
-                .map_err(|inner_defects| defects.extend(inner_defects))
+                .map_err(|inner_defects| {
+                    defects.extend(ConfigDefect::conversion("Distribution", inner_defects))
+                })
                 .unwrap_or_default(),
         );
         let swap_parameters = Some(swap.convert_to_swap_parameters());
@@ -391,19 +429,9 @@ impl SnsConfigurationFile {
             governance_parameters,
         };
 
-        // Step 4: Validate.
+        // Step 4: Report defects, validation is left to the caller.
         if !defects.is_empty() {
-            return Err(format!(
-                "Unable to convert configuration file to proposal for the following \
-                 reason(s):\n  -{}",
-                defects.join("\n  -"),
-            ));
-        }
-        if let Err(err) = SnsInitPayload::try_from(result.clone()) {
-            return Err(format!(
-                "Unable to convert configuration file to proposal: {}",
-                err,
-            ));
+            return Err(defects);
         }
 
         // Step 5: Ship it!
@@ -412,7 +440,47 @@ impl SnsConfigurationFile {
 
     pub fn try_convert_to_executed_sns_init(&self) -> Result<SnsInitPayload, String> {
         let create_sns = self.try_convert_to_create_service_nervous_system()?;
-        let executed_create_sns = ExecutedCreateServiceNervousSystemProposal {
+        SnsInitPayload::try_from(Self::execute(create_sns))
+    }
+
+    /// Runs every validator, as if the configuration was proposed and then executed
+    ///
+    /// Unlike the conversions, this reports all defects of a stage at once, each
+    /// addressed by its path in the configuration file.
+    /// Later stages are only run if the earlier ones pass.
+    pub fn validate(&self) -> Vec<ConfigDefect> {
+        let create_sns = match self.convert_to_create_service_nervous_system_unvalidated() {
+            Ok(create_sns) => create_sns,
+            Err(defects) => return defects,
+        };
+
+        let payload = match SnsInitPayload::try_from_unvalidated(create_sns.clone()) {
+            Ok(payload) => payload,
+            Err(err) => return ConfigDefect::conversion("", [err]),
+        };
+        let errors = payload.pre_execution_errors();
+        if !errors.is_empty() {
+            return ConfigDefect::from_validation_errors(ValidationStage::PreExecution, errors);
+        }
+
+        match SnsInitPayload::try_from_executed_unvalidated(Self::execute(create_sns)) {
+            Ok(payload) => ConfigDefect::from_validation_errors(
+                ValidationStage::PostExecution,
+                payload.post_execution_errors(),
+            ),
+            Err(err) => vec![ConfigDefect {
+                stage: ValidationStage::PostExecution,
+                path: String::new(),
+                code: None,
+                message: err,
+            }],
+        }
+    }
+
+    fn execute(
+        create_sns: CreateServiceNervousSystem,
+    ) -> ExecutedCreateServiceNervousSystemProposal {
+        ExecutedCreateServiceNervousSystemProposal {
             current_timestamp_seconds: SystemTime::now()
                 .duration_since(UNIX_EPOCH)
                 .unwrap()
@@ -425,9 +493,126 @@ impl SnsConfigurationFile {
             // `proposal_id` only exists to be exposed to the user for audit purposes, which don't apply here.
             // But it's required, so we can just use any arbitrary value.
             proposal_id: 10,
-        };
+        }
+    }
+}
+
+/// Stage of [SnsConfigurationFile::validate] a defect was found in
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationStage {
+    /// Converting the file to a proposal
+    Conversion,
+    /// Validating the proposal before it is executed
+    PreExecution,
+    /// Validating the SNS init payload after the proposal is executed
+    PostExecution,
+}
+
+/// A defect in a configuration file
+#[derive(serde::Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ConfigDefect {
+    pub stage: ValidationStage,
+    /// Path of the offending field in the configuration file,
+    /// e.g. `Swap.VestingSchedule.events`. Empty if the defect is not tied to a field
+    pub path: String,
+    /// Set for defects found by the SNS validators, conversion defects have no code
+    pub code: Option<SnsValidationErrorCode>,
+    pub message: String,
+}
+
+impl ConfigDefect {
+    fn conversion(path: &str, messages: impl IntoIterator<Item = String>) -> Vec<Self> {
+        messages
+            .into_iter()
+            .map(|message| Self {
+                stage: ValidationStage::Conversion,
+                path: path.to_string(),
+                code: None,
+                message,
+            })
+            .collect()
+    }
 
-        SnsInitPayload::try_from(executed_create_sns)
+    fn from_validation_errors(
+        stage: ValidationStage,
+        errors: Vec<SnsValidationError>,
+    ) -> Vec<Self> {
+        errors
+            .into_iter()
+            .map(|error| Self {
+                stage,
+                path: config_path(&error.path).to_string(),
+                code: Some(error.code),
+                message: error.message,
+            })
+            .collect()
+    }
+}
+
+/// Maps a [SnsValidationError::path] to the configuration file field it comes from
+fn config_path(payload_path: &str) -> &'static str {
+    match payload_path {
+        "metadata.name" => "name",
+        "metadata.description" => "description",
+        "metadata.logo" => "logo_b64",
+        "metadata.url" => "url",
+        "fallback_controller_principal_ids" => "fallback_controller_principals",
+        "dapp_canisters" => "dapp_canisters",
+        "token.name" => "Token.name",
+        "token.symbol" => "Token.symbol",
+        "token.logo" => "Token.logo_b64",
+        "token.transaction_fee_e8s" => "Token.transaction_fee",
+        "governance.proposal_reject_cost_e8s" => "Proposals.rejection_fee",
+        "governance.initial_voting_period_seconds" => "Proposals.initial_voting_period",
+        "governance.wait_for_quiet_deadline_increase_seconds" => {
+            "Proposals.maximum_wait_for_quiet_deadline_extension"
+        }
+        "governance.neuron_minimum_stake_e8s" => "Neurons.minimum_creation_stake",
+        "governance.neuron_minimum_dissolve_delay_to_vote_seconds" => {
+            "Voting.minimum_dissolve_delay"
+        }
+        "governance.max_dissolve_delay_seconds" => {
+            "Voting.MaximumVotingPowerBonuses.DissolveDelay.duration"
+        }
+        "governance.max_dissolve_delay_bonus_percentage" => {
+            "Voting.MaximumVotingPowerBonuses.DissolveDelay.bonus"
+        }
+        "governance.max_neuron_age_seconds_for_age_bonus" => {
+            "Voting.MaximumVotingPowerBonuses.Age.duration"
+        }
+        "governance.max_age_bonus_percentage" => "Voting.MaximumVotingPowerBonuses.Age.bonus",
+        "governance.initial_reward_rate_basis_points" => "Voting.RewardRate.initial",
+        "governance.final_reward_rate_basis_points" => "Voting.RewardRate.final",
+        "governance.reward_rate_transition_duration_seconds" => {
+            "Voting.RewardRate.transition_duration"
+        }
+        "distribution.developer_distribution.developer_neurons" => "Distribution.Neurons",
+        "distribution.treasury_distribution" => "Distribution.InitialBalances.governance",
+        "distribution.swap_distribution"
+        | "distribution.swap_distribution.initial_swap_amount_e8s"
+        | "distribution.swap_distribution.total_e8s" => "Distribution.InitialBalances.swap",
+        "swap.min_participants" => "Swap.minimum_participants",
+        "swap.min_icp_e8s" => "Swap.minimum_icp",
+        "swap.max_icp_e8s" => "Swap.maximum_icp",
+        "swap.min_direct_participation_icp_e8s" => "Swap.minimum_direct_participation_icp",
+        "swap.max_direct_participation_icp_e8s" => "Swap.maximum_direct_participation_icp",
+        "swap.min_participant_icp_e8s" => "Swap.minimum_participant_icp",
+        "swap.max_participant_icp_e8s" => "Swap.maximum_participant_icp",
+        "swap.confirmation_text" => "Swap.confirmation_text",
+        "swap.restricted_countries" => "Swap.restricted_countries",
+        "swap.neuron_basket_construction_parameters" => "Swap.VestingSchedule",
+        "swap.neuron_basket_construction_parameters.count" => "Swap.VestingSchedule.events",
+        "swap.neuron_basket_construction_parameters.dissolve_delay_interval_seconds" => {
+            "Swap.VestingSchedule.interval"
+        }
+        "swap.start_timestamp_seconds" => "Swap.start_time",
+        "swap.due_timestamp_seconds" => "Swap.duration",
+        "swap" => "Swap",
+        p if p.starts_with("swap.neurons_fund_") => "Swap.neurons_fund_participation",
+        p if p.starts_with("distribution") => "Distribution",
+        // Only set when the proposal is executed
+        _ => "",
     }
 }
 
@@ -771,3 +956,775 @@ impl VestingSchedule {
         }
     }
 }
+
+// Reverse conversion, i.e. from a (deployed) SNS back to the configuration
+// file format, so that it can be edited and reused for a new launch.
+
+fn required<T>(defects: &mut Vec<String>, field_name: &str, value: Option<T>) -> Option<T> {
+    if value.is_none() {
+        defects.push(format!("{} is missing.", field_name));
+    }
+    value
+}
+
+fn tokens(e8s: u64) -> nns_pb::Tokens {
+    nns_pb::Tokens { e8s: Some(e8s) }
+}
+
+fn duration(seconds: u64) -> nns_pb::Duration {
+    nns_pb::Duration {
+        seconds: Some(seconds),
+    }
+}
+
+fn basis_points(basis_points: u64) -> nns_pb::Percentage {
+    nns_pb::Percentage::from_basis_points(basis_points)
+}
+
+impl SnsConfigurationFile {
+    /// Parses a configuration file in the `sns_init.yaml` format
+    pub fn from_yaml(yaml: &str) -> Result<Self, String> {
+        serde_yaml::from_str(yaml)
+            .map_err(|err| format!("Unable to parse configuration file: {}", err))
+    }
+
+    /// Serializes to the `sns_init.yaml` format, using the `humanize` formatters
+    pub fn to_yaml(&self) -> Result<String, String> {
+        serde_yaml::to_string(self)
+            .map_err(|err| format!("Unable to serialize configuration file: {}", err))
+    }
+
+    /// Inverse of [Self::try_convert_to_create_service_nervous_system]
+    /// `nns_proposal` is not part of the proposal payload, so it must be supplied
+    pub fn try_from_create_service_nervous_system(
+        src: &CreateServiceNervousSystem,
+        nns_proposal: NnsProposal,
+    ) -> Result<Self, String> {
+        let CreateServiceNervousSystem {
+            name,
+            description,
+            url,
+            logo,
+            fallback_controller_principal_ids,
+            dapp_canisters,
+            initial_token_distribution,
+            swap_parameters,
+            ledger_parameters,
+            governance_parameters,
+        } = src;
+
+        let mut defects = vec![];
+
+        let name = required(&mut defects, "name", name.clone()).unwrap_or_default();
+        let description =
+            required(&mut defects, "description", description.clone()).unwrap_or_default();
+        let url = required(&mut defects, "url", url.clone()).unwrap_or_default();
+        let logo_b64 = logo
+            .as_ref()
+            .and_then(|image| image.base64_encoding.clone())
+            .unwrap_or_default();
+
+        let fallback_controller_principals = fallback_controller_principal_ids
+            .iter()
+            .map(|principal_id| principal_id.to_string())
+            .collect();
+        let dapp_canisters = dapp_canisters
+            .iter()
+            .filter_map(|canister| canister.id)
+            .map(|principal_id| principal_id.to_string())
+            .collect();
+
+        let token =
+            Token::try_from_ledger_parameters(&ledger_parameters.clone().unwrap_or_default())
+                .map_err(|inner_defects| defects.extend(inner_defects))
+                .ok();
+        let governance = try_convert_from_governance_parameters(
+            &governance_parameters.clone().unwrap_or_default(),
+        )
+        .map_err(|inner_defects| defects.extend(inner_defects))
+        .ok();
+        let distribution = Distribution::try_from_initial_token_distribution(
+            &initial_token_distribution.clone().unwrap_or_default(),
+        )
+        .map_err(|inner_defects| defects.extend(inner_defects))
+        .ok();
+        let swap = Swap::try_from_swap_parameters(&swap_parameters.clone().unwrap_or_default())
+            .map_err(|inner_defects| defects.extend(inner_defects))
+            .ok();
+
+        let (Some(token), Some((proposals, neurons, voting)), Some(distribution), Some(swap), true) =
+            (token, governance, distribution, swap, defects.is_empty())
+        else {
+            return Err(format!(
+                "Unable to convert proposal to configuration file for the following \
+                 reason(s):\n  -{}",
+                defects.join("\n  -"),
+            ));
+        };
+
+        Ok(SnsConfigurationFile {
+            name,
+            description,
+            logo_b64,
+            url,
+            principals: vec![],
+            fallback_controller_principals,
+            dapp_canisters,
+            token,
+            proposals,
+            neurons,
+            voting,
+            distribution,
+            swap,
+            nns_proposal,
+        })
+    }
+
+    /// Recovers the configuration file of an SNS from its init payload
+    ///
+    /// The swap start time of day and duration are derived from the swap
+    /// timestamps, so the payload must have been executed.
+    pub fn try_from_sns_init_payload(
+        src: &SnsInitPayload,
+        nns_proposal: NnsProposal,
+    ) -> Result<Self, String> {
+        let create_sns = try_convert_from_sns_init_payload(src)?;
+        Self::try_from_create_service_nervous_system(&create_sns, nns_proposal)
+    }
+}
+
+fn try_convert_from_sns_init_payload(
+    src: &SnsInitPayload,
+) -> Result<CreateServiceNervousSystem, String> {
+    let SnsInitPayload {
+        transaction_fee_e8s,
+        token_name,
+        token_symbol,
+        proposal_reject_cost_e8s,
+        neuron_minimum_stake_e8s,
+        fallback_controller_principal_ids,
+        logo,
+        url,
+        name,
+        description,
+        neuron_minimum_dissolve_delay_to_vote_seconds,
+        initial_reward_rate_basis_points,
+        final_reward_rate_basis_points,
+        reward_rate_transition_duration_seconds,
+        max_dissolve_delay_seconds,
+        max_neuron_age_seconds_for_age_bonus,
+        max_dissolve_delay_bonus_percentage,
+        max_age_bonus_percentage,
+        initial_voting_period_seconds,
+        wait_for_quiet_deadline_increase_seconds,
+        confirmation_text,
+        restricted_countries,
+        dapp_canisters,
+        min_participants,
+        min_icp_e8s: _,
+        max_icp_e8s: _,
+        min_direct_participation_icp_e8s,
+        max_direct_participation_icp_e8s,
+        min_participant_icp_e8s,
+        max_participant_icp_e8s,
+        swap_start_timestamp_seconds,
+        swap_due_timestamp_seconds,
+        neuron_basket_construction_parameters,
+        nns_proposal_id: _,
+        neurons_fund_participation,
+        neurons_fund_participants: _,
+        token_logo,
+        neurons_fund_participation_constraints: _,
+        initial_token_distribution,
+    } = src;
+
+    let mut defects = vec![];
+
+    let fallback_controller_principal_ids = fallback_controller_principal_ids
+        .iter()
+        .filter_map(|principal_id| {
+            Principal::from_str(principal_id)
+                .map_err(|err| {
+                    defects.push(format!(
+                        "Unable to parse PrincipalId ({:?}) in \
+                         fallback_controller_principal_ids. Reason: {}",
+                        principal_id, err,
+                    ))
+                })
+                .ok()
+        })
+        .collect();
+
+    let initial_token_distribution = match initial_token_distribution {
+        Some(
+            sns_pb::sns_init_payload::InitialTokenDistribution::FractionalDeveloperVotingPower(
+                distribution,
+            ),
+        ) => {
+            let sns_pb::FractionalDeveloperVotingPower {
+                developer_distribution,
+                treasury_distribution,
+                swap_distribution,
+                airdrop_distribution,
+            } = distribution;
+
+            if airdrop_distribution
+                .as_ref()
+                .is_some_and(|airdrop| !airdrop.airdrop_neurons.is_empty())
+            {
+                defects.push(
+                    "airdrop_distribution is not supported by the configuration file format."
+                        .to_string(),
+                );
+            }
+
+            let developer_neurons = developer_distribution
+                .iter()
+                .flat_map(|distribution| distribution.developer_neurons.iter())
+                .map(|neuron| nns_governance_pb::NeuronDistribution {
+                    controller: neuron.controller,
+                    dissolve_delay: Some(duration(neuron.dissolve_delay_seconds)),
+                    memo: Some(neuron.memo),
+                    stake: Some(tokens(neuron.stake_e8s)),
+                    vesting_period: Some(duration(
+                        neuron.vesting_period_seconds.unwrap_or_default(),
+                    )),
+                })
+                .collect();
+
+            Some(nns_governance_pb::InitialTokenDistribution {
+                developer_distribution: Some(nns_governance_pb::DeveloperDistribution {
+                    developer_neurons,
+                }),
+                treasury_distribution: treasury_distribution.as_ref().map(|treasury| {
+                    nns_governance_pb::TreasuryDistribution {
+                        total: Some(tokens(treasury.total_e8s)),
+                    }
+                }),
+                swap_distribution: swap_distribution.as_ref().map(|swap| {
+                    nns_governance_pb::SwapDistribution {
+                        total: Some(tokens(swap.total_e8s)),
+                    }
+                }),
+            })
+        }
+        None => None,
+    };
+
+    // Only executed payloads know when the swap starts and ends.
+    let (start_time, swap_duration) =
+        match (swap_start_timestamp_seconds, swap_due_timestamp_seconds) {
+            (Some(start), Some(due)) => (
+                Some(GlobalTimeOfDay {
+                    seconds_after_utc_midnight: Some(start % ONE_DAY_SECONDS),
+                }),
+                Some(duration(due.saturating_sub(*start))),
+            ),
+            _ => (None, None),
+        };
+
+    let neuron_basket_construction_parameters =
+        neuron_basket_construction_parameters
+            .as_ref()
+            .map(
+                |basket| nns_governance_pb::NeuronBasketConstructionParameters {
+                    count: Some(basket.count),
+                    dissolve_delay_interval: Some(duration(basket.dissolve_delay_interval_seconds)),
+                },
+            );
+
+    if !defects.is_empty() {
+        return Err(format!(
+            "Unable to convert SnsInitPayload to proposal for the following \
+             reason(s):\n  -{}",
+            defects.join("\n  -"),
+        ));
+    }
+
+    Ok(CreateServiceNervousSystem {
+        name: name.clone(),
+        description: description.clone(),
+        url: url.clone(),
+        logo: logo.clone().map(|logo| Image {
+            base64_encoding: Some(logo),
+        }),
+
+        fallback_controller_principal_ids,
+        dapp_canisters: dapp_canisters
+            .as_ref()
+            .map(|dapp_canisters| dapp_canisters.canisters.clone())
+            .unwrap_or_default(),
+
+        initial_token_distribution,
+        swap_parameters: Some(nns_governance_pb::SwapParameters {
+            minimum_participants: *min_participants,
+            minimum_icp: None,
+            maximum_icp: None,
+            minimum_direct_participation_icp: min_direct_participation_icp_e8s.map(tokens),
+            maximum_direct_participation_icp: max_direct_participation_icp_e8s.map(tokens),
+            minimum_participant_icp: min_participant_icp_e8s.map(tokens),
+            maximum_participant_icp: max_participant_icp_e8s.map(tokens),
+            neuron_basket_construction_parameters,
+            confirmation_text: confirmation_text.clone(),
+            restricted_countries: restricted_countries.clone(),
+            start_time,
+            duration: swap_duration,
+            neurons_fund_investment_icp: None,
+            neurons_fund_participation: *neurons_fund_participation,
+        }),
+        ledger_parameters: Some(nns_governance_pb::LedgerParameters {
+            transaction_fee: transaction_fee_e8s.map(tokens),
+            token_name: token_name.clone(),
+            token_symbol: token_symbol.clone(),
+            token_logo: token_logo.clone().map(|logo| Image {
+                base64_encoding: Some(logo),
+            }),
+        }),
+        governance_parameters: Some(nns_governance_pb::GovernanceParameters {
+            proposal_rejection_fee: proposal_reject_cost_e8s.map(tokens),
+            proposal_initial_voting_period: initial_voting_period_seconds.map(duration),
+            proposal_wait_for_quiet_deadline_increase: wait_for_quiet_deadline_increase_seconds
+                .map(duration),
+            neuron_minimum_stake: neuron_minimum_stake_e8s.map(tokens),
+            neuron_minimum_dissolve_delay_to_vote: neuron_minimum_dissolve_delay_to_vote_seconds
+                .map(duration),
+            neuron_maximum_dissolve_delay: max_dissolve_delay_seconds.map(duration),
+            // SnsInitPayload stores these as whole percentages.
+            neuron_maximum_dissolve_delay_bonus: max_dissolve_delay_bonus_percentage
+                .map(|percentage| basis_points(percentage.saturating_mul(100))),
+            neuron_maximum_age_for_age_bonus: max_neuron_age_seconds_for_age_bonus.map(duration),
+            neuron_maximum_age_bonus: max_age_bonus_percentage
+                .map(|percentage| basis_points(percentage.saturating_mul(100))),
+            voting_reward_parameters: Some(nns_governance_pb::VotingRewardParameters {
+                initial_reward_rate: initial_reward_rate_basis_points.map(basis_points),
+                final_reward_rate: final_reward_rate_basis_points.map(basis_points),
+                reward_rate_transition_duration: reward_rate_transition_duration_seconds
+                    .map(duration),
+            }),
+        }),
+    })
+}
+
+impl Token {
+    fn try_from_ledger_parameters(
+        src: &nns_governance_pb::LedgerParameters,
+    ) -> Result<Self, Vec<String>> {
+        let nns_governance_pb::LedgerParameters {
+            transaction_fee,
+            token_name,
+            token_symbol,
+            token_logo,
+        } = src;
+
+        let mut defects = vec![];
+
+        let name = required(
+            &mut defects,
+            "ledger_parameters.token_name",
+            token_name.clone(),
+        );
+        let symbol = required(
+            &mut defects,
+            "ledger_parameters.token_symbol",
+            token_symbol.clone(),
+        );
+        let transaction_fee = required(
+            &mut defects,
+            "ledger_parameters.transaction_fee",
+            *transaction_fee,
+        );
+        let logo_b64 = token_logo
+            .as_ref()
+            .and_then(|image| image.base64_encoding.clone())
+            .unwrap_or_default();
+
+        let (Some(name), Some(symbol), Some(transaction_fee)) = (name, symbol, transaction_fee)
+        else {
+            return Err(defects);
+        };
+
+        Ok(Token {
+            name,
+            symbol,
+            transaction_fee,
+            logo_b64,
+        })
+    }
+}
+
+fn try_convert_from_governance_parameters(
+    src: &nns_governance_pb::GovernanceParameters,
+) -> Result<(Proposals, Neurons, Voting), Vec<String>> {
+    let nns_governance_pb::GovernanceParameters {
+        proposal_rejection_fee,
+        proposal_initial_voting_period,
+        proposal_wait_for_quiet_deadline_increase,
+        neuron_minimum_stake,
+        neuron_minimum_dissolve_delay_to_vote,
+        neuron_maximum_dissolve_delay,
+        neuron_maximum_dissolve_delay_bonus,
+        neuron_maximum_age_for_age_bonus,
+        neuron_maximum_age_bonus,
+        voting_reward_parameters,
+    } = src;
+
+    let mut defects = vec![];
+
+    let rejection_fee = required(
+        &mut defects,
+        "governance_parameters.proposal_rejection_fee",
+        *proposal_rejection_fee,
+    );
+    let initial_voting_period = required(
+        &mut defects,
+        "governance_parameters.proposal_initial_voting_period",
+        *proposal_initial_voting_period,
+    );
+    let maximum_wait_for_quiet_deadline_extension = required(
+        &mut defects,
+        "governance_parameters.proposal_wait_for_quiet_deadline_increase",
+        *proposal_wait_for_quiet_deadline_increase,
+    );
+    let minimum_creation_stake = required(
+        &mut defects,
+        "governance_parameters.neuron_minimum_stake",
+        *neuron_minimum_stake,
+    );
+    let minimum_dissolve_delay = required(
+        &mut defects,
+        "governance_parameters.neuron_minimum_dissolve_delay_to_vote",
+        *neuron_minimum_dissolve_delay_to_vote,
+    );
+    let dissolve_delay_duration = required(
+        &mut defects,
+        "governance_parameters.neuron_maximum_dissolve_delay",
+        *neuron_maximum_dissolve_delay,
+    );
+    let age_duration = required(
+        &mut defects,
+        "governance_parameters.neuron_maximum_age_for_age_bonus",
+        *neuron_maximum_age_for_age_bonus,
+    );
+
+    let reward_rate = voting_reward_parameters.clone().unwrap_or_default();
+    let initial = required(
+        &mut defects,
+        "governance_parameters.voting_reward_parameters.initial_reward_rate",
+        reward_rate.initial_reward_rate,
+    );
+    let r#final = required(
+        &mut defects,
+        "governance_parameters.voting_reward_parameters.final_reward_rate",
+        reward_rate.final_reward_rate,
+    );
+    let transition_duration = required(
+        &mut defects,
+        "governance_parameters.voting_reward_parameters.reward_rate_transition_duration",
+        reward_rate.reward_rate_transition_duration,
+    );
+
+    let (
+        Some(rejection_fee),
+        Some(initial_voting_period),
+        Some(maximum_wait_for_quiet_deadline_extension),
+        Some(minimum_creation_stake),
+        Some(minimum_dissolve_delay),
+        Some(dissolve_delay_duration),
+        Some(age_duration),
+        Some(initial),
+        Some(r#final),
+        Some(transition_duration),
+    ) = (
+        rejection_fee,
+        initial_voting_period,
+        maximum_wait_for_quiet_deadline_extension,
+        minimum_creation_stake,
+        minimum_dissolve_delay,
+        dissolve_delay_duration,
+        age_duration,
+        initial,
+        r#final,
+        transition_duration,
+    )
+    else {
+        return Err(defects);
+    };
+
+    let proposals = Proposals {
+        rejection_fee,
+        initial_voting_period,
+        maximum_wait_for_quiet_deadline_extension,
+    };
+    let neurons = Neurons {
+        minimum_creation_stake,
+    };
+    let voting = Voting {
+        minimum_dissolve_delay,
+        maximum_voting_power_bonuses: MaximumVotingPowerBonuses {
+            dissolve_delay: Bonus {
+                duration: dissolve_delay_duration,
+                bonus: neuron_maximum_dissolve_delay_bonus.unwrap_or(basis_points(0)),
+            },
+            age: Bonus {
+                duration: age_duration,
+                bonus: neuron_maximum_age_bonus.unwrap_or(basis_points(0)),
+            },
+        },
+        reward_rate: RewardRate {
+            initial,
+            r#final,
+            transition_duration,
+        },
+    };
+
+    Ok((proposals, neurons, voting))
+}
+
+impl Distribution {
+    fn try_from_initial_token_distribution(
+        src: &nns_governance_pb::InitialTokenDistribution,
+    ) -> Result<Self, Vec<String>> {
+        let nns_governance_pb::InitialTokenDistribution {
+            developer_distribution,
+            treasury_distribution,
+            swap_distribution,
+        } = src;
+
+        let mut defects = vec![];
+
+        let neurons = developer_distribution
+            .iter()
+            .flat_map(|distribution| distribution.developer_neurons.iter())
+            .filter_map(|neuron| {
+                Neuron::try_from_neuron_distribution(neuron)
+                    .map_err(|inner_defects| defects.extend(inner_defects))
+                    .ok()
+            })
+            .collect::<Vec<_>>();
+
+        let governance = required(
+            &mut defects,
+            "initial_token_distribution.treasury_distribution.total",
+            treasury_distribution
+                .as_ref()
+                .and_then(|treasury| treasury.total),
+        );
+        let swap = required(
+            &mut defects,
+            "initial_token_distribution.swap_distribution.total",
+            swap_distribution.as_ref().and_then(|swap| swap.total),
+        );
+
+        let (Some(governance), Some(swap), true) = (governance, swap, defects.is_empty()) else {
+            return Err(defects);
+        };
+
+        let total_e8s = neurons
+            .iter()
+            .map(|neuron| neuron.stake.e8s.unwrap_or_default())
+            .chain([
+                governance.e8s.unwrap_or_default(),
+                swap.e8s.unwrap_or_default(),
+            ])
+            .fold(0u64, u64::saturating_add);
+
+        Ok(Distribution {
+            neurons,
+            initial_balances: InitialBalances { governance, swap },
+            total: tokens(total_e8s),
+        })
+    }
+}
+
+impl Neuron {
+    fn try_from_neuron_distribution(
+        src: &nns_governance_pb::NeuronDistribution,
+    ) -> Result<Self, Vec<String>> {
+        let nns_governance_pb::NeuronDistribution {
+            controller,
+            dissolve_delay,
+            memo,
+            stake,
+            vesting_period,
+        } = src;
+
+        let mut defects = vec![];
+
+        let controller = required(
+            &mut defects,
+            "developer_distribution.developer_neurons.controller",
+            *controller,
+        );
+        let stake = required(
+            &mut defects,
+            "developer_distribution.developer_neurons.stake",
+            *stake,
+        );
+        let dissolve_delay = required(
+            &mut defects,
+            "developer_distribution.developer_neurons.dissolve_delay",
+            *dissolve_delay,
+        );
+
+        let (Some(controller), Some(stake), Some(dissolve_delay)) =
+            (controller, stake, dissolve_delay)
+        else {
+            return Err(defects);
+        };
+
+        Ok(Neuron {
+            principal: controller.to_string(),
+            stake,
+            memo: memo.unwrap_or_default(),
+            dissolve_delay,
+            vesting_period: vesting_period.unwrap_or(duration(0)),
+        })
+    }
+}
+
+impl Swap {
+    fn try_from_swap_parameters(
+        src: &nns_governance_pb::SwapParameters,
+    ) -> Result<Self, Vec<String>> {
+        let nns_governance_pb::SwapParameters {
+            minimum_participants,
+            minimum_icp,
+            maximum_icp,
+            minimum_direct_participation_icp,
+            maximum_direct_participation_icp,
+            minimum_participant_icp,
+            maximum_participant_icp,
+            neuron_basket_construction_parameters,
+            confirmation_text,
+            restricted_countries,
+            start_time,
+            duration: swap_duration,
+            neurons_fund_investment_icp,
+            neurons_fund_participation,
+        } = src;
+
+        let mut defects = vec![];
+
+        let minimum_participants = required(
+            &mut defects,
+            "swap_parameters.minimum_participants",
+            *minimum_participants,
+        );
+        let minimum_participant_icp = required(
+            &mut defects,
+            "swap_parameters.minimum_participant_icp",
+            *minimum_participant_icp,
+        );
+        let maximum_participant_icp = required(
+            &mut defects,
+            "swap_parameters.maximum_participant_icp",
+            *maximum_participant_icp,
+        );
+        let basket = required(
+            &mut defects,
+            "swap_parameters.neuron_basket_construction_parameters",
+            neuron_basket_construction_parameters.clone(),
+        );
+        // Init payloads that have not been executed have no duration
+        let swap_duration = required(&mut defects, "swap_parameters.duration", *swap_duration);
+
+        let (
+            Some(minimum_participants),
+            Some(minimum_participant_icp),
+            Some(maximum_participant_icp),
+            Some(basket),
+            Some(swap_duration),
+        ) = (
+            minimum_participants,
+            minimum_participant_icp,
+            maximum_participant_icp,
+            basket,
+            swap_duration,
+        )
+        else {
+            return Err(defects);
+        };
+
+        let vesting_schedule = VestingSchedule {
+            events: basket.count.unwrap_or_default(),
+            interval: basket.dissolve_delay_interval.unwrap_or(duration(0)),
+        };
+
+        Ok(Swap {
+            minimum_participants,
+            minimum_icp: *minimum_icp,
+            maximum_icp: *maximum_icp,
+            minimum_direct_participation_icp: *minimum_direct_participation_icp,
+            maximum_direct_participation_icp: *maximum_direct_participation_icp,
+            minimum_participant_icp,
+            maximum_participant_icp,
+            confirmation_text: confirmation_text.clone(),
+            restricted_countries: restricted_countries
+                .as_ref()
+                .map(|countries| countries.iso_codes.clone()),
+            vesting_schedule,
+            start_time: *start_time,
+            duration: swap_duration,
+            neurons_fund_investment_icp: *neurons_fund_investment_icp,
+            neurons_fund_participation: *neurons_fund_participation,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_CONFIG: &str = include_str!("../test_sns_init_v2.yaml");
+
+    fn test_config() -> SnsConfigurationFile {
+        SnsConfigurationFile::from_yaml(TEST_CONFIG).unwrap()
+    }
+
+    #[test]
+    fn yaml_round_trip() {
+        let config = test_config();
+        let yaml = config.to_yaml().unwrap();
+        assert_eq!(SnsConfigurationFile::from_yaml(&yaml).unwrap(), config);
+    }
+
+    #[test]
+    fn proposal_round_trip() {
+        let config = test_config();
+        let proposal = config
+            .try_convert_to_create_service_nervous_system()
+            .unwrap();
+        let recovered = SnsConfigurationFile::try_from_create_service_nervous_system(
+            &proposal,
+            config.nns_proposal.clone(),
+        )
+        .unwrap();
+        assert_eq!(recovered, config);
+    }
+
+    #[test]
+    fn executed_init_payload_round_trip() {
+        let config = test_config();
+        let payload = config.try_convert_to_executed_sns_init().unwrap();
+        let mut recovered =
+            SnsConfigurationFile::try_from_sns_init_payload(&payload, config.nns_proposal.clone())
+                .unwrap();
+        // Once executed, the swap starts at a random time of day
+        assert!(recovered.swap.start_time.is_some());
+        recovered.swap.start_time = config.swap.start_time;
+        assert_eq!(recovered, config);
+    }
+
+    #[test]
+    fn unexecuted_init_payload_has_no_swap_duration() {
+        let config = test_config();
+        let proposal = config
+            .try_convert_to_create_service_nervous_system()
+            .unwrap();
+        let payload = SnsInitPayload::try_from(proposal).unwrap();
+        let err =
+            SnsConfigurationFile::try_from_sns_init_payload(&payload, config.nns_proposal.clone())
+                .unwrap_err();
+        assert!(err.contains("swap_parameters.duration"));
+    }
+}
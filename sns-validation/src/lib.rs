@@ -2,4 +2,7 @@ pub mod config;
 mod consts;
 pub mod humanize;
 pub mod pbs;
+pub mod simulation;
 mod validation;
+
+pub use validation::sns_init::{SnsValidationError, SnsValidationErrorCode};
@@ -35,6 +35,17 @@ impl TryFrom<CreateServiceNervousSystem> for SnsInitPayload {
     type Error = String;
 
     fn try_from(src: CreateServiceNervousSystem) -> Result<Self, String> {
+        let result = Self::try_from_unvalidated(src)?;
+
+        result.validate_pre_execution()?;
+
+        Ok(result)
+    }
+}
+
+impl SnsInitPayload {
+    /// Converts the proposal without running the pre execution validators
+    pub(crate) fn try_from_unvalidated(src: CreateServiceNervousSystem) -> Result<Self, String> {
         let CreateServiceNervousSystem {
             name,
             description,
@@ -273,7 +284,55 @@ impl TryFrom<CreateServiceNervousSystem> for SnsInitPayload {
             max_icp_e8s: None,
         };
 
-        result.validate_pre_execution()?;
+        Ok(result)
+    }
+
+    /// Converts the executed proposal without running any validators
+    pub(crate) fn try_from_executed_unvalidated(
+        src: ExecutedCreateServiceNervousSystemProposal,
+    ) -> Result<Self, String> {
+        let mut defects = vec![];
+
+        let current_timestamp_seconds = src.current_timestamp_seconds;
+        let nns_proposal_id = Some(src.proposal_id);
+        let neurons_fund_participation_constraints = src.neurons_fund_participation_constraints;
+        let start_time = src
+            .create_service_nervous_system
+            .swap_parameters
+            .as_ref()
+            .and_then(|swap_parameters| swap_parameters.start_time);
+        let duration = src
+            .create_service_nervous_system
+            .swap_parameters
+            .as_ref()
+            .and_then(|swap_parameters| swap_parameters.duration);
+
+        let (swap_start_timestamp_seconds, swap_due_timestamp_seconds) =
+            match CreateServiceNervousSystem::swap_start_and_due_timestamps(
+                start_time.unwrap_or(src.random_swap_start_time),
+                duration.unwrap_or_default(),
+                current_timestamp_seconds,
+            ) {
+                Ok((swap_start_timestamp_seconds, swap_due_timestamp_seconds)) => (
+                    Some(swap_start_timestamp_seconds),
+                    Some(swap_due_timestamp_seconds),
+                ),
+                Err(err) => {
+                    defects.push(err);
+                    (None, None)
+                }
+            };
+
+        if !defects.is_empty() {
+            return Err(defects.join("\n"));
+        }
+
+        let mut result = SnsInitPayload::try_from_unvalidated(src.create_service_nervous_system)?;
+
+        result.nns_proposal_id = nns_proposal_id;
+        result.swap_start_timestamp_seconds = swap_start_timestamp_seconds;
+        result.swap_due_timestamp_seconds = swap_due_timestamp_seconds;
+        result.neurons_fund_participation_constraints = neurons_fund_participation_constraints;
 
         Ok(result)
     }
@@ -467,44 +526,9 @@ impl TryFrom<ExecutedCreateServiceNervousSystemProposal> for SnsInitPayload {
     type Error = String;
 
     fn try_from(src: ExecutedCreateServiceNervousSystemProposal) -> Result<Self, Self::Error> {
-        let mut defects = vec![];
-
-        let current_timestamp_seconds = src.current_timestamp_seconds;
-        let nns_proposal_id = Some(src.proposal_id);
-        let neurons_fund_participation_constraints = src.neurons_fund_participation_constraints;
-        let start_time = src
-            .create_service_nervous_system
-            .swap_parameters
-            .as_ref()
-            .and_then(|swap_parameters| swap_parameters.start_time);
-        let duration = src
-            .create_service_nervous_system
-            .swap_parameters
-            .as_ref()
-            .and_then(|swap_parameters| swap_parameters.duration);
-
-        let (swap_start_timestamp_seconds, swap_due_timestamp_seconds) =
-            match CreateServiceNervousSystem::swap_start_and_due_timestamps(
-                start_time.unwrap_or(src.random_swap_start_time),
-                duration.unwrap_or_default(),
-                current_timestamp_seconds,
-            ) {
-                Ok((swap_start_timestamp_seconds, swap_due_timestamp_seconds)) => (
-                    Some(swap_start_timestamp_seconds),
-                    Some(swap_due_timestamp_seconds),
-                ),
-                Err(err) => {
-                    defects.push(err);
-                    (None, None)
-                }
-            };
+        SnsInitPayload::try_from(src.create_service_nervous_system.clone())?;
 
-        let mut result = SnsInitPayload::try_from(src.create_service_nervous_system)?;
-
-        result.nns_proposal_id = nns_proposal_id;
-        result.swap_start_timestamp_seconds = swap_start_timestamp_seconds;
-        result.swap_due_timestamp_seconds = swap_due_timestamp_seconds;
-        result.neurons_fund_participation_constraints = neurons_fund_participation_constraints;
+        let result = SnsInitPayload::try_from_executed_unvalidated(src)?;
 
         result.validate_post_execution()?;
 
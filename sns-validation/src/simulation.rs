@@ -0,0 +1,576 @@
+//! Simulates the outcome of a decentralization swap for a SNS configuration
+//!
+//! Everything here is pure and deterministic, so it can run in the browser
+//! to preview what a configuration means before it is deployed.
+//! Voting power is computed at genesis, i.e. every neuron has an age of 0
+//! and only receives the dissolve delay bonus.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::{Distribution, Neurons, SnsConfigurationFile, Swap, Voting},
+    pbs::sns_swap_pb::NeuronsFundParticipationConstraints,
+};
+
+/// A group of direct participants committing the same amount of ICP
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cohort {
+    pub participants: u64,
+    pub icp_e8s_each: u64,
+}
+
+/// Hypothetical participation in the swap
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Participation {
+    /// Direct participants, in the order they commit
+    pub cohorts: Vec<Cohort>,
+    /// Ideal Neurons' Fund matching for the resulting direct participation,
+    /// only used if the configuration opts into Neurons' Fund participation
+    pub neurons_fund_ideal_icp_e8s: u64,
+    /// Constraints set by NNS governance on execution. Without them
+    /// the Neurons' Fund matches at most the direct participation
+    pub neurons_fund_constraints: Option<NeuronsFundParticipationConstraints>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum NeuronOwner {
+    Developer {
+        principal: String,
+        memo: u64,
+    },
+    /// Index into [Participation::cohorts]
+    Cohort(usize),
+    NeuronsFund,
+}
+
+/// A SNS neuron created at genesis or by the swap
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SimulatedNeuron {
+    pub owner: NeuronOwner,
+    /// Number of identical neurons, one per accepted participant for cohorts
+    pub count: u64,
+    pub stake_e8s: u64,
+    pub dissolve_delay_seconds: u64,
+    pub vesting_period_seconds: u64,
+    pub voting_power: u64,
+}
+
+impl SimulatedNeuron {
+    /// Seconds after genesis at which the stake can be liquid,
+    /// assuming the neuron starts dissolving as soon as it is allowed to
+    pub fn liquid_after_seconds(&self) -> u64 {
+        self.vesting_period_seconds
+            .saturating_add(self.dissolve_delay_seconds)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CohortOutcome {
+    pub accepted_participants: u64,
+    pub icp_e8s_each: u64,
+    pub sns_tokens_e8s_each: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SwapOutcome {
+    /// Why the swap would be aborted, `None` if it succeeds
+    pub failure_reason: Option<String>,
+    pub cohorts: Vec<CohortOutcome>,
+    pub accepted_participants: u64,
+    pub rejected_participants: u64,
+    pub direct_participation_icp_e8s: u64,
+    pub neurons_fund_participation_icp_e8s: u64,
+}
+
+impl SwapOutcome {
+    pub fn succeeded(&self) -> bool {
+        self.failure_reason.is_none()
+    }
+
+    pub fn total_participation_icp_e8s(&self) -> u64 {
+        self.direct_participation_icp_e8s
+            .saturating_add(self.neurons_fund_participation_icp_e8s)
+    }
+}
+
+/// Where the SNS tokens end up
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Allocation {
+    pub developers_e8s: u64,
+    pub treasury_e8s: u64,
+    pub direct_participants_e8s: u64,
+    pub neurons_fund_e8s: u64,
+    /// Swap tokens not handed out, either due to rounding or a failed swap
+    pub unsold_e8s: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Simulation {
+    pub swap: SwapOutcome,
+    pub allocation: Allocation,
+    pub neurons: Vec<SimulatedNeuron>,
+    pub total_voting_power: u64,
+    /// Number of neurons staking less than `Neurons.minimum_creation_stake`
+    pub undersized_neurons: u64,
+}
+
+impl SnsConfigurationFile {
+    pub fn simulate(&self, participation: &Participation) -> Simulation {
+        simulate(
+            &self.distribution,
+            &self.swap,
+            &self.neurons,
+            &self.voting,
+            participation,
+        )
+    }
+}
+
+fn mul_div(a: u64, b: u64, c: u64) -> u64 {
+    if c == 0 {
+        return 0;
+    }
+    ((a as u128 * b as u128) / c as u128).min(u64::MAX as u128) as u64
+}
+
+/// Voting power of a neuron at genesis
+fn voting_power(stake_e8s: u64, dissolve_delay_seconds: u64, voting: &Voting) -> u64 {
+    let min_dissolve_delay = voting.minimum_dissolve_delay.seconds.unwrap_or_default();
+    if dissolve_delay_seconds < min_dissolve_delay {
+        return 0;
+    }
+    let bonus = &voting.maximum_voting_power_bonuses.dissolve_delay;
+    let max_dissolve_delay = bonus.duration.seconds.unwrap_or_default();
+    let bonus_basis_points = bonus.bonus.basis_points.unwrap_or_default();
+
+    let bonus_e8s = mul_div(
+        mul_div(
+            stake_e8s,
+            dissolve_delay_seconds.min(max_dissolve_delay),
+            max_dissolve_delay,
+        ),
+        bonus_basis_points,
+        10_000,
+    );
+    stake_e8s.saturating_add(bonus_e8s)
+}
+
+/// Splits `stake_e8s` into a basket as the swap does, the remainder goes to the first neurons
+fn basket(stake_e8s: u64, swap: &Swap) -> Vec<(u64, u64)> {
+    let count = swap.vesting_schedule.events.max(1);
+    let interval = swap.vesting_schedule.interval.seconds.unwrap_or_default();
+    let remainder = stake_e8s % count;
+    (0..count)
+        .map(|i| {
+            let stake = stake_e8s / count + u64::from(i < remainder);
+            (stake, interval.saturating_mul(i))
+        })
+        .collect()
+}
+
+fn run_swap(swap: &Swap, participation: &Participation) -> SwapOutcome {
+    let min_participant = swap.minimum_participant_icp.e8s.unwrap_or_default();
+    let max_participant = swap.maximum_participant_icp.e8s.unwrap_or(u64::MAX);
+    let max_direct = swap
+        .maximum_direct_participation_icp
+        .or(swap.maximum_icp)
+        .and_then(|t| t.e8s)
+        .unwrap_or(u64::MAX);
+
+    let mut direct = 0u64;
+    let mut accepted_total = 0;
+    let mut rejected_total = 0;
+    let mut cohorts = Vec::with_capacity(participation.cohorts.len());
+    for cohort in &participation.cohorts {
+        let icp_each = cohort.icp_e8s_each.min(max_participant);
+        let accepted = if icp_each < min_participant || icp_each == 0 {
+            0
+        } else {
+            // Participants are accepted in order until the swap is full
+            cohort.participants.min((max_direct - direct) / icp_each)
+        };
+        direct += accepted * icp_each;
+        accepted_total += accepted;
+        rejected_total += cohort.participants - accepted;
+        cohorts.push(CohortOutcome {
+            accepted_participants: accepted,
+            icp_e8s_each: icp_each,
+            sns_tokens_e8s_each: 0,
+        });
+    }
+
+    let neurons_fund = if swap.neurons_fund_participation != Some(true) {
+        0
+    } else if let Some(constraints) = &participation.neurons_fund_constraints {
+        constraints
+            .effective_participation_icp_e8s(direct, participation.neurons_fund_ideal_icp_e8s)
+            .unwrap_or_default()
+    } else {
+        participation.neurons_fund_ideal_icp_e8s.min(direct)
+    };
+
+    let min_direct = swap
+        .minimum_direct_participation_icp
+        .or(swap.minimum_icp)
+        .and_then(|t| t.e8s)
+        .unwrap_or_default();
+    let failure_reason = if accepted_total < swap.minimum_participants {
+        Some(format!(
+            "Only {accepted_total} participants were accepted, at least {} are required",
+            swap.minimum_participants
+        ))
+    } else if direct < min_direct {
+        Some(format!(
+            "Direct participation of {direct} e8s is below the minimum of {min_direct} e8s"
+        ))
+    } else {
+        None
+    };
+
+    SwapOutcome {
+        failure_reason,
+        cohorts,
+        accepted_participants: accepted_total,
+        rejected_participants: rejected_total,
+        direct_participation_icp_e8s: direct,
+        neurons_fund_participation_icp_e8s: neurons_fund,
+    }
+}
+
+/// Computes the final token allocation, neurons and voting power for a configuration
+pub fn simulate(
+    distribution: &Distribution,
+    swap: &Swap,
+    neurons: &Neurons,
+    voting: &Voting,
+    participation: &Participation,
+) -> Simulation {
+    let mut outcome = run_swap(swap, participation);
+    let swap_tokens = distribution.initial_balances.swap.e8s.unwrap_or_default();
+
+    let mut allocation = Allocation {
+        treasury_e8s: distribution
+            .initial_balances
+            .governance
+            .e8s
+            .unwrap_or_default(),
+        ..Default::default()
+    };
+    let mut sim_neurons = vec![];
+
+    for neuron in &distribution.neurons {
+        let stake = neuron.stake.e8s.unwrap_or_default();
+        let dissolve_delay = neuron.dissolve_delay.seconds.unwrap_or_default();
+        allocation.developers_e8s += stake;
+        sim_neurons.push(SimulatedNeuron {
+            owner: NeuronOwner::Developer {
+                principal: neuron.principal.clone(),
+                memo: neuron.memo,
+            },
+            count: 1,
+            stake_e8s: stake,
+            dissolve_delay_seconds: dissolve_delay,
+            vesting_period_seconds: neuron.vesting_period.seconds.unwrap_or_default(),
+            voting_power: voting_power(stake, dissolve_delay, voting),
+        });
+    }
+
+    let total_icp = outcome.total_participation_icp_e8s();
+    if outcome.succeeded() && total_icp > 0 {
+        for (idx, cohort) in outcome.cohorts.iter_mut().enumerate() {
+            if cohort.accepted_participants == 0 {
+                continue;
+            }
+            cohort.sns_tokens_e8s_each = mul_div(swap_tokens, cohort.icp_e8s_each, total_icp);
+            allocation.direct_participants_e8s +=
+                cohort.sns_tokens_e8s_each * cohort.accepted_participants;
+            sim_neurons.extend(basket(cohort.sns_tokens_e8s_each, swap).into_iter().map(
+                |(stake, dissolve_delay)| SimulatedNeuron {
+                    owner: NeuronOwner::Cohort(idx),
+                    count: cohort.accepted_participants,
+                    stake_e8s: stake,
+                    dissolve_delay_seconds: dissolve_delay,
+                    vesting_period_seconds: 0,
+                    voting_power: voting_power(stake, dissolve_delay, voting),
+                },
+            ));
+        }
+
+        allocation.neurons_fund_e8s = mul_div(
+            swap_tokens,
+            outcome.neurons_fund_participation_icp_e8s,
+            total_icp,
+        );
+        if allocation.neurons_fund_e8s > 0 {
+            sim_neurons.extend(basket(allocation.neurons_fund_e8s, swap).into_iter().map(
+                |(stake, dissolve_delay)| SimulatedNeuron {
+                    owner: NeuronOwner::NeuronsFund,
+                    count: 1,
+                    stake_e8s: stake,
+                    dissolve_delay_seconds: dissolve_delay,
+                    vesting_period_seconds: 0,
+                    voting_power: voting_power(stake, dissolve_delay, voting),
+                },
+            ));
+        }
+    }
+    allocation.unsold_e8s = swap_tokens
+        .saturating_sub(allocation.direct_participants_e8s)
+        .saturating_sub(allocation.neurons_fund_e8s);
+
+    let total_voting_power = sim_neurons
+        .iter()
+        .map(|n| n.voting_power.saturating_mul(n.count))
+        .fold(0u64, u64::saturating_add);
+
+    let minimum_stake = neurons.minimum_creation_stake.e8s.unwrap_or_default();
+    let undersized_neurons = sim_neurons
+        .iter()
+        .filter(|n| n.stake_e8s < minimum_stake)
+        .map(|n| n.count)
+        .sum();
+
+    Simulation {
+        swap: outcome,
+        allocation,
+        neurons: sim_neurons,
+        total_voting_power,
+        undersized_neurons,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::{
+            Bonus, InitialBalances, MaximumVotingPowerBonuses, Neuron, RewardRate, VestingSchedule,
+        },
+        pbs::nns_pb,
+    };
+
+    fn tokens(e8s: u64) -> nns_pb::Tokens {
+        nns_pb::Tokens { e8s: Some(e8s) }
+    }
+
+    fn seconds(seconds: u64) -> nns_pb::Duration {
+        nns_pb::Duration {
+            seconds: Some(seconds),
+        }
+    }
+
+    fn percentage(basis_points: u64) -> nns_pb::Percentage {
+        nns_pb::Percentage {
+            basis_points: Some(basis_points),
+        }
+    }
+
+    fn distribution() -> Distribution {
+        Distribution {
+            neurons: vec![Neuron {
+                principal: "dev".into(),
+                stake: tokens(1_000),
+                memo: 0,
+                dissolve_delay: seconds(200),
+                vesting_period: seconds(50),
+            }],
+            initial_balances: InitialBalances {
+                governance: tokens(5_000),
+                swap: tokens(10_000),
+            },
+            total: tokens(16_000),
+        }
+    }
+
+    fn swap() -> Swap {
+        Swap {
+            minimum_participants: 2,
+            minimum_icp: None,
+            maximum_icp: None,
+            minimum_direct_participation_icp: Some(tokens(100)),
+            maximum_direct_participation_icp: Some(tokens(1_000)),
+            minimum_participant_icp: tokens(10),
+            maximum_participant_icp: tokens(400),
+            confirmation_text: None,
+            restricted_countries: None,
+            vesting_schedule: VestingSchedule {
+                events: 3,
+                interval: seconds(100),
+            },
+            start_time: None,
+            duration: seconds(24 * 60 * 60),
+            neurons_fund_investment_icp: None,
+            neurons_fund_participation: Some(true),
+        }
+    }
+
+    fn neurons() -> Neurons {
+        Neurons {
+            minimum_creation_stake: tokens(100),
+        }
+    }
+
+    /// Full bonus (2x) at a dissolve delay of 200 seconds, no voting power under 100
+    fn voting() -> Voting {
+        Voting {
+            minimum_dissolve_delay: seconds(100),
+            maximum_voting_power_bonuses: MaximumVotingPowerBonuses {
+                dissolve_delay: Bonus {
+                    duration: seconds(200),
+                    bonus: percentage(10_000),
+                },
+                age: Bonus {
+                    duration: seconds(400),
+                    bonus: percentage(2_500),
+                },
+            },
+            reward_rate: RewardRate {
+                initial: percentage(100),
+                r#final: percentage(100),
+                transition_duration: seconds(0),
+            },
+        }
+    }
+
+    fn participation(cohorts: &[(u64, u64)]) -> Participation {
+        Participation {
+            cohorts: cohorts
+                .iter()
+                .map(|&(participants, icp_e8s_each)| Cohort {
+                    participants,
+                    icp_e8s_each,
+                })
+                .collect(),
+            neurons_fund_ideal_icp_e8s: 2_000,
+            neurons_fund_constraints: None,
+        }
+    }
+
+    fn run(swap: &Swap, participation: &Participation) -> Simulation {
+        simulate(&distribution(), swap, &neurons(), &voting(), participation)
+    }
+
+    #[test]
+    fn direct_and_neurons_fund_allocation() {
+        // The second cohort is capped at 400 ICP each and only one fits under the maximum
+        let sim = run(&swap(), &participation(&[(2, 300), (5, 500)]));
+
+        assert!(sim.swap.succeeded());
+        assert_eq!(sim.swap.accepted_participants, 3);
+        assert_eq!(sim.swap.rejected_participants, 4);
+        assert_eq!(sim.swap.direct_participation_icp_e8s, 1_000);
+        // Matches at most the direct participation without constraints
+        assert_eq!(sim.swap.neurons_fund_participation_icp_e8s, 1_000);
+
+        assert_eq!(sim.swap.cohorts[0].sns_tokens_e8s_each, 1_500);
+        assert_eq!(sim.swap.cohorts[1].icp_e8s_each, 400);
+        assert_eq!(sim.swap.cohorts[1].sns_tokens_e8s_each, 2_000);
+        assert_eq!(
+            sim.allocation,
+            Allocation {
+                developers_e8s: 1_000,
+                treasury_e8s: 5_000,
+                direct_participants_e8s: 5_000,
+                neurons_fund_e8s: 5_000,
+                unsold_e8s: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn neurons_fund_opt_out() {
+        let swap = Swap {
+            neurons_fund_participation: None,
+            ..swap()
+        };
+        let sim = run(&swap, &participation(&[(2, 300), (5, 500)]));
+
+        assert_eq!(sim.swap.neurons_fund_participation_icp_e8s, 0);
+        assert_eq!(sim.allocation.neurons_fund_e8s, 0);
+        assert_eq!(sim.swap.cohorts[0].sns_tokens_e8s_each, 3_000);
+        assert_eq!(sim.allocation.direct_participants_e8s, 10_000);
+        assert!(sim
+            .neurons
+            .iter()
+            .all(|n| n.owner != NeuronOwner::NeuronsFund));
+    }
+
+    #[test]
+    fn basket_splits_stake_and_dissolve_delay() {
+        assert_eq!(
+            basket(1_000, &swap()),
+            vec![(334, 0), (333, 100), (333, 200)]
+        );
+
+        let sim = run(&swap(), &participation(&[(2, 300), (5, 500)]));
+        let cohort_neurons: Vec<_> = sim
+            .neurons
+            .iter()
+            .filter(|n| n.owner == NeuronOwner::Cohort(0))
+            .map(|n| (n.count, n.stake_e8s, n.dissolve_delay_seconds))
+            .collect();
+        assert_eq!(
+            cohort_neurons,
+            vec![(2, 500, 0), (2, 500, 100), (2, 500, 200)]
+        );
+
+        let fund_neurons: Vec<_> = sim
+            .neurons
+            .iter()
+            .filter(|n| n.owner == NeuronOwner::NeuronsFund)
+            .map(|n| n.stake_e8s)
+            .collect();
+        assert_eq!(fund_neurons, vec![1_667, 1_667, 1_666]);
+
+        let developer = &sim.neurons[0];
+        assert_eq!(developer.liquid_after_seconds(), 250);
+        assert_eq!(sim.undersized_neurons, 0);
+    }
+
+    #[test]
+    fn voting_power_at_genesis() {
+        let voting = voting();
+        assert_eq!(voting_power(1_000, 99, &voting), 0);
+        assert_eq!(voting_power(1_000, 100, &voting), 1_500);
+        assert_eq!(voting_power(1_000, 200, &voting), 2_000);
+        // The bonus is capped at the maximum dissolve delay
+        assert_eq!(voting_power(1_000, 400, &voting), 2_000);
+
+        let sim = run(&swap(), &participation(&[(2, 300), (5, 500)]));
+        let cohort_voting_power: Vec<_> = sim
+            .neurons
+            .iter()
+            .filter(|n| n.owner == NeuronOwner::Cohort(0))
+            .map(|n| n.voting_power)
+            .collect();
+        assert_eq!(cohort_voting_power, vec![0, 750, 1_000]);
+        assert_eq!(sim.total_voting_power, 13_664);
+    }
+
+    #[test]
+    fn failed_swap() {
+        // A single participant, the minimum is 2
+        let sim = run(&swap(), &participation(&[(1, 300)]));
+        assert!(!sim.swap.succeeded());
+        assert_eq!(sim.allocation.direct_participants_e8s, 0);
+        assert_eq!(sim.allocation.neurons_fund_e8s, 0);
+        assert_eq!(sim.allocation.unsold_e8s, 10_000);
+        assert_eq!(sim.neurons.len(), 1);
+        assert_eq!(sim.total_voting_power, 2_000);
+
+        // Enough participants but below the minimum direct participation
+        let sim = run(&swap(), &participation(&[(3, 20)]));
+        assert!(sim
+            .swap
+            .failure_reason
+            .as_deref()
+            .is_some_and(|reason| reason.contains("below the minimum")));
+        assert_eq!(sim.allocation.unsold_e8s, 10_000);
+
+        // Participants under the minimum commitment are rejected
+        let sim = run(&swap(), &participation(&[(3, 5)]));
+        assert_eq!(sim.swap.accepted_participants, 0);
+        assert_eq!(sim.swap.rejected_participants, 3);
+        assert!(!sim.swap.succeeded());
+    }
+}
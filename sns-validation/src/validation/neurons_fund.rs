@@ -381,3 +381,52 @@ impl TryFrom<&LinearScalingCoefficient> for ValidatedLinearScalingCoefficient {
         })
     }
 }
+
+impl ValidatedLinearScalingCoefficient {
+    fn contains(&self, direct_participation_icp_e8s: u64) -> bool {
+        (self.from_direct_participation_icp_e8s..self.to_direct_participation_icp_e8s)
+            .contains(&direct_participation_icp_e8s)
+    }
+
+    /// Scales the ideal Neurons' Fund participation down to the effective one
+    fn scale(&self, ideal_participation_icp_e8s: u64) -> u64 {
+        let scaled = (ideal_participation_icp_e8s as u128 * self.slope_numerator as u128)
+            / self.slope_denominator as u128
+            + self.intercept_icp_e8s as u128;
+        scaled.min(u64::MAX as u128) as u64
+    }
+}
+
+impl NeuronsFundParticipationConstraints {
+    /// Effective Neurons' Fund participation for a given direct participation
+    ///
+    /// `ideal_participation_icp_e8s` is the value of the ideal matching function at
+    /// `direct_participation_icp_e8s`, it is scaled down by the coefficient interval
+    /// containing the direct participation and capped by `max_neurons_fund_participation_icp_e8s`.
+    pub fn effective_participation_icp_e8s(
+        &self,
+        direct_participation_icp_e8s: u64,
+        ideal_participation_icp_e8s: u64,
+    ) -> Result<u64, NeuronsFundParticipationConstraintsValidationError> {
+        self.validate()?;
+        // Both are checked by `validate`
+        let threshold = self
+            .min_direct_participation_threshold_icp_e8s
+            .unwrap_or_default();
+        let max_participation = self
+            .max_neurons_fund_participation_icp_e8s
+            .unwrap_or_default();
+        if direct_participation_icp_e8s < threshold {
+            return Ok(0);
+        }
+
+        let effective = self
+            .coefficient_intervals
+            .iter()
+            .filter_map(|c| ValidatedLinearScalingCoefficient::try_from(c).ok())
+            .find(|c| c.contains(direct_participation_icp_e8s))
+            .map(|c| c.scale(ideal_participation_icp_e8s))
+            .unwrap_or_default();
+        Ok(effective.min(max_participation))
+    }
+}
@@ -5,6 +5,7 @@ use std::{
 };
 
 use candid::Principal;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     humanize::E8,
@@ -37,6 +38,59 @@ pub const MIN_SNS_NEURONS_PER_BASKET: u64 = 2;
 
 pub const MAX_SNS_NEURONS_PER_BASKET: u64 = 10;
 
+/// Machine readable reason for a [SnsValidationError]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnsValidationErrorCode {
+    /// A required value was not set
+    Missing,
+    /// A value was set where it is not allowed (yet)
+    MustBeUnset,
+    TooShort,
+    TooLong,
+    /// A numeric value is outside of its allowed bounds
+    OutOfRange,
+    InvalidFormat,
+    Duplicate,
+    /// The value is rejected by policy (e.g. banned names)
+    NotAllowed,
+    /// The value conflicts with another field
+    Inconsistent,
+}
+
+/// A validation failure of a [SnsInitPayload]
+///
+/// `path` is a dotted path into the payload, grouped the same way
+/// the SNS configuration is (e.g. `swap.neuron_basket_construction_parameters.count`)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnsValidationError {
+    pub path: String,
+    pub code: SnsValidationErrorCode,
+    pub message: String,
+}
+
+impl SnsValidationError {
+    pub fn new(
+        path: impl Into<String>,
+        code: SnsValidationErrorCode,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for SnsValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for SnsValidationError {}
+
 enum MinDirectParticipationThresholdValidationError {
     // This value must be specified.
     Unspecified,
@@ -182,9 +236,44 @@ impl std::fmt::Display for NeuronsFundParticipationConstraintsValidationError {
     }
 }
 
-impl From<NeuronsFundParticipationConstraintsValidationError> for Result<(), String> {
+impl From<NeuronsFundParticipationConstraintsValidationError> for SnsValidationError {
+    fn from(value: NeuronsFundParticipationConstraintsValidationError) -> Self {
+        use MaxNeuronsFundParticipationValidationError as MaxErr;
+        use MinDirectParticipationThresholdValidationError as MinErr;
+        use NeuronsFundParticipationConstraintsValidationError as Err;
+        use SnsValidationErrorCode as Code;
+
+        const PATH: &str = "swap.neurons_fund_participation_constraints";
+        let (path, code) = match &value {
+            Err::SetBeforeProposalExecution => (PATH.to_string(), Code::MustBeUnset),
+            // Either a bare swap field name, or a sentence about the constraints themselves.
+            Err::RelatedFieldUnspecified(field) if !field.contains(' ') => {
+                (format!("swap.{field}"), Code::Missing)
+            }
+            Err::RelatedFieldUnspecified(_) => (PATH.to_string(), Code::Missing),
+            Err::MinDirectParticipationThresholdValidationError(e) => (
+                format!("{PATH}.min_direct_participation_threshold_icp_e8s"),
+                match e {
+                    MinErr::Unspecified => Code::Missing,
+                    _ => Code::Inconsistent,
+                },
+            ),
+            Err::MaxNeuronsFundParticipationValidationError(e) => (
+                format!("{PATH}.max_neurons_fund_participation_icp_e8s"),
+                match e {
+                    MaxErr::Unspecified => Code::Missing,
+                    _ => Code::Inconsistent,
+                },
+            ),
+            Err::Local(_) => (PATH.to_string(), Code::Inconsistent),
+        };
+        SnsValidationError::new(path, code, value.to_string())
+    }
+}
+
+impl From<NeuronsFundParticipationConstraintsValidationError> for Result<(), SnsValidationError> {
     fn from(value: NeuronsFundParticipationConstraintsValidationError) -> Self {
-        Err(value.to_string())
+        Err(value.into())
     }
 }
 
@@ -239,9 +328,36 @@ impl std::fmt::Display for NeuronBasketConstructionParametersValidationError {
     }
 }
 
-impl From<NeuronBasketConstructionParametersValidationError> for Result<(), String> {
+impl From<NeuronBasketConstructionParametersValidationError> for SnsValidationError {
+    fn from(val: NeuronBasketConstructionParametersValidationError) -> Self {
+        use NeuronBasketConstructionParametersValidationError as Err;
+        use SnsValidationErrorCode as Code;
+
+        let (field, code) = match val {
+            Err::ExceedsMaximalDissolveDelay(_) | Err::ExceedsU64 => {
+                ("dissolve_delay_interval_seconds", Code::Inconsistent)
+            }
+            Err::BasketSizeTooSmall | Err::BasketSizeTooBig => ("count", Code::OutOfRange),
+            Err::InadequateDissolveDelay => ("dissolve_delay_interval_seconds", Code::OutOfRange),
+            Err::UnexpectedInLegacyFlow => {
+                return Self::new(
+                    "swap.neuron_basket_construction_parameters",
+                    Code::MustBeUnset,
+                    val.to_string(),
+                )
+            }
+        };
+        Self::new(
+            format!("swap.neuron_basket_construction_parameters.{field}"),
+            code,
+            val.to_string(),
+        )
+    }
+}
+
+impl From<NeuronBasketConstructionParametersValidationError> for Result<(), SnsValidationError> {
     fn from(val: NeuronBasketConstructionParametersValidationError) -> Self {
-        Err(val.to_string())
+        Err(val.into())
     }
 }
 
@@ -257,7 +373,7 @@ impl FractionalDeveloperVotingPower {
         developer_distribution: &DeveloperDistribution,
         airdrop_distribution: &AirdropDistribution,
         nervous_system_parameters: &NervousSystemParameters,
-    ) -> Result<(), String> {
+    ) -> Result<(), SnsValidationError> {
         let neuron_minimum_dissolve_delay_to_vote_seconds = nervous_system_parameters
             .neuron_minimum_dissolve_delay_to_vote_seconds
             .as_ref()
@@ -275,9 +391,13 @@ impl FractionalDeveloperVotingPower {
             .count();
 
         if missing_developer_principals_count != 0 {
-            return Err(format!(
-                "Error: {} developer_neurons are missing controllers",
-                missing_developer_principals_count
+            return Err(SnsValidationError::new(
+                "distribution.developer_distribution.developer_neurons",
+                SnsValidationErrorCode::Missing,
+                format!(
+                    "Error: {} developer_neurons are missing controllers",
+                    missing_developer_principals_count
+                ),
             ));
         }
 
@@ -293,10 +413,12 @@ impl FractionalDeveloperVotingPower {
             .collect::<BTreeMap<_, _>>();
 
         if deduped_dev_neurons.len() != developer_distribution.developer_neurons.len() {
-            return Err(
+            return Err(SnsValidationError::new(
+                "distribution.developer_distribution.developer_neurons",
+                SnsValidationErrorCode::Duplicate,
                 "Error: Neurons with the same controller and memo found in developer_neurons"
                     .to_string(),
-            );
+            ));
         }
 
         // The max number of DeveloperDistributions that can be specified in the SnsInitPayload.
@@ -306,10 +428,14 @@ impl FractionalDeveloperVotingPower {
         const MAX_AIRDROP_DISTRIBUTION_COUNT: usize = 1000;
 
         if deduped_dev_neurons.len() > MAX_DEVELOPER_DISTRIBUTION_COUNT {
-            return Err(format!(
+            return Err(SnsValidationError::new(
+                "distribution.developer_distribution.developer_neurons",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
                 "Error: The number of developer neurons must be less than {}. Current count is {}",
                 MAX_DEVELOPER_DISTRIBUTION_COUNT,
                 deduped_dev_neurons.len(),
+            ),
             ));
         }
 
@@ -321,11 +447,15 @@ impl FractionalDeveloperVotingPower {
 
         for (controller, memo) in deduped_dev_neurons.keys() {
             if NEURON_BASKET_MEMO_RANGE_START <= *memo && *memo <= SALE_NEURON_MEMO_RANGE_END {
-                return Err(format!(
-                    "Error: Developer neuron with controller {} cannot have a memo in the range {} to {}",
-                    controller.unwrap(),
-                    NEURON_BASKET_MEMO_RANGE_START,
-                    SALE_NEURON_MEMO_RANGE_END
+                return Err(SnsValidationError::new(
+                    "distribution.developer_distribution.developer_neurons",
+                    SnsValidationErrorCode::OutOfRange,
+                    format!(
+                        "Error: Developer neuron with controller {} cannot have a memo in the range {} to {}",
+                        controller.unwrap(),
+                        NEURON_BASKET_MEMO_RANGE_START,
+                        SALE_NEURON_MEMO_RANGE_END
+                    ),
                 ));
             }
         }
@@ -337,9 +467,13 @@ impl FractionalDeveloperVotingPower {
             .count();
 
         if missing_airdrop_principals_count != 0 {
-            return Err(format!(
-                "Error: {} airdrop_neurons are missing controllers",
-                missing_airdrop_principals_count
+            return Err(SnsValidationError::new(
+                "distribution.airdrop_distribution.airdrop_neurons",
+                SnsValidationErrorCode::Missing,
+                format!(
+                    "Error: {} airdrop_neurons are missing controllers",
+                    missing_airdrop_principals_count
+                ),
             ));
         }
 
@@ -355,27 +489,37 @@ impl FractionalDeveloperVotingPower {
             .collect::<BTreeMap<_, _>>();
 
         if deduped_airdrop_neurons.len() != airdrop_distribution.airdrop_neurons.len() {
-            return Err(
+            return Err(SnsValidationError::new(
+                "distribution.airdrop_distribution.airdrop_neurons",
+                SnsValidationErrorCode::Duplicate,
                 "Error: Neurons with the same controller and memo detected in airdrop_neurons"
                     .to_string(),
-            );
+            ));
         }
 
         if deduped_airdrop_neurons.len() > MAX_AIRDROP_DISTRIBUTION_COUNT {
-            return Err(format!(
+            return Err(SnsValidationError::new(
+                "distribution.airdrop_distribution.airdrop_neurons",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
                 "Error: The number of airdrop neurons must be less than {}. Current count is {}",
                 MAX_AIRDROP_DISTRIBUTION_COUNT,
                 deduped_airdrop_neurons.len(),
+            ),
             ));
         }
 
         for (controller, memo) in deduped_airdrop_neurons.keys() {
             if NEURON_BASKET_MEMO_RANGE_START <= *memo && *memo <= SALE_NEURON_MEMO_RANGE_END {
-                return Err(format!(
-                    "Error: Airdrop neuron with controller {} cannot have a memo in the range {} to {}",
-                    controller.unwrap(),
-                    NEURON_BASKET_MEMO_RANGE_START,
-                    SALE_NEURON_MEMO_RANGE_END
+                return Err(SnsValidationError::new(
+                    "distribution.airdrop_distribution.airdrop_neurons",
+                    SnsValidationErrorCode::OutOfRange,
+                    format!(
+                        "Error: Airdrop neuron with controller {} cannot have a memo in the range {} to {}",
+                        controller.unwrap(),
+                        NEURON_BASKET_MEMO_RANGE_START,
+                        SALE_NEURON_MEMO_RANGE_END
+                    ),
                 ));
             }
         }
@@ -389,10 +533,14 @@ impl FractionalDeveloperVotingPower {
         }
 
         if !duplicated_neuron_principals.is_empty() {
-            return Err(format!(
-                "Error: The following controllers are present in AirdropDistribution \
+            return Err(SnsValidationError::new(
+                "distribution",
+                SnsValidationErrorCode::Duplicate,
+                format!(
+                    "Error: The following controllers are present in AirdropDistribution \
                 and DeveloperDistribution: {:?}",
-                duplicated_neuron_principals
+                    duplicated_neuron_principals
+                ),
             ));
         }
 
@@ -406,10 +554,14 @@ impl FractionalDeveloperVotingPower {
             });
 
         if !configured_at_least_one_voting_neuron {
-            return Err(format!(
+            return Err(SnsValidationError::new(
+                "distribution",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
                 "Error: There needs to be at least one voting-eligible neuron configured. To be \
                  eligible to vote, a neuron must have dissolve_delay_seconds of at least {}",
                 neuron_minimum_dissolve_delay_to_vote_seconds
+            ),
             ));
         }
 
@@ -424,9 +576,13 @@ impl FractionalDeveloperVotingPower {
             .collect();
 
         if !misconfigured_dissolve_delay_principals.is_empty() {
-            return Err(format!(
-                "Error: The following PrincipalIds have a dissolve_delay_seconds configured greater than \
+            return Err(SnsValidationError::new(
+                "distribution",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
+                    "Error: The following PrincipalIds have a dissolve_delay_seconds configured greater than \
                  the allowed max_dissolve_delay_seconds ({}): {:?}", max_dissolve_delay_seconds, misconfigured_dissolve_delay_principals
+                ),
             ));
         }
 
@@ -436,25 +592,38 @@ impl FractionalDeveloperVotingPower {
     pub fn validate(
         &self,
         nervous_system_parameters: &NervousSystemParameters,
-    ) -> Result<(), String> {
-        let developer_distribution = self
-            .developer_distribution
-            .as_ref()
-            .ok_or("Error: developer_distribution must be specified")?;
+    ) -> Result<(), SnsValidationError> {
+        let developer_distribution = self.developer_distribution.as_ref().ok_or_else(|| {
+            SnsValidationError::new(
+                "distribution.developer_distribution",
+                SnsValidationErrorCode::Missing,
+                "Error: developer_distribution must be specified",
+            )
+        })?;
 
-        self.treasury_distribution
-            .as_ref()
-            .ok_or("Error: treasury_distribution must be specified")?;
+        self.treasury_distribution.as_ref().ok_or_else(|| {
+            SnsValidationError::new(
+                "distribution.treasury_distribution",
+                SnsValidationErrorCode::Missing,
+                "Error: treasury_distribution must be specified",
+            )
+        })?;
 
-        let swap_distribution = self
-            .swap_distribution
-            .as_ref()
-            .ok_or("Error: swap_distribution must be specified")?;
+        let swap_distribution = self.swap_distribution.as_ref().ok_or_else(|| {
+            SnsValidationError::new(
+                "distribution.swap_distribution",
+                SnsValidationErrorCode::Missing,
+                "Error: swap_distribution must be specified",
+            )
+        })?;
 
-        let airdrop_distribution = self
-            .airdrop_distribution
-            .as_ref()
-            .ok_or("Error: airdrop_distribution must be specified")?;
+        let airdrop_distribution = self.airdrop_distribution.as_ref().ok_or_else(|| {
+            SnsValidationError::new(
+                "distribution.airdrop_distribution",
+                SnsValidationErrorCode::Missing,
+                "Error: airdrop_distribution must be specified",
+            )
+        })?;
 
         self.validate_neurons(
             developer_distribution,
@@ -464,27 +633,45 @@ impl FractionalDeveloperVotingPower {
 
         match Self::get_total_distributions(&airdrop_distribution.airdrop_neurons) {
             Ok(_) => (),
-            Err(_) => return Err("Error: The sum of all airdrop allocated tokens overflowed and is an invalid distribution".to_string()),
+            Err(_) => return Err(SnsValidationError::new(
+                "distribution",
+                SnsValidationErrorCode::OutOfRange,
+                "Error: The sum of all airdrop allocated tokens overflowed and is an invalid distribution".to_string(),
+            )),
         };
 
         if swap_distribution.initial_swap_amount_e8s == 0 {
-            return Err(
+            return Err(SnsValidationError::new(
+                "distribution.swap_distribution.initial_swap_amount_e8s",
+                SnsValidationErrorCode::OutOfRange,
                 "Error: swap_distribution.initial_swap_amount_e8s must be greater than 0"
                     .to_string(),
-            );
+            ));
         }
 
         if swap_distribution.total_e8s < swap_distribution.initial_swap_amount_e8s {
-            return Err("Error: swap_distribution.total_e8 must be greater than or equal to swap_distribution.initial_swap_amount_e8s".to_string());
+            return Err(SnsValidationError::new(
+                "distribution.swap_distribution.total_e8s",
+                SnsValidationErrorCode::Inconsistent,
+                "Error: swap_distribution.total_e8 must be greater than or equal to swap_distribution.initial_swap_amount_e8s".to_string(),
+            ));
         }
 
         let total_developer_e8s = match Self::get_total_distributions(&developer_distribution.developer_neurons) {
             Ok(total) => total,
-            Err(_) => return Err("Error: The sum of all developer allocated tokens overflowed and is an invalid distribution".to_string()),
+            Err(_) => return Err(SnsValidationError::new(
+                "distribution",
+                SnsValidationErrorCode::OutOfRange,
+                "Error: The sum of all developer allocated tokens overflowed and is an invalid distribution".to_string(),
+            )),
         };
 
         if total_developer_e8s > swap_distribution.total_e8s {
-            return Err("Error: The sum of all developer allocated tokens must be less than or equal to swap_distribution.total_e8s".to_string());
+            return Err(SnsValidationError::new(
+                "distribution.swap_distribution",
+                SnsValidationErrorCode::Inconsistent,
+                "Error: The sum of all developer allocated tokens must be less than or equal to swap_distribution.total_e8s".to_string(),
+            ));
         }
 
         Ok(())
@@ -592,7 +779,16 @@ impl SnsInitPayload {
     }
 
     pub fn validate_pre_execution(&self) -> Result<Self, String> {
-        let validation_fns = [
+        self.join_validation_results(&self.pre_execution_validations())
+    }
+
+    /// Every pre execution error, deduplicated by message
+    pub fn pre_execution_errors(&self) -> Vec<SnsValidationError> {
+        Self::collect_errors(&self.pre_execution_validations())
+    }
+
+    fn pre_execution_validations(&self) -> Vec<Result<(), SnsValidationError>> {
+        vec![
             self.validate_token_symbol(),
             self.validate_token_name(),
             self.validate_token_logo(),
@@ -631,13 +827,20 @@ impl SnsInitPayload {
             // Obsolete fields are not set
             self.validate_min_icp_e8s(),
             self.validate_max_icp_e8s(),
-        ];
-
-        self.join_validation_results(&validation_fns)
+        ]
     }
 
     pub fn validate_post_execution(&self) -> Result<Self, String> {
-        let validation_fns = [
+        self.join_validation_results(&self.post_execution_validations())
+    }
+
+    /// Every post execution error, deduplicated by message
+    pub fn post_execution_errors(&self) -> Vec<SnsValidationError> {
+        Self::collect_errors(&self.post_execution_validations())
+    }
+
+    fn post_execution_validations(&self) -> Vec<Result<(), SnsValidationError>> {
+        vec![
             self.validate_token_symbol(),
             self.validate_token_name(),
             self.validate_token_logo(),
@@ -678,26 +881,30 @@ impl SnsInitPayload {
             self.validate_swap_due_timestamp_seconds(),
             self.validate_neurons_fund_participation_constraints(false),
             self.validate_neurons_fund_participation(),
-        ];
-
-        self.join_validation_results(&validation_fns)
+        ]
     }
 
-    fn join_validation_results(
-        &self,
-        validation_fns: &[Result<(), String>],
-    ) -> Result<Self, String> {
+    fn collect_errors(
+        validation_fns: &[Result<(), SnsValidationError>],
+    ) -> Vec<SnsValidationError> {
         let mut seen_messages = HashSet::new();
-        let defect_messages = validation_fns
+        validation_fns
             .iter()
-            .filter_map(|validation_fn| match validation_fn {
-                Err(msg) => Some(msg),
-                Ok(_) => None,
-            })
-            .filter(|&x|
+            .filter_map(|validation_fn| validation_fn.as_ref().err())
+            .filter(|x|
                 // returns true iff the set did not already contain the value
-                seen_messages.insert(x.clone()))
+                seen_messages.insert(&x.message))
             .cloned()
+            .collect()
+    }
+
+    fn join_validation_results(
+        &self,
+        validation_fns: &[Result<(), SnsValidationError>],
+    ) -> Result<Self, String> {
+        let defect_messages = Self::collect_errors(validation_fns)
+            .into_iter()
+            .map(|error| error.message)
             .collect::<Vec<String>>()
             .join("\n");
 
@@ -708,11 +915,14 @@ impl SnsInitPayload {
         }
     }
 
-    fn validate_token_symbol(&self) -> Result<(), String> {
-        let token_symbol = self
-            .token_symbol
-            .as_ref()
-            .ok_or_else(|| "Error: token-symbol must be specified".to_string())?;
+    fn validate_token_symbol(&self) -> Result<(), SnsValidationError> {
+        let token_symbol = self.token_symbol.as_ref().ok_or_else(|| {
+            SnsValidationError::new(
+                "token.symbol",
+                SnsValidationErrorCode::Missing,
+                "Error: token-symbol must be specified",
+            )
+        })?;
 
         // The maximum number of characters allowed for token symbol.
         const MAX_TOKEN_SYMBOL_LENGTH: usize = 10;
@@ -724,37 +934,56 @@ impl SnsInitPayload {
         const BANNED_TOKEN_SYMBOLS: &[&str] = &["ICP", "DFINITY"];
 
         if token_symbol.len() > MAX_TOKEN_SYMBOL_LENGTH {
-            return Err(format!(
+            return Err(SnsValidationError::new(
+                "token.symbol",
+                SnsValidationErrorCode::TooLong,
+                format!(
                 "Error: token-symbol must be fewer than {} characters, given character count: {}",
                 MAX_TOKEN_SYMBOL_LENGTH,
                 token_symbol.len()
+            ),
             ));
         }
 
         if token_symbol.len() < MIN_TOKEN_SYMBOL_LENGTH {
-            return Err(format!(
+            return Err(SnsValidationError::new(
+                "token.symbol",
+                SnsValidationErrorCode::TooShort,
+                format!(
                 "Error: token-symbol must be greater than {} characters, given character count: {}",
                 MIN_TOKEN_SYMBOL_LENGTH,
                 token_symbol.len()
+            ),
             ));
         }
 
         if token_symbol != token_symbol.trim() {
-            return Err("Token symbol must not have leading or trailing whitespaces".to_string());
+            return Err(SnsValidationError::new(
+                "token.symbol",
+                SnsValidationErrorCode::InvalidFormat,
+                "Token symbol must not have leading or trailing whitespaces".to_string(),
+            ));
         }
 
         if BANNED_TOKEN_SYMBOLS.contains(&token_symbol.to_uppercase().as_ref()) {
-            return Err("Banned token symbol, please chose another one.".to_string());
+            return Err(SnsValidationError::new(
+                "token.symbol",
+                SnsValidationErrorCode::NotAllowed,
+                "Banned token symbol, please chose another one.".to_string(),
+            ));
         }
 
         Ok(())
     }
 
-    fn validate_token_name(&self) -> Result<(), String> {
-        let token_name = self
-            .token_name
-            .as_ref()
-            .ok_or_else(|| "Error: token-name must be specified".to_string())?;
+    fn validate_token_name(&self) -> Result<(), SnsValidationError> {
+        let token_name = self.token_name.as_ref().ok_or_else(|| {
+            SnsValidationError::new(
+                "token.name",
+                SnsValidationErrorCode::Missing,
+                "Error: token-name must be specified",
+            )
+        })?;
 
         // The maximum number of characters allowed for token name.
         const MAX_TOKEN_NAME_LENGTH: usize = 255;
@@ -766,23 +995,35 @@ impl SnsInitPayload {
         const BANNED_TOKEN_NAMES: &[&str] = &["internetcomputer", "internetcomputerprotocol"];
 
         if token_name.len() > MAX_TOKEN_NAME_LENGTH {
-            return Err(format!(
-                "Error: token-name must be fewer than {} characters, given character count: {}",
-                MAX_TOKEN_NAME_LENGTH,
-                token_name.len()
+            return Err(SnsValidationError::new(
+                "token.name",
+                SnsValidationErrorCode::TooLong,
+                format!(
+                    "Error: token-name must be fewer than {} characters, given character count: {}",
+                    MAX_TOKEN_NAME_LENGTH,
+                    token_name.len()
+                ),
             ));
         }
 
         if token_name.len() < MIN_TOKEN_NAME_LENGTH {
-            return Err(format!(
+            return Err(SnsValidationError::new(
+                "token.name",
+                SnsValidationErrorCode::TooShort,
+                format!(
                 "Error: token-name must be greater than {} characters, given character count: {}",
                 MIN_TOKEN_NAME_LENGTH,
                 token_name.len()
+            ),
             ));
         }
 
         if token_name != token_name.trim() {
-            return Err("Token name must not have leading or trailing whitespaces".to_string());
+            return Err(SnsValidationError::new(
+                "token.name",
+                SnsValidationErrorCode::InvalidFormat,
+                "Token name must not have leading or trailing whitespaces".to_string(),
+            ));
         }
 
         if BANNED_TOKEN_NAMES.contains(
@@ -793,17 +1034,24 @@ impl SnsInitPayload {
                 .collect::<String>()
                 .as_ref(),
         ) {
-            return Err("Banned token name, please chose another one.".to_string());
+            return Err(SnsValidationError::new(
+                "token.name",
+                SnsValidationErrorCode::NotAllowed,
+                "Banned token name, please chose another one.".to_string(),
+            ));
         }
 
         Ok(())
     }
 
-    fn validate_token_logo(&self) -> Result<(), String> {
-        let token_logo = self
-            .token_logo
-            .as_ref()
-            .ok_or_else(|| "Error: token_logo must be specified".to_string())?;
+    fn validate_token_logo(&self) -> Result<(), SnsValidationError> {
+        let token_logo = self.token_logo.as_ref().ok_or_else(|| {
+            SnsValidationError::new(
+                "token.logo",
+                SnsValidationErrorCode::Missing,
+                "Error: token_logo must be specified",
+            )
+        })?;
 
         const PREFIX: &str = "data:image/png;base64,";
         // The maximum number of characters allowed for a SNS logo encoding.
@@ -811,16 +1059,24 @@ impl SnsInitPayload {
         const MAX_LOGO_LENGTH: usize = 341334;
 
         if token_logo.len() > MAX_LOGO_LENGTH {
-            return Err(format!(
-                "Error: token_logo must be less than {} characters, roughly 256 Kb",
-                MAX_LOGO_LENGTH
+            return Err(SnsValidationError::new(
+                "token.logo",
+                SnsValidationErrorCode::TooLong,
+                format!(
+                    "Error: token_logo must be less than {} characters, roughly 256 Kb",
+                    MAX_LOGO_LENGTH
+                ),
             ));
         }
 
         if !token_logo.starts_with(PREFIX) {
-            return Err(format!(
-                "Error: token_logo must be a base64 encoded PNG, but the provided \
+            return Err(SnsValidationError::new(
+                "token.logo",
+                SnsValidationErrorCode::InvalidFormat,
+                format!(
+                    "Error: token_logo must be a base64 encoded PNG, but the provided \
                 string doesn't begin with `{PREFIX}`."
+                ),
             ));
         }
 
@@ -832,11 +1088,15 @@ impl SnsInitPayload {
         Ok(())
     }
 
-    fn validate_token_distribution(&self) -> Result<(), String> {
-        let initial_token_distribution = self
-            .initial_token_distribution
-            .as_ref()
-            .ok_or_else(|| "Error: initial-token-distribution must be specified".to_string())?;
+    fn validate_token_distribution(&self) -> Result<(), SnsValidationError> {
+        let initial_token_distribution =
+            self.initial_token_distribution.as_ref().ok_or_else(|| {
+                SnsValidationError::new(
+                    "distribution",
+                    SnsValidationErrorCode::Missing,
+                    "Error: initial-token-distribution must be specified",
+                )
+            })?;
 
         let nervous_system_parameters = self.get_nervous_system_parameters();
 
@@ -849,40 +1109,59 @@ impl SnsInitPayload {
         Ok(())
     }
 
-    fn validate_transaction_fee_e8s(&self) -> Result<(), String> {
+    fn validate_transaction_fee_e8s(&self) -> Result<(), SnsValidationError> {
         match self.transaction_fee_e8s {
             Some(_) => Ok(()),
-            None => Err("Error: transaction_fee_e8s must be specified.".to_string()),
+            None => Err(SnsValidationError::new(
+                "token.transaction_fee_e8s",
+                SnsValidationErrorCode::Missing,
+                "Error: transaction_fee_e8s must be specified.".to_string(),
+            )),
         }
     }
 
-    fn validate_proposal_reject_cost_e8s(&self) -> Result<(), String> {
+    fn validate_proposal_reject_cost_e8s(&self) -> Result<(), SnsValidationError> {
         match self.proposal_reject_cost_e8s {
             Some(_) => Ok(()),
-            None => Err("Error: proposal_reject_cost_e8s must be specified.".to_string()),
+            None => Err(SnsValidationError::new(
+                "governance.proposal_reject_cost_e8s",
+                SnsValidationErrorCode::Missing,
+                "Error: proposal_reject_cost_e8s must be specified.".to_string(),
+            )),
         }
     }
 
-    fn validate_neuron_minimum_stake_e8s(&self) -> Result<(), String> {
+    fn validate_neuron_minimum_stake_e8s(&self) -> Result<(), SnsValidationError> {
         let neuron_minimum_stake_e8s = self
             .neuron_minimum_stake_e8s
             .expect("Error: neuron_minimum_stake_e8s must be specified.");
-        let initial_token_distribution = self
-            .initial_token_distribution
-            .as_ref()
-            .ok_or_else(|| "Error: initial-token-distribution must be specified".to_string())?;
+        let initial_token_distribution =
+            self.initial_token_distribution.as_ref().ok_or_else(|| {
+                SnsValidationError::new(
+                    "distribution",
+                    SnsValidationErrorCode::Missing,
+                    "Error: initial-token-distribution must be specified",
+                )
+            })?;
 
         match initial_token_distribution {
             InitialTokenDistribution::FractionalDeveloperVotingPower(f) => {
-                let developer_distribution = f
-                    .developer_distribution
-                    .as_ref()
-                    .ok_or_else(|| "Error: developer_distribution must be specified".to_string())?;
-
-                let airdrop_distribution = f
-                    .airdrop_distribution
-                    .as_ref()
-                    .ok_or_else(|| "Error: airdrop_distribution must be specified".to_string())?;
+                let developer_distribution =
+                    f.developer_distribution.as_ref().ok_or_else(|| {
+                        SnsValidationError::new(
+                            "distribution.developer_distribution",
+                            SnsValidationErrorCode::Missing,
+                            "Error: developer_distribution must be specified",
+                        )
+                    })?;
+
+                let airdrop_distribution = f.airdrop_distribution.as_ref().ok_or_else(|| {
+                    SnsValidationError::new(
+                        "distribution.airdrop_distribution",
+                        SnsValidationErrorCode::Missing,
+                        "Error: airdrop_distribution must be specified",
+                    )
+                })?;
 
                 let min_stake_infringing_developer_neurons: Vec<(Principal, u64)> =
                     developer_distribution
@@ -902,11 +1181,15 @@ impl SnsInitPayload {
                         .collect();
 
                 if !min_stake_infringing_developer_neurons.is_empty() {
-                    return Err(format!(
-                        "Error: {} developer neurons have a stake below the minimum stake ({} e8s):  \n {:?}",
-                        min_stake_infringing_developer_neurons.len(),
-                        neuron_minimum_stake_e8s,
-                        min_stake_infringing_developer_neurons,
+                    return Err(SnsValidationError::new(
+                        "distribution.developer_distribution.developer_neurons",
+                        SnsValidationErrorCode::OutOfRange,
+                        format!(
+                            "Error: {} developer neurons have a stake below the minimum stake ({} e8s):  \n {:?}",
+                            min_stake_infringing_developer_neurons.len(),
+                            neuron_minimum_stake_e8s,
+                            min_stake_infringing_developer_neurons,
+                        ),
                     ));
                 }
 
@@ -928,11 +1211,15 @@ impl SnsInitPayload {
                         .collect();
 
                 if !min_stake_infringing_airdrop_neurons.is_empty() {
-                    return Err(format!(
-                        "Error: {} airdrop neurons have a stake below the minimum stake ({} e8s):  \n {:?}",
-                        min_stake_infringing_airdrop_neurons.len(),
-                        neuron_minimum_stake_e8s,
-                        min_stake_infringing_airdrop_neurons,
+                    return Err(SnsValidationError::new(
+                        "distribution.airdrop_distribution.airdrop_neurons",
+                        SnsValidationErrorCode::OutOfRange,
+                        format!(
+                            "Error: {} airdrop neurons have a stake below the minimum stake ({} e8s):  \n {:?}",
+                            min_stake_infringing_airdrop_neurons.len(),
+                            neuron_minimum_stake_e8s,
+                            min_stake_infringing_airdrop_neurons,
+                        ),
                     ));
                 }
             }
@@ -941,7 +1228,9 @@ impl SnsInitPayload {
         Ok(())
     }
 
-    fn validate_neuron_minimum_dissolve_delay_to_vote_seconds(&self) -> Result<(), String> {
+    fn validate_neuron_minimum_dissolve_delay_to_vote_seconds(
+        &self,
+    ) -> Result<(), SnsValidationError> {
         // As this is not currently configurable, pull the default value from
         let max_dissolve_delay_seconds = *NervousSystemParameters::with_default_values()
             .max_dissolve_delay_seconds
@@ -951,37 +1240,51 @@ impl SnsInitPayload {
         let neuron_minimum_dissolve_delay_to_vote_seconds = self
             .neuron_minimum_dissolve_delay_to_vote_seconds
             .ok_or_else(|| {
-                "Error: neuron-minimum-dissolve-delay-to-vote-seconds must be specified".to_string()
+                SnsValidationError::new(
+                    "governance.neuron_minimum_dissolve_delay_to_vote_seconds",
+                    SnsValidationErrorCode::Missing,
+                    "Error: neuron-minimum-dissolve-delay-to-vote-seconds must be specified",
+                )
             })?;
 
         if neuron_minimum_dissolve_delay_to_vote_seconds > max_dissolve_delay_seconds {
-            return Err(format!(
-                "The minimum dissolve delay to vote ({}) cannot be greater than the max \
+            return Err(SnsValidationError::new(
+                "governance.neuron_minimum_dissolve_delay_to_vote_seconds",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
+                    "The minimum dissolve delay to vote ({}) cannot be greater than the max \
                 dissolve delay ({})",
-                neuron_minimum_dissolve_delay_to_vote_seconds, max_dissolve_delay_seconds
+                    neuron_minimum_dissolve_delay_to_vote_seconds, max_dissolve_delay_seconds
+                ),
             ));
         }
 
         Ok(())
     }
 
-    fn validate_fallback_controller_principal_ids(&self) -> Result<(), String> {
+    fn validate_fallback_controller_principal_ids(&self) -> Result<(), SnsValidationError> {
         if self.fallback_controller_principal_ids.is_empty() {
-            return Err(
+            return Err(SnsValidationError::new(
+                "fallback_controller_principal_ids",
+                SnsValidationErrorCode::Missing,
                 "Error: At least one principal ID must be supplied as a fallback controller \
                  in case the initial token swap fails."
                     .to_string(),
-            );
+            ));
         }
 
         if self.fallback_controller_principal_ids.len()
             > MAX_FALLBACK_CONTROLLER_PRINCIPAL_IDS_COUNT
         {
-            return Err(format!(
-                "Error: The number of fallback_controller_principal_ids \
+            return Err(SnsValidationError::new(
+                "fallback_controller_principal_ids",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
+                    "Error: The number of fallback_controller_principal_ids \
                 must be less than {}. Current count is {}",
-                MAX_FALLBACK_CONTROLLER_PRINCIPAL_IDS_COUNT,
-                self.fallback_controller_principal_ids.len()
+                    MAX_FALLBACK_CONTROLLER_PRINCIPAL_IDS_COUNT,
+                    self.fallback_controller_principal_ids.len()
+                ),
             ));
         }
 
@@ -997,13 +1300,17 @@ impl SnsInitPayload {
             .partition(|item| item.1.is_ok());
 
         if !invalid_principals.is_empty() {
-            return Err(format!(
+            return Err(SnsValidationError::new(
+                "fallback_controller_principal_ids",
+                SnsValidationErrorCode::InvalidFormat,
+                format!(
                 "Error: One or more fallback_controller_principal_ids is not a valid principal id. \
                 The follow principals are invalid: {:?}",
                 invalid_principals
                     .into_iter()
                     .map(|pair| pair.0)
                     .collect::<Vec<_>>()
+            ),
             ));
         }
 
@@ -1014,33 +1321,46 @@ impl SnsInitPayload {
             .collect();
 
         if unique_principals.len() != valid_principals.len() {
-            return Err(
+            return Err(SnsValidationError::new(
+                "fallback_controller_principal_ids",
+                SnsValidationErrorCode::Duplicate,
                 "Error: Duplicate PrincipalIds found in fallback_controller_principal_ids"
                     .to_string(),
-            );
+            ));
         }
 
         Ok(())
     }
 
-    fn validate_logo(&self) -> Result<(), String> {
-        let logo = self
-            .logo
-            .as_ref()
-            .ok_or_else(|| "Error: logo must be specified".to_string())?;
+    fn validate_logo(&self) -> Result<(), SnsValidationError> {
+        let logo = self.logo.as_ref().ok_or_else(|| {
+            SnsValidationError::new(
+                "metadata.logo",
+                SnsValidationErrorCode::Missing,
+                "Error: logo must be specified",
+            )
+        })?;
 
         const PREFIX: &str = "data:image/png;base64,";
         const MAX_LOGO_LENGTH: usize = 341334;
 
         // TODO: Should we check that it's a valid PNG?
         if logo.len() > MAX_LOGO_LENGTH {
-            return Err(format!(
-                "SnsMetadata.logo must be less than {} characters, roughly 256 Kb",
-                MAX_LOGO_LENGTH
+            return Err(SnsValidationError::new(
+                "metadata.logo",
+                SnsValidationErrorCode::TooLong,
+                format!(
+                    "SnsMetadata.logo must be less than {} characters, roughly 256 Kb",
+                    MAX_LOGO_LENGTH
+                ),
             ));
         }
         if !logo.starts_with(PREFIX) {
-            return Err(format!("SnsMetadata.logo must be a base64 encoded PNG, but the provided string does't begin with `{PREFIX}`."));
+            return Err(SnsValidationError::new(
+                "metadata.logo",
+                SnsValidationErrorCode::InvalidFormat,
+                format!("SnsMetadata.logo must be a base64 encoded PNG, but the provided string does't begin with `{PREFIX}`."),
+            ));
         }
 
         // TODO: add b64 validation
@@ -1050,47 +1370,71 @@ impl SnsInitPayload {
         Ok(())
     }
 
-    fn validate_url(&self) -> Result<(), String> {
-        let url = self.url.as_ref().ok_or("Error: url must be specified")?;
+    fn validate_url(&self) -> Result<(), SnsValidationError> {
+        let url = self.url.as_ref().ok_or_else(|| {
+            SnsValidationError::new(
+                "metadata.url",
+                SnsValidationErrorCode::Missing,
+                "Error: url must be specified",
+            )
+        })?;
         let field_name = "SnsMetadata.url";
         let max_length = 512;
         let min_length = 10;
         // // Check that the URL is a sensible length
         if url.len() > max_length {
-            return Err(format!(
-                "{field_name} must be less than {max_length} characters long, but it is {} characters long. (Field was set to `{url}`.)",
-                url.len(),
+            return Err(SnsValidationError::new(
+                "metadata.url",
+                SnsValidationErrorCode::TooLong,
+                format!(
+                    "{field_name} must be less than {max_length} characters long, but it is {} characters long. (Field was set to `{url}`.)",
+                    url.len(),
+                ),
             ));
         }
         if url.len() < min_length {
-            return Err(format!(
-                "{field_name} must be greater or equal to than {min_length} characters long, but it is {} characters long. (Field was set to `{url}`.)",
-                url.len(),
+            return Err(SnsValidationError::new(
+                "metadata.url",
+                SnsValidationErrorCode::TooShort,
+                format!(
+                    "{field_name} must be greater or equal to than {min_length} characters long, but it is {} characters long. (Field was set to `{url}`.)",
+                    url.len(),
+                ),
             ));
         }
 
         //
 
         if !url.starts_with("https://") {
-            return Err(format!(
-                "{field_name} must begin with https://. (Field was set to `{url}`.)",
+            return Err(SnsValidationError::new(
+                "metadata.url",
+                SnsValidationErrorCode::InvalidFormat,
+                format!("{field_name} must begin with https://. (Field was set to `{url}`.)",),
             ));
         }
 
         let parts_url: Vec<&str> = url.split("://").collect();
         if parts_url.len() > 2 {
-            return Err(format!(
-                "{field_name} contains an invalid sequence of characters"
+            return Err(SnsValidationError::new(
+                "metadata.url",
+                SnsValidationErrorCode::InvalidFormat,
+                format!("{field_name} contains an invalid sequence of characters"),
             ));
         }
 
         if parts_url.len() < 2 {
-            return Err(format!("{field_name} is missing content after protocol."));
+            return Err(SnsValidationError::new(
+                "metadata.url",
+                SnsValidationErrorCode::InvalidFormat,
+                format!("{field_name} is missing content after protocol."),
+            ));
         }
 
         if url.contains('@') {
-            return Err(format!(
-                "{field_name} cannot contain authentication information"
+            return Err(SnsValidationError::new(
+                "metadata.url",
+                SnsValidationErrorCode::InvalidFormat,
+                format!("{field_name} cannot contain authentication information"),
             ));
         }
 
@@ -1103,216 +1447,338 @@ impl SnsInitPayload {
         Ok(())
     }
 
-    fn validate_name(&self) -> Result<(), String> {
+    fn validate_name(&self) -> Result<(), SnsValidationError> {
         // The maximum number of characters allowed for a SNS name.
         const MAX_NAME_LENGTH: usize = 255;
 
         // The minimum number of characters allowed for a SNS name.
         const MIN_NAME_LENGTH: usize = 4;
-        let name = self.name.as_ref().ok_or("Error: name must be specified")?;
+        let name = self.name.as_ref().ok_or_else(|| {
+            SnsValidationError::new(
+                "metadata.name",
+                SnsValidationErrorCode::Missing,
+                "Error: name must be specified",
+            )
+        })?;
         if name.len() > MAX_NAME_LENGTH {
-            return Err(format!(
-                "SnsMetadata.name must be less than {} characters",
-                MAX_NAME_LENGTH
+            return Err(SnsValidationError::new(
+                "metadata.name",
+                SnsValidationErrorCode::TooLong,
+                format!(
+                    "SnsMetadata.name must be less than {} characters",
+                    MAX_NAME_LENGTH
+                ),
             ));
         } else if name.len() < MIN_NAME_LENGTH {
-            return Err(format!(
-                "SnsMetadata.name must be greater than {} characters",
-                MIN_NAME_LENGTH
+            return Err(SnsValidationError::new(
+                "metadata.name",
+                SnsValidationErrorCode::TooShort,
+                format!(
+                    "SnsMetadata.name must be greater than {} characters",
+                    MIN_NAME_LENGTH
+                ),
             ));
         }
         Ok(())
     }
 
-    fn validate_description(&self) -> Result<(), String> {
+    fn validate_description(&self) -> Result<(), SnsValidationError> {
         // The maximum number of characters allowed for a SNS description.
         const MAX_DESCRIPTION_LENGTH: usize = 2000;
 
         // The minimum number of characters allowed for a SNS description.
         const MIN_DESCRIPTION_LENGTH: usize = 10;
-        let description = self
-            .description
-            .as_ref()
-            .ok_or("Error: description must be specified")?;
+        let description = self.description.as_ref().ok_or_else(|| {
+            SnsValidationError::new(
+                "metadata.description",
+                SnsValidationErrorCode::Missing,
+                "Error: description must be specified",
+            )
+        })?;
 
         if description.len() > MAX_DESCRIPTION_LENGTH {
-            return Err(format!(
-                "SnsMetadata.description must be less than {} characters",
-                MAX_DESCRIPTION_LENGTH
+            return Err(SnsValidationError::new(
+                "metadata.description",
+                SnsValidationErrorCode::TooLong,
+                format!(
+                    "SnsMetadata.description must be less than {} characters",
+                    MAX_DESCRIPTION_LENGTH
+                ),
             ));
         } else if description.len() < MIN_DESCRIPTION_LENGTH {
-            return Err(format!(
-                "SnsMetadata.description must be greater than {} characters",
-                MIN_DESCRIPTION_LENGTH
+            return Err(SnsValidationError::new(
+                "metadata.description",
+                SnsValidationErrorCode::TooShort,
+                format!(
+                    "SnsMetadata.description must be greater than {} characters",
+                    MIN_DESCRIPTION_LENGTH
+                ),
             ));
         }
         Ok(())
     }
 
-    fn validate_initial_reward_rate_basis_points(&self) -> Result<(), String> {
-        let initial_reward_rate_basis_points = self
-            .initial_reward_rate_basis_points
-            .ok_or("Error: initial_reward_rate_basis_points must be specified")?;
+    fn validate_initial_reward_rate_basis_points(&self) -> Result<(), SnsValidationError> {
+        let initial_reward_rate_basis_points =
+            self.initial_reward_rate_basis_points.ok_or_else(|| {
+                SnsValidationError::new(
+                    "governance.initial_reward_rate_basis_points",
+                    SnsValidationErrorCode::Missing,
+                    "Error: initial_reward_rate_basis_points must be specified",
+                )
+            })?;
         if initial_reward_rate_basis_points
             > VotingRewardsParameters::INITIAL_REWARD_RATE_BASIS_POINTS_CEILING
         {
-            Err(format!(
-                "Error: initial_reward_rate_basis_points must be less than or equal to {}",
-                VotingRewardsParameters::INITIAL_REWARD_RATE_BASIS_POINTS_CEILING
+            Err(SnsValidationError::new(
+                "governance.initial_reward_rate_basis_points",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
+                    "Error: initial_reward_rate_basis_points must be less than or equal to {}",
+                    VotingRewardsParameters::INITIAL_REWARD_RATE_BASIS_POINTS_CEILING
+                ),
             ))
         } else {
             Ok(())
         }
     }
 
-    fn validate_final_reward_rate_basis_points(&self) -> Result<(), String> {
-        let initial_reward_rate_basis_points = self
-            .initial_reward_rate_basis_points
-            .ok_or("Error: initial_reward_rate_basis_points must be specified")?;
-        let final_reward_rate_basis_points = self
-            .final_reward_rate_basis_points
-            .ok_or("Error: final_reward_rate_basis_points must be specified")?;
+    fn validate_final_reward_rate_basis_points(&self) -> Result<(), SnsValidationError> {
+        let initial_reward_rate_basis_points =
+            self.initial_reward_rate_basis_points.ok_or_else(|| {
+                SnsValidationError::new(
+                    "governance.initial_reward_rate_basis_points",
+                    SnsValidationErrorCode::Missing,
+                    "Error: initial_reward_rate_basis_points must be specified",
+                )
+            })?;
+        let final_reward_rate_basis_points =
+            self.final_reward_rate_basis_points.ok_or_else(|| {
+                SnsValidationError::new(
+                    "governance.final_reward_rate_basis_points",
+                    SnsValidationErrorCode::Missing,
+                    "Error: final_reward_rate_basis_points must be specified",
+                )
+            })?;
         if final_reward_rate_basis_points > initial_reward_rate_basis_points {
-            Err(
+            Err(SnsValidationError::new(
+                "governance.final_reward_rate_basis_points",
+                SnsValidationErrorCode::Inconsistent,
                 format!(
                     "Error: final_reward_rate_basis_points ({}) must be less than or equal to initial_reward_rate_basis_points ({})", final_reward_rate_basis_points,
                     initial_reward_rate_basis_points
-                )
-            )
+                ),
+            ))
         } else {
             Ok(())
         }
     }
 
-    fn validate_reward_rate_transition_duration_seconds(&self) -> Result<(), String> {
+    fn validate_reward_rate_transition_duration_seconds(&self) -> Result<(), SnsValidationError> {
         let _reward_rate_transition_duration_seconds = self
             .reward_rate_transition_duration_seconds
-            .ok_or("Error: reward_rate_transition_duration_seconds must be specified")?;
+            .ok_or_else(|| {
+                SnsValidationError::new(
+                    "governance.reward_rate_transition_duration_seconds",
+                    SnsValidationErrorCode::Missing,
+                    "Error: reward_rate_transition_duration_seconds must be specified",
+                )
+            })?;
         Ok(())
     }
 
-    fn validate_max_dissolve_delay_seconds(&self) -> Result<(), String> {
-        let _max_dissolve_delay_seconds = self
-            .max_dissolve_delay_seconds
-            .ok_or("Error: max_dissolve_delay_seconds must be specified")?;
+    fn validate_max_dissolve_delay_seconds(&self) -> Result<(), SnsValidationError> {
+        let _max_dissolve_delay_seconds = self.max_dissolve_delay_seconds.ok_or_else(|| {
+            SnsValidationError::new(
+                "governance.max_dissolve_delay_seconds",
+                SnsValidationErrorCode::Missing,
+                "Error: max_dissolve_delay_seconds must be specified",
+            )
+        })?;
         Ok(())
     }
 
-    fn validate_max_neuron_age_seconds_for_age_bonus(&self) -> Result<(), String> {
-        let _max_neuron_age_seconds_for_age_bonus = self
-            .max_neuron_age_seconds_for_age_bonus
-            .ok_or("Error: max_neuron_age_seconds_for_age_bonus must be specified")?;
+    fn validate_max_neuron_age_seconds_for_age_bonus(&self) -> Result<(), SnsValidationError> {
+        let _max_neuron_age_seconds_for_age_bonus =
+            self.max_neuron_age_seconds_for_age_bonus.ok_or_else(|| {
+                SnsValidationError::new(
+                    "governance.max_neuron_age_seconds_for_age_bonus",
+                    SnsValidationErrorCode::Missing,
+                    "Error: max_neuron_age_seconds_for_age_bonus must be specified",
+                )
+            })?;
         Ok(())
     }
 
-    fn validate_max_dissolve_delay_bonus_percentage(&self) -> Result<(), String> {
-        let max_dissolve_delay_bonus_percentage = self
-            .max_dissolve_delay_bonus_percentage
-            .ok_or("Error: max_dissolve_delay_bonus_percentage must be specified")?;
+    fn validate_max_dissolve_delay_bonus_percentage(&self) -> Result<(), SnsValidationError> {
+        let max_dissolve_delay_bonus_percentage =
+            self.max_dissolve_delay_bonus_percentage.ok_or_else(|| {
+                SnsValidationError::new(
+                    "governance.max_dissolve_delay_bonus_percentage",
+                    SnsValidationErrorCode::Missing,
+                    "Error: max_dissolve_delay_bonus_percentage must be specified",
+                )
+            })?;
 
         if max_dissolve_delay_bonus_percentage
             > NervousSystemParameters::MAX_DISSOLVE_DELAY_BONUS_PERCENTAGE_CEILING
         {
-            Err(format!(
-                "max_dissolve_delay_bonus_percentage must be less than {}",
-                NervousSystemParameters::MAX_DISSOLVE_DELAY_BONUS_PERCENTAGE_CEILING
+            Err(SnsValidationError::new(
+                "governance.max_dissolve_delay_bonus_percentage",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
+                    "max_dissolve_delay_bonus_percentage must be less than {}",
+                    NervousSystemParameters::MAX_DISSOLVE_DELAY_BONUS_PERCENTAGE_CEILING
+                ),
             ))
         } else {
             Ok(())
         }
     }
 
-    fn validate_max_age_bonus_percentage(&self) -> Result<(), String> {
-        let max_age_bonus_percentage = self
-            .max_age_bonus_percentage
-            .ok_or("Error: max_age_bonus_percentage must be specified")?;
+    fn validate_max_age_bonus_percentage(&self) -> Result<(), SnsValidationError> {
+        let max_age_bonus_percentage = self.max_age_bonus_percentage.ok_or_else(|| {
+            SnsValidationError::new(
+                "governance.max_age_bonus_percentage",
+                SnsValidationErrorCode::Missing,
+                "Error: max_age_bonus_percentage must be specified",
+            )
+        })?;
         if max_age_bonus_percentage > NervousSystemParameters::MAX_AGE_BONUS_PERCENTAGE_CEILING {
-            Err(format!(
-                "max_age_bonus_percentage must be less than {}",
-                NervousSystemParameters::MAX_AGE_BONUS_PERCENTAGE_CEILING
+            Err(SnsValidationError::new(
+                "governance.max_age_bonus_percentage",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
+                    "max_age_bonus_percentage must be less than {}",
+                    NervousSystemParameters::MAX_AGE_BONUS_PERCENTAGE_CEILING
+                ),
             ))
         } else {
             Ok(())
         }
     }
 
-    fn validate_initial_voting_period_seconds(&self) -> Result<(), String> {
-        let initial_voting_period_seconds = self
-            .initial_voting_period_seconds
-            .ok_or("Error: initial_voting_period_seconds must be specified")?;
+    fn validate_initial_voting_period_seconds(&self) -> Result<(), SnsValidationError> {
+        let initial_voting_period_seconds =
+            self.initial_voting_period_seconds.ok_or_else(|| {
+                SnsValidationError::new(
+                    "governance.initial_voting_period_seconds",
+                    SnsValidationErrorCode::Missing,
+                    "Error: initial_voting_period_seconds must be specified",
+                )
+            })?;
 
         if initial_voting_period_seconds
             < NervousSystemParameters::INITIAL_VOTING_PERIOD_SECONDS_FLOOR
         {
-            Err(format!(
-                "NervousSystemParameters.initial_voting_period_seconds must be greater than {}",
-                NervousSystemParameters::INITIAL_VOTING_PERIOD_SECONDS_FLOOR
+            Err(SnsValidationError::new(
+                "governance.initial_voting_period_seconds",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
+                    "NervousSystemParameters.initial_voting_period_seconds must be greater than {}",
+                    NervousSystemParameters::INITIAL_VOTING_PERIOD_SECONDS_FLOOR
+                ),
             ))
         } else if initial_voting_period_seconds
             > NervousSystemParameters::INITIAL_VOTING_PERIOD_SECONDS_CEILING
         {
-            Err(format!(
-                "NervousSystemParameters.initial_voting_period_seconds must be less than {}",
-                NervousSystemParameters::INITIAL_VOTING_PERIOD_SECONDS_CEILING
+            Err(SnsValidationError::new(
+                "governance.initial_voting_period_seconds",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
+                    "NervousSystemParameters.initial_voting_period_seconds must be less than {}",
+                    NervousSystemParameters::INITIAL_VOTING_PERIOD_SECONDS_CEILING
+                ),
             ))
         } else {
             Ok(())
         }
     }
 
-    fn validate_wait_for_quiet_deadline_increase_seconds(&self) -> Result<(), String> {
+    fn validate_wait_for_quiet_deadline_increase_seconds(&self) -> Result<(), SnsValidationError> {
         let wait_for_quiet_deadline_increase_seconds = self
             .wait_for_quiet_deadline_increase_seconds
-            .ok_or("Error: wait_for_quiet_deadline_increase_seconds must be specified")?;
-        let initial_voting_period_seconds = self
-            .initial_voting_period_seconds
-            .ok_or("Error: initial_voting_period_seconds must be specified")?;
+            .ok_or_else(|| {
+                SnsValidationError::new(
+                    "governance.wait_for_quiet_deadline_increase_seconds",
+                    SnsValidationErrorCode::Missing,
+                    "Error: wait_for_quiet_deadline_increase_seconds must be specified",
+                )
+            })?;
+        let initial_voting_period_seconds =
+            self.initial_voting_period_seconds.ok_or_else(|| {
+                SnsValidationError::new(
+                    "governance.initial_voting_period_seconds",
+                    SnsValidationErrorCode::Missing,
+                    "Error: initial_voting_period_seconds must be specified",
+                )
+            })?;
 
         if wait_for_quiet_deadline_increase_seconds
             < NervousSystemParameters::WAIT_FOR_QUIET_DEADLINE_INCREASE_SECONDS_FLOOR
         {
-            Err(format!(
-                "NervousSystemParameters.wait_for_quiet_deadline_increase_seconds must be greater than or equal to {}",
-                NervousSystemParameters::WAIT_FOR_QUIET_DEADLINE_INCREASE_SECONDS_FLOOR
+            Err(SnsValidationError::new(
+                "governance.wait_for_quiet_deadline_increase_seconds",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
+                    "NervousSystemParameters.wait_for_quiet_deadline_increase_seconds must be greater than or equal to {}",
+                    NervousSystemParameters::WAIT_FOR_QUIET_DEADLINE_INCREASE_SECONDS_FLOOR
+                ),
             ))
         } else if wait_for_quiet_deadline_increase_seconds
             > NervousSystemParameters::WAIT_FOR_QUIET_DEADLINE_INCREASE_SECONDS_CEILING
         {
-            Err(format!(
-                "NervousSystemParameters.wait_for_quiet_deadline_increase_seconds must be less than or equal to {}",
-                NervousSystemParameters::WAIT_FOR_QUIET_DEADLINE_INCREASE_SECONDS_CEILING
+            Err(SnsValidationError::new(
+                "governance.wait_for_quiet_deadline_increase_seconds",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
+                    "NervousSystemParameters.wait_for_quiet_deadline_increase_seconds must be less than or equal to {}",
+                    NervousSystemParameters::WAIT_FOR_QUIET_DEADLINE_INCREASE_SECONDS_CEILING
+                ),
             ))
         // If `wait_for_quiet_deadline_increase_seconds > initial_voting_period_seconds / 2`, any flip (including an initial `yes` vote)
         // will always cause the deadline to be increased. That seems like unreasonable behavior, so we prevent that from being
         // the case.
         } else if wait_for_quiet_deadline_increase_seconds > initial_voting_period_seconds / 2 {
-            Err(format!(
-                "NervousSystemParameters.wait_for_quiet_deadline_increase_seconds is {}, but must be less than or equal to half the initial voting period, {}",
-                initial_voting_period_seconds, initial_voting_period_seconds / 2
+            Err(SnsValidationError::new(
+                "governance.wait_for_quiet_deadline_increase_seconds",
+                SnsValidationErrorCode::Inconsistent,
+                format!(
+                    "NervousSystemParameters.wait_for_quiet_deadline_increase_seconds is {}, but must be less than or equal to half the initial voting period, {}",
+                    initial_voting_period_seconds, initial_voting_period_seconds / 2
+                ),
             ))
         } else {
             Ok(())
         }
     }
 
-    fn validate_dapp_canisters(&self) -> Result<(), String> {
+    fn validate_dapp_canisters(&self) -> Result<(), SnsValidationError> {
         let dapp_canisters = match &self.dapp_canisters {
             None => return Ok(()),
             Some(dapp_canisters) => dapp_canisters,
         };
 
         if dapp_canisters.canisters.len() > MAX_DAPP_CANISTERS_COUNT {
-            return Err(format!(
+            return Err(SnsValidationError::new(
+                "dapp_canisters",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
                 "Error: The number of dapp_canisters exceeded the maximum allowed canisters at \
                 initialization. Count is {}. Maximum allowed is {}.",
                 dapp_canisters.canisters.len(),
                 MAX_DAPP_CANISTERS_COUNT,
+            ),
             ));
         }
 
         for (index, canister) in dapp_canisters.canisters.iter().enumerate() {
             if canister.id.is_none() {
-                return Err(format!("Error: dapp_canisters[{}] id field is None", index));
+                return Err(SnsValidationError::new(
+                    "dapp_canisters",
+                    SnsValidationErrorCode::Missing,
+                    format!("Error: dapp_canisters[{}] id field is None", index),
+                ));
             }
         }
 
@@ -1324,7 +1790,11 @@ impl SnsInitPayload {
             .map(|canister| canister.id)
             .collect();
         if unique_dapp_canisters.len() != dapp_canisters.canisters.len() {
-            return Err("Error: Duplicate ids found in dapp_canisters".to_string());
+            return Err(SnsValidationError::new(
+                "dapp_canisters",
+                SnsValidationErrorCode::Duplicate,
+                "Error: Duplicate ids found in dapp_canisters".to_string(),
+            ));
         }
 
         // let nns_canisters = &[
@@ -1366,41 +1836,47 @@ impl SnsInitPayload {
         Ok(())
     }
 
-    fn validate_confirmation_text(&self) -> Result<(), String> {
+    fn validate_confirmation_text(&self) -> Result<(), SnsValidationError> {
         if let Some(confirmation_text) = &self.confirmation_text {
             if MAX_CONFIRMATION_TEXT_BYTES < confirmation_text.len() {
-                return Err(
+                return Err(SnsValidationError::new(
+                    "swap.confirmation_text",
+                    SnsValidationErrorCode::TooLong,
                     format!(
                         "NervousSystemParameters.confirmation_text must be fewer than {} bytes, given bytes: {}",
                         MAX_CONFIRMATION_TEXT_BYTES,
                         confirmation_text.len(),
-                    )
-                );
+                    ),
+                ));
             }
             let confirmation_text_length = confirmation_text.chars().count();
             if confirmation_text_length < MIN_CONFIRMATION_TEXT_LENGTH {
-                return Err(
+                return Err(SnsValidationError::new(
+                    "swap.confirmation_text",
+                    SnsValidationErrorCode::TooShort,
                     format!(
                         "NervousSystemParameters.confirmation_text must be greater than {} characters, given character count: {}",
                         MIN_CONFIRMATION_TEXT_LENGTH,
                         confirmation_text_length,
-                    )
-                );
+                    ),
+                ));
             }
             if MAX_CONFIRMATION_TEXT_LENGTH < confirmation_text_length {
-                return Err(
+                return Err(SnsValidationError::new(
+                    "swap.confirmation_text",
+                    SnsValidationErrorCode::TooLong,
                     format!(
                         "NervousSystemParameters.confirmation_text must be fewer than {} characters, given character count: {}",
                         MAX_CONFIRMATION_TEXT_LENGTH,
                         confirmation_text_length,
-                    )
-                );
+                    ),
+                ));
             }
         }
         Ok(())
     }
 
-    fn validate_restricted_countries(&self) -> Result<(), String> {
+    fn validate_restricted_countries(&self) -> Result<(), SnsValidationError> {
         // if let Some(restricted_countries) = &self.restricted_countries {
         //     if restricted_countries.iso_codes.is_empty() {
         //         return RestrictedCountriesValidationError::EmptyList.into();
@@ -1427,17 +1903,27 @@ impl SnsInitPayload {
         Ok(())
     }
 
-    fn validate_neuron_basket_construction_params(&self) -> Result<(), String> {
+    fn validate_neuron_basket_construction_params(&self) -> Result<(), SnsValidationError> {
         let neuron_basket_construction_parameters = self
             .neuron_basket_construction_parameters
             .as_ref()
-            .ok_or("Error: neuron_basket_construction_parameters must be specified")?;
+            .ok_or_else(|| {
+                SnsValidationError::new(
+                    "swap.neuron_basket_construction_parameters",
+                    SnsValidationErrorCode::Missing,
+                    "Error: neuron_basket_construction_parameters must be specified",
+                )
+            })?;
 
         // Check that `NeuronBasket` dissolve delay does not exceed
         // the maximum dissolve delay.
-        let max_dissolve_delay_seconds = self
-            .max_dissolve_delay_seconds
-            .ok_or("Error: max_dissolve_delay_seconds must be specified")?;
+        let max_dissolve_delay_seconds = self.max_dissolve_delay_seconds.ok_or_else(|| {
+            SnsValidationError::new(
+                "governance.max_dissolve_delay_seconds",
+                SnsValidationErrorCode::Missing,
+                "Error: max_dissolve_delay_seconds must be specified",
+            )
+        })?;
         // The maximal dissolve delay of a neuron from a basket created by
         // `NeuronBasketConstructionParameters::generate_vesting_schedule`
         // will equal `(count - 1) * dissolve_delay_interval_seconds`.
@@ -1466,139 +1952,227 @@ impl SnsInitPayload {
         Ok(())
     }
 
-    fn validate_min_participants(&self) -> Result<(), String> {
-        let min_participants = self
-            .min_participants
-            .ok_or("Error: min_participants must be specified")?;
+    fn validate_min_participants(&self) -> Result<(), SnsValidationError> {
+        let min_participants = self.min_participants.ok_or_else(|| {
+            SnsValidationError::new(
+                "swap.min_participants",
+                SnsValidationErrorCode::Missing,
+                "Error: min_participants must be specified",
+            )
+        })?;
 
         if min_participants == 0 {
-            return Err("Error: min_participants must be > 0".to_string());
+            return Err(SnsValidationError::new(
+                "swap.min_participants",
+                SnsValidationErrorCode::OutOfRange,
+                "Error: min_participants must be > 0".to_string(),
+            ));
         }
 
         // Needed as the SwapInit min_participants field is a u32
         if min_participants > (u32::MAX as u64) {
-            return Err(format!(
-                "Error: min_participants cannot be greater than {}",
-                u32::MAX
+            return Err(SnsValidationError::new(
+                "swap.min_participants",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
+                    "Error: min_participants cannot be greater than {}",
+                    u32::MAX
+                ),
             ));
         }
 
         Ok(())
     }
 
-    fn validate_min_direct_participation_icp_e8s(&self) -> Result<(), String> {
-        let min_direct_participation_icp_e8s = self
-            .min_direct_participation_icp_e8s
-            .ok_or("Error: min_direct_participation_icp_e8s must be specified")?;
+    fn validate_min_direct_participation_icp_e8s(&self) -> Result<(), SnsValidationError> {
+        let min_direct_participation_icp_e8s =
+            self.min_direct_participation_icp_e8s.ok_or_else(|| {
+                SnsValidationError::new(
+                    "swap.min_direct_participation_icp_e8s",
+                    SnsValidationErrorCode::Missing,
+                    "Error: min_direct_participation_icp_e8s must be specified",
+                )
+            })?;
 
         if min_direct_participation_icp_e8s == 0 {
-            return Err("Error: min_direct_participation_icp_e8s must be > 0".to_string());
+            return Err(SnsValidationError::new(
+                "swap.min_direct_participation_icp_e8s",
+                SnsValidationErrorCode::OutOfRange,
+                "Error: min_direct_participation_icp_e8s must be > 0".to_string(),
+            ));
         }
 
         Ok(())
     }
 
-    fn validate_max_icp_e8s(&self) -> Result<(), String> {
+    fn validate_max_icp_e8s(&self) -> Result<(), SnsValidationError> {
         if self.max_icp_e8s.is_some() {
-            return Err(
+            return Err(SnsValidationError::new(
+                "swap.max_icp_e8s",
+                SnsValidationErrorCode::MustBeUnset,
                 "Error: max_icp_e8s cannot be specified now that Matched Funding is enabled"
                     .to_string(),
-            );
+            ));
         }
 
         Ok(())
     }
 
-    fn validate_min_icp_e8s(&self) -> Result<(), String> {
+    fn validate_min_icp_e8s(&self) -> Result<(), SnsValidationError> {
         if self.min_icp_e8s.is_some() {
-            return Err(
+            return Err(SnsValidationError::new(
+                "swap.min_icp_e8s",
+                SnsValidationErrorCode::MustBeUnset,
                 "Error: min_icp_e8s cannot be specified now that Matched Funding is enabled"
                     .to_string(),
-            );
+            ));
         };
 
         Ok(())
     }
 
-    fn validate_max_direct_participation_icp_e8s(&self) -> Result<(), String> {
-        let max_direct_participation_icp_e8s = self
-            .max_direct_participation_icp_e8s
-            .ok_or("Error: max_direct_participation_icp_e8s must be specified")?;
+    fn validate_max_direct_participation_icp_e8s(&self) -> Result<(), SnsValidationError> {
+        let max_direct_participation_icp_e8s =
+            self.max_direct_participation_icp_e8s.ok_or_else(|| {
+                SnsValidationError::new(
+                    "swap.max_direct_participation_icp_e8s",
+                    SnsValidationErrorCode::Missing,
+                    "Error: max_direct_participation_icp_e8s must be specified",
+                )
+            })?;
 
-        let min_direct_participation_icp_e8s = self
-            .min_direct_participation_icp_e8s
-            .ok_or("Error: min_direct_participation_icp_e8s must be specified")?;
+        let min_direct_participation_icp_e8s =
+            self.min_direct_participation_icp_e8s.ok_or_else(|| {
+                SnsValidationError::new(
+                    "swap.min_direct_participation_icp_e8s",
+                    SnsValidationErrorCode::Missing,
+                    "Error: min_direct_participation_icp_e8s must be specified",
+                )
+            })?;
 
         if max_direct_participation_icp_e8s < min_direct_participation_icp_e8s {
-            return Err(format!(
-                "max_direct_participation_icp_e8s ({}) must be >= min_direct_participation_icp_e8s ({})",
-                max_direct_participation_icp_e8s, min_direct_participation_icp_e8s
+            return Err(SnsValidationError::new(
+                "swap.max_direct_participation_icp_e8s",
+                SnsValidationErrorCode::Inconsistent,
+                format!(
+                    "max_direct_participation_icp_e8s ({}) must be >= min_direct_participation_icp_e8s ({})",
+                    max_direct_participation_icp_e8s, min_direct_participation_icp_e8s
+                ),
             ));
         }
 
         if max_direct_participation_icp_e8s > MAX_DIRECT_ICP_CONTRIBUTION_TO_SWAP {
-            return Err(format!(
-                "Error: max_direct_participation_icp_e8s ({}) can be at most {} ICP E8s",
-                max_direct_participation_icp_e8s, MAX_DIRECT_ICP_CONTRIBUTION_TO_SWAP
+            return Err(SnsValidationError::new(
+                "swap.max_direct_participation_icp_e8s",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
+                    "Error: max_direct_participation_icp_e8s ({}) can be at most {} ICP E8s",
+                    max_direct_participation_icp_e8s, MAX_DIRECT_ICP_CONTRIBUTION_TO_SWAP
+                ),
             ));
         }
 
-        let min_participants = self
-            .min_participants
-            .ok_or("Error: min_participants must be specified")?;
+        let min_participants = self.min_participants.ok_or_else(|| {
+            SnsValidationError::new(
+                "swap.min_participants",
+                SnsValidationErrorCode::Missing,
+                "Error: min_participants must be specified",
+            )
+        })?;
 
-        let min_participant_icp_e8s = self
-            .min_participant_icp_e8s
-            .ok_or("Error: min_participant_icp_e8s must be specified")?;
+        let min_participant_icp_e8s = self.min_participant_icp_e8s.ok_or_else(|| {
+            SnsValidationError::new(
+                "swap.min_participant_icp_e8s",
+                SnsValidationErrorCode::Missing,
+                "Error: min_participant_icp_e8s must be specified",
+            )
+        })?;
 
         if max_direct_participation_icp_e8s
             < min_participants.saturating_mul(min_participant_icp_e8s)
         {
-            return Err(format!(
-                "Error: max_direct_participation_icp_e8s ({}) must be >= min_participants ({}) * min_participant_icp_e8s ({})",
-                max_direct_participation_icp_e8s, min_participants, min_participant_icp_e8s
+            return Err(SnsValidationError::new(
+                "swap.max_direct_participation_icp_e8s",
+                SnsValidationErrorCode::Inconsistent,
+                format!(
+                    "Error: max_direct_participation_icp_e8s ({}) must be >= min_participants ({}) * min_participant_icp_e8s ({})",
+                    max_direct_participation_icp_e8s, min_participants, min_participant_icp_e8s
+                ),
             ));
         }
 
         Ok(())
     }
 
-    fn validate_min_participant_icp_e8s(&self) -> Result<(), String> {
-        let min_participant_icp_e8s = self
-            .min_participant_icp_e8s
-            .ok_or("Error: min_participant_icp_e8s must be specified")?;
+    fn validate_min_participant_icp_e8s(&self) -> Result<(), SnsValidationError> {
+        let min_participant_icp_e8s = self.min_participant_icp_e8s.ok_or_else(|| {
+            SnsValidationError::new(
+                "swap.min_participant_icp_e8s",
+                SnsValidationErrorCode::Missing,
+                "Error: min_participant_icp_e8s must be specified",
+            )
+        })?;
 
-        let max_direct_participation_icp_e8s = self
-            .max_direct_participation_icp_e8s
-            .ok_or("Error: max_direct_participation_icp_e8s must be specified")?;
+        let max_direct_participation_icp_e8s =
+            self.max_direct_participation_icp_e8s.ok_or_else(|| {
+                SnsValidationError::new(
+                    "swap.max_direct_participation_icp_e8s",
+                    SnsValidationErrorCode::Missing,
+                    "Error: max_direct_participation_icp_e8s must be specified",
+                )
+            })?;
 
-        let sns_transaction_fee_e8s = self
-            .transaction_fee_e8s
-            .ok_or("Error: transaction_fee_e8s must be specified")?;
+        let sns_transaction_fee_e8s = self.transaction_fee_e8s.ok_or_else(|| {
+            SnsValidationError::new(
+                "token.transaction_fee_e8s",
+                SnsValidationErrorCode::Missing,
+                "Error: transaction_fee_e8s must be specified",
+            )
+        })?;
 
-        let neuron_minimum_stake_e8s = self
-            .neuron_minimum_stake_e8s
-            .ok_or("Error: neuron_minimum_stake_e8s must be specified")?;
+        let neuron_minimum_stake_e8s = self.neuron_minimum_stake_e8s.ok_or_else(|| {
+            SnsValidationError::new(
+                "governance.neuron_minimum_stake_e8s",
+                SnsValidationErrorCode::Missing,
+                "Error: neuron_minimum_stake_e8s must be specified",
+            )
+        })?;
 
         let neuron_basket_construction_parameters_count = self
             .neuron_basket_construction_parameters
             .as_ref()
-            .ok_or("Error: neuron_basket_construction_parameters must be specified")?
+            .ok_or_else(|| {
+                SnsValidationError::new(
+                    "swap.neuron_basket_construction_parameters",
+                    SnsValidationErrorCode::Missing,
+                    "Error: neuron_basket_construction_parameters must be specified",
+                )
+            })?
             .count;
 
         let sns_tokens_e8s = self
             .get_swap_distribution()
-            .map_err(|_| "Error: the SwapDistribution must be specified")?
+            .map_err(|_| {
+                SnsValidationError::new(
+                    "distribution.swap_distribution",
+                    SnsValidationErrorCode::Missing,
+                    "Error: the SwapDistribution must be specified",
+                )
+            })?
             .initial_swap_amount_e8s;
 
         let min_participant_sns_e8s = min_participant_icp_e8s as u128 * sns_tokens_e8s as u128
             / max_direct_participation_icp_e8s as u128;
 
         if neuron_minimum_stake_e8s <= sns_transaction_fee_e8s {
-            return Err(format!(
-                "Error: neuron_minimum_stake_e8s={} is too small. It needs to be \
+            return Err(SnsValidationError::new(
+                "governance.neuron_minimum_stake_e8s",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
+                    "Error: neuron_minimum_stake_e8s={} is too small. It needs to be \
                  greater than the transaction fee ({} e8s)",
-                neuron_minimum_stake_e8s, sns_transaction_fee_e8s
+                    neuron_minimum_stake_e8s, sns_transaction_fee_e8s
+                ),
             ));
         }
 
@@ -1607,142 +2181,241 @@ impl SnsInitPayload {
                 * (neuron_minimum_stake_e8s + sns_transaction_fee_e8s) as u128;
 
         if !min_participant_icp_e8s_big_enough {
-            return Err(format!(
-                "Error: min_participant_icp_e8s={} is too small. It needs to be \
+            return Err(SnsValidationError::new(
+                "swap.min_participant_icp_e8s",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
+                    "Error: min_participant_icp_e8s={} is too small. It needs to be \
                  large enough to ensure that participants will end up with \
                  enough SNS tokens to form {} SNS neurons, each of which \
                  require at least {} SNS e8s, plus {} e8s in transaction \
                  fees. More precisely, the following inequality must hold: \
                  min_participant_icp_e8s >= neuron_basket_count * \
                  (neuron_minimum_stake_e8s + transaction_fee_e8s) * max_icp_e8s / sns_tokens_e8s",
-                min_participant_icp_e8s,
-                neuron_basket_construction_parameters_count,
-                neuron_minimum_stake_e8s,
-                sns_transaction_fee_e8s,
+                    min_participant_icp_e8s,
+                    neuron_basket_construction_parameters_count,
+                    neuron_minimum_stake_e8s,
+                    sns_transaction_fee_e8s,
+                ),
             ));
         }
 
         Ok(())
     }
 
-    fn validate_max_participant_icp_e8s(&self) -> Result<(), String> {
-        let max_participant_icp_e8s = self
-            .max_participant_icp_e8s
-            .ok_or("Error: max_participant_icp_e8s must be specified")?;
+    fn validate_max_participant_icp_e8s(&self) -> Result<(), SnsValidationError> {
+        let max_participant_icp_e8s = self.max_participant_icp_e8s.ok_or_else(|| {
+            SnsValidationError::new(
+                "swap.max_participant_icp_e8s",
+                SnsValidationErrorCode::Missing,
+                "Error: max_participant_icp_e8s must be specified",
+            )
+        })?;
 
-        let min_participant_icp_e8s = self
-            .min_participant_icp_e8s
-            .ok_or("Error: min_participant_icp_e8s must be specified")?;
+        let min_participant_icp_e8s = self.min_participant_icp_e8s.ok_or_else(|| {
+            SnsValidationError::new(
+                "swap.min_participant_icp_e8s",
+                SnsValidationErrorCode::Missing,
+                "Error: min_participant_icp_e8s must be specified",
+            )
+        })?;
 
         if max_participant_icp_e8s < min_participant_icp_e8s {
-            return Err(format!(
-                "Error: max_participant_icp_e8s ({}) must be >= min_participant_icp_e8s ({})",
-                max_participant_icp_e8s, min_participant_icp_e8s
+            return Err(SnsValidationError::new(
+                "swap.max_participant_icp_e8s",
+                SnsValidationErrorCode::Inconsistent,
+                format!(
+                    "Error: max_participant_icp_e8s ({}) must be >= min_participant_icp_e8s ({})",
+                    max_participant_icp_e8s, min_participant_icp_e8s
+                ),
             ));
         }
 
-        let max_direct_participation_icp_e8s = self
-            .max_direct_participation_icp_e8s
-            .ok_or("Error: max_direct_participation_icp_e8s must be specified")?;
+        let max_direct_participation_icp_e8s =
+            self.max_direct_participation_icp_e8s.ok_or_else(|| {
+                SnsValidationError::new(
+                    "swap.max_direct_participation_icp_e8s",
+                    SnsValidationErrorCode::Missing,
+                    "Error: max_direct_participation_icp_e8s must be specified",
+                )
+            })?;
 
         if max_participant_icp_e8s > max_direct_participation_icp_e8s {
-            return Err(format!(
-                "max_participant_icp_e8s ({}) must be <= max_direct_participation_icp_e8s ({})",
-                max_participant_icp_e8s, max_direct_participation_icp_e8s
+            return Err(SnsValidationError::new(
+                "swap.max_participant_icp_e8s",
+                SnsValidationErrorCode::Inconsistent,
+                format!(
+                    "max_participant_icp_e8s ({}) must be <= max_direct_participation_icp_e8s ({})",
+                    max_participant_icp_e8s, max_direct_participation_icp_e8s
+                ),
             ));
         }
 
         Ok(())
     }
 
-    fn validate_participation_constraints(&self) -> Result<(), String> {
+    fn validate_participation_constraints(&self) -> Result<(), SnsValidationError> {
         // (1)
-        let min_direct_participation_icp_e8s = self
-            .min_direct_participation_icp_e8s
-            .ok_or("Error: min_direct_participation_icp_e8s must be specified")?;
+        let min_direct_participation_icp_e8s =
+            self.min_direct_participation_icp_e8s.ok_or_else(|| {
+                SnsValidationError::new(
+                    "swap.min_direct_participation_icp_e8s",
+                    SnsValidationErrorCode::Missing,
+                    "Error: min_direct_participation_icp_e8s must be specified",
+                )
+            })?;
 
-        let max_direct_participation_icp_e8s = self
-            .max_direct_participation_icp_e8s
-            .ok_or("Error: max_direct_participation_icp_e8s must be specified")?;
+        let max_direct_participation_icp_e8s =
+            self.max_direct_participation_icp_e8s.ok_or_else(|| {
+                SnsValidationError::new(
+                    "swap.max_direct_participation_icp_e8s",
+                    SnsValidationErrorCode::Missing,
+                    "Error: max_direct_participation_icp_e8s must be specified",
+                )
+            })?;
 
-        let min_participant_icp_e8s = self
-            .min_participant_icp_e8s
-            .ok_or("Error: min_participant_icp_e8s must be specified")?;
+        let min_participant_icp_e8s = self.min_participant_icp_e8s.ok_or_else(|| {
+            SnsValidationError::new(
+                "swap.min_participant_icp_e8s",
+                SnsValidationErrorCode::Missing,
+                "Error: min_participant_icp_e8s must be specified",
+            )
+        })?;
 
-        let max_participant_icp_e8s = self
-            .max_participant_icp_e8s
-            .ok_or("Error: max_participant_icp_e8s must be specified")?;
+        let max_participant_icp_e8s = self.max_participant_icp_e8s.ok_or_else(|| {
+            SnsValidationError::new(
+                "swap.max_participant_icp_e8s",
+                SnsValidationErrorCode::Missing,
+                "Error: max_participant_icp_e8s must be specified",
+            )
+        })?;
 
-        let min_participants = self
-            .min_participants
-            .ok_or("Error: min_participants must be specified")?;
+        let min_participants = self.min_participants.ok_or_else(|| {
+            SnsValidationError::new(
+                "swap.min_participants",
+                SnsValidationErrorCode::Missing,
+                "Error: min_participants must be specified",
+            )
+        })?;
 
         let initial_swap_amount_e8s = self
             .get_swap_distribution()
-            .map_err(|_| "Error: the SwapDistribution must be specified")?
+            .map_err(|_| {
+                SnsValidationError::new(
+                    "distribution.swap_distribution",
+                    SnsValidationErrorCode::Missing,
+                    "Error: the SwapDistribution must be specified",
+                )
+            })?
             .initial_swap_amount_e8s;
 
         let neuron_basket_construction_parameters_count = self
             .neuron_basket_construction_parameters
             .as_ref()
-            .ok_or("Error: neuron_basket_construction_parameters must be specified")?
+            .ok_or_else(|| {
+                SnsValidationError::new(
+                    "swap.neuron_basket_construction_parameters",
+                    SnsValidationErrorCode::Missing,
+                    "Error: neuron_basket_construction_parameters must be specified",
+                )
+            })?
             .count;
 
-        let neuron_minimum_stake_e8s = self
-            .neuron_minimum_stake_e8s
-            .ok_or("Error: neuron_minimum_stake_e8s must be specified")?;
+        let neuron_minimum_stake_e8s = self.neuron_minimum_stake_e8s.ok_or_else(|| {
+            SnsValidationError::new(
+                "governance.neuron_minimum_stake_e8s",
+                SnsValidationErrorCode::Missing,
+                "Error: neuron_minimum_stake_e8s must be specified",
+            )
+        })?;
 
-        let sns_transaction_fee_e8s = self
-            .transaction_fee_e8s
-            .ok_or("Error: transaction_fee_e8s must be specified")?;
+        let sns_transaction_fee_e8s = self.transaction_fee_e8s.ok_or_else(|| {
+            SnsValidationError::new(
+                "token.transaction_fee_e8s",
+                SnsValidationErrorCode::Missing,
+                "Error: transaction_fee_e8s must be specified",
+            )
+        })?;
 
         // (2)
         if min_direct_participation_icp_e8s == 0 {
-            return Err("Error: min_direct_participation_icp_e8s must be > 0".to_string());
+            return Err(SnsValidationError::new(
+                "swap.min_direct_participation_icp_e8s",
+                SnsValidationErrorCode::OutOfRange,
+                "Error: min_direct_participation_icp_e8s must be > 0".to_string(),
+            ));
         }
         if min_participant_icp_e8s == 0 {
-            return Err("Error: min_participant_icp_e8s must be > 0".to_string());
+            return Err(SnsValidationError::new(
+                "swap.min_participant_icp_e8s",
+                SnsValidationErrorCode::OutOfRange,
+                "Error: min_participant_icp_e8s must be > 0".to_string(),
+            ));
         }
         if min_participants == 0 {
-            return Err("Error: min_participants must be > 0".to_string());
+            return Err(SnsValidationError::new(
+                "swap.min_participants",
+                SnsValidationErrorCode::OutOfRange,
+                "Error: min_participants must be > 0".to_string(),
+            ));
         }
         // Needed as the SwapInit min_participants field is a `u32`.
         if min_participants > (u32::MAX as u64) {
-            return Err(format!(
-                "Error: min_participants cannot be greater than {}",
-                u32::MAX
+            return Err(SnsValidationError::new(
+                "swap.min_participants",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
+                    "Error: min_participants cannot be greater than {}",
+                    u32::MAX
+                ),
             ));
         }
 
         // (3)
         if max_direct_participation_icp_e8s < min_direct_participation_icp_e8s {
-            return Err(format!(
-                "Error: max_direct_participation_icp_e8s ({}) \
+            return Err(SnsValidationError::new(
+                "swap.max_direct_participation_icp_e8s",
+                SnsValidationErrorCode::Inconsistent,
+                format!(
+                    "Error: max_direct_participation_icp_e8s ({}) \
                  must be >= min_direct_participation_icp_e8s ({})",
-                max_direct_participation_icp_e8s, min_direct_participation_icp_e8s
+                    max_direct_participation_icp_e8s, min_direct_participation_icp_e8s
+                ),
             ));
         }
         if max_participant_icp_e8s < min_participant_icp_e8s {
-            return Err(format!(
-                "Error: max_participant_icp_e8s ({}) must be >= min_participant_icp_e8s ({})",
-                max_participant_icp_e8s, min_participant_icp_e8s
+            return Err(SnsValidationError::new(
+                "swap.max_participant_icp_e8s",
+                SnsValidationErrorCode::Inconsistent,
+                format!(
+                    "Error: max_participant_icp_e8s ({}) must be >= min_participant_icp_e8s ({})",
+                    max_participant_icp_e8s, min_participant_icp_e8s
+                ),
             ));
         }
 
         // (4)
         if max_participant_icp_e8s > max_direct_participation_icp_e8s {
-            return Err(format!(
-                "Error: max_participant_icp_e8s ({}) \
+            return Err(SnsValidationError::new(
+                "swap.max_participant_icp_e8s",
+                SnsValidationErrorCode::Inconsistent,
+                format!(
+                    "Error: max_participant_icp_e8s ({}) \
                  must be <= max_direct_participation_icp_e8s ({})",
-                max_participant_icp_e8s, max_direct_participation_icp_e8s
+                    max_participant_icp_e8s, max_direct_participation_icp_e8s
+                ),
             ));
         }
 
         // (5)
         if max_direct_participation_icp_e8s > MAX_DIRECT_ICP_CONTRIBUTION_TO_SWAP {
-            return Err(format!(
-                "Error: max_direct_participation_icp_e8s ({}) can be at most {} ICP E8s",
-                max_direct_participation_icp_e8s, MAX_DIRECT_ICP_CONTRIBUTION_TO_SWAP
+            return Err(SnsValidationError::new(
+                "swap.max_direct_participation_icp_e8s",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
+                    "Error: max_direct_participation_icp_e8s ({}) can be at most {} ICP E8s",
+                    max_direct_participation_icp_e8s, MAX_DIRECT_ICP_CONTRIBUTION_TO_SWAP
+                ),
             ));
         }
 
@@ -1750,19 +2423,27 @@ impl SnsInitPayload {
         if max_direct_participation_icp_e8s
             < min_participants.saturating_mul(min_participant_icp_e8s)
         {
-            return Err(format!(
-                "Error: max_direct_participation_icp_e8s ({}) \
+            return Err(SnsValidationError::new(
+                "swap.max_direct_participation_icp_e8s",
+                SnsValidationErrorCode::Inconsistent,
+                format!(
+                    "Error: max_direct_participation_icp_e8s ({}) \
                  must be >= min_participants ({}) * min_participant_icp_e8s ({})",
-                max_direct_participation_icp_e8s, min_participants, min_participant_icp_e8s
+                    max_direct_participation_icp_e8s, min_participants, min_participant_icp_e8s
+                ),
             ));
         }
 
         // (7)
         if neuron_minimum_stake_e8s <= sns_transaction_fee_e8s {
-            return Err(format!(
-                "Error: neuron_minimum_stake_e8s={} is too small. It needs to be \
+            return Err(SnsValidationError::new(
+                "governance.neuron_minimum_stake_e8s",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
+                    "Error: neuron_minimum_stake_e8s={} is too small. It needs to be \
                  greater than the transaction fee ({} e8s)",
-                neuron_minimum_stake_e8s, sns_transaction_fee_e8s
+                    neuron_minimum_stake_e8s, sns_transaction_fee_e8s
+                ),
             ));
         }
 
@@ -1776,8 +2457,11 @@ impl SnsInitPayload {
                 * (neuron_minimum_stake_e8s + sns_transaction_fee_e8s) as u128;
 
         if !min_participant_icp_e8s_big_enough {
-            return Err(format!(
-                "Error: min_participant_icp_e8s={} is too small. It needs to be \
+            return Err(SnsValidationError::new(
+                "swap.min_participant_icp_e8s",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
+                    "Error: min_participant_icp_e8s={} is too small. It needs to be \
                  large enough to ensure that participants will end up with \
                  enough SNS tokens to form {} SNS neurons, each of which \
                  require at least {} SNS e8s, plus {} e8s in transaction \
@@ -1785,10 +2469,11 @@ impl SnsInitPayload {
                  min_participant_icp_e8s >= neuron_basket_count \
                  * (neuron_minimum_stake_e8s + transaction_fee_e8s) \
                  * max_direct_participation_icp_e8s / initial_swap_amount_e8s",
-                min_participant_icp_e8s,
-                neuron_basket_construction_parameters_count,
-                neuron_minimum_stake_e8s,
-                sns_transaction_fee_e8s,
+                    min_participant_icp_e8s,
+                    neuron_basket_construction_parameters_count,
+                    neuron_minimum_stake_e8s,
+                    sns_transaction_fee_e8s,
+                ),
             ));
         }
 
@@ -1805,7 +2490,10 @@ impl SnsInitPayload {
             * neuron_basket_construction_parameters_count as u128
             / min_participant_icp_e8s as u128;
         if max_sns_neurons_for_direct_participants > MAX_NEURONS_FOR_DIRECT_PARTICIPANTS as u128 {
-            return Err(format!(
+            return Err(SnsValidationError::new(
+                "swap.max_direct_participation_icp_e8s",
+                SnsValidationErrorCode::OutOfRange,
+                format!(
                 "Error: The number of SNS neurons created for direct participants of a successful \
                  swap ((max_direct_participation_icp_e8s={}) \
                  * (neuron_basket_construction_parameters_count={}) \
@@ -1816,103 +2504,150 @@ impl SnsInitPayload {
                 min_participant_icp_e8s,
                 max_sns_neurons_for_direct_participants,
                 MAX_NEURONS_FOR_DIRECT_PARTICIPANTS
+            ),
             ));
         }
 
         Ok(())
     }
 
-    fn validate_nns_proposal_id_pre_execution(&self) -> Result<(), String> {
+    fn validate_nns_proposal_id_pre_execution(&self) -> Result<(), SnsValidationError> {
         if self.nns_proposal_id.is_none() {
             Ok(())
         } else {
-            Err(format!(
-                "Error: nns_proposal_id cannot be specified pre_execution, but was {:?}",
-                self.nns_proposal_id
+            Err(SnsValidationError::new(
+                "nns_proposal_id",
+                SnsValidationErrorCode::MustBeUnset,
+                format!(
+                    "Error: nns_proposal_id cannot be specified pre_execution, but was {:?}",
+                    self.nns_proposal_id
+                ),
             ))
         }
     }
 
-    fn validate_nns_proposal_id(&self) -> Result<(), String> {
+    fn validate_nns_proposal_id(&self) -> Result<(), SnsValidationError> {
         match self.nns_proposal_id {
-            None => Err("Error: nns_proposal_id must be specified".to_string()),
+            None => Err(SnsValidationError::new(
+                "nns_proposal_id",
+                SnsValidationErrorCode::Missing,
+                "Error: nns_proposal_id must be specified".to_string(),
+            )),
             Some(_) => Ok(()),
         }
     }
 
-    fn validate_neurons_fund_participants_pre_execution(&self) -> Result<(), String> {
+    fn validate_neurons_fund_participants_pre_execution(&self) -> Result<(), SnsValidationError> {
         if self.neurons_fund_participants.is_none() {
             Ok(())
         } else {
-            Err(format!(
+            Err(SnsValidationError::new(
+                "swap.neurons_fund_participants",
+                SnsValidationErrorCode::MustBeUnset,
+                format!(
                 "Error: neurons_fund_participants cannot be specified pre_execution, but was {:?}",
                 self.neurons_fund_participants
+            ),
             ))
         }
     }
 
-    fn validate_neurons_fund_participants(&self) -> Result<(), String> {
+    fn validate_neurons_fund_participants(&self) -> Result<(), SnsValidationError> {
         if self.neurons_fund_participants.is_none() {
             Ok(())
         } else {
-            Err(format!(
+            Err(SnsValidationError::new(
+                "swap.neurons_fund_participants",
+                SnsValidationErrorCode::MustBeUnset,
+                format!(
                 "Error: neurons_fund_participants can be set only by Swap; was initialized to {:?}",
                 self.neurons_fund_participants
+            ),
             ))
         }
     }
 
-    fn validate_swap_start_timestamp_seconds_pre_execution(&self) -> Result<(), String> {
+    fn validate_swap_start_timestamp_seconds_pre_execution(
+        &self,
+    ) -> Result<(), SnsValidationError> {
         if self.swap_start_timestamp_seconds.is_none() {
             Ok(())
         } else {
-            Err(format!(
-                "Error: swap_start_timestamp_seconds cannot be specified pre_execution, but was {:?}",
-                self.swap_start_timestamp_seconds
+            Err(SnsValidationError::new(
+                "swap.start_timestamp_seconds",
+                SnsValidationErrorCode::MustBeUnset,
+                format!(
+                    "Error: swap_start_timestamp_seconds cannot be specified pre_execution, but was {:?}",
+                    self.swap_start_timestamp_seconds
+                ),
             ))
         }
     }
 
-    fn validate_swap_start_timestamp_seconds(&self) -> Result<(), String> {
+    fn validate_swap_start_timestamp_seconds(&self) -> Result<(), SnsValidationError> {
         match self.swap_start_timestamp_seconds {
             Some(_) => Ok(()),
-            None => Err("Error: swap_start_timestamp_seconds must be specified".to_string()),
+            None => Err(SnsValidationError::new(
+                "swap.start_timestamp_seconds",
+                SnsValidationErrorCode::Missing,
+                "Error: swap_start_timestamp_seconds must be specified".to_string(),
+            )),
         }
     }
 
-    fn validate_swap_due_timestamp_seconds_pre_execution(&self) -> Result<(), String> {
+    fn validate_swap_due_timestamp_seconds_pre_execution(&self) -> Result<(), SnsValidationError> {
         if self.swap_due_timestamp_seconds.is_none() {
             Ok(())
         } else {
-            Err(format!(
+            Err(SnsValidationError::new(
+                "swap.due_timestamp_seconds",
+                SnsValidationErrorCode::MustBeUnset,
+                format!(
                 "Error: swap_due_timestamp_seconds cannot be specified pre_execution, but was {:?}",
                 self.swap_due_timestamp_seconds
+            ),
             ))
         }
     }
 
-    fn validate_swap_due_timestamp_seconds(&self) -> Result<(), String> {
-        let swap_start_timestamp_seconds = self
-            .swap_start_timestamp_seconds
-            .ok_or("Error: swap_start_timestamp_seconds must be specified")?;
+    fn validate_swap_due_timestamp_seconds(&self) -> Result<(), SnsValidationError> {
+        let swap_start_timestamp_seconds = self.swap_start_timestamp_seconds.ok_or_else(|| {
+            SnsValidationError::new(
+                "swap.start_timestamp_seconds",
+                SnsValidationErrorCode::Missing,
+                "Error: swap_start_timestamp_seconds must be specified",
+            )
+        })?;
 
-        let swap_due_timestamp_seconds = self
-            .swap_due_timestamp_seconds
-            .ok_or("Error: swap_due_timestamp_seconds must be specified")?;
+        let swap_due_timestamp_seconds = self.swap_due_timestamp_seconds.ok_or_else(|| {
+            SnsValidationError::new(
+                "swap.due_timestamp_seconds",
+                SnsValidationErrorCode::Missing,
+                "Error: swap_due_timestamp_seconds must be specified",
+            )
+        })?;
 
         if swap_due_timestamp_seconds < swap_start_timestamp_seconds {
-            return Err(format!(
-                "Error: swap_due_timestamp_seconds({}) must be after swap_start_timestamp_seconds({})",
-                swap_due_timestamp_seconds, swap_start_timestamp_seconds,
+            return Err(SnsValidationError::new(
+                "swap.due_timestamp_seconds",
+                SnsValidationErrorCode::Inconsistent,
+                format!(
+                    "Error: swap_due_timestamp_seconds({}) must be after swap_start_timestamp_seconds({})",
+                    swap_due_timestamp_seconds, swap_start_timestamp_seconds,
+                ),
             ));
         }
 
         Ok(())
     }
 
-    pub fn validate_neurons_fund_participation(&self) -> Result<(), String> {
+    pub fn validate_neurons_fund_participation(&self) -> Result<(), SnsValidationError> {
         if self.neurons_fund_participation.is_none() {
-            return Err("SnsInitPayload.neurons_fund_participation must be specified".into());
+            return Err(SnsValidationError::new(
+                "swap.neurons_fund_participation",
+                SnsValidationErrorCode::Missing,
+                "SnsInitPayload.neurons_fund_participation must be specified".to_string(),
+            ));
         }
         Ok(())
     }
@@ -1920,7 +2655,7 @@ impl SnsInitPayload {
     pub fn validate_neurons_fund_participation_constraints(
         &self,
         is_pre_execution: bool,
-    ) -> Result<(), String> {
+    ) -> Result<(), SnsValidationError> {
         // This field must be set by NNS Governance at proposal execution time, not before.
         // This check will also catch the situation in which we are in the legacy (pre-1-prop) flow,
         // in which the `neurons_fund_participation_constraints`` field must not be set at all.
@@ -1956,7 +2691,6 @@ impl SnsInitPayload {
                 NeuronsFundParticipationConstraintsValidationError::RelatedFieldUnspecified(
                     "min_direct_participation_icp_e8s".to_string(),
                 )
-                .to_string()
             })?;
         if min_direct_participation_threshold_icp_e8s < min_direct_participation_icp_e8s {
             return Result::from(NeuronsFundParticipationConstraintsValidationError::MinDirectParticipationThresholdValidationError(
@@ -1971,7 +2705,6 @@ impl SnsInitPayload {
                 NeuronsFundParticipationConstraintsValidationError::RelatedFieldUnspecified(
                     "max_direct_participation_icp_e8s".to_string(),
                 )
-                .to_string()
             })?;
         if min_direct_participation_threshold_icp_e8s > max_direct_participation_icp_e8s {
             return Result::from(NeuronsFundParticipationConstraintsValidationError::MinDirectParticipationThresholdValidationError(
@@ -1995,7 +2728,6 @@ impl SnsInitPayload {
             NeuronsFundParticipationConstraintsValidationError::RelatedFieldUnspecified(
                 "min_participant_icp_e8s".to_string(),
             )
-            .to_string()
         })?;
         if 0 < max_neurons_fund_participation_icp_e8s
             && max_neurons_fund_participation_icp_e8s < min_participant_icp_e8s
@@ -2015,7 +2747,6 @@ impl SnsInitPayload {
                 NeuronsFundParticipationConstraintsValidationError::RelatedFieldUnspecified(
                     "max_direct_participation_icp_e8s".to_string(),
                 )
-                .to_string()
             })?;
         if max_neurons_fund_participation_icp_e8s > max_direct_participation_icp_e8s {
             return Result::from(NeuronsFundParticipationConstraintsValidationError::MaxNeuronsFundParticipationValidationError(
@@ -2028,12 +2759,12 @@ impl SnsInitPayload {
 
         neurons_fund_participation_constraints
             .validate()
-            .map_err(|err| {
-                NeuronsFundParticipationConstraintsValidationError::Local(err).to_string()
-            })
+            .map_err(|err| NeuronsFundParticipationConstraintsValidationError::Local(err).into())
     }
 
-    pub fn validate_all_post_execution_swap_parameters_are_set(&self) -> Result<(), String> {
+    pub fn validate_all_post_execution_swap_parameters_are_set(
+        &self,
+    ) -> Result<(), SnsValidationError> {
         let mut missing_one_proposal_fields = vec![];
         if self.nns_proposal_id.is_none() {
             missing_one_proposal_fields.push("nns_proposal_id")
@@ -2054,18 +2785,22 @@ impl SnsInitPayload {
         if missing_one_proposal_fields.is_empty() {
             Ok(())
         } else {
-            Err(format!(
+            Err(SnsValidationError::new(
+                "swap",
+                SnsValidationErrorCode::Missing,
+                format!(
                 "Error in validate_all_post_execution_swap_parameters_are_set: The one-proposal \
                 SNS initialization requires some SnsInitPayload parameters to be Some. But the \
                 following fields were set to None: {}",
                 missing_one_proposal_fields.join(", ")
+            ),
             ))
         }
     }
 
     pub fn validate_all_non_legacy_pre_execution_swap_parameters_are_set(
         &self,
-    ) -> Result<(), String> {
+    ) -> Result<(), SnsValidationError> {
         let mut missing_one_proposal_fields = vec![];
         if self.min_participants.is_none() {
             missing_one_proposal_fields.push("min_participants")
@@ -2097,11 +2832,15 @@ impl SnsInitPayload {
         if missing_one_proposal_fields.is_empty() {
             Ok(())
         } else {
-            Err(format!(
+            Err(SnsValidationError::new(
+                "swap",
+                SnsValidationErrorCode::Missing,
+                format!(
                 "Error in validate_all_non_legacy_pre_execution_swap_parameters_are_set: The one-\
                 proposal SNS initialization requires some SnsInitPayload parameters to be Some. \
                 But the following fields were set to None: {}",
                 missing_one_proposal_fields.join(", ")
+            ),
             ))
         }
     }
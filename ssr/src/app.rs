@@ -11,6 +11,7 @@ use crate::{
         leaderboard::Leaderboard,
         logout::Logout,
         menu::{AuthorizedUserToSeedContent, Menu},
+        notifs::Notif,
         post_view::{single_post::SinglePost, PostView, PostViewCtx},
         privacy::PrivacyPolicy,
         profile::{profile_post::ProfilePost, ProfilePostsContext, ProfileView},
@@ -19,7 +20,8 @@ use crate::{
         settings::Settings,
         terms::TermsOfService,
         token::{
-            create::{CreateToken, CreateTokenCtx, CreateTokenSettings},
+            approvals::TokenApprovals,
+            create::{CreateToken, CreateTokenCtx, CreateTokenSettings, CreateTokenSimulation},
             create_token_faq::CreateTokenFAQ,
             info::TokenInfo,
             transfer::TokenTransfer,
@@ -31,7 +33,7 @@ use crate::{
         audio_state::AudioState, canisters::Canisters, content_seed_client::ContentSeedClient,
         history::HistoryCtx,
     },
-    utils::event_streaming::EventHistory,
+    utils::event_streaming::{consent::ConsentCtx, queue::EventQueue, EventHistory},
 };
 
 use leptos::*;
@@ -100,11 +102,14 @@ pub fn App() -> impl IntoView {
     });
 
     // Analytics
+    provide_context(EventHistory::default());
+    provide_context(EventQueue::default());
+    let consent = ConsentCtx::default();
+    provide_context(consent);
     let enable_ga4_script = create_rw_signal(false);
     #[cfg(feature = "ga4")]
     {
         enable_ga4_script.set(true);
-        provide_context(EventHistory::default());
     }
 
     view! {
@@ -117,7 +122,8 @@ pub fn App() -> impl IntoView {
 
         // GA4 Global Site Tag (gtag.js) - Google Analytics
         // G-6W5Q2MRX0E to test locally | G-PLNNETMSLM
-        <Show when=enable_ga4_script>
+        // Loaded only once the user opts into tracking
+        <Show when=move || enable_ga4_script() && consent.consent.get().allows_pii()>
             <Script
                 async_="true"
                 src=concat!("https://www.googletagmanager.com/gtag/js?id=", "G-PLNNETMSLM")
@@ -159,14 +165,17 @@ pub fn App() -> impl IntoView {
                         <Route path="/wallet/:id" view=Wallet />
                         <Route path="/wallet" view=Wallet />
                         <Route path="/leaderboard" view=Leaderboard />
+                        <Route path="/notifs" view=Notif />
                         <Route path="/account-transfer" view=AccountTransfer />
                         <Route path="/logout" view=Logout />
                         <Route path="/token/create" view=CreateToken />
                         <Route path="/token/create/settings" view=CreateTokenSettings />
                         <Route path="/token/create/faq" view=CreateTokenFAQ />
+                        <Route path="/token/create/simulate" view=CreateTokenSimulation />
                         <Route path="/token/info/:token_root/:key_principal" view=TokenInfo />
                         <Route path="/token/info/:token_root" view=TokenInfo />
                         <Route path="/token/transfer/:token_root" view=TokenTransfer />
+                        <Route path="/token/approvals/:token_root" view=TokenApprovals />
                         <Route path="/board" view=ICPumpLanding />
                         <Route path="/icpump-ai" view=ICPumpAi/>
                         // <Route path="/test" view=TestIndex/>
@@ -18,6 +18,7 @@ use yral_types::delegated_identity::DelegatedIdentityWire;
 fn delegate_identity_with_max_age(
     from: &impl Identity,
     max_age: Duration,
+    targets: Option<Vec<Principal>>,
 ) -> DelegatedIdentityWire {
     let to_secret = k256::SecretKey::random(&mut OsRng);
     let to_identity = Secp256k1Identity::from_private_key(to_secret.clone());
@@ -26,7 +27,7 @@ fn delegate_identity_with_max_age(
     let delegation = Delegation {
         pubkey: to_identity.public_key().unwrap(),
         expiration: expiry_ns,
-        targets: None,
+        targets,
     };
     let sig = from.sign_delegation(&delegation).unwrap();
     let signed_delegation = SignedDelegation {
@@ -45,12 +46,21 @@ fn delegate_identity_with_max_age(
 }
 
 pub fn delegate_identity(from: &impl Identity) -> DelegatedIdentityWire {
-    delegate_identity_with_max_age(from, DELEGATION_MAX_AGE)
+    delegate_identity_with_max_age(from, DELEGATION_MAX_AGE, None)
 }
 
 pub fn delegate_short_lived_identity(from: &impl Identity) -> DelegatedIdentityWire {
     let max_age = Duration::from_secs(24 * 60 * 60); // 1 day
-    delegate_identity_with_max_age(from, max_age)
+    delegate_identity_with_max_age(from, max_age, None)
+}
+
+/// Delegation that can only call the `targets` canisters, expiring after `max_age`
+pub fn delegate_scoped_identity(
+    from: &impl Identity,
+    targets: Vec<Principal>,
+    max_age: Duration,
+) -> DelegatedIdentityWire {
+    delegate_identity_with_max_age(from, max_age, Some(targets))
 }
 
 #[derive(Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
@@ -80,6 +80,8 @@ async fn try_extract_identity_from_google_sub(
     };
     let principal = Principal::from_text(principal_text)?;
     let Some(identity_secret) = fetch_identity_from_kv(kv, principal).await? else {
+        // Stale association, the identity no longer exists
+        kv.delete(principal_lookup_key(sub_id)).await?;
         return Ok(None);
     };
 
@@ -94,12 +96,27 @@ async fn extract_identity_and_associate_with_google_sub(
     let identity_secret = try_extract_identity(jar, kv)
         .await?
         .ok_or_else(|| ServerFnError::new("Attempting google login without an identity"))?;
-    let identity = Secp256k1Identity::from_private_key(identity_secret);
-    let principal = identity.sender().unwrap();
-    kv.write(principal_lookup_key(sub_id), principal.to_text())
+    let principal = Secp256k1Identity::from_private_key(identity_secret.clone())
+        .sender()
+        .unwrap();
+    // Identities associated with a login must outlive the anonymous ttl,
+    // persisted first so the association never points at an expiring identity
+    kv.write(
+        principal.to_text(),
+        identity_secret.to_jwk_string().to_string(),
+    )
+    .await?;
+    let associated = kv
+        .compare_and_swap(principal_lookup_key(sub_id), None, principal.to_text())
         .await?;
+    if !associated {
+        // A concurrent login associated another identity first
+        return try_extract_identity_from_google_sub(kv, sub_id)
+            .await?
+            .ok_or_else(|| ServerFnError::new("Google login raced with identity removal"));
+    }
 
-    Ok(identity)
+    Ok(Secp256k1Identity::from_private_key(identity_secret))
 }
 
 pub async fn perform_google_auth_impl(
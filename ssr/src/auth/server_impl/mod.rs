@@ -5,7 +5,7 @@ pub mod store;
 use axum::response::IntoResponse;
 use axum_extra::extract::{
     cookie::{Cookie, Key, SameSite},
-    SignedCookieJar,
+    PrivateCookieJar, SignedCookieJar,
 };
 use candid::Principal;
 use http::header;
@@ -37,6 +37,30 @@ fn set_cookies(resp: &ResponseOptions, jar: impl IntoResponse) {
     }
 }
 
+/// Name of the cookie used to seal values, authenticated along with them
+const SEALED_VALUE_COOKIE: &str = "sealed";
+
+/// Encrypt `value` with the cookie key, for secrets that are kept at rest
+pub fn seal_with_cookie_key(key: &Key, value: String) -> Result<String, ServerFnError> {
+    let resp = PrivateCookieJar::new(key.clone())
+        .add(Cookie::new(SEALED_VALUE_COOKIE, value))
+        .into_response();
+    let sealed = resp
+        .headers()
+        .get(header::SET_COOKIE)
+        .ok_or_else(|| ServerFnError::new("failed to seal value"))?;
+    let cookie = Cookie::parse_encoded(sealed.to_str()?.to_string())?;
+    Ok(cookie.value().to_string())
+}
+
+/// Decrypt a value sealed by [seal_with_cookie_key],
+/// `None` if it was tampered with or sealed with another key
+pub fn unseal_with_cookie_key(key: &Key, sealed: String) -> Option<String> {
+    PrivateCookieJar::new(key.clone())
+        .decrypt(Cookie::new(SEALED_VALUE_COOKIE, sealed))
+        .map(|cookie| cookie.value().to_string())
+}
+
 pub fn extract_principal_from_cookie(
     jar: &SignedCookieJar,
 ) -> Result<Option<Principal>, ServerFnError> {
@@ -71,13 +95,16 @@ pub async fn try_extract_identity(
     fetch_identity_from_kv(kv, principal).await
 }
 
+/// Anonymous identities are only reachable through the refresh cookie,
+/// so they expire along with it unless associated with a login
 async fn generate_and_save_identity(kv: &KVStoreImpl) -> Result<Secp256k1Identity, ServerFnError> {
     let base_identity_key = k256::SecretKey::random(&mut OsRng);
     let base_identity = Secp256k1Identity::from_private_key(base_identity_key.clone());
     let principal = base_identity.sender().unwrap();
 
     let base_jwk = base_identity_key.to_jwk_string();
-    kv.write(principal.to_text(), base_jwk.to_string()).await?;
+    kv.write_with_ttl(principal.to_text(), base_jwk.to_string(), REFRESH_MAX_AGE)
+        .await?;
     Ok(base_identity)
 }
 
@@ -86,7 +113,8 @@ async fn save_identity(kv: &KVStoreImpl, id: JwkEcKey) -> Result<Secp256k1Identi
     let principal = base_identity.sender().unwrap();
 
     let base_jwk = id.to_string();
-    kv.write(principal.to_text(), base_jwk).await?;
+    kv.write_with_ttl(principal.to_text(), base_jwk, REFRESH_MAX_AGE)
+        .await?;
     Ok(base_identity)
 }
 
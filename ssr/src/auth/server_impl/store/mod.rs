@@ -1,5 +1,8 @@
 pub mod redb_kv;
 pub mod redis_kv;
+#[cfg(feature = "sql-kv")]
+pub mod sql_kv;
+use std::time::Duration;
 
 use enum_dispatch::enum_dispatch;
 use redis::RedisError;
@@ -15,12 +18,46 @@ pub enum KVError {
     Redis(#[from] RedisError),
     #[error("{0}")]
     Bb8(#[from] bb8::RunError<RedisError>),
+    #[cfg(feature = "sql-kv")]
+    #[error(transparent)]
+    Sql(#[from] sqlx::Error),
 }
 
 #[enum_dispatch]
 pub(crate) trait KVStore: Send {
     async fn read(&self, key: String) -> Result<Option<String>, KVError>;
+    /// Writes `value` without an expiry, clearing any previous one
     async fn write(&self, key: String, value: String) -> Result<(), KVError>;
+    /// Writes `value`, the key reads as absent once `ttl` has elapsed
+    async fn write_with_ttl(
+        &self,
+        key: String,
+        value: String,
+        ttl: Duration,
+    ) -> Result<(), KVError>;
+    /// Returns true if the key existed
+    async fn delete(&self, key: String) -> Result<bool, KVError>;
+    /// Atomically sets `key` to `new` if its current value is `expected`
+    /// (`None` meaning absent), keeping the existing expiry.
+    /// Returns true if the value was swapped
+    async fn compare_and_swap(
+        &self,
+        key: String,
+        expected: Option<String>,
+        new: String,
+    ) -> Result<bool, KVError>;
+    /// Adds `member` to the set `index`
+    /// indexes live apart from keys and never expire, members are pruned by the caller
+    async fn index_add(&self, index: String, member: String) -> Result<(), KVError>;
+    /// Returns true if `member` was in the set `index`
+    async fn index_remove(&self, index: String, member: String) -> Result<bool, KVError>;
+    /// Members of the set `index`, in no particular order
+    async fn index_members(&self, index: String) -> Result<Vec<String>, KVError>;
+    /// All live key-value pairs whose key starts with `prefix`
+    ///
+    /// Admin and maintenance use only, this walks the whole keyspace on redis.
+    /// Request paths keep an index of their keys instead, see [KVStore::index_add]
+    async fn scan_prefix(&self, prefix: String) -> Result<Vec<(String, String)>, KVError>;
 }
 
 #[derive(Clone)]
@@ -28,4 +65,85 @@ pub(crate) trait KVStore: Send {
 pub enum KVStoreImpl {
     ReDB(redb_kv::ReDBKV),
     Redis(redis_kv::RedisKV),
+    #[cfg(feature = "sql-kv")]
+    Sql(sql_kv::SqlKV),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{redb_kv::ReDBKV, KVStore, KVStoreImpl};
+
+    async fn check_store(kv: KVStoreImpl) {
+        assert_eq!(kv.read("a".into()).await.unwrap(), None);
+        kv.write("a".into(), "1".into()).await.unwrap();
+        assert_eq!(kv.read("a".into()).await.unwrap().as_deref(), Some("1"));
+
+        // Compare and swap
+        assert!(!kv
+            .compare_and_swap("a".into(), None, "2".into())
+            .await
+            .unwrap());
+        assert!(!kv
+            .compare_and_swap("a".into(), Some("0".into()), "2".into())
+            .await
+            .unwrap());
+        assert!(kv
+            .compare_and_swap("a".into(), Some("1".into()), "2".into())
+            .await
+            .unwrap());
+        assert!(kv
+            .compare_and_swap("b".into(), None, "1".into())
+            .await
+            .unwrap());
+        assert_eq!(kv.read("a".into()).await.unwrap().as_deref(), Some("2"));
+
+        // Expired keys read as absent and can be claimed again
+        kv.write_with_ttl("c".into(), "1".into(), Duration::ZERO)
+            .await
+            .unwrap();
+        assert_eq!(kv.read("c".into()).await.unwrap(), None);
+        assert!(kv
+            .compare_and_swap("c".into(), None, "2".into())
+            .await
+            .unwrap());
+
+        assert!(kv.delete("a".into()).await.unwrap());
+        assert!(!kv.delete("a".into()).await.unwrap());
+        assert_eq!(kv.read("a".into()).await.unwrap(), None);
+
+        // Indexes are independent of keys
+        kv.index_add("idx".into(), "x".into()).await.unwrap();
+        kv.index_add("idx".into(), "y".into()).await.unwrap();
+        kv.index_add("idx".into(), "x".into()).await.unwrap();
+        kv.index_add("other".into(), "z".into()).await.unwrap();
+        let mut members = kv.index_members("idx".into()).await.unwrap();
+        members.sort();
+        assert_eq!(members, ["x", "y"]);
+        assert!(kv.index_remove("idx".into(), "x".into()).await.unwrap());
+        assert!(!kv.index_remove("idx".into(), "x".into()).await.unwrap());
+        assert_eq!(kv.index_members("idx".into()).await.unwrap(), ["y"]);
+        assert!(kv.index_members("none".into()).await.unwrap().is_empty());
+
+        let mut scanned = kv.scan_prefix("".into()).await.unwrap();
+        scanned.sort();
+        assert_eq!(
+            scanned,
+            [("b".into(), "1".into()), ("c".into(), "2".into())]
+        );
+    }
+
+    #[tokio::test]
+    async fn redb_store() {
+        check_store(KVStoreImpl::ReDB(ReDBKV::in_memory().unwrap())).await;
+    }
+
+    #[cfg(feature = "sql-kv")]
+    #[tokio::test]
+    async fn sql_store() {
+        use super::sql_kv::SqlKV;
+
+        check_store(KVStoreImpl::Sql(SqlKV::in_memory().await.unwrap())).await;
+    }
 }
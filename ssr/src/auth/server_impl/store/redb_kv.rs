@@ -1,23 +1,40 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
-use redb::{Database, TableDefinition};
+use redb::{Database, MultimapTableDefinition, ReadableTable, TableDefinition};
 use tokio::task::spawn_blocking;
 
+use crate::utils::time::current_epoch;
+
 use super::{KVError, KVStore};
 
 const TABLE: TableDefinition<&str, &str> = TableDefinition::new("kv");
 const RAW_METADATA_TABLE: TableDefinition<&str, &str> = TableDefinition::new("kv-meta");
+/// Expiry of keys written with a ttl, in seconds since epoch
+const EXPIRY_TABLE: TableDefinition<&str, u64> = TableDefinition::new("kv-expiry");
+const INDEX_TABLE: MultimapTableDefinition<&str, &str> = MultimapTableDefinition::new("kv-index");
 
 #[derive(Clone)]
 pub struct ReDBKV(Arc<Database>);
 
 impl ReDBKV {
     pub fn new() -> Result<Self, redb::Error> {
-        let db = Database::create("./redb-kv.db")?;
+        Self::init(Database::create("./redb-kv.db")?)
+    }
+
+    #[cfg(test)]
+    pub fn in_memory() -> Result<Self, redb::Error> {
+        use redb::backends::InMemoryBackend;
+
+        Self::init(Database::builder().create_with_backend(InMemoryBackend::new())?)
+    }
+
+    fn init(db: Database) -> Result<Self, redb::Error> {
         let write_txn = db.begin_write()?;
         {
             write_txn.open_table(TABLE)?;
             write_txn.open_table(RAW_METADATA_TABLE)?;
+            write_txn.open_table(EXPIRY_TABLE)?;
+            write_txn.open_multimap_table(INDEX_TABLE)?;
         }
         write_txn.commit()?;
         Ok(Self(Arc::new(db)))
@@ -33,12 +50,24 @@ impl ReDBKV {
     }
 }
 
+fn is_expired(
+    expiry: &impl ReadableTable<&'static str, u64>,
+    key: &str,
+) -> Result<bool, redb::Error> {
+    let now = current_epoch().as_secs();
+    Ok(expiry.get(key)?.is_some_and(|exp| exp.value() <= now))
+}
+
 impl KVStore for ReDBKV {
     async fn read(&self, key: String) -> Result<Option<String>, KVError> {
         self.spawn_blocking(move |db| {
             let read_txn = db.begin_read()?;
             let value = {
                 let table = read_txn.open_table(TABLE)?;
+                let expiry = read_txn.open_table(EXPIRY_TABLE)?;
+                if is_expired(&expiry, &key)? {
+                    return Ok(None);
+                }
                 let v = table.get(key.as_str())?;
                 v.map(|ag| ag.value().to_string())
             };
@@ -54,6 +83,8 @@ impl KVStore for ReDBKV {
             {
                 let mut table = write_txn.open_table(TABLE)?;
                 table.insert(key.as_str(), value.as_str())?;
+                let mut expiry = write_txn.open_table(EXPIRY_TABLE)?;
+                expiry.remove(key.as_str())?;
             }
             write_txn.commit()?;
             Ok::<_, redb::Error>(())
@@ -61,4 +92,144 @@ impl KVStore for ReDBKV {
         .await
         .unwrap()
     }
+
+    async fn write_with_ttl(
+        &self,
+        key: String,
+        value: String,
+        ttl: Duration,
+    ) -> Result<(), KVError> {
+        let expires_at = (current_epoch() + ttl).as_secs();
+        self.spawn_blocking(move |db| {
+            let write_txn = db.begin_write()?;
+            {
+                let mut table = write_txn.open_table(TABLE)?;
+                table.insert(key.as_str(), value.as_str())?;
+                let mut expiry = write_txn.open_table(EXPIRY_TABLE)?;
+                expiry.insert(key.as_str(), expires_at)?;
+            }
+            write_txn.commit()?;
+            Ok::<_, redb::Error>(())
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn delete(&self, key: String) -> Result<bool, KVError> {
+        self.spawn_blocking(move |db| {
+            let write_txn = db.begin_write()?;
+            let existed = {
+                let mut table = write_txn.open_table(TABLE)?;
+                let mut expiry = write_txn.open_table(EXPIRY_TABLE)?;
+                let expired = is_expired(&expiry, &key)?;
+                expiry.remove(key.as_str())?;
+                let removed = table.remove(key.as_str())?.is_some();
+                removed && !expired
+            };
+            write_txn.commit()?;
+            Ok(existed)
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn compare_and_swap(
+        &self,
+        key: String,
+        expected: Option<String>,
+        new: String,
+    ) -> Result<bool, KVError> {
+        self.spawn_blocking(move |db| {
+            // redb serializes write transactions, so the read and write below are atomic
+            let write_txn = db.begin_write()?;
+            let swapped = {
+                let mut table = write_txn.open_table(TABLE)?;
+                let mut expiry = write_txn.open_table(EXPIRY_TABLE)?;
+                let current = if is_expired(&expiry, &key)? {
+                    None
+                } else {
+                    table.get(key.as_str())?.map(|v| v.value().to_string())
+                };
+                if current != expected {
+                    false
+                } else {
+                    if current.is_none() {
+                        // Do not resurrect the expiry of a stale value
+                        expiry.remove(key.as_str())?;
+                    }
+                    table.insert(key.as_str(), new.as_str())?;
+                    true
+                }
+            };
+            write_txn.commit()?;
+            Ok(swapped)
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn index_add(&self, index: String, member: String) -> Result<(), KVError> {
+        self.spawn_blocking(move |db| {
+            let write_txn = db.begin_write()?;
+            {
+                let mut table = write_txn.open_multimap_table(INDEX_TABLE)?;
+                table.insert(index.as_str(), member.as_str())?;
+            }
+            write_txn.commit()?;
+            Ok::<_, redb::Error>(())
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn index_remove(&self, index: String, member: String) -> Result<bool, KVError> {
+        self.spawn_blocking(move |db| {
+            let write_txn = db.begin_write()?;
+            let removed = {
+                let mut table = write_txn.open_multimap_table(INDEX_TABLE)?;
+                table.remove(index.as_str(), member.as_str())?
+            };
+            write_txn.commit()?;
+            Ok(removed)
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn index_members(&self, index: String) -> Result<Vec<String>, KVError> {
+        self.spawn_blocking(move |db| {
+            let read_txn = db.begin_read()?;
+            let table = read_txn.open_multimap_table(INDEX_TABLE)?;
+            let mut members = vec![];
+            for member in table.get(index.as_str())? {
+                members.push(member?.value().to_string());
+            }
+            Ok(members)
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn scan_prefix(&self, prefix: String) -> Result<Vec<(String, String)>, KVError> {
+        self.spawn_blocking(move |db| {
+            let read_txn = db.begin_read()?;
+            let table = read_txn.open_table(TABLE)?;
+            let expiry = read_txn.open_table(EXPIRY_TABLE)?;
+            let mut entries = vec![];
+            for entry in table.range(prefix.as_str()..)? {
+                let (k, v) = entry?;
+                let k = k.value();
+                if !k.starts_with(prefix.as_str()) {
+                    break;
+                }
+                if is_expired(&expiry, k)? {
+                    continue;
+                }
+                entries.push((k.to_string(), v.value().to_string()));
+            }
+            Ok(entries)
+        })
+        .await
+        .unwrap()
+    }
 }
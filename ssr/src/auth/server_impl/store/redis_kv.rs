@@ -1,5 +1,7 @@
+use std::time::Duration;
+
 use bb8_redis::RedisConnectionManager;
-use redis::{AsyncCommands, RedisError};
+use redis::{AsyncCommands, RedisError, Script};
 
 use super::{KVError, KVStore};
 
@@ -14,6 +16,33 @@ impl RedisKV {
 }
 
 const AUTH_FIELD: &str = "auth";
+/// Indexes are sets, kept apart from the hashes holding values
+const INDEX_PREFIX: &str = "kv-index:";
+
+/// KEYS[1] = key
+/// ARGV[1] = field, ARGV[2] = "1" if a value is expected, ARGV[3] = expected, ARGV[4] = new
+const CAS_SCRIPT: &str = r"
+local cur = redis.call('HGET', KEYS[1], ARGV[1])
+if ARGV[2] == '1' then
+    if cur ~= ARGV[3] then return 0 end
+elseif cur then
+    return 0
+end
+redis.call('HSET', KEYS[1], ARGV[1], ARGV[4])
+return 1
+";
+
+/// Escapes glob metacharacters for `SCAN MATCH`
+fn escape_glob(prefix: &str) -> String {
+    let mut escaped = String::with_capacity(prefix.len());
+    for c in prefix.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
 
 impl KVStore for RedisKV {
     async fn read(&self, key: String) -> Result<Option<String>, KVError> {
@@ -24,7 +53,99 @@ impl KVStore for RedisKV {
 
     async fn write(&self, key: String, value: String) -> Result<(), KVError> {
         let mut con = self.0.get().await?;
-        con.hset::<_, _, _, ()>(key, AUTH_FIELD, value).await?;
+        redis::pipe()
+            .atomic()
+            .hset(&key, AUTH_FIELD, value)
+            .ignore()
+            .persist(&key)
+            .ignore()
+            .query_async::<_, ()>(&mut *con)
+            .await?;
         Ok(())
     }
+
+    async fn write_with_ttl(
+        &self,
+        key: String,
+        value: String,
+        ttl: Duration,
+    ) -> Result<(), KVError> {
+        let mut con = self.0.get().await?;
+        redis::pipe()
+            .atomic()
+            .hset(&key, AUTH_FIELD, value)
+            .ignore()
+            .expire(&key, ttl.as_secs().max(1) as i64)
+            .ignore()
+            .query_async::<_, ()>(&mut *con)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete(&self, key: String) -> Result<bool, KVError> {
+        let mut con = self.0.get().await?;
+        let removed: u64 = con.del(key).await?;
+        Ok(removed > 0)
+    }
+
+    async fn compare_and_swap(
+        &self,
+        key: String,
+        expected: Option<String>,
+        new: String,
+    ) -> Result<bool, KVError> {
+        let mut con = self.0.get().await?;
+        let swapped: i64 = Script::new(CAS_SCRIPT)
+            .key(key)
+            .arg(AUTH_FIELD)
+            .arg(if expected.is_some() { "1" } else { "0" })
+            .arg(expected.unwrap_or_default())
+            .arg(new)
+            .invoke_async(&mut *con)
+            .await?;
+        Ok(swapped == 1)
+    }
+
+    async fn index_add(&self, index: String, member: String) -> Result<(), KVError> {
+        let mut con = self.0.get().await?;
+        con.sadd::<_, _, ()>(format!("{INDEX_PREFIX}{index}"), member)
+            .await?;
+        Ok(())
+    }
+
+    async fn index_remove(&self, index: String, member: String) -> Result<bool, KVError> {
+        let mut con = self.0.get().await?;
+        let removed: u64 = con.srem(format!("{INDEX_PREFIX}{index}"), member).await?;
+        Ok(removed > 0)
+    }
+
+    async fn index_members(&self, index: String) -> Result<Vec<String>, KVError> {
+        let mut con = self.0.get().await?;
+        let members: Vec<String> = con.smembers(format!("{INDEX_PREFIX}{index}")).await?;
+        Ok(members)
+    }
+
+    async fn scan_prefix(&self, prefix: String) -> Result<Vec<(String, String)>, KVError> {
+        let mut con = self.0.get().await?;
+        let keys: Vec<String> = {
+            let mut iter = con
+                .scan_match::<_, String>(format!("{}*", escape_glob(&prefix)))
+                .await?;
+            let mut keys = vec![];
+            while let Some(key) = iter.next_item().await {
+                keys.push(key);
+            }
+            keys
+        };
+
+        let mut entries = Vec::with_capacity(keys.len());
+        for key in keys {
+            // Keys may expire between the scan and the read
+            let value: Option<String> = con.hget(&key, AUTH_FIELD).await?;
+            if let Some(value) = value {
+                entries.push((key, value));
+            }
+        }
+        Ok(entries)
+    }
 }
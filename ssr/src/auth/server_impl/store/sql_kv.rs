@@ -0,0 +1,186 @@
+use std::time::Duration;
+
+use sqlx::{any::AnyPoolOptions, AnyPool, Row};
+
+use crate::utils::time::current_epoch;
+
+use super::{KVError, KVStore};
+
+/// `expires_at` is in seconds since epoch, `NULL` for keys without a ttl.
+/// The statements below are valid for both sqlite and postgres
+const CREATE_TABLE: &str = "CREATE TABLE IF NOT EXISTS kv (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL,
+    expires_at BIGINT
+)";
+
+const CREATE_INDEX_TABLE: &str = "CREATE TABLE IF NOT EXISTS kv_index (
+    idx TEXT NOT NULL,
+    member TEXT NOT NULL,
+    PRIMARY KEY (idx, member)
+)";
+
+/// SQL backed store, sqlite for a single node and postgres for a fleet
+///
+/// The backend is picked from the url scheme (`sqlite://` or `postgres://`)
+#[derive(Clone)]
+pub struct SqlKV(AnyPool);
+
+impl SqlKV {
+    pub async fn new(database_url: &str) -> Result<Self, sqlx::Error> {
+        Self::connect(AnyPoolOptions::new(), database_url).await
+    }
+
+    /// A private in memory sqlite database
+    #[cfg(test)]
+    pub async fn in_memory() -> Result<Self, sqlx::Error> {
+        // Every sqlite connection opens its own in memory database
+        Self::connect(AnyPoolOptions::new().max_connections(1), "sqlite::memory:").await
+    }
+
+    async fn connect(options: AnyPoolOptions, database_url: &str) -> Result<Self, sqlx::Error> {
+        sqlx::any::install_default_drivers();
+        let pool = options.connect(database_url).await?;
+        sqlx::query(CREATE_TABLE).execute(&pool).await?;
+        sqlx::query(CREATE_INDEX_TABLE).execute(&pool).await?;
+        Ok(Self(pool))
+    }
+
+    async fn upsert(
+        &self,
+        key: String,
+        value: String,
+        expires_at: Option<i64>,
+    ) -> Result<(), KVError> {
+        sqlx::query(
+            "INSERT INTO kv (key, value, expires_at) VALUES ($1, $2, $3)
+            ON CONFLICT (key) DO UPDATE SET value = excluded.value, expires_at = excluded.expires_at",
+        )
+        .bind(key)
+        .bind(value)
+        .bind(expires_at)
+        .execute(&self.0)
+        .await?;
+        Ok(())
+    }
+}
+
+fn now() -> i64 {
+    current_epoch().as_secs() as i64
+}
+
+impl KVStore for SqlKV {
+    async fn read(&self, key: String) -> Result<Option<String>, KVError> {
+        let row = sqlx::query(
+            "SELECT value FROM kv WHERE key = $1 AND (expires_at IS NULL OR expires_at > $2)",
+        )
+        .bind(key)
+        .bind(now())
+        .fetch_optional(&self.0)
+        .await?;
+        Ok(row.map(|r| r.try_get::<String, _>("value")).transpose()?)
+    }
+
+    async fn write(&self, key: String, value: String) -> Result<(), KVError> {
+        self.upsert(key, value, None).await
+    }
+
+    async fn write_with_ttl(
+        &self,
+        key: String,
+        value: String,
+        ttl: Duration,
+    ) -> Result<(), KVError> {
+        let expires_at = (current_epoch() + ttl).as_secs() as i64;
+        self.upsert(key, value, Some(expires_at)).await
+    }
+
+    async fn delete(&self, key: String) -> Result<bool, KVError> {
+        let Some(row) = sqlx::query("DELETE FROM kv WHERE key = $1 RETURNING expires_at")
+            .bind(key)
+            .fetch_optional(&self.0)
+            .await?
+        else {
+            return Ok(false);
+        };
+        let expires_at: Option<i64> = row.try_get("expires_at")?;
+        Ok(expires_at.map_or(true, |exp| exp > now()))
+    }
+
+    async fn compare_and_swap(
+        &self,
+        key: String,
+        expected: Option<String>,
+        new: String,
+    ) -> Result<bool, KVError> {
+        // Single statements are atomic in both backends
+        let res = match expected {
+            Some(expected) => {
+                // Placeholders must appear in order for sqlite
+                sqlx::query(
+                    "UPDATE kv SET value = $1 WHERE key = $2
+                    AND (expires_at IS NULL OR expires_at > $3) AND value = $4",
+                )
+                .bind(new)
+                .bind(key)
+                .bind(now())
+                .bind(expected)
+                .execute(&self.0)
+                .await?
+            }
+            None => {
+                sqlx::query(
+                    "INSERT INTO kv (key, value, expires_at) VALUES ($1, $2, NULL)
+                    ON CONFLICT (key) DO UPDATE SET value = excluded.value, expires_at = NULL
+                    WHERE kv.expires_at IS NOT NULL AND kv.expires_at <= $3",
+                )
+                .bind(key)
+                .bind(new)
+                .bind(now())
+                .execute(&self.0)
+                .await?
+            }
+        };
+        Ok(res.rows_affected() == 1)
+    }
+
+    async fn index_add(&self, index: String, member: String) -> Result<(), KVError> {
+        sqlx::query("INSERT INTO kv_index (idx, member) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+            .bind(index)
+            .bind(member)
+            .execute(&self.0)
+            .await?;
+        Ok(())
+    }
+
+    async fn index_remove(&self, index: String, member: String) -> Result<bool, KVError> {
+        let res = sqlx::query("DELETE FROM kv_index WHERE idx = $1 AND member = $2")
+            .bind(index)
+            .bind(member)
+            .execute(&self.0)
+            .await?;
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn index_members(&self, index: String) -> Result<Vec<String>, KVError> {
+        let rows = sqlx::query("SELECT member FROM kv_index WHERE idx = $1")
+            .bind(index)
+            .fetch_all(&self.0)
+            .await?;
+        rows.into_iter().map(|r| Ok(r.try_get("member")?)).collect()
+    }
+
+    async fn scan_prefix(&self, prefix: String) -> Result<Vec<(String, String)>, KVError> {
+        let rows = sqlx::query(
+            "SELECT key, value FROM kv WHERE substr(key, 1, length($1)) = $1
+            AND (expires_at IS NULL OR expires_at > $2)",
+        )
+        .bind(prefix)
+        .bind(now())
+        .fetch_all(&self.0)
+        .await?;
+        rows.into_iter()
+            .map(|r| Ok((r.try_get("key")?, r.try_get("value")?)))
+            .collect()
+    }
+}
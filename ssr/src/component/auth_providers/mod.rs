@@ -2,7 +2,6 @@
 mod google;
 #[cfg(feature = "local-auth")]
 mod local_storage;
-
 use candid::Principal;
 use codee::string::FromToStringCodec;
 use ic_agent::Identity;
@@ -191,9 +190,13 @@ mod server_fn_impl {
     #[cfg(feature = "backend-admin")]
     mod backend_admin {
         use candid::Principal;
-        use leptos::ServerFnError;
+        use leptos::{expect_context, ServerFnError};
 
-        use crate::state::canisters::unauth_canisters;
+        use crate::{
+            auth::server_impl::store::KVStoreImpl,
+            state::canisters::unauth_canisters,
+            utils::notifications::inbox::{server_impl::notify_once, NotificationKind},
+        };
         use yral_canisters_client::individual_user_template::KnownPrincipalType;
 
         pub async fn issue_referral_rewards_impl(
@@ -237,6 +240,19 @@ mod server_fn_impl {
             )
             .await?;
 
+            let kv: KVStoreImpl = expect_context();
+            let referee = user_details.principal_id;
+            let notified = notify_once(
+                &kv,
+                format!("referral:{referee}"),
+                referrer_details.profile_owner,
+                NotificationKind::ReferralReward { referee },
+            )
+            .await;
+            if let Err(e) = notified {
+                log::warn!("failed to notify referrer of {referee}: {e}");
+            }
+
             Ok(())
         }
 
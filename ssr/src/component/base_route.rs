@@ -12,7 +12,7 @@ use crate::{
     auth::{
         extract_identity, generate_anonymous_identity_if_required, set_anonymous_identity_cookie,
     },
-    component::spinner::FullScreenSpinner,
+    component::{consent_banner::ConsentBanner, spinner::FullScreenSpinner},
     state::{
         auth::AuthState,
         canisters::{do_canister_auth, AuthCansResource, Canisters},
@@ -149,6 +149,7 @@ pub fn BaseRoute() -> impl IntoView {
                         view! {
                             <CtxProvider temp_identity>
                                 <Outlet />
+                                <ConsentBanner />
                             </CtxProvider>
                         }
                     })
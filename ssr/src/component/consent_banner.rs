@@ -0,0 +1,41 @@
+use leptos::*;
+
+use crate::utils::event_streaming::consent::{
+    use_restore_consent, use_set_consent, ConsentCtx, TrackingConsent,
+};
+
+/// Asks for tracking consent until the user makes a choice,
+/// must be rendered under `BaseRoute`
+#[component]
+pub fn ConsentBanner() -> impl IntoView {
+    let ConsentCtx { consent, .. } = expect_context();
+    let set_consent = use_set_consent();
+    use_restore_consent();
+
+    view! {
+        <Show when=move || consent() == TrackingConsent::Unset>
+            <div class="fixed bottom-20 inset-x-4 z-[60] flex flex-col gap-3 p-4 rounded-lg bg-neutral-900 text-white text-sm">
+                <span>
+                    "We use analytics to improve Yral. Allow us to link usage data to your account? "
+                    <a class="text-primary-600" href="/privacy-policy">
+                        Privacy Policy
+                    </a>
+                </span>
+                <div class="flex flex-row gap-4 justify-end">
+                    <button
+                        class="px-4 py-1 rounded-full border border-white/50"
+                        on:click=move |_| set_consent.dispatch(TrackingConsent::Denied)
+                    >
+                        Decline
+                    </button>
+                    <button
+                        class="px-4 py-1 rounded-full bg-primary-600"
+                        on:click=move |_| set_consent.dispatch(TrackingConsent::Granted)
+                    >
+                        Accept
+                    </button>
+                </div>
+            </div>
+        </Show>
+    }
+}
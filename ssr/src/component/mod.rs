@@ -6,6 +6,7 @@ pub mod bullet_loader;
 pub mod canisters_prov;
 pub mod coming_soon;
 pub mod connect;
+pub mod consent_banner;
 pub mod content_upload;
 pub mod dashbox;
 pub mod feed_popup;
@@ -55,7 +55,19 @@ pub fn ScrollingPostView<F: Fn() -> V + Clone + 'static, V>(
                 <For
                     each=move || video_queue().into_iter().enumerate()
                     key=move |(_, details)| (details.canister_id, details.post_id)
-                    children=move |(queue_idx, _details)| {
+                    children=move |(_, details)| {
+                        // posts can be evicted from the front of the queue, track the index
+                        let (canister_id, post_id) = (details.canister_id, details.post_id);
+                        let queue_idx = create_memo(move |_| {
+                            video_queue
+                                .with(|q| {
+                                    q.iter()
+                                        .position(|p| {
+                                            p.canister_id == canister_id && p.post_id == post_id
+                                        })
+                                })
+                                .unwrap_or_default()
+                        });
                         let container_ref = create_node_ref::<html::Div>();
                         let next_videos = fetch_next_videos.clone();
                         use_intersection_observer_with_options(
@@ -66,6 +78,7 @@ pub fn ScrollingPostView<F: Fn() -> V + Clone + 'static, V>(
                                     return;
                                 };
                                 let rect = visible.bounding_client_rect();
+                                let queue_idx = queue_idx.get_untracked();
                                 if rect.y() == rect.height()
                                     || queue_idx == current_idx.get_untracked()
                                 {
@@ -86,13 +99,13 @@ pub fn ScrollingPostView<F: Fn() -> V + Clone + 'static, V>(
                             let Some(container) = container_ref() else {
                                 return;
                             };
-                            if current_idx() == queue_idx && recovering_state.get_untracked() {
+                            if current_idx() == queue_idx() && recovering_state.get_untracked() {
                                 container.scroll_into_view();
                                 recovering_state.set(false);
                             }
                         });
                         let show_video = create_memo(move |_| {
-                            queue_idx.abs_diff(current_idx()) <= 20
+                            queue_idx().abs_diff(current_idx()) <= 20
                         });
                         view! {
                             <div _ref=container_ref class="snap-always snap-end w-full h-full">
@@ -27,6 +27,9 @@ pub const REFERRER_STORE: &str = "referrer";
 pub const USER_CANISTER_ID_STORE: &str = "user-canister-id";
 pub const USER_PRINCIPAL_STORE: &str = "user-principal";
 pub const USER_ONBOARDING_STORE: &str = "user-onboarding";
+pub const TOKEN_SPENDERS_STORE: &str = "token-spenders";
+pub const PENDING_VIDEO_UPLOAD_STORE: &str = "pending-video-upload";
+pub const TRACKING_CONSENT_STORE: &str = "tracking-consent";
 
 pub static OFF_CHAIN_AGENT_URL: Lazy<Url> =
     Lazy::new(|| Url::parse("https://icp-off-chain-agent.fly.dev/").unwrap());
@@ -1,6 +1,5 @@
 #[cfg(feature = "local-bin")]
 pub mod containers;
-
 use std::{
     env,
     fs::OpenOptions,
@@ -14,9 +13,18 @@ use leptos_router::RouteListing;
 use crate::{
     auth::server_impl::store::KVStoreImpl,
     state::{canisters::Canisters, server::AppState},
-    utils::token::icpump::ICPumpSearchGrpcChannel,
+    utils::{
+        event_streaming::sinks::AnalyticsSinks,
+        ic::spawn_call_metrics_log,
+        leaderboard::server_impl::LeaderboardCache,
+        ml_feed::{ml_feed_grpc::MLFeed, MlFeedConfig},
+        token::icpump::ICPumpSearchGrpcChannel,
+    },
 };
 
+/// How often the canister call metrics are logged
+const CALL_METRICS_LOG_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
 #[cfg(feature = "cloudflare")]
 fn init_cf() -> gob_cloudflare::CloudflareAuth {
     use gob_cloudflare::{CloudflareAuth, Credentials};
@@ -27,6 +35,14 @@ fn init_cf() -> gob_cloudflare::CloudflareAuth {
     CloudflareAuth::new(creds)
 }
 
+#[cfg(feature = "cloudflare")]
+fn init_cf_tus() -> crate::utils::cf_tus::CfTusClient {
+    use crate::utils::cf_tus::CfTusClient;
+    let token = env::var("CF_TOKEN").expect("`CF_TOKEN` is required!");
+    let account_id = env::var("CF_ACCOUNT_ID").expect("`CF_ACCOUNT_ID` is required!");
+    CfTusClient::new(&account_id, &token)
+}
+
 fn init_cookie_key() -> Key {
     let cookie_key_raw = {
         #[cfg(not(feature = "local-bin"))]
@@ -213,6 +229,66 @@ fn init_qstash_client() -> crate::utils::qstash::QStashClient {
     QStashClient::new(&auth_token)
 }
 
+/// Sinks named in the comma separated `ANALYTICS_SINKS`
+/// (`ga4`, `warehouse`, `http` and `log`),
+/// defaults to GA4 and the warehouse with the `ga4` feature and to `log` otherwise
+fn init_analytics_sinks() -> AnalyticsSinks {
+    use crate::{
+        consts::{GTAG_MEASUREMENT_ID, OFF_CHAIN_AGENT_GRPC_URL},
+        utils::event_streaming::sinks::{
+            AnalyticsSinkImpl, Ga4Sink, HttpSink, LogSink, WarehouseSink,
+        },
+    };
+
+    let default_sinks = if cfg!(feature = "ga4") {
+        "ga4,warehouse"
+    } else {
+        "log"
+    };
+    let sinks = env::var("ANALYTICS_SINKS").unwrap_or_else(|_| default_sinks.to_string());
+
+    let sinks = sinks
+        .split(',')
+        .map(str::trim)
+        .filter(|sink| !sink.is_empty())
+        .filter_map(|sink| -> Option<AnalyticsSinkImpl> {
+            // default sinks are skipped if their secret is missing, i.e in development
+            let secret = |var: &str| {
+                let value = env::var(var).ok();
+                if value.is_none() {
+                    log::warn!("`{var}` is not set, disabling analytics sink `{sink}`");
+                }
+                value
+            };
+            Some(match sink {
+                "ga4" => {
+                    let api_secret = secret("GA4_API_SECRET")?;
+                    Ga4Sink::new(*GTAG_MEASUREMENT_ID, &api_secret).into()
+                }
+                "warehouse" => {
+                    let auth_token = secret("GRPC_AUTH_TOKEN")?;
+                    WarehouseSink::new(&OFF_CHAIN_AGENT_GRPC_URL, &auth_token).into()
+                }
+                "http" => {
+                    let url = env::var("ANALYTICS_COLLECTOR_URL")
+                        .expect("`ANALYTICS_COLLECTOR_URL` is required!");
+                    let url = url.parse().expect("Invalid `ANALYTICS_COLLECTOR_URL`");
+                    HttpSink::new(url, env::var("ANALYTICS_COLLECTOR_TOKEN").ok()).into()
+                }
+                "log" => match env::var("ANALYTICS_LOG_FILE") {
+                    Ok(path) => LogSink::to_file(path)
+                        .expect("Couldn't open `ANALYTICS_LOG_FILE`")
+                        .into(),
+                    Err(_) => LogSink::default().into(),
+                },
+                _ => panic!("Unknown analytics sink `{sink}`"),
+            })
+        })
+        .collect();
+
+    AnalyticsSinks(sinks)
+}
+
 pub struct AppStateRes {
     pub app_state: AppState,
     #[cfg(feature = "local-bin")]
@@ -222,6 +298,7 @@ pub struct AppStateRes {
 pub struct AppStateBuilder {
     leptos_options: LeptosOptions,
     routes: Vec<RouteListing>,
+    analytics_sinks: Option<AnalyticsSinks>,
     #[cfg(feature = "local-bin")]
     containers: containers::TestContainers,
 }
@@ -231,11 +308,18 @@ impl AppStateBuilder {
         Self {
             leptos_options,
             routes,
+            analytics_sinks: None,
             #[cfg(feature = "local-bin")]
             containers: containers::TestContainers::default(),
         }
     }
 
+    /// Use `sinks` instead of the ones configured by `ANALYTICS_SINKS`
+    pub fn analytics_sinks(mut self, sinks: AnalyticsSinks) -> Self {
+        self.analytics_sinks = Some(sinks);
+        self
+    }
+
     async fn init_kv(&mut self) -> KVStoreImpl {
         #[cfg(feature = "redis-kv")]
         {
@@ -253,7 +337,19 @@ impl AppStateBuilder {
             KVStoreImpl::Redis(RedisKV::new(&redis_url).await.unwrap())
         }
 
-        #[cfg(not(feature = "redis-kv"))]
+        #[cfg(all(feature = "sql-kv", not(feature = "redis-kv")))]
+        {
+            use crate::auth::server_impl::store::sql_kv::SqlKV;
+            let database_url = env::var("KV_DATABASE_URL")
+                .unwrap_or_else(|_| "sqlite://./sql-kv.db?mode=rwc".to_string());
+            KVStoreImpl::Sql(
+                SqlKV::new(&database_url)
+                    .await
+                    .expect("Failed to initialize SQL KV"),
+            )
+        }
+
+        #[cfg(not(any(feature = "redis-kv", feature = "sql-kv")))]
         {
             use crate::auth::server_impl::store::redb_kv::ReDBKV;
             KVStoreImpl::ReDB(ReDBKV::new().expect("Failed to initialize ReDB"))
@@ -268,14 +364,21 @@ impl AppStateBuilder {
             self.containers.start_metadata().await;
         }
 
+        let canisters = Canisters::default();
+        let leaderboard = LeaderboardCache::default();
+        leaderboard.spawn_refresh(canisters.clone(), kv.clone());
+        spawn_call_metrics_log(CALL_METRICS_LOG_INTERVAL);
+
         let app_state = AppState {
             leptos_options: self.leptos_options,
-            canisters: Canisters::default(),
+            canisters,
             routes: self.routes,
             #[cfg(feature = "backend-admin")]
             admin_canisters: init_admin_canisters(),
             #[cfg(feature = "cloudflare")]
             cloudflare: init_cf(),
+            #[cfg(feature = "cloudflare")]
+            cf_tus: init_cf_tus(),
             kv,
             cookie_key: init_cookie_key(),
             #[cfg(feature = "oauth-ssr")]
@@ -287,6 +390,9 @@ impl AppStateBuilder {
             #[cfg(feature = "qstash")]
             qstash: init_qstash_client(),
             grpc_icpump_search_channel: init_grpc_icpump_search_channel().await,
+            ml_feed: MLFeed::new(MlFeedConfig::from_env()),
+            analytics_sinks: self.analytics_sinks.unwrap_or_else(init_analytics_sinks),
+            leaderboard,
         };
 
         AppStateRes {
@@ -25,6 +25,8 @@ pub async fn server_fn_handler(
             provide_context(app_state.admin_canisters.clone());
             #[cfg(feature = "cloudflare")]
             provide_context(app_state.cloudflare.clone());
+            #[cfg(feature = "cloudflare")]
+            provide_context(app_state.cf_tus.clone());
             provide_context(app_state.kv.clone());
             provide_context(app_state.cookie_key.clone());
             #[cfg(feature = "oauth-ssr")]
@@ -40,6 +42,9 @@ pub async fn server_fn_handler(
             provide_context(app_state.qstash.clone());
 
             provide_context(app_state.grpc_icpump_search_channel.clone());
+            provide_context(app_state.ml_feed.clone());
+            provide_context(app_state.analytics_sinks.clone());
+            provide_context(app_state.leaderboard.clone());
         },
         request,
     )
@@ -59,6 +64,8 @@ pub async fn leptos_routes_handler(
             provide_context(app_state.admin_canisters.clone());
             #[cfg(feature = "cloudflare")]
             provide_context(app_state.cloudflare.clone());
+            #[cfg(feature = "cloudflare")]
+            provide_context(app_state.cf_tus.clone());
             provide_context(app_state.kv.clone());
             provide_context(app_state.cookie_key.clone());
             #[cfg(feature = "oauth-ssr")]
@@ -74,6 +81,9 @@ pub async fn leptos_routes_handler(
             provide_context(app_state.qstash.clone());
 
             provide_context(app_state.grpc_icpump_search_channel.clone());
+            provide_context(app_state.ml_feed.clone());
+            provide_context(app_state.analytics_sinks.clone());
+            provide_context(app_state.leaderboard.clone());
         },
         App,
     );
@@ -132,11 +142,20 @@ async fn main() {
     };
 
     // build our application with a route
-    let app = Router::new()
-        .route(
-            "/api/*fn_name",
-            get(server_fn_handler).post(server_fn_handler),
-        )
+    let app = Router::new().route(
+        "/api/*fn_name",
+        get(server_fn_handler).post(server_fn_handler),
+    );
+    #[cfg(not(feature = "cloudflare"))]
+    let app = app.nest(
+        "/mock-tus",
+        hot_or_not_web_leptos_ssr::page::upload::mock_tus_router(),
+    );
+    let app = app.nest(
+        "/admin/analytics-deletions",
+        hot_or_not_web_leptos_ssr::utils::event_streaming::consent::server_impl::deletion_router(),
+    );
+    let app = app
         .leptos_routes_with_handler(routes, get(leptos_routes_handler))
         .fallback(file_and_error_handler)
         .with_state(res.app_state);
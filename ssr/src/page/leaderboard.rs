@@ -1,13 +1,166 @@
-use crate::component::coming_soon::ComingSoonGraphic;
+use candid::Principal;
 use leptos::*;
-use leptos_icons::*;
+
+use crate::{
+    component::{back_btn::BackButton, infinite_scroller::InfiniteScroller, title::Title},
+    state::canisters::authenticated_canisters,
+    utils::leaderboard::{
+        get_own_leaderboard_entry, LeaderboardEntry, LeaderboardPeriod, LeaderboardProvider,
+    },
+};
+
+const FETCH_CNT: usize = 20;
+
+#[component]
+fn PeriodTab(period: LeaderboardPeriod, current: RwSignal<LeaderboardPeriod>) -> impl IntoView {
+    let selected = move || current() == period;
+
+    view! {
+        <button
+            class="flex-1 py-2 rounded-full text-sm font-semibold"
+            class=("bg-primary-600", selected)
+            class=("text-white/50", move || !selected())
+            on:click=move |_| current.set(period)
+        >
+            {period.label()}
+        </button>
+    }
+}
+
+#[component]
+fn EntryView(
+    entry: LeaderboardEntry,
+    #[prop(into, optional)] highlight: MaybeSignal<bool>,
+    #[prop(optional)] _ref: NodeRef<html::Div>,
+) -> impl IntoView {
+    let profile_url = format!("/profile/{}/stakes", entry.profile.principal);
+    let stats = entry.stats;
+
+    view! {
+        <div
+            _ref=_ref
+            class="grid grid-cols-6 items-center gap-2 w-full px-4 py-3 rounded-md"
+            class=("bg-primary-600/20", highlight)
+        >
+            <span class="text-lg font-bold text-white/50">{entry.rank}</span>
+            <a href=profile_url class="col-span-3 flex flex-row items-center gap-3">
+                <img
+                    class="w-10 h-10 rounded-full object-cover"
+                    src=entry.profile.profile_pic_or_random()
+                />
+                <div class="flex flex-col overflow-hidden">
+                    <span class="truncate">{entry.profile.display_name_or_fallback()}</span>
+                    <span class="text-xs text-white/50">
+                        {format!("{:.0}% won", stats.win_rate())} " · "
+                        {format!("{} streak", stats.best_streak)}
+                    </span>
+                </div>
+            </a>
+            <span
+                class="col-span-2 justify-self-end font-semibold"
+                class=("text-red-500", stats.net_earnings < 0)
+            >
+                {format!("{} COYNs", stats.net_earnings)}
+            </span>
+        </div>
+    }
+}
+
+#[component]
+fn OwnRank(period: LeaderboardPeriod) -> impl IntoView {
+    let cans_wire_res = authenticated_canisters();
+    let own_entry = create_local_resource(
+        move || period,
+        move |period| {
+            let cans_wire_res = cans_wire_res.clone();
+            async move {
+                let cans_wire = cans_wire_res.wait_untracked().await?;
+                get_own_leaderboard_entry(cans_wire, period).await
+            }
+        },
+    );
+
+    view! {
+        <Suspense>
+            {move || {
+                own_entry()
+                    .map(|res| match res {
+                        Ok(Some(entry)) => view! { <EntryView entry highlight=true /> }.into_view(),
+                        Ok(None) => {
+                            view! {
+                                <span class="text-sm text-white/50 text-center">
+                                    Play Hot or Not to get ranked
+                                </span>
+                            }
+                                .into_view()
+                        }
+                        Err(e) => {
+                            log::warn!("failed to fetch own leaderboard rank: {e}");
+                            ().into_view()
+                        }
+                    })
+            }}
+        </Suspense>
+    }
+}
+
+#[component]
+fn Rankings(period: LeaderboardPeriod, own_canister: Signal<Option<Principal>>) -> impl IntoView {
+    view! {
+        <div class="flex flex-col gap-2 w-full">
+            <InfiniteScroller
+                provider=LeaderboardProvider::new(period)
+                fetch_count=FETCH_CNT
+                children=move |entry, _ref| {
+                    let user_canister = entry.user_canister;
+                    let highlight = Signal::derive(move || own_canister() == Some(user_canister));
+                    view! { <EntryView entry highlight _ref=_ref.unwrap_or_default() /> }
+                }
+                empty_content=|| {
+                    view! {
+                        <span class="text-white/50 text-center py-8">No rankings yet</span>
+                    }
+                }
+            />
+        </div>
+    }
+}
 
 #[component]
 pub fn Leaderboard() -> impl IntoView {
+    let period = create_rw_signal(LeaderboardPeriod::default());
+    let cans_wire_res = authenticated_canisters();
+    let own_canister_res = create_local_resource(
+        || (),
+        move |_| {
+            let cans_wire_res = cans_wire_res.clone();
+            async move {
+                let cans_wire = cans_wire_res.wait_untracked().await.ok()?;
+                Some(cans_wire.canisters().ok()?.user_canister())
+            }
+        },
+    );
+    let own_canister = Signal::derive(move || own_canister_res().flatten());
+
     view! {
-        <div class="flex flex-col bg-black items-center gap-4 justify-center w-dvw h-dvh">
-            <Icon class="w-36 h-36" icon=ComingSoonGraphic />
-            <span class="text-white text-xl">Coming Soon</span>
+        <div class="min-h-screen w-full flex flex-col items-center gap-6 pt-2 pb-16 bg-black text-white">
+            <Title justify_center=false>
+                <div class="flex flex-row justify-between">
+                    <BackButton fallback="/menu".to_string() />
+                    <span class="font-bold text-2xl">Leaderboard</span>
+                    <div></div>
+                </div>
+            </Title>
+            <div class="flex flex-col gap-4 w-full md:w-10/12 lg:w-8/12 px-4">
+                <div class="flex flex-row gap-2 p-1 rounded-full bg-white/10">
+                    {LeaderboardPeriod::ALL
+                        .into_iter()
+                        .map(|p| view! { <PeriodTab period=p current=period /> })
+                        .collect_view()}
+                </div>
+                {move || view! { <OwnRank period=period() /> }}
+                {move || view! { <Rankings period=period() own_canister /> }}
+            </div>
         </div>
     }
 }
@@ -260,6 +260,7 @@ pub fn Menu() -> impl IntoView {
                 />
                 <MenuItem href="/refer-earn" text="Refer & Earn" icon=icondata::AiGiftFilled />
                 <MenuItem href="/leaderboard" text="Leaderboard" icon=icondata::ChTrophy />
+                <MenuItem href="/notifs" text="Notifications" icon=icondata::AiBellOutlined />
                 <MenuItem
                     href=social::TELEGRAM
                     text="Talk to the team"
@@ -1,41 +1,262 @@
-use leptos::*;
+use codee::string::FromToStringCodec;
+use leptos::{html::Input, *};
+use leptos_icons::*;
+use leptos_use::{storage::use_local_storage, use_event_listener};
 
 use crate::{
-    component::canisters_prov::AuthCansProvider,
-    state::auth::account_connected_reader,
-    utils::{notifications::get_token_for_principal, profile::ProfileDetails},
+    component::{
+        back_btn::BackButton, infinite_scroller::InfiniteScroller, title::Title, toggle::Toggle,
+    },
+    consts::NOTIFICATIONS_ENABLED_STORE,
+    state::canisters::{authenticated_canisters, CanistersAuthWire},
+    utils::notifications::{
+        get_token_for_principal,
+        inbox::{
+            get_notification_prefs, mark_notifications_read, set_notification_prefs, Notification,
+            NotificationCategory, NotificationPrefs, NotificationsProvider,
+        },
+    },
 };
 
+const FETCH_CNT: usize = 20;
+
 #[component]
-fn NotifInnerComponent(details: ProfileDetails) -> impl IntoView {
-    let (_, _) = account_connected_reader();
+fn NotificationItem(
+    notif: Notification,
+    cans_wire: CanistersAuthWire,
+    #[prop(optional)] _ref: NodeRef<html::A>,
+) -> impl IntoView {
+    let read = create_rw_signal(notif.read);
+    let id = notif.id.clone();
+    let on_click = move |_| {
+        if read.get_untracked() {
+            return;
+        }
+        read.set(true);
+        let cans_wire = cans_wire.clone();
+        let id = id.clone();
+        spawn_local(async move {
+            if let Err(e) = mark_notifications_read(cans_wire, vec![id]).await {
+                log::warn!("failed to mark notification as read: {e}");
+            }
+        });
+    };
+
+    view! {
+        <a
+            _ref=_ref
+            href=notif.kind.href()
+            class="flex flex-row items-center gap-3 w-full px-4 py-3 rounded-md bg-white/5"
+            on:click=on_click
+        >
+            <div
+                class="w-2 h-2 shrink-0 rounded-full"
+                class=("bg-primary-600", move || !read())
+            ></div>
+            <div class="flex flex-col overflow-hidden">
+                <span class="font-semibold truncate">{notif.kind.title()}</span>
+                <span class="text-sm text-white/50">{notif.kind.body()}</span>
+            </div>
+        </a>
+    }
+}
 
-    let on_token_click = create_action(move |()| async move {
-        get_token_for_principal(details.principal.to_string()).await;
+#[component]
+fn Inbox(cans_wire: CanistersAuthWire) -> impl IntoView {
+    // bumped to refetch the inbox after bulk updates
+    let refresh = create_rw_signal(0u32);
+    let cans_wire_c = cans_wire.clone();
+    let mark_all_read = create_action(move |()| {
+        let cans_wire = cans_wire_c.clone();
+        async move {
+            match mark_notifications_read(cans_wire, vec![]).await {
+                Ok(()) => refresh.update(|r| *r += 1),
+                Err(e) => log::warn!("failed to mark notifications as read: {e}"),
+            }
+        }
     });
 
     view! {
-        <h1>"YRAL Notifs for"</h1>
-        <h2>{details.username_or_principal()}</h2>
-        <br />
-        <div class="flex flex-row gap-2 text-black">
-            <button
-                class="p-2 bg-gray-200 rounded-md"
-                on:click=move |_| on_token_click.dispatch(())
-            >
-                "Get Token"
-            </button>
+        <div class="flex flex-col gap-2 w-full">
+            <div class="flex flex-row justify-between items-center">
+                <span class="text-lg font-semibold">Inbox</span>
+                <button
+                    class="text-sm text-primary-600 disabled:text-white/50"
+                    disabled=mark_all_read.pending()
+                    on:click=move |_| mark_all_read.dispatch(())
+                >
+                    Mark all read
+                </button>
+            </div>
+            {move || {
+                refresh();
+                let cans_wire = cans_wire.clone();
+                view! {
+                    <InfiniteScroller
+                        provider=NotificationsProvider::new(cans_wire.clone())
+                        fetch_count=FETCH_CNT
+                        children=move |notif, _ref| {
+                            view! {
+                                <NotificationItem
+                                    notif
+                                    cans_wire=cans_wire.clone()
+                                    _ref=_ref.unwrap_or_default()
+                                />
+                            }
+                        }
+                        empty_content=|| {
+                            view! {
+                                <span class="text-white/50 text-center py-8">
+                                    "You're all caught up"
+                                </span>
+                            }
+                        }
+                    />
+                }
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn SettingRow(
+    #[prop(into)] text: String,
+    checked: Signal<bool>,
+    node_ref: NodeRef<Input>,
+) -> impl IntoView {
+    view! {
+        <div class="grid grid-cols-2 items-center w-full">
+            <span>{text}</span>
+            <div class="justify-self-end">
+                <Toggle checked node_ref />
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn PushToggle(
+    prefs: RwSignal<NotificationPrefs>,
+    save_prefs: Action<NotificationPrefs, ()>,
+    principal: String,
+) -> impl IntoView {
+    let (notifs_enabled, set_notifs_enabled, _) =
+        use_local_storage::<bool, FromToStringCodec>(NOTIFICATIONS_ENABLED_STORE);
+    let toggle_ref = create_node_ref::<Input>();
+
+    _ = use_event_listener(toggle_ref, ev::change, move |ev| {
+        let enabled = event_target_checked(&ev);
+        if enabled {
+            let principal = principal.clone();
+            spawn_local(async move { get_token_for_principal(principal).await });
+        }
+        set_notifs_enabled(enabled);
+        prefs.update(|p| p.push_enabled = enabled);
+        save_prefs.dispatch(prefs.get_untracked());
+    });
+
+    view! {
+        <SettingRow text="Push notifications" checked=notifs_enabled node_ref=toggle_ref />
+    }
+}
+
+#[component]
+fn CategoryToggle(
+    category: NotificationCategory,
+    prefs: RwSignal<NotificationPrefs>,
+    save_prefs: Action<NotificationPrefs, ()>,
+) -> impl IntoView {
+    let toggle_ref = create_node_ref::<Input>();
+    let enabled = Signal::derive(move || prefs.with(|p| !p.is_muted(category)));
+
+    _ = use_event_listener(toggle_ref, ev::change, move |ev| {
+        let enabled = event_target_checked(&ev);
+        prefs.update(|p| p.set_muted(category, !enabled));
+        save_prefs.dispatch(prefs.get_untracked());
+    });
+
+    view! { <SettingRow text=category.label() checked=enabled node_ref=toggle_ref /> }
+}
+
+#[component]
+fn NotificationSettings(cans_wire: CanistersAuthWire, prefs: NotificationPrefs) -> impl IntoView {
+    let principal = cans_wire
+        .canisters()
+        .map(|cans| cans.user_principal().to_text())
+        .unwrap_or_default();
+    let prefs = create_rw_signal(prefs);
+    let save_prefs = create_action(move |prefs: &NotificationPrefs| {
+        let cans_wire = cans_wire.clone();
+        let prefs = prefs.clone();
+        async move {
+            if let Err(e) = set_notification_prefs(cans_wire, prefs).await {
+                log::warn!("failed to save notification preferences: {e}");
+            }
+        }
+    });
+
+    view! {
+        <div class="flex flex-col gap-4 w-full">
+            <span class="text-lg font-semibold">Settings</span>
+            <PushToggle prefs save_prefs principal />
+            {NotificationCategory::ALL
+                .into_iter()
+                .map(|category| view! { <CategoryToggle category prefs save_prefs /> })
+                .collect_view()}
         </div>
     }
 }
 
 #[component]
 pub fn Notif() -> impl IntoView {
+    let cans_wire_res = authenticated_canisters();
+    let notif_data = create_local_resource(
+        || (),
+        move |_| {
+            let cans_wire_res = cans_wire_res.clone();
+            async move {
+                let cans_wire = cans_wire_res.wait_untracked().await?;
+                let prefs = get_notification_prefs(cans_wire.clone()).await?;
+                Ok::<_, ServerFnError>((cans_wire, prefs))
+            }
+        },
+    );
+
     view! {
-        <div class="h-screen w-screen grid grid-cols-1 justify-items-center place-content-center">
-            <AuthCansProvider let:cans>
-                <NotifInnerComponent details=cans.profile_details() />
-            </AuthCansProvider>
+        <div class="min-h-screen w-full flex flex-col items-center gap-6 pt-2 pb-16 bg-black text-white">
+            <Title justify_center=false>
+                <div class="flex flex-row justify-between">
+                    <BackButton fallback="/menu".to_string() />
+                    <span class="font-bold text-2xl">Notifications</span>
+                    <div></div>
+                </div>
+            </Title>
+            <div class="flex flex-col gap-8 w-full md:w-10/12 lg:w-8/12 px-4">
+                <Suspense>
+                    {move || {
+                        notif_data()
+                            .map(|res| match res {
+                                Ok((cans_wire, prefs)) => {
+                                    view! {
+                                        <NotificationSettings cans_wire=cans_wire.clone() prefs />
+                                        <Inbox cans_wire />
+                                    }
+                                        .into_view()
+                                }
+                                Err(e) => {
+                                    log::warn!("failed to load notifications: {e}");
+                                    view! {
+                                        <div class="flex flex-col items-center gap-2 py-8 text-white/50">
+                                            <Icon class="text-4xl" icon=icondata::AiBellOutlined />
+                                            <span>Failed to load notifications</span>
+                                        </div>
+                                    }
+                                        .into_view()
+                                }
+                            })
+                    }}
+                </Suspense>
+            </div>
         </div>
     }
 }
@@ -11,9 +11,10 @@ use crate::{
         spinner::SpinnerFit,
     },
     page::post_view::BetEligiblePostCtx,
-    state::canisters::{unauth_canisters, Canisters},
+    state::canisters::{authenticated_canisters, unauth_canisters, Canisters},
     try_or_redirect_opt,
     utils::{
+        leaderboard::register_player,
         posts::PostDetails,
         profile::{BetDetails, BetKind, BetOutcome},
         time::to_hh_mm_ss,
@@ -141,6 +142,7 @@ fn HNButtonOverlay(
     bet_direction: RwSignal<Option<BetKind>>,
     refetch_bet: Trigger,
 ) -> impl IntoView {
+    let cans_wire_res = authenticated_canisters();
     let place_bet_action = create_action(
         move |(canisters, bet_direction, bet_amount): &(Canisters<true>, BetKind, u64)| {
             let post_can_id = post.canister_id;
@@ -148,9 +150,20 @@ fn HNButtonOverlay(
             let cans = canisters.clone();
             let bet_amount = *bet_amount;
             let bet_direction = *bet_direction;
+            let cans_wire_res = cans_wire_res.clone();
             async move {
                 match bet_on_post(cans, bet_amount, bet_direction, post_id, post_can_id).await {
-                    Ok(_) => Some(()),
+                    Ok(_) => {
+                        // Best effort, the bet itself went through
+                        let registered = match cans_wire_res.wait_untracked().await {
+                            Ok(cans_wire) => register_player(cans_wire).await,
+                            Err(e) => Err(e),
+                        };
+                        if let Err(e) = registered {
+                            log::warn!("failed to register for the leaderboard: {e}");
+                        }
+                        Some(())
+                    }
                     Err(e) => {
                         log::error!("{e}");
                         None
@@ -418,6 +431,10 @@ fn MaybeHNButtons(
         move |_| {
             let post = post.get_value();
             async move {
+                // The owner withdrew hot or not consent after publishing
+                if !post.is_hot_or_not() {
+                    return Some(false);
+                }
                 let canisters = unauth_canisters();
                 let user = canisters.individual_user(post.canister_id).await;
                 let res = user
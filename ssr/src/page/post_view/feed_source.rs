@@ -0,0 +1,994 @@
+use std::collections::HashSet;
+
+use candid::Principal;
+use futures::{
+    future::{self, LocalBoxFuture},
+    stream::{self, FuturesOrdered},
+    FutureExt, Stream, StreamExt,
+};
+use yral_canisters_client::post_cache::{self, NsfwFilter};
+
+use crate::{
+    page::profile::profile_iter::{
+        FixedFetchCursor, PostsRes, ProfVideoStream, ProfileVideoStream,
+    },
+    state::canisters::Canisters,
+    utils::{
+        hashtags::normalize_hashtag,
+        post_edits::apply_post_overrides,
+        posts::{get_post_uid_raw, FetchCursor, PostDetails, PostViewError},
+        types::PostId,
+    },
+};
+
+use super::video_iter::{FeedResultType, FetchVideosRes, PostsStream};
+
+/// Parameters passed to every [FeedSource] on fetch
+#[derive(Clone, Default)]
+pub struct FeedParams {
+    pub chunks: usize,
+    pub allow_nsfw: bool,
+    /// Posts already queued for the user
+    /// sources backed by a recommender use this to filter out seen posts
+    pub video_queue: Vec<PostDetails>,
+}
+
+/// A source of posts for the home feed
+/// Sources own their cursor and advance it on every successful fetch,
+/// so a source is built once and fetched from for the whole session
+pub trait FeedSource {
+    fn fetch<'b>(
+        &'b mut self,
+        params: &'b FeedParams,
+    ) -> LocalBoxFuture<'b, Result<FetchVideosRes, PostViewError>>;
+}
+
+/// Resolve `posts` in chunks, applying the owners' edits and deletions
+/// posts marked NSFW by their owner are dropped unless `allow_nsfw`
+fn resolve_posts<const AUTH: bool>(
+    canisters: &Canisters<AUTH>,
+    posts: impl IntoIterator<Item = PostId>,
+    chunks: usize,
+    allow_nsfw: bool,
+) -> PostsStream {
+    let chunk_stream = posts
+        .into_iter()
+        .map(|(canister_id, post_id)| {
+            let canisters = canisters.clone();
+            async move { get_post_uid_raw(&canisters, canister_id, post_id).await }
+        })
+        .collect::<FuturesOrdered<_>>()
+        .filter_map(|res| async { res.transpose() })
+        .chunks(chunks)
+        .then(move |chunk| async move {
+            let mut posts = vec![];
+            let mut errs = vec![];
+            for res in chunk {
+                match res {
+                    Ok(post) => posts.push(post),
+                    Err(e) => errs.push(Err(e)),
+                }
+            }
+            apply_post_overrides(posts)
+                .await
+                .into_iter()
+                .filter(|post| allow_nsfw || !post.is_nsfw)
+                .map(Ok)
+                .chain(errs)
+                .collect()
+        });
+    Box::pin(chunk_stream)
+}
+
+fn empty_res(res_type: FeedResultType) -> FetchVideosRes {
+    FetchVideosRes {
+        posts_stream: Box::pin(stream::empty()),
+        end: true,
+        res_type,
+    }
+}
+
+/// Top posts from the post cache canister
+pub struct PostCacheSource<const AUTH: bool> {
+    canisters: Canisters<AUTH>,
+    cursor: FetchCursor,
+}
+
+impl<const AUTH: bool> PostCacheSource<AUTH> {
+    pub fn new(canisters: &Canisters<AUTH>, cursor: FetchCursor) -> Self {
+        Self {
+            canisters: canisters.clone(),
+            cursor,
+        }
+    }
+}
+
+impl<const AUTH: bool> FeedSource for PostCacheSource<AUTH> {
+    fn fetch<'b>(
+        &'b mut self,
+        params: &'b FeedParams,
+    ) -> LocalBoxFuture<'b, Result<FetchVideosRes, PostViewError>> {
+        async move {
+            let canisters = &self.canisters;
+            let cursor = self.cursor;
+            let nsfw_filter = if params.allow_nsfw {
+                NsfwFilter::IncludeNsfw
+            } else {
+                NsfwFilter::ExcludeNsfw
+            };
+            let top_posts_fut = canisters.with_retry("post_cache.get_top_posts", || async move {
+                let post_cache = canisters.post_cache().await;
+                post_cache
+                    .get_top_posts_aggregated_from_canisters_on_this_network_for_home_feed_cursor(
+                        cursor.start,
+                        cursor.limit,
+                        None,
+                        None,
+                        Some(nsfw_filter),
+                    )
+                    .await
+            });
+            let top_posts = match top_posts_fut.await? {
+                post_cache::Result_::Ok(top_posts) => top_posts,
+                post_cache::Result_::Err(post_cache::TopPostsFetchError::ReachedEndOfItemsList) => {
+                    return Ok(empty_res(FeedResultType::PostCache))
+                }
+                post_cache::Result_::Err(_) => {
+                    return Err(PostViewError::Canister(
+                        "canister refused to send posts".into(),
+                    ))
+                }
+            };
+
+            let end = top_posts.len() < self.cursor.limit as usize;
+            self.cursor.advance();
+
+            Ok(FetchVideosRes {
+                posts_stream: resolve_posts(
+                    &self.canisters,
+                    top_posts
+                        .into_iter()
+                        .map(|item| (item.publisher_canister_id, item.post_id)),
+                    params.chunks,
+                    params.allow_nsfw,
+                ),
+                end,
+                res_type: FeedResultType::PostCache,
+            })
+        }
+        .boxed_local()
+    }
+}
+
+/// Recommendations from the ML feed server
+/// falls back to the post cache if the server is unreachable
+pub struct MlFeedSource<const AUTH: bool> {
+    canisters: Canisters<AUTH>,
+    cursor: FetchCursor,
+    fallback: PostCacheSource<AUTH>,
+}
+
+impl<const AUTH: bool> MlFeedSource<AUTH> {
+    pub fn new(canisters: &Canisters<AUTH>, cursor: FetchCursor) -> Self {
+        Self {
+            canisters: canisters.clone(),
+            cursor,
+            fallback: PostCacheSource::new(canisters, cursor),
+        }
+    }
+}
+
+/// Posts from the end of the queue sent as "recently seen" to the feed cache
+/// keeps the request size constant regardless of session length
+#[cfg(feature = "hydrate")]
+const RECENTLY_SEEN_WINDOW: usize = 30;
+
+#[cfg(feature = "hydrate")]
+async fn fetch_ml_feed_direct(
+    limit: u32,
+    video_queue: Vec<PostDetails>,
+) -> Result<Vec<PostId>, tonic::Status> {
+    use candid::Principal;
+    use codee::string::JsonSerdeCodec;
+    use leptos::*;
+    use leptos_use::storage::use_local_storage;
+
+    use crate::{
+        consts::USER_CANISTER_ID_STORE, state::canisters::auth_canisters_store,
+        utils::ml_feed::ml_feed_grpcweb::MLFeed,
+    };
+
+    let ml_feed: MLFeed = expect_context();
+
+    let (user_canister_id_local_storage, _, _) =
+        use_local_storage::<Option<Principal>, JsonSerdeCodec>(USER_CANISTER_ID_STORE);
+    let user_canister_id;
+    if let Some(canister_id) = user_canister_id_local_storage.get_untracked() {
+        user_canister_id = canister_id;
+    } else {
+        let cans_store = auth_canisters_store();
+        let mut cans_stream = cans_store.to_stream();
+        let cans;
+        loop {
+            if let Some(cans_val) = cans_stream.next().await.flatten() {
+                cans = cans_val;
+                break;
+            } else {
+                continue;
+            }
+        }
+        user_canister_id = cans.user_canister();
+    }
+
+    ml_feed
+        .get_next_feed(&user_canister_id, limit, video_queue)
+        .await
+}
+
+impl<const AUTH: bool> FeedSource for MlFeedSource<AUTH> {
+    fn fetch<'b>(
+        &'b mut self,
+        params: &'b FeedParams,
+    ) -> LocalBoxFuture<'b, Result<FetchVideosRes, PostViewError>> {
+        async move {
+            #[cfg(feature = "hydrate")]
+            {
+                use leptos::ServerFnError;
+
+                use crate::utils::ml_feed::{
+                    get_ml_feed_cached, is_transient, SeenPost, ML_FEED_UNREACHABLE,
+                };
+
+                let limit = self.cursor.limit as u32;
+                let recently_seen = params
+                    .video_queue
+                    .iter()
+                    .rev()
+                    .take(RECENTLY_SEEN_WINDOW)
+                    .map(SeenPost::from)
+                    .collect();
+
+                let top_posts = match get_ml_feed_cached(limit, recently_seen).await {
+                    Ok(top_posts) => top_posts,
+                    // The server already made its attempt, don't wait on a second one
+                    Err(ServerFnError::ServerError(e)) if e == ML_FEED_UNREACHABLE => {
+                        log::warn!("ml feed unreachable, falling back to post cache");
+                        return self.fallback.fetch(params).await;
+                    }
+                    Err(e) => {
+                        log::warn!("ml feed cache failed, fetching directly: {e}");
+                        let recent_queue = params.video_queue[params
+                            .video_queue
+                            .len()
+                            .saturating_sub(RECENTLY_SEEN_WINDOW)..]
+                            .to_vec();
+                        match fetch_ml_feed_direct(limit, recent_queue).await {
+                            Ok(top_posts) => top_posts,
+                            Err(e) if is_transient(&e) => {
+                                log::warn!("ml feed unreachable, falling back to post cache: {e}");
+                                return self.fallback.fetch(params).await;
+                            }
+                            Err(e) => {
+                                return Err(PostViewError::MLFeedError(format!(
+                                    "Error fetching ml feed: {:?}",
+                                    e
+                                )));
+                            }
+                        }
+                    }
+                };
+                self.cursor.advance_and_set_limit(self.cursor.limit);
+
+                Ok(FetchVideosRes {
+                    posts_stream: resolve_posts(
+                        &self.canisters,
+                        top_posts,
+                        params.chunks,
+                        params.allow_nsfw,
+                    ),
+                    end: false,
+                    res_type: FeedResultType::MLFeed,
+                })
+            }
+
+            #[cfg(not(feature = "hydrate"))]
+            {
+                _ = (params, &self.canisters, self.cursor, &mut self.fallback);
+                Ok(empty_res(FeedResultType::MLFeed))
+            }
+        }
+        .boxed_local()
+    }
+}
+
+/// The user's ML feed cache stored in their individual canister
+/// falls back to the post cache if the cache is empty
+pub struct MlFeedCacheSource {
+    canisters: Canisters<true>,
+    cursor: FetchCursor,
+    fallback: PostCacheSource<true>,
+}
+
+impl MlFeedCacheSource {
+    pub fn new(canisters: &Canisters<true>, cursor: FetchCursor) -> Self {
+        Self {
+            canisters: canisters.clone(),
+            cursor,
+            fallback: PostCacheSource::new(canisters, cursor),
+        }
+    }
+}
+
+impl FeedSource for MlFeedCacheSource {
+    fn fetch<'b>(
+        &'b mut self,
+        params: &'b FeedParams,
+    ) -> LocalBoxFuture<'b, Result<FetchVideosRes, PostViewError>> {
+        async move {
+            let canisters = &self.canisters;
+            let cursor = self.cursor;
+            let top_posts = canisters
+                .with_retry("individual_user.get_ml_feed_cache", || async move {
+                    let user_canister = canisters.authenticated_user().await;
+                    user_canister
+                        .get_ml_feed_cache_paginated(cursor.start, cursor.limit)
+                        .await
+                })
+                .await?;
+            if top_posts.is_empty() {
+                return self.fallback.fetch(params).await;
+            }
+            self.cursor.advance_and_set_limit(self.cursor.limit);
+
+            Ok(FetchVideosRes {
+                posts_stream: resolve_posts(
+                    &self.canisters,
+                    top_posts
+                        .into_iter()
+                        .map(|item| (item.canister_id, item.post_id)),
+                    params.chunks,
+                    params.allow_nsfw,
+                ),
+                end: false,
+                res_type: FeedResultType::MLFeedCache,
+            })
+        }
+        .boxed_local()
+    }
+}
+
+/// Fetches from `primary`, switching to `fallback` for the batch if it fails
+pub struct FallbackSource<P, F> {
+    primary: P,
+    fallback: F,
+    warm_up: usize,
+}
+
+impl<P, F> FallbackSource<P, F> {
+    pub fn new(primary: P, fallback: F) -> Self {
+        Self {
+            primary,
+            fallback,
+            warm_up: 0,
+        }
+    }
+
+    /// Serve from `fallback` until `warm_up` posts are queued,
+    /// i.e recommenders need some history to work with
+    pub fn with_warm_up(mut self, warm_up: usize) -> Self {
+        self.warm_up = warm_up;
+        self
+    }
+}
+
+impl<P: FeedSource, F: FeedSource> FeedSource for FallbackSource<P, F> {
+    fn fetch<'b>(
+        &'b mut self,
+        params: &'b FeedParams,
+    ) -> LocalBoxFuture<'b, Result<FetchVideosRes, PostViewError>> {
+        async move {
+            if params.video_queue.len() < self.warm_up {
+                return self.fallback.fetch(params).await;
+            }
+            match self.primary.fetch(params).await {
+                Ok(res) => Ok(res),
+                Err(e) => {
+                    log::warn!("feed source failed, falling back: {e}");
+                    self.fallback.fetch(params).await
+                }
+            }
+        }
+        .boxed_local()
+    }
+}
+
+/// Pages of a creator's posts, newest first
+/// implemented by [Canisters], stubbed in tests
+pub trait CreatorPosts {
+    fn creator_posts<'b>(
+        &'b self,
+        creator: Principal,
+        cursor: FixedFetchCursor<10>,
+    ) -> LocalBoxFuture<'b, Result<PostsRes, PostViewError>>;
+}
+
+impl<const AUTH: bool> CreatorPosts for Canisters<AUTH> {
+    fn creator_posts<'b>(
+        &'b self,
+        creator: Principal,
+        cursor: FixedFetchCursor<10>,
+    ) -> LocalBoxFuture<'b, Result<PostsRes, PostViewError>> {
+        ProfileVideoStream::<10>::fetch_next_posts(cursor, self, creator).boxed_local()
+    }
+}
+
+/// Upper bound on the creators loaded by [followed_creators]
+const MAX_FOLLOWED_CREATORS: usize = 50;
+
+/// Individual user canisters of the creators followed by the user
+/// creators that can't be resolved are skipped
+pub async fn followed_creators(canisters: &Canisters<true>) -> Vec<Principal> {
+    let user = canisters.authenticated_user().await;
+    let mut followed = vec![];
+    let mut last_index = None;
+    while followed.len() < MAX_FOLLOWED_CREATORS {
+        let page = match user
+            .get_principals_this_profile_follows_paginated(last_index)
+            .await
+        {
+            Ok(page) => page,
+            Err(e) => {
+                log::warn!("failed to fetch followed creators: {e}");
+                break;
+            }
+        };
+        let Some(&(idx, _)) = page.last() else {
+            break;
+        };
+        last_index = Some(idx);
+        followed.extend(page.into_iter().map(|(_, creator)| creator));
+    }
+    followed.truncate(MAX_FOLLOWED_CREATORS);
+
+    future::join_all(followed.into_iter().map(|creator| async move {
+        match canisters
+            .get_individual_canister_by_user_principal(creator)
+            .await
+        {
+            Ok(canister) => canister,
+            Err(e) => {
+                log::warn!("failed to resolve the canister of {creator}: {e}, skipping");
+                None
+            }
+        }
+    }))
+    .await
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Latest posts from a set of creators, newest first
+/// `creators` are the creators' individual user canisters
+pub struct FollowedCreatorsSource<C> {
+    posts: C,
+    creators: Vec<Principal>,
+    cursor: FixedFetchCursor<10>,
+}
+
+impl<C> FollowedCreatorsSource<C> {
+    pub fn new(posts: C, creators: Vec<Principal>) -> Self {
+        Self {
+            posts,
+            creators,
+            cursor: FixedFetchCursor {
+                start: 0,
+                limit: 10,
+            },
+        }
+    }
+}
+
+impl<C: CreatorPosts> FeedSource for FollowedCreatorsSource<C> {
+    fn fetch<'b>(
+        &'b mut self,
+        params: &'b FeedParams,
+    ) -> LocalBoxFuture<'b, Result<FetchVideosRes, PostViewError>> {
+        async move {
+            let posts_of = &self.posts;
+            let cursor = self.cursor;
+            let pages = future::join_all(self.creators.iter().map(|&creator| async move {
+                (creator, posts_of.creator_posts(creator, cursor).await)
+            }))
+            .await;
+
+            let mut posts = vec![];
+            let mut ended = HashSet::new();
+            for (creator, page) in pages {
+                match page {
+                    Ok(page) => {
+                        posts.extend(page.posts);
+                        if page.end {
+                            ended.insert(creator);
+                        }
+                    }
+                    Err(e) => {
+                        log::warn!("failed to fetch posts for creator {creator}: {e}, skipping");
+                        ended.insert(creator);
+                    }
+                }
+            }
+            self.creators.retain(|c| !ended.contains(c));
+            self.cursor.advance();
+
+            posts.retain(|post| params.allow_nsfw || !post.is_nsfw);
+            posts.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+            let chunks = posts
+                .chunks(params.chunks.max(1))
+                .map(|chunk| chunk.iter().cloned().map(Ok).collect())
+                .collect::<Vec<_>>();
+
+            Ok(FetchVideosRes {
+                posts_stream: Box::pin(stream::iter(chunks)),
+                end: self.creators.is_empty(),
+                res_type: FeedResultType::FollowedCreators,
+            })
+        }
+        .boxed_local()
+    }
+}
+
+/// Narrows down posts from `inner` to ones tagged with any of the trending hashtags
+pub struct TrendingHashtagsSource<S> {
+    inner: S,
+    hashtags: HashSet<String>,
+}
+
+impl<S> TrendingHashtagsSource<S> {
+    pub fn new(inner: S, hashtags: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        Self {
+            inner,
+            hashtags: hashtags
+                .into_iter()
+                .filter_map(|tag| normalize_hashtag(tag.as_ref()).ok().flatten())
+                .collect(),
+        }
+    }
+}
+
+impl<S: FeedSource> FeedSource for TrendingHashtagsSource<S> {
+    fn fetch<'b>(
+        &'b mut self,
+        params: &'b FeedParams,
+    ) -> LocalBoxFuture<'b, Result<FetchVideosRes, PostViewError>> {
+        async move {
+            let res = self.inner.fetch(params).await?;
+            let hashtags = self.hashtags.clone();
+            let posts_stream = res
+                .posts_stream
+                .map(move |chunk| {
+                    chunk
+                        .into_iter()
+                        .filter(|post| {
+                            let Ok(post) = post else {
+                                return true;
+                            };
+                            post.hastags.iter().any(|tag| {
+                                normalize_hashtag(tag)
+                                    .ok()
+                                    .flatten()
+                                    .is_some_and(|tag| hashtags.contains(&tag))
+                            })
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .filter(|chunk| future::ready(!chunk.is_empty()));
+
+            Ok(FetchVideosRes {
+                posts_stream: Box::pin(posts_stream),
+                end: res.end,
+                res_type: FeedResultType::TrendingHashtags,
+            })
+        }
+        .boxed_local()
+    }
+}
+
+struct WeightedSource {
+    weight: usize,
+    source: Box<dyn FeedSource>,
+    end: bool,
+}
+
+/// Mixes multiple sources, taking `weight` posts from each source in turn
+/// A failing source is skipped for the batch instead of failing the whole feed,
+/// an ended source is not fetched from again
+#[derive(Default)]
+pub struct WeightedInterleaver {
+    sources: Vec<WeightedSource>,
+}
+
+impl WeightedInterleaver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sources with a weight of 0 are ignored
+    pub fn with_source(mut self, weight: usize, source: impl FeedSource + 'static) -> Self {
+        if weight > 0 {
+            self.sources.push(WeightedSource {
+                weight,
+                source: Box::new(source),
+                end: false,
+            });
+        }
+        self
+    }
+}
+
+type FlatPostsStream = std::pin::Pin<Box<dyn Stream<Item = Result<PostDetails, PostViewError>>>>;
+
+/// Round robin over `streams`, posts already queued or yielded by another source are skipped
+fn interleave(
+    streams: Vec<(usize, FlatPostsStream)>,
+    mut seen: HashSet<PostId>,
+    chunks: usize,
+) -> PostsStream {
+    let merged = stream::unfold(
+        (streams, 0usize, 0usize),
+        |(mut streams, mut idx, mut taken)| async move {
+            loop {
+                if streams.is_empty() {
+                    return None;
+                }
+                idx %= streams.len();
+                let (weight, posts) = &mut streams[idx];
+                if taken >= *weight {
+                    idx += 1;
+                    taken = 0;
+                    continue;
+                }
+                match posts.next().await {
+                    Some(post) => {
+                        taken += 1;
+                        return Some((post, (streams, idx, taken)));
+                    }
+                    None => {
+                        streams.remove(idx);
+                        taken = 0;
+                    }
+                }
+            }
+        },
+    )
+    .filter(move |post| {
+        let fresh = match post {
+            Ok(post) => seen.insert((post.canister_id, post.post_id)),
+            Err(_) => true,
+        };
+        future::ready(fresh)
+    });
+    Box::pin(merged.chunks(chunks.max(1)))
+}
+
+impl FeedSource for WeightedInterleaver {
+    fn fetch<'b>(
+        &'b mut self,
+        params: &'b FeedParams,
+    ) -> LocalBoxFuture<'b, Result<FetchVideosRes, PostViewError>> {
+        async move {
+            let mut active: Vec<_> = self.sources.iter_mut().filter(|s| !s.end).collect();
+            let results = future::join_all(active.iter_mut().map(|s| s.source.fetch(params))).await;
+
+            let mut streams = vec![];
+            let mut last_err = None;
+            for (source, res) in active.into_iter().zip(results) {
+                match res {
+                    Ok(res) => {
+                        source.end = res.end;
+                        let posts: FlatPostsStream =
+                            Box::pin(res.posts_stream.flat_map(stream::iter));
+                        streams.push((source.weight, posts));
+                    }
+                    Err(e) => {
+                        log::warn!("feed source failed: {e}, skipping");
+                        last_err = Some(e);
+                    }
+                }
+            }
+
+            if streams.is_empty() {
+                if let Some(e) = last_err {
+                    return Err(e);
+                }
+            }
+
+            let seen = params
+                .video_queue
+                .iter()
+                .map(|post| (post.canister_id, post.post_id))
+                .collect();
+            Ok(FetchVideosRes {
+                posts_stream: interleave(streams, seen, params.chunks),
+                end: self.sources.iter().all(|s| s.end),
+                res_type: FeedResultType::Interleaved,
+            })
+        }
+        .boxed_local()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, VecDeque};
+
+    use candid::Principal;
+    use futures::{executor::block_on, future::LocalBoxFuture, stream, FutureExt, StreamExt};
+    use web_time::Duration;
+
+    use super::{
+        CreatorPosts, FallbackSource, FeedParams, FeedSource, FollowedCreatorsSource,
+        TrendingHashtagsSource, WeightedInterleaver,
+    };
+    use crate::{
+        page::{
+            post_view::video_iter::{FeedResultType, FetchVideosRes},
+            profile::profile_iter::{FixedFetchCursor, PostsRes},
+        },
+        utils::posts::{PostDetails, PostViewError},
+    };
+
+    fn post(post_id: u64) -> PostDetails {
+        PostDetails {
+            canister_id: Principal::anonymous(),
+            post_id,
+            uid: format!("video-{post_id}"),
+            description: String::new(),
+            views: 0,
+            likes: 0,
+            display_name: String::new(),
+            propic_url: String::new(),
+            liked_by_user: None,
+            poster_principal: Principal::anonymous(),
+            hastags: vec![],
+            is_nsfw: false,
+            hot_or_not_feed_ranking_score: None,
+            created_at: Duration::ZERO,
+        }
+    }
+
+    /// Serves the queued batches in order, failing on `Err` batches
+    struct StubSource {
+        batches: VecDeque<Result<Vec<u64>, String>>,
+        fetches: usize,
+        map_post: fn(PostDetails) -> PostDetails,
+    }
+
+    impl StubSource {
+        fn new(batches: impl IntoIterator<Item = Result<Vec<u64>, String>>) -> Self {
+            Self {
+                batches: batches.into_iter().collect(),
+                fetches: 0,
+                map_post: |post| post,
+            }
+        }
+    }
+
+    impl FeedSource for StubSource {
+        fn fetch<'b>(
+            &'b mut self,
+            params: &'b FeedParams,
+        ) -> LocalBoxFuture<'b, Result<FetchVideosRes, PostViewError>> {
+            self.fetches += 1;
+            let batch = self.batches.pop_front().unwrap_or(Ok(vec![]));
+            let end = self.batches.is_empty();
+            let chunks = params.chunks.max(1);
+            let map_post = self.map_post;
+            async move {
+                let posts = batch.map_err(PostViewError::Canister)?;
+                let chunks: Vec<Vec<_>> = posts
+                    .chunks(chunks)
+                    .map(|chunk| chunk.iter().map(|&id| Ok(map_post(post(id)))).collect())
+                    .collect();
+                Ok(FetchVideosRes {
+                    posts_stream: Box::pin(stream::iter(chunks)),
+                    end,
+                    res_type: FeedResultType::PostCache,
+                })
+            }
+            .boxed_local()
+        }
+    }
+
+    fn fetch_ids(source: &mut impl FeedSource) -> Result<(Vec<u64>, bool), String> {
+        fetch_ids_with(source, FeedParams::default())
+    }
+
+    fn fetch_ids_with(
+        source: &mut impl FeedSource,
+        params: FeedParams,
+    ) -> Result<(Vec<u64>, bool), String> {
+        let params = FeedParams {
+            chunks: 2,
+            ..params
+        };
+        block_on(async {
+            let res = source.fetch(&params).await.map_err(|e| e.to_string())?;
+            let ids: Vec<u64> = res
+                .posts_stream
+                .flat_map(stream::iter)
+                .map(|post| post.unwrap().post_id)
+                .collect()
+                .await;
+            Ok((ids, res.end))
+        })
+    }
+
+    #[test]
+    fn stub_source_chunks_and_ends() {
+        let mut source = StubSource::new([Ok(vec![1, 2, 3]), Ok(vec![4])]);
+        assert_eq!(fetch_ids(&mut source), Ok((vec![1, 2, 3], false)));
+        assert_eq!(fetch_ids(&mut source), Ok((vec![4], true)));
+    }
+
+    #[test]
+    fn fallback_unused_while_primary_succeeds() {
+        let mut source = FallbackSource::new(
+            StubSource::new([Ok(vec![1, 2])]),
+            StubSource::new([Ok(vec![9])]),
+        );
+        assert_eq!(fetch_ids(&mut source), Ok((vec![1, 2], true)));
+        assert_eq!(source.fallback.fetches, 0);
+    }
+
+    #[test]
+    fn fallback_serves_failed_batches() {
+        let mut source = FallbackSource::new(
+            StubSource::new([Err("down".into()), Ok(vec![3])]),
+            StubSource::new([Ok(vec![1, 2]), Ok(vec![4])]),
+        );
+        assert_eq!(fetch_ids(&mut source), Ok((vec![1, 2], false)));
+        // The primary is retried on the next batch
+        assert_eq!(fetch_ids(&mut source), Ok((vec![3], true)));
+        assert_eq!(source.fallback.fetches, 1);
+    }
+
+    #[test]
+    fn fallback_failure_is_returned() {
+        let mut source = FallbackSource::new(
+            StubSource::new([Err("down".into())]),
+            StubSource::new([Err("also down".into())]),
+        );
+        assert!(fetch_ids(&mut source).unwrap_err().contains("also down"));
+    }
+
+    #[test]
+    fn fallback_serves_warm_up() {
+        let mut source = FallbackSource::new(
+            StubSource::new([Ok(vec![1]), Ok(vec![2])]),
+            StubSource::new([Ok(vec![9]), Ok(vec![8])]),
+        )
+        .with_warm_up(1);
+        assert_eq!(fetch_ids(&mut source), Ok((vec![9], false)));
+        let queued = FeedParams {
+            video_queue: vec![post(9)],
+            ..Default::default()
+        };
+        assert_eq!(fetch_ids_with(&mut source, queued), Ok((vec![1], false)));
+    }
+
+    /// Creator `n` has the posts `n * 100 + i` created at `i` seconds, served in pages of 10
+    struct StubCreators {
+        posts: HashMap<Principal, Result<usize, String>>,
+    }
+
+    fn creator(n: u8) -> Principal {
+        Principal::from_slice(&[n])
+    }
+
+    impl CreatorPosts for StubCreators {
+        fn creator_posts<'b>(
+            &'b self,
+            creator: Principal,
+            cursor: FixedFetchCursor<10>,
+        ) -> LocalBoxFuture<'b, Result<PostsRes, PostViewError>> {
+            let res = match &self.posts[&creator] {
+                Ok(count) => {
+                    let n = creator.as_slice()[0] as u64;
+                    let newest = (*count as u64).saturating_sub(cursor.start);
+                    let oldest = newest.saturating_sub(cursor.limit);
+                    let posts = (oldest..newest)
+                        .rev()
+                        .map(|i| PostDetails {
+                            canister_id: creator,
+                            created_at: Duration::from_secs(i),
+                            ..post(n * 100 + i)
+                        })
+                        .collect();
+                    Ok(PostsRes {
+                        posts,
+                        end: oldest == 0,
+                    })
+                }
+                Err(e) => Err(PostViewError::Canister(e.clone())),
+            };
+            async move { res }.boxed_local()
+        }
+    }
+
+    #[test]
+    fn followed_creators_newest_first() {
+        let posts = HashMap::from([(creator(1), Ok(12)), (creator(2), Ok(2))]);
+        let mut source =
+            FollowedCreatorsSource::new(StubCreators { posts }, vec![creator(1), creator(2)]);
+
+        let (ids, end) = fetch_ids(&mut source).unwrap();
+        assert!(!end);
+        assert_eq!(ids.len(), 12);
+        assert_eq!(ids[..4], [111, 110, 109, 108]);
+        assert_eq!(ids[10..], [201, 200]);
+        // Only the creator with more posts is fetched from again
+        assert_eq!(fetch_ids(&mut source), Ok((vec![101, 100], true)));
+    }
+
+    #[test]
+    fn followed_creators_skip_failures() {
+        let posts = HashMap::from([(creator(1), Ok(1)), (creator(2), Err("gone".into()))]);
+        let mut source =
+            FollowedCreatorsSource::new(StubCreators { posts }, vec![creator(1), creator(2)]);
+        assert_eq!(fetch_ids(&mut source), Ok((vec![100], true)));
+    }
+
+    #[test]
+    fn trending_hashtags_filters_inner_posts() {
+        let mut inner = StubSource::new([Ok(vec![1, 2, 3, 4])]);
+        inner.map_post = |post| PostDetails {
+            hastags: match post.post_id {
+                1 => vec!["Travel".into()],
+                3 => vec!["food".into(), "#FITNESS".into()],
+                _ => vec!["other".into()],
+            },
+            ..post
+        };
+        let mut source = TrendingHashtagsSource::new(inner, ["#travel", "Fitness"]);
+        assert_eq!(fetch_ids(&mut source), Ok((vec![1, 3], true)));
+    }
+
+    #[test]
+    fn interleaver_follows_weights() {
+        let mut source = WeightedInterleaver::new()
+            .with_source(2, StubSource::new([Ok(vec![1, 2, 3, 4, 5])]))
+            .with_source(1, StubSource::new([Ok(vec![10, 11]), Ok(vec![12])]))
+            .with_source(0, StubSource::new([Ok(vec![99])]));
+        assert_eq!(
+            fetch_ids(&mut source),
+            Ok((vec![1, 2, 10, 3, 4, 11, 5], false))
+        );
+        // The ended source isn't fetched from again
+        assert_eq!(fetch_ids(&mut source), Ok((vec![12], true)));
+    }
+
+    #[test]
+    fn interleaver_skips_duplicates_and_failures() {
+        let mut source = WeightedInterleaver::new()
+            .with_source(1, StubSource::new([Ok(vec![1, 2, 3])]))
+            .with_source(1, StubSource::new([Ok(vec![2, 4])]))
+            .with_source(1, StubSource::new([Err("down".into())]));
+        let queued = FeedParams {
+            video_queue: vec![post(3)],
+            ..Default::default()
+        };
+        assert_eq!(
+            fetch_ids_with(&mut source, queued),
+            Ok((vec![1, 2, 4], false))
+        );
+    }
+
+    #[test]
+    fn interleaver_fails_when_every_source_fails() {
+        let mut source = WeightedInterleaver::new()
+            .with_source(1, StubSource::new([Err("down".into())]))
+            .with_source(1, StubSource::new([Err("also down".into())]));
+        assert!(fetch_ids(&mut source).is_err());
+    }
+}
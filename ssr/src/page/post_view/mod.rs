@@ -1,5 +1,6 @@
 mod bet;
 pub mod error;
+pub mod feed_source;
 pub mod overlay;
 pub mod single_post;
 pub mod video_iter;
@@ -24,7 +25,7 @@ use leptos::*;
 use leptos_router::*;
 use leptos_use::{storage::use_local_storage, use_debounce_fn};
 
-use video_iter::{FeedResultType, VideoFetchStream};
+use video_iter::VideoFetchStream;
 
 #[derive(Params, PartialEq, Clone, Copy)]
 struct PostParams {
@@ -38,18 +39,107 @@ pub struct BetEligiblePostCtx {
     pub can_place_bet: RwSignal<bool>,
 }
 
+/// Posts kept behind the current post after GC, for scrolling back
+const GC_KEEP_BEHIND: usize = 10;
+/// GC is triggered once the user is this many posts past the kept window
+const GC_TRIGGER_BEHIND: usize = 40;
+/// Upper bound on prefetched posts waiting in the priority queue
+const MAX_PRIORITY_Q_LEN: usize = 50;
+
 #[derive(Clone, Default)]
 pub struct PostViewCtx {
     fetch_cursor: RwSignal<FetchCursor>,
-    // TODO: this is a dead simple with no GC
-    // We're using virtual lists for DOM, so this doesn't consume much memory
-    // as uids only occupy 32 bytes each
-    // but ideally this should be cleaned up
+    // Only a window of posts around `current_idx` is kept
+    // posts far behind the current post are evicted by `gc_video_queue`
     video_queue: RwSignal<Vec<PostDetails>>,
     current_idx: RwSignal<usize>,
     queue_end: RwSignal<bool>,
-    priority_q: RwSignal<DoublePriorityQueue<PostDetails, (usize, Reverse<usize>)>>, // we are using DoublePriorityQueue for GC in the future through pop_min
+    priority_q: RwSignal<DoublePriorityQueue<PostDetails, (usize, Reverse<usize>)>>, // lowest priority posts are dropped through pop_min in `gc_priority_q`
     batch_cnt: RwSignal<usize>,
+    // Built on the first fetch from `fetch_cursor`, kept for the whole session
+    // taken out while a fetch is running
+    feed: RwSignal<Option<VideoFetchStream>>,
+}
+
+impl PostViewCtx {
+    /// Evict posts more than `keep_behind` posts behind the current post
+    /// returns the number of evicted posts
+    fn gc_video_queue(&self, keep_behind: usize) -> usize {
+        let evict = self.current_idx.get_untracked().saturating_sub(keep_behind);
+        if evict == 0 {
+            return 0;
+        }
+        // update both together so the current post (and the URL) stays the same
+        batch(|| {
+            self.video_queue.update(|v| {
+                v.drain(..evict);
+            });
+            self.current_idx.update(|c| *c -= evict);
+        });
+        evict
+    }
+
+    /// Drop the lowest priority posts once the priority queue grows too large
+    /// Take the feed out for a fetch, [Self::put_feed] returns it
+    fn take_feed(&self) -> Option<VideoFetchStream> {
+        self.feed.try_update_untracked(Option::take).flatten()
+    }
+
+    fn put_feed(&self, feed: VideoFetchStream) {
+        self.feed.try_update_untracked(|f| *f = Some(feed));
+    }
+
+    fn gc_priority_q(&self) {
+        self.priority_q.update_untracked(|q| {
+            while q.len() > MAX_PRIORITY_Q_LEN {
+                q.pop_min();
+            }
+        });
+    }
+
+    /// Apply `f` to the cached copies of a post, i.e after its owner edited it
+    pub fn update_post(&self, canister_id: Principal, post_id: u64, f: impl Fn(&mut PostDetails)) {
+        let is_post = |p: &PostDetails| p.canister_id == canister_id && p.post_id == post_id;
+        self.video_queue
+            .update(|q| q.iter_mut().filter(|p| is_post(p)).for_each(&f));
+        self.priority_q.update_untracked(|q| {
+            let cached: Vec<_> = q
+                .iter()
+                .filter(|(p, _)| is_post(p))
+                .map(|(p, _)| p.clone())
+                .collect();
+            // Items are hashed, they must be reinserted to be changed
+            for post in cached {
+                let Some((mut post, priority)) = q.remove(&post) else {
+                    continue;
+                };
+                f(&mut post);
+                q.push(post, priority);
+            }
+        });
+    }
+
+    /// Drop the cached copies of a hidden post
+    pub fn remove_post(&self, canister_id: Principal, post_id: u64) {
+        let is_post = |p: &PostDetails| p.canister_id == canister_id && p.post_id == post_id;
+        batch(|| {
+            let Some(pos) = self
+                .video_queue
+                .with_untracked(|q| q.iter().position(is_post))
+            else {
+                return;
+            };
+            self.video_queue.update(|q| {
+                q.remove(pos);
+            });
+            // keep the current post the same
+            if pos < self.current_idx.get_untracked() {
+                self.current_idx.update(|c| *c -= 1);
+            }
+        });
+        self.priority_q
+            .update_untracked(|q| q.retain(|p, _| !is_post(p)));
+    }
 }
 
 #[component]
@@ -58,37 +148,45 @@ pub fn CommonPostViewWithUpdates(
     fetch_video_action: Action<(), ()>,
     threshold_trigger_fetch: usize,
 ) -> impl IntoView {
+    let ctx: PostViewCtx = expect_context();
     let PostViewCtx {
         fetch_cursor,
         video_queue,
         current_idx,
         queue_end,
         ..
-    } = expect_context();
+    } = ctx.clone();
 
     let recovering_state = create_rw_signal(false);
     if let Some(initial_post) = initial_post.clone() {
+        let feed_started = ctx.feed.with_untracked(Option::is_some);
         fetch_cursor.update_untracked(|f| {
             // we've already fetched the first posts
-            if f.start > 1 || queue_end.get_untracked() {
+            if feed_started || queue_end.get_untracked() {
                 recovering_state.set(true);
                 return;
             }
             f.start = 1;
             f.limit = 1;
         });
-        video_queue.update_untracked(|v| {
-            if v.len() > 1 {
-                // Safe to do a GC here
-                let rem = 0..(current_idx.get_untracked().saturating_sub(6));
-                current_idx.update(|c| *c -= rem.len());
-                v.drain(rem);
-                return;
-            }
-            *v = vec![initial_post];
-        })
+        if video_queue.with_untracked(|v| v.len() > 1) {
+            // Safe to do a GC here
+            ctx.gc_video_queue(GC_KEEP_BEHIND);
+        } else {
+            video_queue.update_untracked(|v| *v = vec![initial_post]);
+        }
     }
 
+    create_effect(move |_| {
+        if current_idx() < GC_KEEP_BEHIND + GC_TRIGGER_BEHIND {
+            return;
+        }
+        // evicting shifts the DOM, scroll back to the current post
+        if ctx.gc_video_queue(GC_KEEP_BEHIND) > 0 {
+            recovering_state.set(true);
+        }
+    });
+
     create_effect(move |_| {
         if !recovering_state.get_untracked() {
             fetch_video_action.dispatch(());
@@ -135,18 +233,20 @@ pub fn CommonPostViewWithUpdates(
 
 #[component]
 pub fn PostViewWithUpdates(initial_post: Option<PostDetails>) -> impl IntoView {
+    let ctx: PostViewCtx = expect_context();
     let PostViewCtx {
         fetch_cursor,
         video_queue,
         queue_end,
         ..
-    } = expect_context();
+    } = ctx.clone();
 
     let (nsfw_enabled, _, _) = use_local_storage::<bool, FromToStringCodec>(NSFW_TOGGLE_STORE);
     let auth_canisters: RwSignal<Option<Canisters<true>>> = expect_context();
 
-    let fetch_video_action = create_action(move |_| async move {
-        loop {
+    let fetch_video_action = create_action(move |_| {
+        let ctx = ctx.clone();
+        async move {
             let Some(cursor) = fetch_cursor.try_get_untracked() else {
                 return;
             };
@@ -156,36 +256,36 @@ pub fn PostViewWithUpdates(initial_post: Option<PostDetails>) -> impl IntoView {
             let Some(nsfw_enabled) = nsfw_enabled.try_get_untracked() else {
                 return;
             };
-            let unauth_canisters = unauth_canisters();
-
-            let chunks = if let Some(canisters) = auth_canisters.as_ref() {
-                let fetch_stream = VideoFetchStream::new(canisters, cursor);
-                fetch_stream.fetch_post_uids_chunked(3, nsfw_enabled).await
-            } else {
-                let fetch_stream = VideoFetchStream::new(&unauth_canisters, cursor);
-                fetch_stream.fetch_post_uids_chunked(3, nsfw_enabled).await
-            };
-
-            let res = try_or_redirect!(chunks);
-            let mut chunks = res.posts_stream;
-            let mut cnt = 0;
-            while let Some(chunk) = chunks.next().await {
-                cnt += chunk.len();
-                video_queue.try_update(|q| {
-                    for uid in chunk {
-                        let uid = try_or_redirect!(uid);
-                        q.push(uid);
-                    }
+            let mut feed = ctx
+                .take_feed()
+                .unwrap_or_else(|| match auth_canisters.as_ref() {
+                    Some(canisters) => VideoFetchStream::post_cache(canisters, cursor),
+                    None => VideoFetchStream::post_cache(&unauth_canisters(), cursor),
                 });
+
+            loop {
+                let chunks = feed.fetch_next(3, nsfw_enabled, vec![]).await;
+
+                let res = try_or_redirect!(chunks);
+                let mut chunks = res.posts_stream;
+                let mut cnt = 0;
+                while let Some(chunk) = chunks.next().await {
+                    cnt += chunk.len();
+                    video_queue.try_update(|q| {
+                        for uid in chunk {
+                            let uid = try_or_redirect!(uid);
+                            q.push(uid);
+                        }
+                    });
+                }
+                if res.end || cnt >= 8 {
+                    queue_end.try_set(res.end);
+                    break;
+                }
             }
-            if res.end || cnt >= 8 {
-                queue_end.try_set(res.end);
-                break;
-            }
-            fetch_cursor.try_update(|c| c.advance());
-        }
 
-        fetch_cursor.try_update(|c| c.advance());
+            ctx.put_feed(feed);
+        }
     });
 
     view! { <CommonPostViewWithUpdates initial_post fetch_video_action threshold_trigger_fetch=10 /> }
@@ -193,6 +293,7 @@ pub fn PostViewWithUpdates(initial_post: Option<PostDetails>) -> impl IntoView {
 
 #[component]
 pub fn PostViewWithUpdatesMLFeed(initial_post: Option<PostDetails>) -> impl IntoView {
+    let ctx: PostViewCtx = expect_context();
     let PostViewCtx {
         fetch_cursor,
         video_queue,
@@ -200,7 +301,7 @@ pub fn PostViewWithUpdatesMLFeed(initial_post: Option<PostDetails>) -> impl Into
         priority_q,
         batch_cnt,
         ..
-    } = expect_context();
+    } = ctx.clone();
 
     let (nsfw_enabled, _, _) = use_local_storage::<bool, FromToStringCodec>(NSFW_TOGGLE_STORE);
 
@@ -208,11 +309,21 @@ pub fn PostViewWithUpdatesMLFeed(initial_post: Option<PostDetails>) -> impl Into
 
     let fetch_video_action = create_action(move |_| {
         let auth_cans = auth_cans.clone();
+        let ctx = ctx.clone();
         async move {
+            let Some(cursor) = fetch_cursor.try_get_untracked() else {
+                return;
+            };
+            let mut feed = match ctx.take_feed() {
+                Some(feed) => feed,
+                None => {
+                    let canisters = auth_cans.wait_untracked().await;
+                    let cans_true = canisters.unwrap().canisters().unwrap();
+                    VideoFetchStream::hybrid(&cans_true, cursor).await
+                }
+            };
+
             while priority_q.with_untracked(|q| q.len()) < 15 {
-                let Some(cursor) = fetch_cursor.try_get_untracked() else {
-                    return;
-                };
                 let Some(nsfw_enabled) = nsfw_enabled.try_get_untracked() else {
                     return;
                 };
@@ -220,12 +331,8 @@ pub fn PostViewWithUpdatesMLFeed(initial_post: Option<PostDetails>) -> impl Into
                     return;
                 };
 
-                let canisters = auth_cans.wait_untracked().await;
-                let cans_true = canisters.unwrap().canisters().unwrap();
-
-                let mut fetch_stream = VideoFetchStream::new(&cans_true, cursor);
-                let chunks = fetch_stream
-                    .fetch_post_uids_hybrid(3, nsfw_enabled, video_queue.get_untracked())
+                let chunks = feed
+                    .fetch_next(3, nsfw_enabled, video_queue.get_untracked())
                     .await;
 
                 let res = try_or_redirect!(chunks);
@@ -248,19 +355,15 @@ pub fn PostViewWithUpdatesMLFeed(initial_post: Option<PostDetails>) -> impl Into
                 }
 
                 leptos::logging::log!("feed type: {:?}", res.res_type);
-                if res.res_type != FeedResultType::MLFeed {
-                    fetch_cursor.try_update(|c| {
-                        c.set_limit(15);
-                        c.advance_and_set_limit(15)
-                    });
-                }
 
                 if res.end {
                     queue_end.try_set(res.end);
                 }
 
                 batch_cnt.update(|x| *x += 1);
+                ctx.gc_priority_q();
             }
+            ctx.put_feed(feed);
 
             update!(move |video_queue, priority_q| {
                 let mut cnt = 0;
@@ -305,12 +408,16 @@ pub fn PostView() -> impl IntoView {
             let Some(params) = params else {
                 return Err(());
             };
-            let cached_post = video_queue
-                .with_untracked(|q| q.get(current_idx.get_untracked()).cloned())
-                .filter(|post| {
-                    post.canister_id == params.canister_id && post.post_id == params.post_id
-                });
-            if let Some(post) = cached_post {
+            // the post may still be in the kept window (e.g on back navigation)
+            let cached_post = video_queue.with_untracked(|q| {
+                q.iter()
+                    .position(|post| {
+                        post.canister_id == params.canister_id && post.post_id == params.post_id
+                    })
+                    .map(|idx| (idx, q[idx].clone()))
+            });
+            if let Some((idx, post)) = cached_post {
+                current_idx.set(idx);
                 return Ok(Some(post));
             }
 
@@ -27,10 +27,10 @@ use super::{overlay::VideoDetailsOverlay, PostDetails};
 pub fn BgView(
     video_queue: RwSignal<Vec<PostDetails>>,
     current_idx: RwSignal<usize>,
-    idx: usize,
+    #[prop(into)] idx: Signal<usize>,
     children: Children,
 ) -> impl IntoView {
-    let post = create_memo(move |_| video_queue.with(|q| q.get(idx).cloned()));
+    let post = create_memo(move |_| video_queue.with(|q| q.get(idx()).cloned()));
     let uid = move || post().as_ref().map(|q| q.uid.clone()).unwrap_or_default();
 
     let (is_connected, _) = account_connected_reader();
@@ -83,7 +83,7 @@ pub fn BgView(
                 />
             </Show>
             <Show when=move || {
-                referrer_store.get().is_some() && idx == 0 && !is_connected.get()
+                referrer_store.get().is_some() && idx() == 0 && !is_connected.get()
                     && show_refer_login_popup.get()
             }>
                 <FeedPopUp
@@ -225,7 +225,7 @@ pub fn VideoView(
 pub fn VideoViewForQueue(
     video_queue: RwSignal<Vec<PostDetails>>,
     current_idx: RwSignal<usize>,
-    idx: usize,
+    #[prop(into)] idx: Signal<usize>,
     muted: RwSignal<bool>,
 ) -> impl IntoView {
     let container_ref = create_node_ref::<Video>();
@@ -235,7 +235,7 @@ pub fn VideoViewForQueue(
         let Some(vid) = container_ref() else {
             return;
         };
-        if idx != current_idx() {
+        if idx() != current_idx() {
             _ = vid.pause();
             return;
         }
@@ -243,7 +243,7 @@ pub fn VideoViewForQueue(
         _ = vid.play();
     });
 
-    let post = Signal::derive(move || video_queue.with(|q| q.get(idx).cloned()));
+    let post = Signal::derive(move || video_queue.with(|q| q.get(idx()).cloned()));
 
     view! { <VideoView post _ref=container_ref muted /> }
 }
@@ -0,0 +1,212 @@
+use leptos::*;
+use leptos_icons::*;
+
+use crate::{
+    component::bullet_loader::BulletLoader,
+    page::upload::drafts::{
+        delete_draft, list_drafts, save_draft, schedule_draft, Draft, DraftStatus,
+    },
+    state::canisters::{authenticated_canisters, CanistersAuthWire},
+    utils::{
+        bg_url,
+        hashtags::parse_hashtags,
+        time::{parse_local_datetime_to_secs, parse_ns_to_datetime},
+    },
+};
+
+#[derive(Clone)]
+enum DraftOp {
+    Save {
+        description: String,
+        hashtags: String,
+    },
+    Schedule(Option<u64>),
+    Delete,
+}
+
+fn status_text(status: &DraftStatus) -> String {
+    match status {
+        DraftStatus::Draft => "Draft".into(),
+        DraftStatus::Scheduled { at_secs } => format!(
+            "Scheduled for {}",
+            parse_ns_to_datetime(at_secs * 1_000_000_000).unwrap_or_default()
+        ),
+        DraftStatus::Publishing => "Publishing...".into(),
+        DraftStatus::Published { .. } => "Published".into(),
+        DraftStatus::Failed { reason } => format!("Failed to publish: {reason}"),
+    }
+}
+
+#[component]
+fn DraftItem(draft: Draft, cans_wire: CanistersAuthWire, refetch: RwSignal<u32>) -> impl IntoView {
+    let editable = draft.status.is_editable();
+    let enable_hot_or_not = draft.enable_hot_or_not;
+    let is_nsfw = draft.is_nsfw;
+    let scheduled = matches!(draft.status, DraftStatus::Scheduled { .. });
+    let description = create_rw_signal(draft.description.clone());
+    let hashtags = create_rw_signal(draft.hashtags.join(","));
+    let publish_at = create_rw_signal(None::<u64>);
+
+    let uid = draft.uid.clone();
+    let op_action = create_action(move |op: &DraftOp| {
+        let cans_wire = cans_wire.clone();
+        let uid = uid.clone();
+        let op = op.clone();
+        async move {
+            match op {
+                DraftOp::Save {
+                    description,
+                    hashtags,
+                } => {
+                    let hashtags = parse_hashtags(&hashtags)
+                        .map_err(|e| ServerFnError::Args(e.to_string()))?;
+                    save_draft(
+                        cans_wire,
+                        uid,
+                        hashtags,
+                        description,
+                        enable_hot_or_not,
+                        is_nsfw,
+                    )
+                    .await?;
+                }
+                DraftOp::Schedule(at) => {
+                    schedule_draft(cans_wire, uid, at).await?;
+                }
+                DraftOp::Delete => delete_draft(cans_wire, uid).await?,
+            }
+            refetch.update(|r| *r += 1);
+            Ok::<_, ServerFnError>(())
+        }
+    });
+    let pending = op_action.pending();
+    let op_err = move || {
+        op_action.value().with(|v| {
+            v.as_ref()
+                .and_then(|r| r.as_ref().err().map(|e| e.to_string()))
+        })
+    };
+    let on_save = move || {
+        op_action.dispatch(DraftOp::Save {
+            description: description.get_untracked(),
+            hashtags: hashtags.get_untracked(),
+        });
+    };
+
+    view! {
+        <div class="flex flex-row w-full gap-4 p-3 rounded-md border border-white/15">
+            <img class="w-20 aspect-[9/16] object-cover rounded-md" src=bg_url(&draft.uid) />
+            <div class="flex flex-col flex-1 gap-2 text-sm">
+                <span class="text-white/50">{status_text(&draft.status)}</span>
+                <textarea
+                    class="p-2 bg-neutral-800 rounded-md"
+                    rows=2
+                    disabled=!editable
+                    prop:value=description
+                    on:input=move |ev| description.set(event_target_value(&ev))
+                ></textarea>
+                <input
+                    class="p-2 bg-neutral-800 rounded-md"
+                    type="text"
+                    disabled=!editable
+                    prop:value=hashtags
+                    on:input=move |ev| hashtags.set(event_target_value(&ev))
+                />
+                <Show when=move || editable>
+                    <input
+                        class="p-2 bg-neutral-800 rounded-md"
+                        type="datetime-local"
+                        on:input=move |ev| {
+                            publish_at.set(parse_local_datetime_to_secs(&event_target_value(&ev)))
+                        }
+                    />
+                    <div class="flex flex-row flex-wrap gap-2">
+                        <button
+                            class="px-3 py-1 rounded-full bg-primary-600 disabled:bg-primary-400"
+                            disabled=pending
+                            on:click=move |_| on_save()
+                        >
+                            Save
+                        </button>
+                        <button
+                            class="px-3 py-1 rounded-full border border-white/15 disabled:text-white/30"
+                            disabled=move || pending() || publish_at().is_none()
+                            on:click=move |_| {
+                                op_action.dispatch(DraftOp::Schedule(publish_at.get_untracked()))
+                            }
+                        >
+                            Schedule
+                        </button>
+                        <Show when=move || scheduled>
+                            <button
+                                class="px-3 py-1 rounded-full border border-white/15 disabled:text-white/30"
+                                disabled=pending
+                                on:click=move |_| op_action.dispatch(DraftOp::Schedule(None))
+                            >
+                                Unschedule
+                            </button>
+                        </Show>
+                        <button
+                            class="px-3 py-1 rounded-full text-red-500 disabled:text-white/30"
+                            disabled=pending
+                            on:click=move |_| op_action.dispatch(DraftOp::Delete)
+                        >
+                            <Icon icon=icondata::AiDeleteOutlined />
+                        </button>
+                    </div>
+                </Show>
+                {move || op_err().map(|e| view! { <span class="text-red-500">{e}</span> })}
+            </div>
+        </div>
+    }
+}
+
+/// Drafts of the authenticated user, only rendered on their own profile
+#[component]
+pub fn ProfileDrafts() -> impl IntoView {
+    let refetch = create_rw_signal(0u32);
+    let drafts_res = authenticated_canisters().derive(
+        move || refetch(),
+        move |cans_wire, _| async move {
+            let cans_wire = cans_wire?;
+            let drafts = list_drafts(cans_wire.clone()).await?;
+            Ok::<_, ServerFnError>((cans_wire, drafts))
+        },
+    );
+
+    view! {
+        <div class="flex flex-col w-full items-center gap-4">
+            <Suspense fallback=|| {
+                view! {
+                    <div class="w-full flex justify-center items-center py-9">
+                        <BulletLoader />
+                    </div>
+                }
+            }>
+                {move || {
+                    drafts_res()
+                        .map(|res| match res {
+                            Ok((_, drafts)) if drafts.is_empty() => {
+                                view! { <span class="text-white/50 py-9">No drafts yet</span> }
+                                    .into_view()
+                            }
+                            Ok((cans_wire, drafts)) => {
+                                drafts
+                                    .into_iter()
+                                    .map(|draft| {
+                                        view! {
+                                            <DraftItem draft cans_wire=cans_wire.clone() refetch />
+                                        }
+                                    })
+                                    .collect_view()
+                            }
+                            Err(e) => {
+                                view! { <span class="text-red-500 py-9">{e.to_string()}</span> }
+                                    .into_view()
+                            }
+                        })
+                }}
+            </Suspense>
+        </div>
+    }
+}
@@ -1,7 +1,8 @@
+mod drafts;
 mod ic;
 pub mod overlay;
 mod posts;
-mod profile_iter;
+pub mod profile_iter;
 pub mod profile_post;
 mod speculation;
 mod tokens;
@@ -20,6 +21,7 @@ use crate::{
     utils::{posts::PostDetails, profile::ProfileDetails},
 };
 
+use drafts::ProfileDrafts;
 use posts::ProfilePosts;
 use speculation::ProfileSpeculations;
 use tokens::ProfileTokens;
@@ -63,11 +65,21 @@ fn ListSwitcher1(user_canister: Principal, user_principal: Principal) -> impl In
                 "posts" => 0,
                 "stakes" => 1,
                 "tokens" => 2,
+                "drafts" => 3,
                 _ => 0,
             }
         })
     });
 
+    let is_own_profile = authenticated_canisters().derive(
+        || (),
+        move |cans_wire, _| async move {
+            let user = cans_wire?.canisters()?.user_principal();
+            Ok::<_, ServerFnError>(user == user_principal)
+        },
+    );
+    let is_own_profile = move || is_own_profile().and_then(|r| r.ok()).unwrap_or_default();
+
     let tab_class = move |tab_id: usize| {
         if tab_id == current_tab() {
             "text-primary-500 border-b-4 border-primary-500 flex justify-center w-full py-2"
@@ -92,6 +104,16 @@ fn ListSwitcher1(user_canister: Principal, user_principal: Principal) -> impl In
             >
                 <Icon icon=icondata::AiDollarCircleOutlined />
             </A>
+            <Suspense>
+                <Show when=is_own_profile>
+                    <A
+                        class=move || tab_class(3)
+                        href=move || format!("/profile/{}/drafts", user_principal)
+                    >
+                        <Icon icon=icondata::AiFileTextOutlined />
+                    </A>
+                </Show>
+            </Suspense>
         </div>
 
         <div class="flex flex-col gap-y-12 justify-center pb-12 w-11/12 sm:w-7/12">
@@ -104,6 +126,9 @@ fn ListSwitcher1(user_canister: Principal, user_principal: Principal) -> impl In
             <Show when=move || current_tab() == 2>
                 <ProfileTokens user_canister user_principal />
             </Show>
+            <Show when=move || current_tab() == 3 && is_own_profile()>
+                <ProfileDrafts />
+            </Show>
         </div>
     }
 }
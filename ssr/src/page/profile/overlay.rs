@@ -1,7 +1,237 @@
+use std::{cell::Cell, rc::Rc};
+
 use leptos::*;
+use leptos_icons::*;
+use web_time::Duration;
+
+use super::ProfilePostsContext;
+use crate::{
+    component::modal::Modal,
+    page::post_view::PostViewCtx,
+    state::canisters::authenticated_canisters,
+    utils::{
+        hashtags::parse_hashtags,
+        post_edits::{edit_post, hide_post, PostEdit},
+        posts::PostDetails,
+        time::sleep,
+    },
+};
+
+/// Time the owner has to undo hiding a post before it is sent to the server
+const UNDO_HIDE_WINDOW: Duration = Duration::from_secs(5);
+
+#[derive(Clone)]
+struct PendingHide {
+    idx: usize,
+    post: PostDetails,
+    /// Set on undo, owned by the hide task so it survives the overlay unmounting
+    undone: Rc<Cell<bool>>,
+}
+
+#[component]
+fn EditPostModal(
+    show: RwSignal<bool>,
+    post: Memo<Option<PostDetails>>,
+    on_edited: Callback<(PostDetails, PostEdit)>,
+) -> impl IntoView {
+    let description = create_rw_signal(String::new());
+    let hashtags = create_rw_signal(String::new());
+    let is_nsfw = create_rw_signal(false);
+    let enable_hot_or_not = create_rw_signal(false);
+
+    // Reset the form to the current post each time the modal is opened
+    create_effect(move |_| {
+        if !show() {
+            return;
+        }
+        let Some(edit) = post.with_untracked(|p| p.as_ref().map(PostEdit::from_post)) else {
+            return;
+        };
+        description.set(edit.description);
+        hashtags.set(edit.hashtags.join(", "));
+        is_nsfw.set(edit.is_nsfw);
+        enable_hot_or_not.set(edit.enable_hot_or_not);
+    });
+
+    let cans_wire_res = authenticated_canisters();
+    let edit_action = create_action(move |post: &PostDetails| {
+        let cans_wire_res = cans_wire_res.clone();
+        let post = post.clone();
+        async move {
+            let hashtags = parse_hashtags(&hashtags.get_untracked())
+                .map_err(|e| ServerFnError::Args(e.to_string()))?;
+            let edit = PostEdit {
+                description: description.get_untracked(),
+                hashtags,
+                is_nsfw: is_nsfw.get_untracked(),
+                enable_hot_or_not: enable_hot_or_not.get_untracked(),
+            };
+            let cans_wire = cans_wire_res.wait_untracked().await?;
+            let edit = edit_post(cans_wire, post.post_id, edit).await?;
+            on_edited((post, edit));
+            show.set(false);
+            Ok::<_, ServerFnError>(())
+        }
+    });
+    let pending = edit_action.pending();
+    let edit_err = move || {
+        edit_action.value().with(|v| {
+            v.as_ref()
+                .and_then(|r| r.as_ref().err().map(|e| e.to_string()))
+        })
+    };
+    // Hot or not consent can be withdrawn but not granted after publishing
+    let can_enable_hot_or_not =
+        move || post.with(|p| p.as_ref().is_some_and(|p| p.is_hot_or_not()));
+
+    view! {
+        <Modal show>
+            <div class="flex flex-col gap-4 text-white text-sm">
+                <span class="text-lg text-center">Edit Post</span>
+                <textarea
+                    class="p-2 bg-neutral-800 rounded-md"
+                    rows=3
+                    prop:value=description
+                    on:input=move |ev| description.set(event_target_value(&ev))
+                ></textarea>
+                <input
+                    class="p-2 bg-neutral-800 rounded-md"
+                    type="text"
+                    placeholder="#hashtags"
+                    prop:value=hashtags
+                    on:input=move |ev| hashtags.set(event_target_value(&ev))
+                />
+                <label class="flex flex-row gap-2 items-center">
+                    <input
+                        type="checkbox"
+                        prop:checked=is_nsfw
+                        on:change=move |ev| is_nsfw.set(event_target_checked(&ev))
+                    />
+                    NSFW
+                </label>
+                <label class="flex flex-row gap-2 items-center">
+                    <input
+                        type="checkbox"
+                        disabled=move || !can_enable_hot_or_not()
+                        prop:checked=enable_hot_or_not
+                        on:change=move |ev| enable_hot_or_not.set(event_target_checked(&ev))
+                    />
+                    Hot or Not
+                </label>
+                {move || edit_err().map(|e| view! { <span class="text-red-500">{e}</span> })}
+                <button
+                    class="py-2 rounded-full bg-primary-600 disabled:bg-primary-400"
+                    disabled=pending
+                    on:click=move |_| {
+                        if let Some(post) = post.get_untracked() {
+                            edit_action.dispatch(post);
+                        }
+                    }
+                >
+                    Save
+                </button>
+            </div>
+        </Modal>
+    }
+}
 
 #[component]
 pub fn YourProfileOverlay() -> impl IntoView {
+    let ProfilePostsContext {
+        video_queue,
+        current_index,
+        ..
+    } = expect_context();
+    let post_view_ctx: PostViewCtx = expect_context();
+
+    let current_post = create_memo(move |_| video_queue.with(|q| q.get(current_index()).cloned()));
+    let no_post = move || current_post.with(Option::is_none);
+    let show_edit = create_rw_signal(false);
+    let pending_hide = create_rw_signal(None::<PendingHide>);
+    let hide_err = create_rw_signal(None::<String>);
+
+    let ctx = post_view_ctx.clone();
+    let on_edited = Callback::new(move |(post, edit): (PostDetails, PostEdit)| {
+        video_queue.update(|q| {
+            q.iter_mut()
+                .filter(|p| p.canister_id == post.canister_id && p.post_id == post.post_id)
+                .for_each(|p| edit.apply(p));
+        });
+        ctx.update_post(post.canister_id, post.post_id, |p| edit.apply(p));
+    });
+
+    let restore = move |pending: PendingHide| {
+        let Some(len) = video_queue.try_with_untracked(Vec::len) else {
+            return;
+        };
+        let idx = pending.idx.min(len);
+        video_queue.update(|q| q.insert(idx, pending.post));
+        current_index.set(idx);
+    };
+
+    let cans_wire_res = authenticated_canisters();
+    // Spawned rather than an action, the overlay may unmount during the undo window
+    // and the hiding the user was shown must still be sent
+    let send_hide = move |pending: PendingHide| {
+        let cans_wire_res = cans_wire_res.clone();
+        let post_view_ctx = post_view_ctx.clone();
+        spawn_local(async move {
+            let cans_wire = cans_wire_res.wait_untracked().await;
+            sleep(UNDO_HIDE_WINDOW).await;
+            if pending.undone.get() {
+                return;
+            }
+            _ = pending_hide.try_set(None);
+
+            let post = &pending.post;
+            let res = match cans_wire {
+                Ok(cans_wire) => hide_post(cans_wire, post.post_id).await,
+                Err(e) => Err(e),
+            };
+            match res {
+                Ok(()) => post_view_ctx.remove_post(post.canister_id, post.post_id),
+                Err(e) => {
+                    log::warn!("failed to hide post {}: {e}", post.post_id);
+                    _ = hide_err.try_set(Some(format!("Failed to hide post: {e}")));
+                    restore(pending);
+                }
+            }
+        });
+    };
+
+    let on_hide = move || {
+        if pending_hide.with_untracked(Option::is_some) {
+            return;
+        }
+        let idx = current_index.get_untracked();
+        let Some(post) = video_queue
+            .try_update(|q| (idx < q.len()).then(|| q.remove(idx)))
+            .flatten()
+        else {
+            return;
+        };
+        let len = video_queue.with_untracked(Vec::len);
+        if idx >= len {
+            current_index.set(len.saturating_sub(1));
+        }
+        let pending = PendingHide {
+            idx,
+            post,
+            undone: Rc::default(),
+        };
+        hide_err.set(None);
+        pending_hide.set(Some(pending.clone()));
+        send_hide(pending);
+    };
+
+    let on_undo = move || {
+        if let Some(pending) = pending_hide.get_untracked() {
+            pending.undone.set(true);
+            pending_hide.set(None);
+            restore(pending);
+        }
+    };
+
     view! {
         <div class="flex w-full items-center justify-center pt-4 absolute top-0 left-0 bg-transparent z-[4]">
             <div class="rounded-full p-2 text-white bg-black/20">
@@ -9,6 +239,33 @@ pub fn YourProfileOverlay() -> impl IntoView {
                     <span class="font-sans font-semibold">Your Profile</span>
                 </div>
             </div>
+            <div class="absolute right-4 top-4 flex flex-row gap-4 text-white text-2xl">
+                <button disabled=no_post on:click=move |_| show_edit.set(true)>
+                    <Icon class="drop-shadow-lg" icon=icondata::AiEditOutlined />
+                </button>
+                <button disabled=no_post on:click=move |_| on_hide()>
+                    <Icon class="drop-shadow-lg" icon=icondata::AiEyeInvisibleOutlined />
+                </button>
+            </div>
         </div>
+        <Show when=move || pending_hide.with(Option::is_some)>
+            <div class="flex flex-row gap-4 items-center absolute bottom-20 left-1/2 -translate-x-1/2 z-[4] px-4 py-2 rounded-full bg-neutral-900 text-white text-sm">
+                <span>Post hidden</span>
+                <button class="font-semibold text-primary-600" on:click=move |_| on_undo()>
+                    Undo
+                </button>
+            </div>
+        </Show>
+        {move || {
+            hide_err()
+                .map(|e| {
+                    view! {
+                        <div class="absolute bottom-20 left-1/2 -translate-x-1/2 z-[4] px-4 py-2 rounded-full bg-neutral-900 text-red-500 text-sm">
+                            {e}
+                        </div>
+                    }
+                })
+        }}
+        <EditPostModal show=show_edit post=current_post on_edited />
     }
 }
@@ -4,7 +4,10 @@ use yral_canisters_client::individual_user_template::{GetPostsOfUserProfileError
 
 use crate::{
     state::canisters::Canisters,
-    utils::posts::{get_post_uid, PostDetails, PostViewError},
+    utils::{
+        post_edits::apply_post_overrides,
+        posts::{get_post_uid_raw, PostDetails, PostViewError},
+    },
 };
 
 #[derive(Clone, Copy, PartialEq)]
@@ -41,18 +44,22 @@ impl ProfVideoStream<10> for ProfileVideoBetsStream {
         canisters: &Canisters<AUTH>,
         user_canister: Principal,
     ) -> Result<PostsRes, PostViewError> {
-        let user = canisters.individual_user(user_canister).await;
-        let bets = user
-            .get_hot_or_not_bets_placed_by_this_profile_with_pagination(cursor.start)
+        let bets = canisters
+            .with_retry("individual_user.get_bets", || async move {
+                let user = canisters.individual_user(user_canister).await;
+                user.get_hot_or_not_bets_placed_by_this_profile_with_pagination(cursor.start)
+                    .await
+            })
             .await?;
         let end = bets.len() < 10;
         let posts = bets
             .into_iter()
-            .map(|bet| get_post_uid(canisters, bet.canister_id, bet.post_id))
+            .map(|bet| get_post_uid_raw(canisters, bet.canister_id, bet.post_id))
             .collect::<FuturesOrdered<_>>()
             .filter_map(|res| async { res.transpose() })
             .try_collect::<Vec<_>>()
             .await?;
+        let posts = apply_post_overrides(posts).await;
         Ok(PostsRes { posts, end })
     }
 }
@@ -65,9 +72,15 @@ impl<const LIMIT: u64> ProfVideoStream<LIMIT> for ProfileVideoStream<LIMIT> {
         canisters: &Canisters<AUTH>,
         user_canister: Principal,
     ) -> Result<PostsRes, PostViewError> {
-        let user = canisters.individual_user(user_canister).await;
-        let posts = user
-            .get_posts_of_this_user_profile_with_pagination_cursor(cursor.start, cursor.limit)
+        let posts = canisters
+            .with_retry("individual_user.get_posts", || async move {
+                let user = canisters.individual_user(user_canister).await;
+                user.get_posts_of_this_user_profile_with_pagination_cursor(
+                    cursor.start,
+                    cursor.limit,
+                )
+                .await
+            })
             .await?;
         match posts {
             Result11::Ok(v) => {
@@ -76,6 +89,7 @@ impl<const LIMIT: u64> ProfVideoStream<LIMIT> for ProfileVideoStream<LIMIT> {
                     .into_iter()
                     .map(|details| PostDetails::from_canister_post(AUTH, user_canister, details))
                     .collect::<Vec<_>>();
+                let posts = apply_post_overrides(posts).await;
                 Ok(PostsRes { posts, end })
             }
             Result11::Err(GetPostsOfUserProfileError::ReachedEndOfItemsList) => Ok(PostsRes {
@@ -156,13 +156,17 @@ fn ProfilePostBase<IV: IntoView, C: Fn(PostDetails) -> IV + Clone + 'static>(
                 return Some(post);
             };
 
-            match get_post_uid(&canisters, canister_id, post_id).await {
+            let post = match get_post_uid(&canisters, canister_id, post_id).await {
                 Ok(res) => res,
                 Err(e) => {
                     failure_redirect(e);
-                    None
+                    return None;
                 }
+            };
+            if post.is_none() {
+                failure_redirect("Post was hidden or is unavailable");
             }
+            post
         }
     });
     let children_s = store_value(children);
@@ -33,7 +33,7 @@ async fn token_metadata(
     let index = deployed_cans.index;
     get_token_metadata(
         cans,
-        Some(user_principal),
+        Some(user_principal.into()),
         deployed_cans.root,
         governance,
         ledger,
@@ -78,19 +78,18 @@ async fn get_top_post_id_mlcache() -> Result<Option<(Principal, u64)>, ServerFnE
 // TODO: Use this when we shift to the new ml feed for first post
 // #[server]
 // async fn get_top_post_id_mlfeed() -> Result<Option<(Principal, u64)>, ServerFnError> {
-//     use crate::utils::ml_feed::ml_feed_grpc::get_start_feed;
+//     use crate::utils::ml_feed::ml_feed_grpc::MLFeed;
 
 //     let canisters = unauth_canisters();
+//     let ml_feed: MLFeed = expect_context();
 //     let user_canister_principal = canisters.user_canister();
-//     let top_posts_fut = get_start_feed(&user_canister_principal, 1, vec![]);
+//     let top_posts_fut = ml_feed.get_start_feed(&user_canister_principal, 1, vec![]);
 
 //     let top_items = match top_posts_fut.await {
 //         Ok(top_posts) => top_posts,
 //         Err(e) => {
-//             log::error!("failed to fetch top post ml feed: {:?}", e);
-//             return Err(ServerFnError::ServerError(
-//                 "failed to fetch top post ml feed".to_string(),
-//             ));
+//             log::warn!("failed to fetch top post ml feed, falling back to post cache: {e}");
+//             return get_top_post_id().await;
 //         }
 //     };
 //     let Some(top_item) = top_items.first() else {
@@ -4,7 +4,12 @@ use crate::component::title::Title;
 use crate::component::{social::*, toggle::Toggle};
 use crate::consts::NOTIFICATIONS_ENABLED_STORE;
 use crate::state::auth::account_connected_reader;
+use crate::state::canisters::authenticated_canisters;
+use crate::utils::event_streaming::consent::{
+    request_analytics_deletion, use_set_consent, ConsentCtx, TrackingConsent,
+};
 use crate::utils::notifications::get_token_for_principal;
+use crate::utils::notifications::inbox::{get_notification_prefs, set_notification_prefs};
 use crate::utils::profile::ProfileDetails;
 use codee::string::FromToStringCodec;
 use leptos::html::Input;
@@ -114,8 +119,21 @@ fn EnableNotifications(user_details: ProfileDetails) -> impl IntoView {
         use_local_storage::<bool, FromToStringCodec>(NOTIFICATIONS_ENABLED_STORE);
     let toggle_ref = create_node_ref::<Input>();
 
-    let on_token_click = create_action(move |()| async move {
-        get_token_for_principal(user_details.principal.to_string()).await;
+    let cans_wire_res = authenticated_canisters();
+    let on_token_click = create_action(move |()| {
+        let cans_wire_res = cans_wire_res.clone();
+        async move {
+            get_token_for_principal(user_details.principal.to_string()).await;
+            let res = async {
+                let cans_wire = cans_wire_res.wait_untracked().await?;
+                let mut prefs = get_notification_prefs(cans_wire.clone()).await?;
+                prefs.push_enabled = true;
+                set_notification_prefs(cans_wire, prefs).await
+            };
+            if let Err(e) = res.await {
+                log::warn!("failed to enable push notifications: {e}");
+            }
+        }
     });
 
     _ = use_event_listener(toggle_ref, ev::change, move |_| {
@@ -136,6 +154,78 @@ fn EnableNotifications(user_details: ProfileDetails) -> impl IntoView {
     }
 }
 
+#[component]
+fn ShareAnalytics() -> impl IntoView {
+    let ConsentCtx { consent, .. } = expect_context();
+    let set_consent = use_set_consent();
+    let toggle_ref = create_node_ref::<Input>();
+    let granted = Signal::derive(move || consent().allows_pii());
+
+    _ = use_event_listener(toggle_ref, ev::change, move |ev| {
+        let consent = if event_target_checked(&ev) {
+            TrackingConsent::Granted
+        } else {
+            TrackingConsent::Denied
+        };
+        set_consent.dispatch(consent);
+    });
+
+    view! {
+        <div class="grid grid-cols-2 items-center w-full">
+            <div class="flex flex-row gap-4 items-center">
+                <Icon class="text-2xl" icon=icondata::AiBarChartOutlined />
+                <span>Share Analytics</span>
+            </div>
+            <div class="justify-self-end">
+                <Toggle checked=granted node_ref=toggle_ref />
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn DeleteAnalyticsData() -> impl IntoView {
+    let cans_wire_res = authenticated_canisters();
+    let set_consent = use_set_consent();
+    let delete_action = create_action(move |()| {
+        let cans_wire_res = cans_wire_res.clone();
+        async move {
+            let cans_wire = cans_wire_res.wait_untracked().await?;
+            request_analytics_deletion(cans_wire).await?;
+            set_consent.dispatch(TrackingConsent::Denied);
+            Ok::<_, ServerFnError>(())
+        }
+    });
+    let pending = delete_action.pending();
+    let status = move || {
+        delete_action.value().with(|v| match v {
+            Some(Ok(())) => Some(
+                "Deletion requested, analytics sharing is off and your data will be deleted by our team"
+                    .to_string(),
+            ),
+            Some(Err(e)) => Some(format!("Failed to request deletion: {e}")),
+            None => None,
+        })
+    };
+
+    view! {
+        <div class="grid grid-cols-2 items-center w-full">
+            <div class="flex flex-row gap-4 items-center">
+                <Icon class="text-2xl" icon=icondata::AiDeleteOutlined />
+                <span>Delete Analytics Data</span>
+            </div>
+            <button
+                class="justify-self-end text-sm text-primary-600 disabled:text-white/50"
+                disabled=pending
+                on:click=move |_| delete_action.dispatch(())
+            >
+                Request
+            </button>
+        </div>
+        {move || status().map(|s| view! { <span class="text-sm text-white/50">{s}</span> })}
+    }
+}
+
 #[component]
 pub fn Settings() -> impl IntoView {
     view! {
@@ -153,6 +243,8 @@ pub fn Settings() -> impl IntoView {
                 <AuthCansProvider let:canisters>
                     <EnableNotifications user_details=canisters.profile_details() />
                 </AuthCansProvider>
+                <ShareAnalytics />
+                <DeleteAnalyticsData />
             </div>
             <MenuFooter />
         </div>
@@ -0,0 +1,555 @@
+//! ICRC-2 approvals, allowing spenders (DEXes, games) to transfer from the user's account
+//!
+//! ICRC-2 ledgers can't list the spenders of an account, so the spenders approved
+//! from this device are remembered in local storage and their allowances queried
+
+use std::collections::BTreeMap;
+
+use candid::{Nat, Principal};
+use codee::string::JsonSerdeCodec;
+use ic_agent::AgentError;
+use leptos::*;
+use leptos_router::*;
+use leptos_use::storage::use_local_storage;
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+use web_time::Duration;
+use yral_canisters_client::sns_ledger::{
+    AllowanceArgs, ApproveArgs, ApproveError, ApproveResult, TransferFromArgs, TransferFromError,
+    TransferFromResult,
+};
+
+use super::{
+    transfer::{TokenTransferError, TransferIntent},
+    TokenParams,
+};
+use crate::{
+    component::{
+        back_btn::BackButton, canisters_prov::WithAuthCans, spinner::FullScreenSpinner,
+        title::Title,
+    },
+    consts::TOKEN_SPENDERS_STORE,
+    state::canisters::Canisters,
+    utils::{
+        time::{current_epoch, parse_ns_to_datetime},
+        token::{
+            icrc::{subaccount_from_hex, to_ledger_account, IcrcAccount, Subaccount},
+            TokenBalance, TokenMetadata,
+        },
+    },
+};
+
+/// Spenders approved from this device, keyed by ledger canister id
+type KnownSpenders = BTreeMap<String, Vec<IcrcAccount>>;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SpenderAllowance {
+    pub spender: IcrcAccount,
+    pub allowance: TokenBalance,
+    /// Nanoseconds since epoch, `None` if the approval never expires
+    pub expires_at: Option<u64>,
+}
+
+/// Allowances of `spenders` over `account`, skipping revoked and expired approvals
+pub async fn outstanding_allowances<const A: bool>(
+    cans: &Canisters<A>,
+    meta: &TokenMetadata,
+    account: IcrcAccount,
+    spenders: &[IcrcAccount],
+) -> Result<Vec<SpenderAllowance>, AgentError> {
+    let ledger = cans.sns_ledger(meta.ledger).await;
+    let now = current_epoch().as_nanos() as u64;
+    let mut allowances = vec![];
+    for spender in spenders {
+        let res = ledger
+            .icrc_2_allowance(AllowanceArgs {
+                account: to_ledger_account(account),
+                spender: to_ledger_account(*spender),
+            })
+            .await?;
+        if res.allowance == 0u64 || res.expires_at.is_some_and(|exp| exp <= now) {
+            continue;
+        }
+        allowances.push(SpenderAllowance {
+            spender: *spender,
+            allowance: TokenBalance::new(res.allowance, meta.decimals),
+            expires_at: res.expires_at,
+        });
+    }
+    Ok(allowances)
+}
+
+fn map_approve_result(res: ApproveResult) -> Result<Nat, TokenTransferError> {
+    let err = match res {
+        ApproveResult::Ok(block) => return Ok(block),
+        ApproveResult::Err(e) => e,
+    };
+    Err(match err {
+        ApproveError::Duplicate { duplicate_of } => return Ok(duplicate_of),
+        ApproveError::InsufficientFunds { balance } => {
+            TokenTransferError::InsufficientFunds { balance }
+        }
+        ApproveError::BadFee { expected_fee } => TokenTransferError::BadFee { expected_fee },
+        ApproveError::AllowanceChanged { current_allowance } => {
+            TokenTransferError::AllowanceChanged { current_allowance }
+        }
+        ApproveError::Expired { ledger_time } => TokenTransferError::Expired { ledger_time },
+        ApproveError::TooOld => TokenTransferError::TooOld,
+        ApproveError::CreatedInFuture { ledger_time } => {
+            TokenTransferError::CreatedInFuture { ledger_time }
+        }
+        ApproveError::TemporarilyUnavailable => TokenTransferError::TemporarilyUnavailable,
+        ApproveError::GenericError {
+            message,
+            error_code,
+        } => TokenTransferError::GenericError {
+            message,
+            error_code,
+        },
+    })
+}
+
+fn map_transfer_from_result(res: TransferFromResult) -> Result<Nat, TokenTransferError> {
+    let err = match res {
+        TransferFromResult::Ok(block) => return Ok(block),
+        TransferFromResult::Err(e) => e,
+    };
+    Err(match err {
+        TransferFromError::Duplicate { duplicate_of } => return Ok(duplicate_of),
+        TransferFromError::InsufficientFunds { balance } => {
+            TokenTransferError::InsufficientFunds { balance }
+        }
+        TransferFromError::InsufficientAllowance { allowance } => {
+            TokenTransferError::InsufficientAllowance { allowance }
+        }
+        TransferFromError::BadFee { expected_fee } => TokenTransferError::BadFee { expected_fee },
+        TransferFromError::BadBurn { min_burn_amount } => {
+            TokenTransferError::BadBurn { min_burn_amount }
+        }
+        TransferFromError::TooOld => TokenTransferError::TooOld,
+        TransferFromError::CreatedInFuture { ledger_time } => {
+            TokenTransferError::CreatedInFuture { ledger_time }
+        }
+        TransferFromError::TemporarilyUnavailable => TokenTransferError::TemporarilyUnavailable,
+        TransferFromError::GenericError {
+            message,
+            error_code,
+        } => TokenTransferError::GenericError {
+            message,
+            error_code,
+        },
+    })
+}
+
+/// Sets the allowance of `spender` to `amount`, an `amount` of 0 revokes the approval
+pub async fn approve(
+    cans: &Canisters<true>,
+    ledger: Principal,
+    from_subaccount: Option<Subaccount>,
+    spender: IcrcAccount,
+    amount: Nat,
+    expires_at: Option<u64>,
+    intent: &TransferIntent,
+) -> Result<Result<Nat, TokenTransferError>, AgentError> {
+    let res = cans
+        .with_retry("sns_ledger.icrc_2_approve", || {
+            let amount = amount.clone();
+            async move {
+                let ledger = cans.sns_ledger(ledger).await;
+                ledger
+                    .icrc_2_approve(ApproveArgs {
+                        fee: None,
+                        memo: Some(ByteBuf::from(intent.memo.clone())),
+                        from_subaccount: from_subaccount.map(|s| ByteBuf::from(s.to_vec())),
+                        created_at_time: Some(intent.created_at_time),
+                        amount,
+                        expected_allowance: None,
+                        expires_at,
+                        spender: to_ledger_account(spender),
+                    })
+                    .await
+            }
+        })
+        .await?;
+    Ok(map_approve_result(res))
+}
+
+/// Transfers from an account that approved the user as a spender
+pub async fn transfer_from(
+    cans: &Canisters<true>,
+    ledger: Principal,
+    spender_subaccount: Option<Subaccount>,
+    from: IcrcAccount,
+    to: IcrcAccount,
+    amount: Nat,
+    intent: &TransferIntent,
+) -> Result<Result<Nat, TokenTransferError>, AgentError> {
+    let res = cans
+        .with_retry("sns_ledger.icrc_2_transfer_from", || {
+            let amount = amount.clone();
+            async move {
+                let ledger = cans.sns_ledger(ledger).await;
+                ledger
+                    .icrc_2_transfer_from(TransferFromArgs {
+                        to: to_ledger_account(to),
+                        fee: None,
+                        spender_subaccount: spender_subaccount.map(|s| ByteBuf::from(s.to_vec())),
+                        from: to_ledger_account(from),
+                        memo: Some(ByteBuf::from(intent.memo.clone())),
+                        created_at_time: Some(intent.created_at_time),
+                        amount,
+                    })
+                    .await
+            }
+        })
+        .await?;
+    Ok(map_transfer_from_result(res))
+}
+
+fn parse_account(raw: &str) -> Result<IcrcAccount, String> {
+    raw.trim()
+        .parse()
+        .map_err(|_| "Invalid principal or account".to_string())
+}
+
+fn parse_amount(raw: &str, decimals: u8) -> Result<TokenBalance, String> {
+    TokenBalance::parse(raw.trim(), decimals).map_err(|_| "Invalid amount".to_string())
+}
+
+/// Expiry in nanoseconds since epoch, from a number of days (empty for no expiry)
+fn parse_expiry(raw_days: &str) -> Result<Option<u64>, String> {
+    let raw_days = raw_days.trim();
+    if raw_days.is_empty() {
+        return Ok(None);
+    }
+    let invalid = || "Invalid expiry".to_string();
+    let days: u64 = raw_days.parse().map_err(|_| invalid())?;
+    let secs = days.checked_mul(24 * 60 * 60).ok_or_else(invalid)?;
+    let expires_at = current_epoch()
+        .checked_add(Duration::from_secs(secs))
+        .ok_or_else(invalid)?;
+    u64::try_from(expires_at.as_nanos())
+        .map(Some)
+        .map_err(|_| invalid())
+}
+
+#[component]
+fn ApprovalInput(
+    #[prop(into)] label: String,
+    #[prop(into, optional)] placeholder: String,
+    node_ref: NodeRef<html::Input>,
+) -> impl IntoView {
+    view! {
+        <div class="flex flex-col w-full gap-1">
+            <span class="text-white text-sm md:text-base">{label}</span>
+            <input
+                _ref=node_ref
+                placeholder=placeholder
+                class="w-full p-3 bg-white/5 rounded-lg border border-white/15 text-white placeholder-white/40 focus:outline-none text-base md:text-lg"
+            />
+        </div>
+    }
+}
+
+#[component]
+fn ActionStatus(status: RwSignal<Option<Result<String, String>>>) -> impl IntoView {
+    move || {
+        status.get().map(|res| match res {
+            Ok(msg) => view! { <span class="text-sm text-green-500">{msg}</span> },
+            Err(e) => view! { <span class="text-sm text-red-500">{e}</span> },
+        })
+    }
+}
+
+#[component]
+fn TokenApprovalsInner(
+    cans: Canisters<true>,
+    info: TokenMetadata,
+    from_subaccount: Option<Subaccount>,
+) -> impl IntoView {
+    let account = IcrcAccount {
+        owner: cans.user_principal(),
+        subaccount: from_subaccount,
+    };
+    let ledger = info.ledger;
+    let decimals = info.decimals;
+    let symbol = store_value(info.symbol.clone());
+    let info = store_value(info);
+    let cans = store_value(cans);
+
+    let (known_spenders, set_known_spenders, _) =
+        use_local_storage::<KnownSpenders, JsonSerdeCodec>(TOKEN_SPENDERS_STORE);
+    let spenders = Signal::derive(move || {
+        known_spenders.with(|s| s.get(&ledger.to_text()).cloned().unwrap_or_default())
+    });
+    let remember_spender = move |spender: IcrcAccount, keep: bool| {
+        let mut all = known_spenders.get_untracked();
+        let list = all.entry(ledger.to_text()).or_default();
+        list.retain(|s| *s != spender);
+        if keep {
+            list.push(spender);
+        }
+        set_known_spenders(all);
+    };
+
+    let refresh = create_rw_signal(());
+    let allowances = create_local_resource(
+        move || (spenders(), refresh()),
+        move |(spenders, _)| async move {
+            let cans = cans.get_value();
+            outstanding_allowances(&cans, &info.get_value(), account, &spenders)
+                .await
+                .map_err(|e| e.to_string())
+        },
+    );
+
+    let status = create_rw_signal(None::<Result<String, String>>);
+    let ledger_error = move |e: TokenTransferError| e.user_message(decimals, &symbol.get_value());
+
+    let approve_action = create_action(
+        move |(spender, amount, expires_at): &(IcrcAccount, TokenBalance, Option<u64>)| {
+            let (spender, amount, expires_at) = (*spender, amount.clone(), *expires_at);
+            async move {
+                let cans = cans.get_value();
+                let revoke = amount.e8s == 0u64;
+                let res = approve(
+                    &cans,
+                    ledger,
+                    from_subaccount,
+                    spender,
+                    amount.into(),
+                    expires_at,
+                    &TransferIntent::new(),
+                )
+                .await
+                .map_err(|e| e.to_string())?
+                .map_err(ledger_error)?;
+                log::debug!("approve res: {res:?}");
+                remember_spender(spender, !revoke);
+                refresh.set(());
+                Ok(if revoke {
+                    format!("Revoked approval of {spender}")
+                } else {
+                    format!("Approved {spender}")
+                })
+            }
+        },
+    );
+    create_effect(move |_| {
+        if let Some(res) = approve_action.value().get() {
+            status.set(Some(res));
+        }
+    });
+
+    let transfer_from_action = create_action(
+        move |(from, to, amount): &(IcrcAccount, IcrcAccount, TokenBalance)| {
+            let (from, to, amount) = (*from, *to, amount.clone());
+            async move {
+                let cans = cans.get_value();
+                let amount_str = amount.humanize_float();
+                transfer_from(
+                    &cans,
+                    ledger,
+                    from_subaccount,
+                    from,
+                    to,
+                    amount.into(),
+                    &TransferIntent::new(),
+                )
+                .await
+                .map_err(|e| e.to_string())?
+                .map_err(ledger_error)?;
+                Ok(format!(
+                    "Transferred {amount_str} {} from {from}",
+                    symbol.get_value()
+                ))
+            }
+        },
+    );
+    create_effect(move |_| {
+        if let Some(res) = transfer_from_action.value().get() {
+            status.set(Some(res));
+        }
+    });
+    let busy = move || approve_action.pending()() || transfer_from_action.pending()();
+
+    let spender_ref = create_node_ref::<html::Input>();
+    let amount_ref = create_node_ref::<html::Input>();
+    let expiry_ref = create_node_ref::<html::Input>();
+    let on_approve = move |_| {
+        let (Some(spender), Some(amount), Some(expiry)) =
+            (spender_ref(), amount_ref(), expiry_ref())
+        else {
+            return;
+        };
+        let parsed = parse_account(&spender.value()).and_then(|spender| {
+            Ok((
+                spender,
+                parse_amount(&amount.value(), decimals)?,
+                parse_expiry(&expiry.value())?,
+            ))
+        });
+        match parsed {
+            Ok(args) => approve_action.dispatch(args),
+            Err(e) => status.set(Some(Err(e))),
+        }
+    };
+
+    let from_ref = create_node_ref::<html::Input>();
+    let to_ref = create_node_ref::<html::Input>();
+    let spend_amount_ref = create_node_ref::<html::Input>();
+    let on_transfer_from = move |_| {
+        let (Some(from), Some(to), Some(amount)) = (from_ref(), to_ref(), spend_amount_ref())
+        else {
+            return;
+        };
+        let to_raw = to.value();
+        // Spending to the user's own account by default
+        let to = if to_raw.trim().is_empty() {
+            Ok(account)
+        } else {
+            parse_account(&to_raw)
+        };
+        let parsed = parse_account(&from.value())
+            .and_then(|from| Ok((from, to?, parse_amount(&amount.value(), decimals)?)));
+        match parsed {
+            Ok(args) => transfer_from_action.dispatch(args),
+            Err(e) => status.set(Some(Err(e))),
+        }
+    };
+
+    view! {
+        <div class="w-dvw min-h-dvh bg-neutral-800 flex flex-col gap-4">
+            <Title justify_center=false>
+                <div class="grid grid-cols-3 justify-start w-full">
+                    <BackButton fallback="/wallet" />
+                    <span class="font-bold justify-self-center">Approvals</span>
+                </div>
+            </Title>
+            <div class="flex flex-col w-full gap-6 p-4 pb-24">
+                <div class="flex flex-col w-full gap-2">
+                    <span class="text-white font-semibold">Allowances</span>
+                    <Suspense fallback=|| view! { <span class="text-sm text-white/50">Loading...</span> }>
+                        {move || {
+                            allowances()
+                                .map(|res| match res {
+                                    Err(e) => view! { <span class="text-sm text-red-500">{e}</span> }.into_view(),
+                                    Ok(allowances) if allowances.is_empty() => {
+                                        view! { <span class="text-sm text-white/50">No outstanding approvals</span> }.into_view()
+                                    }
+                                    Ok(allowances) => {
+                                        allowances
+                                            .into_iter()
+                                            .map(|a| {
+                                                let spender = a.spender;
+                                                let expiry = a
+                                                    .expires_at
+                                                    .and_then(|exp| parse_ns_to_datetime(exp).ok())
+                                                    .unwrap_or_else(|| "Never".to_string());
+                                                view! {
+                                                    <div class="flex flex-row items-center justify-between gap-2 p-3 rounded-lg bg-white/5 text-white">
+                                                        <div class="flex flex-col min-w-0">
+                                                            <span class="text-xs md:text-sm truncate">{spender.to_string()}</span>
+                                                            <span class="text-xs text-white/50">{format!("Expires: {expiry}")}</span>
+                                                        </div>
+                                                        <span class="text-sm shrink-0">
+                                                            {format!("{} {}", a.allowance.humanize_float_truncate_to_dp(2), symbol.get_value())}
+                                                        </span>
+                                                        <button
+                                                            disabled=busy
+                                                            on:click=move |_| {
+                                                                approve_action.dispatch((spender, TokenBalance::new(0u32.into(), decimals), None))
+                                                            }
+                                                            class="text-xs text-primary-600 shrink-0 disabled:opacity-50"
+                                                        >
+                                                            Revoke
+                                                        </button>
+                                                    </div>
+                                                }
+                                            })
+                                            .collect_view()
+                                    }
+                                })
+                        }}
+                    </Suspense>
+                </div>
+                <div class="flex flex-col w-full gap-3">
+                    <span class="text-white font-semibold">Approve a spender</span>
+                    <ApprovalInput label="Spender" placeholder="Principal or account" node_ref=spender_ref />
+                    <ApprovalInput label="Allowance" node_ref=amount_ref />
+                    <ApprovalInput label="Expires in (days)" placeholder="Never" node_ref=expiry_ref />
+                    <span class="text-xs md:text-sm text-white/60">
+                        {format!("Approving costs a fee of {} {}", info.with_value(|i| i.fees.humanize_float()), symbol.get_value())}
+                    </span>
+                    <button
+                        on:click=on_approve
+                        disabled=busy
+                        class="flex flex-row justify-center text-white md:text-lg w-full rounded-full p-3 bg-primary-600 disabled:opacity-50"
+                    >
+                        Approve
+                    </button>
+                </div>
+                <div class="flex flex-col w-full gap-3">
+                    <span class="text-white font-semibold">Spend an allowance</span>
+                    <ApprovalInput label="From" placeholder="Account that approved you" node_ref=from_ref />
+                    <ApprovalInput label="To" placeholder=account.to_string() node_ref=to_ref />
+                    <ApprovalInput label="Amount" node_ref=spend_amount_ref />
+                    <button
+                        on:click=on_transfer_from
+                        disabled=busy
+                        class="flex flex-row justify-center text-white md:text-lg w-full rounded-full p-3 bg-primary-600 disabled:opacity-50"
+                    >
+                        Transfer
+                    </button>
+                </div>
+                <ActionStatus status />
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn TokenApprovals() -> impl IntoView {
+    let params = use_params::<TokenParams>();
+    let query = use_query_map();
+    let from_subaccount = move || {
+        query.with(|q| {
+            q.get("from_subaccount")
+                .and_then(|s| subaccount_from_hex(s))
+        })
+    };
+
+    let token_metadata_fetch = move |cans: Canisters<true>| {
+        create_resource(
+            move || (params(), from_subaccount()),
+            move |(params, from_subaccount)| {
+                let cans = cans.clone();
+                let source = IcrcAccount {
+                    owner: cans.user_principal(),
+                    subaccount: from_subaccount,
+                };
+                async move {
+                    let Ok(params) = params else {
+                        return Ok::<_, ServerFnError>(None);
+                    };
+                    let meta = params.token_root.get_metadata(Some(source), cans).await;
+                    Ok(meta.map(|m| (m, from_subaccount)))
+                }
+            },
+        )
+    };
+
+    view! {
+        <WithAuthCans
+            fallback=FullScreenSpinner
+            with=token_metadata_fetch
+            children=|(cans, res)| {
+                match res {
+                    Err(e) => view! { <Redirect path=format!("/error?err={e}") /> },
+                    Ok(None) => view! { <Redirect path="/" /> },
+                    Ok(Some((info, from_subaccount))) => view! { <TokenApprovalsInner cans info from_subaccount/> },
+                }
+            }
+        />
+    }
+}
@@ -1,5 +1,7 @@
 #[cfg(feature = "ssr")]
 mod server_impl;
+mod simulation;
+pub use simulation::CreateTokenSimulation;
 
 use crate::{
     component::{back_btn::BackButton, title::Title, token_logo_sanitize::TokenLogoSanitize},
@@ -9,7 +11,7 @@ use crate::{
             TokenCreationCompleted, TokenCreationFailed, TokenCreationStarted,
         },
         profile::ProfileDetails,
-        token::DeployedCdaoCanisters,
+        token::{sns_config::save_token_config, DeployedCdaoCanisters},
         web::FileWithUrl,
     },
 };
@@ -18,7 +20,11 @@ use leptos::*;
 use std::env;
 
 use server_fn::codec::Cbor;
-use sns_validation::{humanize::parse_tokens, pbs::nns_pb::Tokens};
+use sns_validation::{
+    config::{ConfigDefect, SnsConfigurationFile},
+    humanize::parse_tokens,
+    pbs::nns_pb::Tokens,
+};
 use sns_validation::{
     humanize::{
         format_duration, format_percentage, format_tokens, parse_duration, parse_percentage,
@@ -159,6 +165,8 @@ macro_rules! input_component {
             #[prop(into)] placeholder: String,
             #[prop(optional)] initial_value: Option<String>,
             #[prop(optional, into)] input_type: Option<String>,
+            /// Configuration file paths (e.g. `Token.symbol`) whose validation errors are shown here
+            #[prop(optional)] fields: &'static [&'static str],
             updater: U,
             validator: V,
         ) -> impl IntoView {
@@ -168,11 +176,22 @@ macro_rules! input_component {
             if error.get_untracked() {
                 ctx.invalid_cnt.update(|c| *c += 1);
             }
+            let field_error = move || {
+                ctx.field_errors.with(|errs| {
+                    errs.iter()
+                        .find(|e| fields.contains(&e.path.as_str()))
+                        .map(|e| e.message.clone())
+                })
+            };
             let input_ref = create_node_ref::<html::$input_type>();
             let on_input = move || {
                 let Some(input) = input_ref() else {
                     return;
                 };
+                if !fields.is_empty() {
+                    ctx.field_errors
+                        .update(|errs| errs.retain(|e| !fields.contains(&e.path.as_str())));
+                }
                 let value = input.value();
                 match validator(value) {
                     Some(v) => {
@@ -208,7 +227,7 @@ macro_rules! input_component {
                 }
             });
 
-            let input_class =move ||  match show_error() && error() {
+            let input_class =move ||  match (show_error() && error()) || field_error().is_some() {
                 false => format!("w-full p-3  md:p-4 md:py-5 text-white outline-none bg-white/10 border-2 border-solid border-white/20 text-xs  rounded-xl placeholder-neutral-600"),
                 _ =>  format!("w-full p-3  md:p-4 md:py-5 text-white outline-none bg-white/10 border-2 border-solid border-red-500 text-xs  rounded-xl placeholder-neutral-600")
             };
@@ -228,6 +247,9 @@ macro_rules! input_component {
                                 "Invalid "
                         </Show>
                     </span>
+                    <Show when=move || field_error().is_some()>
+                        <span class="text-red-500 text-xs">{field_error}</span>
+                    </Show>
                 </div>
             }
         }
@@ -254,6 +276,8 @@ pub struct CreateTokenCtx {
     form_state: RwSignal<SnsFormState>,
     invalid_cnt: RwSignal<u32>,
     on_form_reset: Trigger,
+    /// Defects found by the SNS validators on the last create attempt
+    field_errors: RwSignal<Vec<ConfigDefect>>,
 }
 
 impl CreateTokenCtx {
@@ -262,6 +286,7 @@ impl CreateTokenCtx {
 
         ctx.form_state.set(SnsFormState::default());
         ctx.invalid_cnt.set(0);
+        ctx.field_errors.set(vec![]);
     }
 }
 
@@ -317,6 +342,17 @@ pub fn CreateToken() -> impl IntoView {
             let sns_form = ctx.form_state.get_untracked();
             let sns_config = sns_form.try_into_config(&cans)?;
 
+            let defects = sns_config.validate();
+            if !defects.is_empty() {
+                let msg = defects
+                    .iter()
+                    .map(|d| d.message.as_str())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                ctx.field_errors.set(defects);
+                return Err(msg);
+            }
+
             let create_sns = sns_config.try_convert_to_executed_sns_init()?;
             let server_available = is_server_available().await.map_err(|e| e.to_string())?;
             log::debug!(
@@ -330,12 +366,31 @@ pub fn CreateToken() -> impl IntoView {
 
             TokenCreationStarted.send_event(create_sns.clone(), auth_cans);
 
-            let _deployed_cans_response =
-                deploy_cdao_canisters(cans_wire, create_sns.clone(), profile_details, canister_id)
-                    .await
-                    .map_err(|e| e.to_string())?;
-
-            Ok(())
+            let deployed_cans = deploy_cdao_canisters(
+                cans_wire.clone(),
+                create_sns.clone(),
+                profile_details,
+                canister_id,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+
+            // Recovered from the deployed payload, so creators can reuse it for a new launch
+            let deployed_config = SnsConfigurationFile::try_from_sns_init_payload(
+                &create_sns,
+                sns_config.nns_proposal.clone(),
+            )
+            .and_then(|config| config.to_yaml())
+            .map_err(|e| log::warn!("failed to export the token config: {e}"))
+            .ok();
+            if let Some(yaml) = &deployed_config {
+                // Only needed to download it again from the token page
+                if let Err(e) = save_token_config(cans_wire, deployed_cans.root, yaml.clone()).await
+                {
+                    log::warn!("failed to keep the token config: {e}");
+                }
+            }
+            Ok(deployed_config)
         }
     });
     let creating = create_action.pending();
@@ -368,6 +423,7 @@ pub fn CreateToken() -> impl IntoView {
                     <TokenImage />
                     <InputBox
                         heading="Token name"
+                        fields=&["name", "Token.name"]
                         placeholder="Add a name to your crypto currency"
                         updater=set_token_name
                         validator=non_empty_string_validator
@@ -379,6 +435,7 @@ pub fn CreateToken() -> impl IntoView {
                 </div>
                 <InputArea
                     heading="Description"
+                    fields=&["description"]
                     placeholder="Fun & friendly internet currency inspired by the legendary Shiba Inu dog 'Kabosu'"
                     updater=set_token_desc
                     validator=non_empty_string_validator
@@ -390,6 +447,7 @@ pub fn CreateToken() -> impl IntoView {
 
                 <InputBox
                     heading="Token Symbol"
+                    fields=&["Token.symbol"]
                     placeholder="Eg. DODGE"
                     updater=set_token_symbol
                     validator=non_empty_string_validator
@@ -401,6 +459,7 @@ pub fn CreateToken() -> impl IntoView {
 
                 <InputBox
                     heading="Distribution"
+                    fields=&["Distribution", "Distribution.InitialBalances.swap"]
                     placeholder="Distribution Tokens"
                     input_type="number"
                     updater=set_total_distribution
@@ -428,6 +487,9 @@ pub fn CreateToken() -> impl IntoView {
                 <div class="w-full flex justify-center underline text-sm text-white my-4 ">
                     <a href="/token/create/settings">View advanced settings</a>
                 </div>
+                <div class="w-full flex justify-center underline text-sm text-white">
+                    <a href="/token/create/simulate">Preview swap & distribution</a>
+                </div>
             </div>
             <TokenCreationPopup
                 creation_action=create_action
@@ -534,6 +596,7 @@ pub fn CreateTokenSettings() -> impl IntoView {
             <form _ref=form_ref>
                 <InputBox
                     heading="Transaction Fee (e8s)"
+                    fields=&["Token.transaction_fee"]
                     input_type="number"
                     placeholder="100"
                     updater=set_transaction_fee
@@ -542,6 +605,7 @@ pub fn CreateTokenSettings() -> impl IntoView {
                 />
                 <InputBox
                     heading="Rejection Fee (Token)"
+                    fields=&["Proposals.rejection_fee"]
                     placeholder="1 Token"
                     updater=set_rejection_fee
                     validator=validate_tokens
@@ -549,6 +613,7 @@ pub fn CreateTokenSettings() -> impl IntoView {
                 />
                 <InputBox
                     heading="Initial Voting Period (days)"
+                    fields=&["Proposals.initial_voting_period"]
                     placeholder="4 days"
                     updater=set_initial_voting_period
                     validator=validate_duration
@@ -556,6 +621,7 @@ pub fn CreateTokenSettings() -> impl IntoView {
                 />
                 <InputBox
                     heading="Maximum wait for quiet deadline extention (days)"
+                    fields=&["Proposals.maximum_wait_for_quiet_deadline_extension"]
                     placeholder="1 day"
                     updater=set_max_wait_deadline_extension
                     validator=validate_duration
@@ -564,6 +630,7 @@ pub fn CreateTokenSettings() -> impl IntoView {
 
                 <InputBox
                     heading="Minimum creation stake (token)"
+                    fields=&["Neurons.minimum_creation_stake"]
                     placeholder="1 token"
                     updater=set_min_creation_stake
                     validator=validate_tokens
@@ -572,6 +639,7 @@ pub fn CreateTokenSettings() -> impl IntoView {
 
                 <InputBox
                     heading="Minimum dissolve delay (months)"
+                    fields=&["Voting.minimum_dissolve_delay"]
                     placeholder="1 month"
                     updater=set_min_dissolve_delay
                     validator=validate_duration
@@ -580,6 +648,7 @@ pub fn CreateTokenSettings() -> impl IntoView {
 
                 <InputBox
                     heading="Age (duration in years)"
+                    fields=&["Voting.MaximumVotingPowerBonuses.Age.duration"]
                     placeholder="4 years"
                     updater=set_age
                     validator=validate_duration
@@ -588,6 +657,7 @@ pub fn CreateTokenSettings() -> impl IntoView {
 
                 <InputBox
                     heading="Age (bonus %)"
+                    fields=&["Voting.MaximumVotingPowerBonuses.Age.bonus"]
                     placeholder="25%"
                     updater=set_age_bonus
                     validator=validate_percentage
@@ -596,6 +666,7 @@ pub fn CreateTokenSettings() -> impl IntoView {
 
                 <InputBox
                     heading="Minimum participants"
+                    fields=&["Swap.minimum_participants"]
                     placeholder="57"
                     input_type="number"
                     updater=set_min_participants
@@ -604,6 +675,7 @@ pub fn CreateTokenSettings() -> impl IntoView {
                 />
                 <InputBox
                     heading="Minimum direct participant icp"
+                    fields=&["Swap.minimum_direct_participation_icp"]
                     placeholder="100,000 tokens"
                     updater=set_min_direct_participants_icp
                     validator=optional_tokens_validator
@@ -613,6 +685,7 @@ pub fn CreateTokenSettings() -> impl IntoView {
                 />
                 <InputBox
                     heading="Maximum direct participant icp"
+                    fields=&["Swap.maximum_direct_participation_icp"]
                     placeholder="1000000 tokens"
                     updater=set_max_direct_participants_icp
                     validator=optional_tokens_validator
@@ -622,6 +695,7 @@ pub fn CreateTokenSettings() -> impl IntoView {
                 />
                 <InputBox
                     heading="Minimum participant icp"
+                    fields=&["Swap.minimum_participant_icp"]
                     placeholder="10 tokens"
                     updater=set_min_participants_icp
                     validator=validate_tokens
@@ -629,6 +703,7 @@ pub fn CreateTokenSettings() -> impl IntoView {
                 />
                 <InputBox
                     heading="Maximum participant icp"
+                    fields=&["Swap.maximum_participant_icp"]
                     placeholder="10,000 tokens"
                     updater=set_max_participants_icp
                     validator=validate_tokens
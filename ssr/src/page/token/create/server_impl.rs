@@ -34,7 +34,14 @@ mod local_claim {
     };
     use yral_qstash_types::ClaimTokensRequest;
 
-    use crate::{consts::CDAO_SWAP_TIME_SECS, utils::ic::AgentWrapper};
+    use crate::{
+        auth::server_impl::store::KVStoreImpl,
+        consts::CDAO_SWAP_TIME_SECS,
+        utils::{
+            ic::AgentWrapper,
+            notifications::inbox::{server_impl::notify, NotificationKind},
+        },
+    };
 
     async fn get_neurons(
         governance: &SnsGovernance<'_>,
@@ -51,7 +58,7 @@ mod local_claim {
         Ok(neurons.neurons)
     }
 
-    async fn claim_tokens(req: ClaimTokensRequest) -> Result<(), ServerFnError> {
+    async fn claim_tokens(kv: &KVStoreImpl, req: ClaimTokensRequest) -> Result<(), ServerFnError> {
         let identity: DelegatedIdentity = req.identity.try_into()?;
         let user_principal = identity
             .sender()
@@ -162,14 +169,24 @@ mod local_claim {
             _ => (),
         }
 
+        let claimed = NotificationKind::TokensClaimed {
+            token_root: req.token_root,
+            amount,
+        };
+        if let Err(e) = notify(kv, user_principal, claimed).await {
+            log::warn!("failed to notify {user_principal} of claimed tokens: {e}");
+        }
+
         Ok(())
     }
 
     pub async fn enqueue_claim_token(req: ClaimTokensRequest) -> Result<(), ServerFnError> {
+        // The job outlives the request and its context
+        let kv: KVStoreImpl = leptos::expect_context();
         tokio::spawn(async move {
             log::info!("started claiming job");
             tokio::time::sleep(Duration::from_secs(CDAO_SWAP_TIME_SECS)).await;
-            if let Err(e) = claim_tokens(req).await {
+            if let Err(e) = claim_tokens(&kv, req).await {
                 log::error!("claim job failed: {e:?}");
             }
             log::info!("claiming completed")
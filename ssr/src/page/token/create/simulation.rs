@@ -0,0 +1,262 @@
+use candid::Principal;
+use leptos::*;
+use sns_validation::{
+    humanize::{format_duration, format_tokens, parse_tokens},
+    pbs::nns_pb::{Duration, Tokens},
+    simulation::{Cohort, NeuronOwner, Participation, Simulation},
+};
+
+use super::CreateTokenCtx;
+use crate::{
+    component::{back_btn::BackButton, title::Title},
+    state::canisters::auth_canisters_store,
+};
+
+fn tokens(e8s: u64) -> String {
+    format_tokens(&Tokens { e8s: Some(e8s) })
+}
+
+fn duration(seconds: u64) -> String {
+    format_duration(&Duration {
+        seconds: Some(seconds),
+    })
+}
+
+fn share(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    part as f64 * 100.0 / total as f64
+}
+
+#[component]
+fn SimInput<T: Fn(String) + 'static>(
+    #[prop(into)] heading: String,
+    #[prop(into)] value: String,
+    on_input: T,
+) -> impl IntoView {
+    view! {
+        <div class="flex flex-col grow gap-y-1 text-sm md:text-base">
+            <span class="text-white font-semibold">{heading}</span>
+            <input
+                value=value
+                on:input=move |ev| on_input(event_target_value(&ev))
+                class="w-full p-3 md:p-4 md:py-5 text-white outline-none bg-white/10 border-2 border-solid border-white/20 text-xs rounded-xl placeholder-neutral-600"
+            />
+        </div>
+    }
+}
+
+#[component]
+fn AllocationBar(sim: Simulation) -> impl IntoView {
+    let a = &sim.allocation;
+    let total = a.developers_e8s
+        + a.treasury_e8s
+        + a.direct_participants_e8s
+        + a.neurons_fund_e8s
+        + a.unsold_e8s;
+    let segments = [
+        ("Developers", a.developers_e8s, "bg-primary-600"),
+        ("Treasury", a.treasury_e8s, "bg-blue-500"),
+        (
+            "Swap participants",
+            a.direct_participants_e8s,
+            "bg-green-500",
+        ),
+        ("Neurons' Fund", a.neurons_fund_e8s, "bg-yellow-500"),
+        ("Unsold", a.unsold_e8s, "bg-neutral-600"),
+    ];
+
+    view! {
+        <div class="flex flex-col gap-2">
+            <div class="flex w-full h-4 rounded-full overflow-hidden bg-white/10">
+                {segments
+                    .iter()
+                    .map(|(_, e8s, color)| {
+                        view! {
+                            <div
+                                class=*color
+                                style=format!("width: {:.2}%", share(*e8s, total))
+                            ></div>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+            {segments
+                .iter()
+                .filter(|(_, e8s, _)| *e8s > 0)
+                .map(|(label, e8s, color)| {
+                    view! {
+                        <div class="flex items-center gap-2 text-xs text-white">
+                            <div class=format!("w-3 h-3 rounded-full {color}")></div>
+                            <span class="grow">{*label}</span>
+                            <span>{tokens(*e8s)}</span>
+                            <span class="text-neutral-400 w-14 text-right">
+                                {format!("{:.1}%", share(*e8s, total))}
+                            </span>
+                        </div>
+                    }
+                })
+                .collect_view()}
+        </div>
+    }
+}
+
+#[component]
+fn NeuronTable(sim: Simulation) -> impl IntoView {
+    let total_vp = sim.total_voting_power;
+    view! {
+        <div class="flex flex-col gap-1 text-xs text-white">
+            <div class="grid grid-cols-5 gap-2 text-neutral-400 font-semibold">
+                <span>Owner</span>
+                <span>Stake</span>
+                <span>Dissolve delay</span>
+                <span>Liquid after</span>
+                <span class="text-right">Voting power</span>
+            </div>
+            {sim
+                .neurons
+                .iter()
+                .map(|n| {
+                    let owner = match &n.owner {
+                        NeuronOwner::Developer { memo, .. } => format!("You (#{memo})"),
+                        NeuronOwner::Cohort(_) => format!("{} participants", n.count),
+                        NeuronOwner::NeuronsFund => "Neurons' Fund".to_string(),
+                    };
+                    view! {
+                        <div class="grid grid-cols-5 gap-2 border-t border-white/10 py-1">
+                            <span>{owner}</span>
+                            <span>{tokens(n.stake_e8s)}</span>
+                            <span>{duration(n.dissolve_delay_seconds)}</span>
+                            <span>{duration(n.liquid_after_seconds())}</span>
+                            <span class="text-right">
+                                {format!(
+                                    "{:.2}%",
+                                    share(n.voting_power.saturating_mul(n.count), total_vp),
+                                )}
+                            </span>
+                        </div>
+                    }
+                })
+                .collect_view()}
+        </div>
+    }
+}
+
+/// Previews the swap outcome and token distribution of the token being created
+#[component]
+pub fn CreateTokenSimulation() -> impl IntoView {
+    let ctx: CreateTokenCtx = use_context().unwrap_or_else(|| {
+        let ctx = CreateTokenCtx::default();
+        provide_context(ctx);
+        ctx
+    });
+    let auth_cans = auth_canisters_store();
+
+    let participants = create_rw_signal(100u64);
+    let icp_each = create_rw_signal(parse_tokens("1 token").unwrap());
+    let nf_ideal = create_rw_signal(Tokens { e8s: Some(0) });
+    let nf_enabled = move || {
+        ctx.form_state
+            .with(|f| f.swap.neurons_fund_participation == Some(true))
+    };
+
+    let simulation = create_memo(move |_| {
+        let participation = Participation {
+            cohorts: vec![Cohort {
+                participants: participants(),
+                icp_e8s_each: icp_each().e8s.unwrap_or_default(),
+            }],
+            neurons_fund_ideal_icp_e8s: nf_ideal().e8s.unwrap_or_default(),
+            neurons_fund_constraints: None,
+        };
+        let user_principal = auth_cans
+            .with(|c| c.as_ref().map(|c| c.user_principal()))
+            .unwrap_or_else(Principal::anonymous);
+        ctx.form_state
+            .with(|f| f.simulate(user_principal, &participation))
+    });
+
+    view! {
+        <div class="w-dvw min-h-dvh bg-black pt-4 flex flex-col gap-4 p-4" style="padding-bottom:5rem;">
+            <Title justify_center=false>
+                <div class="flex justify-between w-full" style="background: black">
+                    <BackButton fallback="/token/create" />
+                    <span class="font-bold justify-self-center">Preview</span>
+                    <div></div>
+                </div>
+            </Title>
+            <div class="flex flex-col w-full gap-4">
+                <SimInput
+                    heading="Participants"
+                    value=participants.get_untracked().to_string()
+                    on_input=move |v| {
+                        if let Ok(v) = v.parse() {
+                            participants.set(v)
+                        }
+                    }
+                />
+                <SimInput
+                    heading="ICP per participant"
+                    value=format_tokens(&icp_each.get_untracked())
+                    on_input=move |v| {
+                        if let Ok(v) = parse_tokens(&v) {
+                            icp_each.set(v)
+                        }
+                    }
+                />
+                <Show when=nf_enabled>
+                    <SimInput
+                        heading="Neurons' Fund matching (ICP)"
+                        value=format_tokens(&nf_ideal.get_untracked())
+                        on_input=move |v| {
+                            if let Ok(v) = parse_tokens(&v) {
+                                nf_ideal.set(v)
+                            }
+                        }
+                    />
+                </Show>
+            </div>
+            {move || {
+                let sim = simulation();
+                let swap = &sim.swap;
+                let status = match &swap.failure_reason {
+                    None => {
+                        format!(
+                            "Swap succeeds with {} participants raising {}",
+                            swap.accepted_participants,
+                            tokens(swap.total_participation_icp_e8s()),
+                        )
+                    }
+                    Some(reason) => format!("Swap fails: {reason}"),
+                };
+                let status_class = if swap.succeeded() {
+                    "text-sm text-green-500"
+                } else {
+                    "text-sm text-red-500"
+                };
+                let rejected = swap.rejected_participants;
+                let undersized = sim.undersized_neurons;
+                view! {
+                    <div class="flex flex-col gap-4">
+                        <span class=status_class>{status}</span>
+                        <Show when=move || rejected != 0>
+                            <span class="text-xs text-neutral-400">
+                                {format!("{rejected} participants would be turned away")}
+                            </span>
+                        </Show>
+                        <Show when=move || undersized != 0>
+                            <span class="text-xs text-red-500">
+                                {format!("{undersized} neurons stake less than the minimum creation stake")}
+                            </span>
+                        </Show>
+                        <span class="text-white font-semibold">Token distribution</span>
+                        <AllocationBar sim=sim.clone() />
+                        <span class="text-white font-semibold">Neurons</span>
+                        <NeuronTable sim=sim.clone() />
+                    </div>
+                }
+            }}
+        </div>
+    }
+}
@@ -1,17 +1,25 @@
 use crate::page::token::RootType;
 use crate::page::token::TokenInfoParams;
-use crate::state::canisters::authenticated_canisters;
+use crate::state::canisters::{authenticated_canisters, unauth_canisters};
 
 use crate::{
     component::{back_btn::BackButton, share_popup::*, spinner::FullScreenSpinner, title::Title},
     page::wallet::{transactions::Transactions, txn::IndexOrLedger},
-    utils::{token::TokenMetadata, web::copy_to_clipboard},
+    utils::{
+        token::{
+            icrc::{subaccount_from_bytes, subaccount_to_hex, to_ledger_account, IcrcAccount},
+            sns_config::get_token_config,
+            TokenBalance, TokenMetadata,
+        },
+        web::{copy_to_clipboard, download_text_file},
+    },
 };
 use candid::Principal;
 use leptos::*;
 use leptos_icons::*;
 use leptos_router::*;
 use serde::{Deserialize, Serialize};
+use yral_canisters_client::sns_index::ListSubaccountsArgs;
 
 #[component]
 fn TokenField(
@@ -52,19 +60,144 @@ fn TokenDetails(meta: TokenMetadata) -> impl IntoView {
     }
 }
 
-pub fn generate_share_link(root: &RootType, key_principal: Principal) -> String {
+pub fn generate_share_link(root: &RootType, key_principal: IcrcAccount) -> String {
     format!("/token/info/{}/{key_principal}?airdrop_amt=100", root)
 }
 
+/// Balances of the owner's other subaccounts, as indexed by the token's index canister
+#[component]
+fn SubaccountBalances(root: RootType, owner: Principal, meta: TokenMetadata) -> impl IntoView {
+    let decimals = meta.decimals;
+    let (ledger, index) = (meta.ledger, meta.index);
+    let balances = create_resource(
+        move || owner,
+        move |owner| async move {
+            let cans = unauth_canisters();
+            let index = cans.sns_index(index).await;
+            let subaccounts = index
+                .list_subaccounts(ListSubaccountsArgs { owner, start: None })
+                .await
+                .ok()?;
+            let ledger = cans.sns_ledger(ledger).await;
+            let mut balances = vec![];
+            for subaccount in subaccounts {
+                let account = IcrcAccount {
+                    owner,
+                    subaccount: subaccount_from_bytes(Some(subaccount)),
+                };
+                // The default subaccount is shown above
+                if account == IcrcAccount::from(owner) {
+                    continue;
+                }
+                let balance = ledger
+                    .icrc_1_balance_of(to_ledger_account(account))
+                    .await
+                    .ok()?;
+                balances.push((account, TokenBalance::new(balance, decimals)));
+            }
+            Some(balances)
+        },
+    );
+    let root = store_value(root);
+    let symbol = store_value(meta.symbol);
+
+    view! {
+        <Suspense>
+            {move || {
+                balances()
+                    .flatten()
+                    .filter(|balances| !balances.is_empty())
+                    .map(|balances| {
+                        view! {
+                            <div class="flex flex-col w-full gap-2">
+                                <span class="text-white text-sm md:text-base font-semibold">
+                                    Subaccounts
+                                </span>
+                                {balances
+                                    .into_iter()
+                                    .map(|(account, balance)| {
+                                        view! {
+                                            <a
+                                                href=format!("/token/info/{}/{account}", root.get_value())
+                                                class="flex flex-row justify-between gap-2 p-2 rounded-lg bg-white/5 text-xs md:text-sm text-white"
+                                            >
+                                                <span class="truncate">{account.to_string()}</span>
+                                                <span class="shrink-0">
+                                                    {format!(
+                                                        "{} {}",
+                                                        balance.humanize_float_truncate_to_dp(2),
+                                                        symbol.get_value(),
+                                                    )}
+                                                </span>
+                                            </a>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </div>
+                        }
+                    })
+            }}
+        </Suspense>
+    }
+}
+
+/// `sns_init.yaml` download, for tokens the viewer created
+#[component]
+fn TokenConfigDownload(root: Principal, #[prop(into)] token_name: String) -> impl IntoView {
+    let config = authenticated_canisters().derive(
+        move || root,
+        move |cans_wire, root| async move { get_token_config(cans_wire?, root).await },
+    );
+    let file_name = format!("{}_sns_init.yaml", token_name.to_lowercase());
+
+    view! {
+        <Suspense>
+            {move || {
+                let file_name = file_name.clone();
+                config()
+                    .and_then(|res| res.ok())
+                    .flatten()
+                    .map(|yaml| {
+                        view! {
+                            <button
+                                on:click=move |_| download_text_file(
+                                    &file_name,
+                                    "application/yaml",
+                                    &yaml,
+                                )
+                                class="w-full p-3 border border-white/15 text-white text-center text-sm md:text-base rounded-full"
+                            >
+                                Download token config
+                            </button>
+                        }
+                    })
+            }}
+        </Suspense>
+    }
+}
+
 #[component]
 fn TokenInfoInner(
     root: RootType,
     meta: TokenMetadata,
-    key_principal: Option<Principal>,
+    key_principal: Option<IcrcAccount>,
     is_user_principal: bool,
 ) -> impl IntoView {
     let meta_c1 = meta.clone();
     let meta_c = meta.clone();
+    let meta_c2 = meta.clone();
+    let source_query = key_principal
+        .and_then(|k| k.subaccount)
+        .map(|subaccount| format!("?from_subaccount={}", subaccount_to_hex(&subaccount)))
+        .unwrap_or_default();
+    let send_link = format!("/token/transfer/{root}{source_query}");
+    let approvals_link = format!("/token/approvals/{root}{source_query}");
+    // Only tokens deployed through the app have a config kept for them
+    let config_root = match &root {
+        RootType::Other(root) => Some(*root),
+        _ => None,
+    };
+    let token_name = meta.name.clone();
     let detail_toggle = create_rw_signal(false);
     let view_detail_icon = Signal::derive(move || {
         if detail_toggle() {
@@ -147,14 +280,29 @@ fn TokenInfoInner(
                 </div>
                     <Show when= move || is_user_principal>
                         <a
-                            href=format!("/token/transfer/{}", root.to_string())
+                            href=approvals_link.clone()
+                            class="w-full p-3 border border-white/15 text-white text-center text-sm md:text-base rounded-full"
+                        >
+                            Manage approvals
+                        </a>
+                        {config_root
+                            .map(|root| {
+                                view! { <TokenConfigDownload root token_name=token_name.clone() /> }
+                            })}
+                        <a
+                            href=send_link.clone()
                             class="fixed bottom-20 left-4 right-4 p-3 bg-primary-600 text-white text-center md:text-lg rounded-full z-50"
                         >
                             Send
                         </a>
                     </Show>
-                {if let Some(key_principal) = key_principal {
-                    view! { <Transactions source=IndexOrLedger::Index { key_principal, index: meta.index } symbol=meta.symbol.clone() decimals/> }
+                {key_principal
+                    .filter(|key| key.subaccount.is_none())
+                    .map(|key| {
+                        view! { <SubaccountBalances root=root.clone() owner=key.owner meta=meta_c2 /> }
+                    })}
+                {if let Some(key_account) = key_principal {
+                    view! { <Transactions source=IndexOrLedger::Index { key_account, index: meta.index, ledger: meta.ledger } symbol=meta.symbol.clone() decimals/> }
                 } else {
                     view! {
                         <Transactions
@@ -170,7 +318,8 @@ fn TokenInfoInner(
 }
 #[derive(Params, PartialEq, Clone, Serialize, Deserialize)]
 pub struct TokenKeyParam {
-    key_principal: Principal,
+    /// ICRC-1 textual account, a bare principal for the default subaccount
+    key_principal: IcrcAccount,
 }
 #[component]
 pub fn TokenInfo() -> impl IntoView {
@@ -194,7 +343,7 @@ pub fn TokenInfo() -> impl IntoView {
                     m,
                     params.token_root,
                     key_principal,
-                    Some(cans.user_principal()) == key_principal,
+                    Some(cans.user_principal()) == key_principal.map(|k| k.owner),
                 )
             }))
         },
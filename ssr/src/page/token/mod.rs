@@ -1,3 +1,4 @@
+pub mod approvals;
 pub mod create;
 pub mod create_token_faq;
 pub mod info;
@@ -17,7 +18,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     state::canisters::Canisters,
-    utils::token::{get_ck_metadata, token_metadata_by_root, TokenMetadata},
+    utils::token::{get_ck_metadata, icrc::IcrcAccount, token_metadata_by_root, TokenMetadata},
 };
 
 #[derive(Params, PartialEq, Clone)]
@@ -40,21 +41,19 @@ pub enum RootType {
 impl RootType {
     pub async fn get_metadata<const A: bool>(
         &self,
-        user_principal: Option<Principal>,
+        user_account: Option<IcrcAccount>,
         cans: Canisters<A>,
     ) -> Option<TokenMetadata> {
         match self {
-            RootType::BTC { ledger, index } => {
-                get_ck_metadata(cans, user_principal, *ledger, *index)
-                    .await
-                    .ok()?
-            }
+            RootType::BTC { ledger, index } => get_ck_metadata(cans, user_account, *ledger, *index)
+                .await
+                .ok()?,
             RootType::USDC { ledger, index } => {
-                get_ck_metadata(cans, user_principal, *ledger, *index)
+                get_ck_metadata(cans, user_account, *ledger, *index)
                     .await
                     .ok()?
             }
-            RootType::Other(root) => token_metadata_by_root(&cans, user_principal, *root)
+            RootType::Other(root) => token_metadata_by_root(&cans, user_account, *root)
                 .await
                 .ok()?,
         }
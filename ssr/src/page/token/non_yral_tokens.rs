@@ -18,9 +18,10 @@ pub async fn eligible_non_yral_supported_tokens(
 
             async move {
                 let token_root = Principal::from_text(token_root).ok()?;
-                let metadata_res = token_metadata_by_root(&cans, Some(user_principal), token_root)
-                    .await
-                    .ok()?;
+                let metadata_res =
+                    token_metadata_by_root(&cans, Some(user_principal.into()), token_root)
+                        .await
+                        .ok()?;
                 if let Some(metadata) = metadata_res {
                     if metadata
                         .balance?
@@ -4,7 +4,7 @@ use leptos_icons::*;
 use crate::{
     component::{overlay::ActionTrackerPopup, token_confetti_symbol::TokenConfettiSymbol},
     page::token::create::CreateTokenCtx,
-    utils::token::TokenBalance,
+    utils::{token::TokenBalance, web::download_text_file},
 };
 
 #[component]
@@ -31,32 +31,52 @@ fn SuccessPopup<ImgIV: IntoView, Img: Fn() -> ImgIV, TxtIV: IntoView, Txt: Fn()
 fn CreateTokenSuccessPopup(
     #[prop(into)] token_name: String,
     #[prop(into)] img_url: String,
+    /// `sns_init.yaml` of the deployed token
+    config_yaml: Option<String>,
 ) -> impl IntoView {
     CreateTokenCtx::reset();
     let profile_url = "/profile/tokens";
+    let config_file_name = format!("{}_sns_init.yaml", token_name.to_lowercase());
     view! {
-        <SuccessPopup
-            img=move || {
-                view! {
-                    <img
-                        class="relative w-20 h-20 rounded-full border-2 border-primary-600 object-conver"
-                        style="height:15rem; width:15rem"
-                        src=img_url.clone()
-                    />
+        <div class="flex flex-col items-center w-full h-full gap-4">
+            <SuccessPopup
+                img=move || {
+                    view! {
+                        <img
+                            class="relative w-20 h-20 rounded-full border-2 border-primary-600 object-conver"
+                            style="height:15rem; width:15rem"
+                            src=img_url.clone()
+                        />
+                    }
                 }
-            }
 
-            text=move || {
-                view! {
-                    Token
-                    <span class="text-primary-600">{format!(" {token_name} ")}</span>
-                    successfully created!
+                text=move || {
+                    view! {
+                        Token
+                        <span class="text-primary-600">{format!(" {token_name} ")}</span>
+                        successfully created!
+                    }
                 }
-            }
 
-            previous_link=profile_url
-            previous_text="Back to profile"
-        />
+                previous_link=profile_url
+                previous_text="Back to profile"
+            />
+            {config_yaml
+                .map(|yaml| {
+                    view! {
+                        <button
+                            on:click=move |_| download_text_file(
+                                &config_file_name,
+                                "application/yaml",
+                                &yaml,
+                            )
+                            class="text-sm underline text-neutral-600"
+                        >
+                            Download token config
+                        </button>
+                    }
+                })}
+        </div>
     }
 }
 
@@ -127,7 +147,7 @@ fn CreateTokenErrorPopup(
 
 #[component]
 pub fn TokenCreationPopup(
-    creation_action: Action<(), Result<(), String>>,
+    creation_action: Action<(), Result<Option<String>, String>>,
     #[prop(into)] token_name: MaybeSignal<String>,
     #[prop(into)] img_url: MaybeSignal<String>,
 ) -> impl IntoView {
@@ -137,11 +157,12 @@ pub fn TokenCreationPopup(
             action=creation_action
             loading_message="Token creation in progress"
             modal=move |res| match res {
-                Ok(_) => {
+                Ok(config_yaml) => {
                     view! {
                         <CreateTokenSuccessPopup
                             img_url=img_url.get_untracked().clone()
                             token_name=token_name.get_untracked().clone()
+                            config_yaml
                         />
                     }
                 }
@@ -7,6 +7,7 @@ use sns_validation::{
     },
     humanize::{parse_duration, parse_percentage, parse_tokens},
     pbs::nns_pb,
+    simulation::{self, Participation, Simulation},
 };
 
 use crate::state::canisters::Canisters;
@@ -246,6 +247,17 @@ impl SnsFormState {
         })
     }
 
+    /// Previews the swap outcome for a hypothetical participation
+    pub fn simulate(&self, user_principal: Principal, participation: &Participation) -> Simulation {
+        simulation::simulate(
+            &self.distribution.clone().into_distribution(user_principal),
+            &self.swap,
+            &self.neurons,
+            &self.voting,
+            participation,
+        )
+    }
+
     pub fn total_distrubution(&self) -> nns_pb::Tokens {
         self.distribution.total
     }
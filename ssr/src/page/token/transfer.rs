@@ -8,54 +8,228 @@ use crate::{
     state::canisters::{authenticated_canisters, Canisters, CanistersAuthWire},
     utils::{
         event_streaming::events::TokensTransferred,
-        token::{TokenBalance, TokenMetadata},
+        notifications::inbox::notify_token_received,
+        time::current_epoch,
+        token::{
+            icrc::{subaccount_from_hex, to_ledger_account, IcrcAccount, Subaccount},
+            TokenBalance, TokenMetadata,
+        },
         web::{copy_to_clipboard, paste_from_clipboard},
     },
 };
-use candid::Principal;
+use candid::{Nat, Principal};
 use leptos::*;
 use leptos_icons::*;
 use leptos_router::*;
 use leptos_use::use_event_listener;
+use rand_chacha::rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
 use server_fn::codec::Cbor;
+use thiserror::Error;
+use web_time::Duration;
 use yral_canisters_client::{
-    sns_ledger::{Account, TransferArg},
+    sns_ledger::{TransferArg, TransferError, TransferResult},
     sns_root::ListSnsCanistersArg,
 };
 
 use super::{popups::TokenTransferPopup, TokenParams};
 
+/// Ledgers only deduplicate transfers created within their transaction window (24h by default),
+/// older intents are replaced instead of being retried
+const TRANSFER_INTENT_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Identifies a single transfer across retries
+///
+/// The ledger rejects a transfer with the same `memo` and `created_at_time` as
+/// `Duplicate`, so resending after a network failure can never send twice
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TransferIntent {
+    pub memo: Vec<u8>,
+    /// Nanoseconds since epoch
+    pub created_at_time: u64,
+}
+
+impl TransferIntent {
+    pub fn new() -> Self {
+        let mut memo = vec![0u8; 16];
+        OsRng.fill_bytes(&mut memo);
+        Self {
+            memo,
+            created_at_time: current_epoch().as_nanos() as u64,
+        }
+    }
+
+    fn is_stale(&self) -> bool {
+        let created_at = Duration::from_nanos(self.created_at_time);
+        current_epoch().saturating_sub(created_at) > TRANSFER_INTENT_MAX_AGE
+    }
+}
+
+impl Default for TransferIntent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Rejection of an ICRC-1 transfer or ICRC-2 approval by the ledger
+/// amounts are in the smallest unit of the token
+#[derive(Serialize, Deserialize, Clone, Debug, Error)]
+pub enum TokenTransferError {
+    #[error("insufficient funds, balance is {balance}")]
+    InsufficientFunds { balance: Nat },
+    #[error("bad fee, expected {expected_fee}")]
+    BadFee { expected_fee: Nat },
+    #[error("amount is below the minimum burn amount {min_burn_amount}")]
+    BadBurn { min_burn_amount: Nat },
+    #[error("transfer is too old")]
+    TooOld,
+    #[error("transfer created in the future, ledger time is {ledger_time}")]
+    CreatedInFuture { ledger_time: u64 },
+    #[error("ledger is temporarily unavailable")]
+    TemporarilyUnavailable,
+    #[error("ledger error {error_code}: {message}")]
+    GenericError { message: String, error_code: Nat },
+    #[error("allowance changed to {current_allowance}")]
+    AllowanceChanged { current_allowance: Nat },
+    #[error("approval expired, ledger time is {ledger_time}")]
+    Expired { ledger_time: u64 },
+    #[error("insufficient allowance {allowance}")]
+    InsufficientAllowance { allowance: Nat },
+}
+
+impl TokenTransferError {
+    /// Error message for the user, with amounts formatted using the token's `decimals`
+    pub fn user_message(&self, decimals: u8, symbol: &str) -> String {
+        let amount = |e8s: &Nat| TokenBalance::new(e8s.clone(), decimals).humanize_float();
+        match self {
+            Self::InsufficientFunds { balance } => {
+                format!(
+                    "Not enough funds, your balance is {} {symbol}",
+                    amount(balance)
+                )
+            }
+            Self::BadFee { expected_fee } => format!(
+                "The transaction fee has changed to {} {symbol}, please try again",
+                amount(expected_fee)
+            ),
+            Self::BadBurn { min_burn_amount } => format!(
+                "Amount must be at least {} {symbol}",
+                amount(min_burn_amount)
+            ),
+            Self::TooOld => "Transfer expired, please try again".to_string(),
+            Self::CreatedInFuture { .. } => {
+                "Your device clock is ahead, please check your system time".to_string()
+            }
+            Self::TemporarilyUnavailable => {
+                "The ledger is temporarily unavailable, please try again later".to_string()
+            }
+            Self::GenericError { message, .. } => message.clone(),
+            Self::AllowanceChanged { current_allowance } => format!(
+                "The allowance has changed to {} {symbol}, please review it",
+                amount(current_allowance)
+            ),
+            Self::Expired { .. } => "Expiry must be in the future".to_string(),
+            Self::InsufficientAllowance { allowance } => format!(
+                "Not enough allowance, {} {symbol} is approved",
+                amount(allowance)
+            ),
+        }
+    }
+}
+
+/// Maps the ledger response to the block index of the transfer
+/// `Duplicate` means an earlier attempt of the same intent went through
+fn map_transfer_result(res: TransferResult) -> Result<Nat, TokenTransferError> {
+    let err = match res {
+        TransferResult::Ok(block) => return Ok(block),
+        TransferResult::Err(e) => e,
+    };
+    Err(match err {
+        TransferError::Duplicate { duplicate_of } => return Ok(duplicate_of),
+        TransferError::InsufficientFunds { balance } => {
+            TokenTransferError::InsufficientFunds { balance }
+        }
+        TransferError::BadFee { expected_fee } => TokenTransferError::BadFee { expected_fee },
+        TransferError::BadBurn { min_burn_amount } => {
+            TokenTransferError::BadBurn { min_burn_amount }
+        }
+        TransferError::TooOld => TokenTransferError::TooOld,
+        TransferError::CreatedInFuture { ledger_time } => {
+            TokenTransferError::CreatedInFuture { ledger_time }
+        }
+        TransferError::TemporarilyUnavailable => TokenTransferError::TemporarilyUnavailable,
+        TransferError::GenericError {
+            message,
+            error_code,
+        } => TokenTransferError::GenericError {
+            message,
+            error_code,
+        },
+    })
+}
+
+/// ICRC-1 transfer from one of the user's subaccounts, transport failures are retried
+/// with the same intent
+async fn icrc1_transfer(
+    cans: &Canisters<true>,
+    ledger_canister: Principal,
+    from_subaccount: Option<Subaccount>,
+    destination: IcrcAccount,
+    amount: &TokenBalance,
+    intent: &TransferIntent,
+) -> Result<Result<Nat, TokenTransferError>, ServerFnError> {
+    let res = cans
+        .with_retry("sns_ledger.icrc_1_transfer", || async move {
+            let sns_ledger = cans.sns_ledger(ledger_canister).await;
+            sns_ledger
+                .icrc_1_transfer(TransferArg {
+                    memo: Some(serde_bytes::ByteBuf::from(intent.memo.clone())),
+                    amount: amount.clone().into(),
+                    fee: None,
+                    from_subaccount: from_subaccount
+                        .map(|s| serde_bytes::ByteBuf::from(s.to_vec())),
+                    to: to_ledger_account(destination),
+                    created_at_time: Some(intent.created_at_time),
+                })
+                .await
+        })
+        .await?;
+    log::debug!("transfer res: {:?}", res);
+    Ok(map_transfer_result(res))
+}
+
 #[server(
     input = Cbor
 )]
 async fn transfer_token_to_user_principal(
     cans_wire: CanistersAuthWire,
-    destination_principal: Principal,
+    from_subaccount: Option<Subaccount>,
+    destination: IcrcAccount,
     ledger_canister: Principal,
     root_canister: Principal,
     amount: TokenBalance,
-) -> Result<(), ServerFnError> {
+    intent: TransferIntent,
+) -> Result<Result<Nat, TokenTransferError>, ServerFnError> {
     let cans = cans_wire.canisters()?;
     // let user_id = user_id.to_owned();
     // let user_principal = user_id.sender()?;
     // let agent = cans.agent.get_agent().await;
     // let user_principal = agent.get_principal()?;
     // log::debug!("user_principal: {:?}", user_principal.to_string());
-    let sns_ledger = cans.sns_ledger(ledger_canister).await;
-    let res = sns_ledger
-        .icrc_1_transfer(TransferArg {
-            memo: Some(serde_bytes::ByteBuf::from(vec![0])),
-            amount: amount.clone().into(),
-            fee: None,
-            from_subaccount: None,
-            to: Account {
-                owner: destination_principal,
-                subaccount: None,
-            },
-            created_at_time: None,
-        })
-        .await?;
-    log::debug!("transfer res: {:?}", res);
+    let block_index = match icrc1_transfer(
+        &cans,
+        ledger_canister,
+        from_subaccount,
+        destination,
+        &amount,
+        &intent,
+    )
+    .await?
+    {
+        Ok(block_index) => block_index,
+        Err(e) => return Ok(Err(e)),
+    };
+    let destination_principal = destination.owner;
 
     // let agent = Agent::builder()
     //     .with_url(AGENT_URL)
@@ -117,33 +291,28 @@ async fn transfer_token_to_user_principal(
     //     .unwrap();
     // println!("add_token res: {:?}", res);
 
-    Ok(())
+    Ok(Ok(block_index))
 }
 
 async fn transfer_ck_token_to_user_principal(
     cans_wire: CanistersAuthWire,
-    destination_principal: Principal,
+    from_subaccount: Option<Subaccount>,
+    destination: IcrcAccount,
     ledger_canister: Principal,
     amount: TokenBalance,
-) -> Result<(), ServerFnError> {
+    intent: TransferIntent,
+) -> Result<Result<Nat, TokenTransferError>, ServerFnError> {
     let cans = cans_wire.canisters()?;
 
-    let sns_ledger = cans.sns_ledger(ledger_canister).await;
-    let res = sns_ledger
-        .icrc_1_transfer(TransferArg {
-            memo: Some(serde_bytes::ByteBuf::from(vec![0])),
-            amount: amount.clone().into(),
-            fee: None,
-            from_subaccount: None,
-            to: Account {
-                owner: destination_principal,
-                subaccount: None,
-            },
-            created_at_time: None,
-        })
-        .await?;
-    log::debug!("transfer res: {:?}", res);
-    Ok(())
+    icrc1_transfer(
+        &cans,
+        ledger_canister,
+        from_subaccount,
+        destination,
+        &amount,
+        &intent,
+    )
+    .await
 }
 
 #[component]
@@ -161,8 +330,16 @@ fn FormError<V: 'static>(#[prop(into)] res: Signal<Result<V, String>>) -> impl I
 }
 
 #[component]
-fn TokenTransferInner(cans: Canisters<true>, root: RootType, info: TokenMetadata) -> impl IntoView {
-    let source_addr = cans.user_principal();
+fn TokenTransferInner(
+    cans: Canisters<true>,
+    root: RootType,
+    info: TokenMetadata,
+    from_subaccount: Option<Subaccount>,
+) -> impl IntoView {
+    let source_addr = IcrcAccount {
+        owner: cans.user_principal(),
+        subaccount: from_subaccount,
+    };
     let copy_source = move || {
         let _ = copy_to_clipboard(&source_addr.to_string());
     };
@@ -180,15 +357,18 @@ fn TokenTransferInner(cans: Canisters<true>, root: RootType, info: TokenMetadata
         Some(())
     });
 
-    let destination_res = create_rw_signal(Ok::<_, String>(None::<Principal>));
+    let destination_res = create_rw_signal(Ok::<_, String>(None::<IcrcAccount>));
     _ = use_event_listener(destination_ref, ev::input, move |_| {
         let Some(input) = destination_ref() else {
             return;
         };
-        let principal_raw = input.value();
-        let principal_res =
-            Principal::from_text(principal_raw).map_err(|_| "Invalid principal".to_string());
-        destination_res.set(principal_res.map(Some));
+        let account_raw = input.value();
+        // Accepts a bare principal or the ICRC-1 textual encoding with a subaccount
+        let account_res = account_raw
+            .trim()
+            .parse::<IcrcAccount>()
+            .map_err(|_| "Invalid principal or account".to_string());
+        destination_res.set(account_res.map(Some));
     });
 
     let amount_ref = create_node_ref::<html::Input>();
@@ -244,10 +424,16 @@ fn TokenTransferInner(cans: Canisters<true>, root: RootType, info: TokenMetadata
     });
 
     let auth_cans_wire = authenticated_canisters();
+    // Reused when the same transfer is resent after a failure,
+    // so the ledger deduplicates it if the earlier attempt went through
+    let pending_intent = store_value(None::<(IcrcAccount, Nat, TransferIntent)>);
+    let decimals = info.decimals;
+    let symbol = info.symbol.clone();
 
     let send_action = create_action(move |&()| {
         let cans = cans.clone();
         let auth_cans_wire = auth_cans_wire.clone();
+        let symbol = symbol.clone();
 
         let root = root.clone();
         async move {
@@ -267,8 +453,19 @@ fn TokenTransferInner(cans: Canisters<true>, root: RootType, info: TokenMetadata
             // Ok(())
 
             let amt = amt_res.get_untracked().unwrap().unwrap();
-
-            match root {
+            let intent = pending_intent
+                .with_value(|pending| {
+                    pending
+                        .as_ref()
+                        .filter(|(dest, e8s, intent)| {
+                            *dest == destination && *e8s == amt.e8s && !intent.is_stale()
+                        })
+                        .map(|(_, _, intent)| intent.clone())
+                })
+                .unwrap_or_default();
+            pending_intent.set_value(Some((destination, amt.e8s.clone(), intent.clone())));
+
+            let (ledger, res) = match root {
                 RootType::Other(root) => {
                     let root_canister = cans.sns_root(root).await;
                     println!("{}", root);
@@ -279,35 +476,57 @@ fn TokenTransferInner(cans: Canisters<true>, root: RootType, info: TokenMetadata
                     let ledger_canister = sns_cans.ledger.unwrap();
                     log::debug!("ledger_canister: {:?}", ledger_canister);
 
-                    transfer_token_to_user_principal(
+                    let res = transfer_token_to_user_principal(
                         auth_cans_wire.wait_untracked().await.unwrap(),
+                        from_subaccount,
                         destination,
                         ledger_canister,
                         root,
                         amt.clone(),
+                        intent,
                     )
                     .await?;
+                    (ledger_canister, res)
                 }
                 RootType::BTC { ledger, .. } => {
-                    transfer_ck_token_to_user_principal(
+                    let res = transfer_ck_token_to_user_principal(
                         auth_cans_wire.wait_untracked().await.unwrap(),
+                        from_subaccount,
                         destination,
                         ledger,
                         amt.clone(),
+                        intent,
                     )
                     .await?;
+                    (ledger, res)
                 }
                 RootType::USDC { ledger, .. } => {
-                    transfer_ck_token_to_user_principal(
+                    let res = transfer_ck_token_to_user_principal(
                         auth_cans_wire.wait_untracked().await.unwrap(),
+                        from_subaccount,
                         destination,
                         ledger,
                         amt.clone(),
+                        intent,
                     )
                     .await?;
+                    (ledger, res)
+                }
+            };
+            // Unless the ledger was unavailable it has settled this intent,
+            // so a retry must be a new transfer
+            if !matches!(res, Err(TokenTransferError::TemporarilyUnavailable)) {
+                pending_intent.set_value(None);
+            }
+            let block_index =
+                res.map_err(|e| ServerFnError::new(e.user_message(decimals, &symbol)))?;
+            TokensTransferred.send_event(amt.e8s.to_string(), destination.owner, cans.clone());
+            if let Ok(cans_wire) = auth_cans_wire.wait_untracked().await {
+                let notified = notify_token_received(cans_wire, ledger, block_index).await;
+                if let Err(e) = notified {
+                    log::warn!("failed to notify recipient of transfer: {e}");
                 }
             }
-            TokensTransferred.send_event(amt.e8s.to_string(), destination, cans.clone());
 
             Ok::<_, ServerFnError>(amt)
         }
@@ -403,24 +622,34 @@ fn TokenTransferInner(cans: Canisters<true>, root: RootType, info: TokenMetadata
 #[component]
 pub fn TokenTransfer() -> impl IntoView {
     let params = use_params::<TokenParams>();
+    let query = use_query_map();
+    let from_subaccount = move || {
+        query.with(|q| {
+            q.get("from_subaccount")
+                .and_then(|s| subaccount_from_hex(s))
+        })
+    };
 
     let token_metadata_fetch = move |cans: Canisters<true>| {
-        create_resource(params, move |params| {
-            let cans = cans.clone();
-            let user_principal = cans.user_principal();
-            async move {
-                let Ok(params) = params else {
-                    return Ok::<_, ServerFnError>(None);
+        create_resource(
+            move || (params(), from_subaccount()),
+            move |(params, from_subaccount)| {
+                let cans = cans.clone();
+                let source = IcrcAccount {
+                    owner: cans.user_principal(),
+                    subaccount: from_subaccount,
                 };
-                // let user = cans.user_canister();
-                let meta = params
-                    .token_root
-                    .get_metadata(Some(user_principal), cans)
-                    .await;
-
-                Ok(meta.map(|m| (m, params.token_root)))
-            }
-        })
+                async move {
+                    let Ok(params) = params else {
+                        return Ok::<_, ServerFnError>(None);
+                    };
+                    // let user = cans.user_canister();
+                    let meta = params.token_root.get_metadata(Some(source), cans).await;
+
+                    Ok(meta.map(|m| (m, params.token_root, from_subaccount)))
+                }
+            },
+        )
     };
 
     view! {
@@ -434,7 +663,7 @@ pub fn TokenTransfer() -> impl IntoView {
                         view! { <Redirect path=format!("/error?err={e}") /> }
                     },
                     Ok(None) => view! { <Redirect path="/" /> },
-                    Ok(Some((info, root))) => view! { <TokenTransferInner cans info root/> },
+                    Ok(Some((info, root, from_subaccount))) => view! { <TokenTransferInner cans info root from_subaccount/> },
                 }
             }
         />
@@ -1,18 +1,23 @@
 use candid::Principal;
+#[cfg(feature = "cloudflare")]
+pub use cf_impl::publish_video;
 #[cfg(all(feature = "cloudflare", feature = "ssr"))]
 use cf_impl::server_func::*;
-#[cfg(feature = "cloudflare")]
-pub use cf_impl::{publish_video, upload_video_stream};
 use leptos::*;
+#[cfg(not(feature = "cloudflare"))]
+pub use mock_impl::publish_video;
 #[cfg(all(not(feature = "cloudflare"), feature = "ssr"))]
 use mock_impl::server_func::*;
-#[cfg(not(feature = "cloudflare"))]
-pub use mock_impl::{publish_video, upload_video_stream};
+#[cfg(all(not(feature = "cloudflare"), feature = "ssr"))]
+pub use mock_impl::tus_server::router as mock_tus_router;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
+use super::validators::VideoMetadata;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct UploadInfo {
     pub uid: String,
+    /// tus endpoint of the upload
     pub upload_url: String,
 }
 
@@ -22,7 +27,11 @@ pub async fn get_upload_info(
     hashtags: Vec<String>,
     description: String,
     file_name: String,
+    video: VideoMetadata,
 ) -> Result<UploadInfo, ServerFnError> {
+    use super::validators::video_preflight;
+    use crate::utils::hashtags::merge_hashtags;
+
     // TODO(SECURITY): authenticate creator
 
     if description.len() < 10 {
@@ -30,11 +39,12 @@ pub async fn get_upload_info(
             "Description must be at least 10 characters".into(),
         ));
     }
-    if hashtags.len() > 8 {
-        return Err(ServerFnError::Args("Too many hashtags".into()));
-    }
+    let hashtags =
+        merge_hashtags(&hashtags, &description).map_err(|e| ServerFnError::Args(e.to_string()))?;
+    // The browser probes the video, reject it before an upload slot is reserved
+    video_preflight(&video).map_err(ServerFnError::Args)?;
 
-    get_upload_info_impl(creator, hashtags, description, file_name).await
+    get_upload_info_impl(creator, hashtags, description, file_name, video.size).await
 }
 
 #[server(GetVideoStatus)]
@@ -49,38 +59,57 @@ mod cf_impl {
 
     use crate::state::canisters::Canisters;
 
-    use super::UploadInfo;
-
     #[cfg(feature = "ssr")]
     pub mod server_func {
+        use std::collections::HashMap;
+
         use candid::Principal;
         use gob_cloudflare::{
-            api::stream_videos::{CreateDownloads, DirectUpload, VideoDetails},
+            api::stream_videos::{CreateDownloads, VideoDetails},
             CloudflareAuth,
         };
         use leptos::{expect_context, ServerFnError};
 
-        use crate::consts::CF_WATERMARK_UID;
-
-        use super::UploadInfo;
-        use std::time::Duration;
+        use crate::{
+            consts::CF_WATERMARK_UID,
+            page::upload::{cf_upload::UploadInfo, validators::MAX_VIDEO_DURATION_SECS},
+            utils::cf_tus::CfTusClient,
+        };
 
+        /// The limits a client can't bypass are the ones enforced by Cloudflare:
+        /// `maxdurationseconds` fails longer videos once processed, and `Upload-Length`,
+        /// the reported size checked by [video_preflight](crate::page::upload::validators::video_preflight),
+        /// caps the bytes accepted, an upload only completes once exactly that many were sent
         pub async fn get_upload_info_impl(
             creator: Principal,
             hashtags: Vec<String>,
             description: String,
             file_name: String,
+            file_size: u64,
         ) -> Result<UploadInfo, ServerFnError> {
-            let cf_api: CloudflareAuth = expect_context();
-            let req = DirectUpload::default()
-                .creator(creator.to_text())
-                .add_meta("hashtags", hashtags.join(","))
-                .add_meta("description", description)
-                .add_meta("fileName", file_name)
-                .add_meta("uploadType", "challenge")
-                .watermark(CF_WATERMARK_UID)
-                .max_duration(Duration::from_secs(60));
-            let res = cf_api.send_auth(req).await?;
+            let cf_tus: CfTusClient = expect_context();
+            let res = cf_tus
+                .create_upload(
+                    &creator.to_text(),
+                    file_size,
+                    &[
+                        ("name", file_name.clone()),
+                        ("maxdurationseconds", MAX_VIDEO_DURATION_SECS.to_string()),
+                        ("watermark", CF_WATERMARK_UID.into()),
+                    ],
+                )
+                .await?;
+            cf_tus
+                .set_meta(
+                    &res.uid,
+                    HashMap::from([
+                        ("hashtags", hashtags.join(",")),
+                        ("description", description),
+                        ("fileName", file_name),
+                        ("uploadType", "challenge".into()),
+                    ]),
+                )
+                .await?;
 
             Ok(UploadInfo {
                 uid: res.uid,
@@ -103,22 +132,6 @@ mod cf_impl {
         }
     }
 
-    pub async fn upload_video_stream(
-        _upload_res: &UploadInfo,
-        _file: &gloo::file::File,
-    ) -> Result<(), gloo::net::Error> {
-        #[cfg(feature = "hydrate")]
-        {
-            use gloo::net::http::Request;
-            use leptos::web_sys::FormData;
-            let form = FormData::new().unwrap();
-            form.append_with_blob("file", _file.as_ref()).unwrap();
-            let req = Request::post(&_upload_res.upload_url).body(form).unwrap();
-            req.send().await?;
-        }
-        Ok(())
-    }
-
     pub async fn publish_video(
         canisters: Canisters<true>,
         hashtags: Vec<String>,
@@ -148,7 +161,6 @@ mod cf_impl {
 
 #[cfg(not(feature = "cloudflare"))]
 mod mock_impl {
-    use super::UploadInfo;
     use crate::state::canisters::Canisters;
     use leptos::ServerFnError;
 
@@ -158,17 +170,20 @@ mod mock_impl {
         use leptos::ServerFnError;
         use std::time::Duration;
 
-        use super::UploadInfo;
+        use super::tus_server;
+        use crate::page::upload::cf_upload::UploadInfo;
 
         pub async fn get_upload_info_impl(
             _creator: Principal,
             _hashtags: Vec<String>,
             _description: String,
             _file_name: String,
+            file_size: u64,
         ) -> Result<UploadInfo, ServerFnError> {
+            let uid = tus_server::create_upload(file_size);
             Ok(UploadInfo {
-                uid: "mock".into(),
-                upload_url: "http://mock.com".into(),
+                upload_url: format!("/mock-tus/{uid}"),
+                uid,
             })
         }
 
@@ -178,15 +193,6 @@ mod mock_impl {
         }
     }
 
-    pub async fn upload_video_stream(
-        _upload_res: &UploadInfo,
-        _file: &gloo::file::File,
-    ) -> Result<(), gloo::net::Error> {
-        use gloo::timers::future::TimeoutFuture;
-        TimeoutFuture::new(1000).await;
-        Ok(())
-    }
-
     pub async fn publish_video(
         _canisters: Canisters<true>,
         _hashtags: Vec<String>,
@@ -195,8 +201,113 @@ mod mock_impl {
         _enable_hot_or_not: bool,
         _is_nsfw: bool,
     ) -> Result<u64, ServerFnError> {
-        use gloo::timers::future::TimeoutFuture;
-        TimeoutFuture::new(1000).await;
+        // Also called on the server by scheduled drafts
+        crate::utils::time::sleep(web_time::Duration::from_secs(1)).await;
         Ok(0)
     }
+
+    /// In memory tus server for local development, chunks are accepted and discarded
+    #[cfg(feature = "ssr")]
+    pub mod tus_server {
+        use std::{
+            collections::HashMap,
+            sync::{
+                atomic::{AtomicU64, Ordering},
+                Mutex,
+            },
+        };
+
+        use axum::{
+            body::Bytes,
+            extract::{DefaultBodyLimit, Path},
+            http::{header::CONTENT_TYPE, HeaderMap, StatusCode},
+            response::{IntoResponse, Response},
+            routing::head,
+            Router,
+        };
+        use once_cell::sync::Lazy;
+
+        use crate::page::upload::tus::TUS_CHUNK_SIZE;
+
+        const TUS_VERSION: &str = "1.0.0";
+
+        struct MockUpload {
+            offset: u64,
+            length: u64,
+        }
+
+        static UPLOADS: Lazy<Mutex<HashMap<String, MockUpload>>> = Lazy::new(Default::default);
+        static NEXT_UPLOAD_ID: AtomicU64 = AtomicU64::new(0);
+
+        /// Register a new upload of `length` bytes, returns its uid
+        pub fn create_upload(length: u64) -> String {
+            let uid = format!("mock-{}", NEXT_UPLOAD_ID.fetch_add(1, Ordering::Relaxed));
+            UPLOADS
+                .lock()
+                .unwrap()
+                .insert(uid.clone(), MockUpload { offset: 0, length });
+            uid
+        }
+
+        fn tus_headers(upload: &MockUpload) -> [(&'static str, String); 3] {
+            [
+                ("Tus-Resumable", TUS_VERSION.into()),
+                ("Upload-Offset", upload.offset.to_string()),
+                ("Upload-Length", upload.length.to_string()),
+            ]
+        }
+
+        async fn upload_status(Path(uid): Path<String>) -> Response {
+            let uploads = UPLOADS.lock().unwrap();
+            let Some(upload) = uploads.get(&uid) else {
+                return StatusCode::NOT_FOUND.into_response();
+            };
+            (
+                StatusCode::OK,
+                [("Cache-Control", "no-store")],
+                tus_headers(upload),
+            )
+                .into_response()
+        }
+
+        async fn upload_chunk(
+            Path(uid): Path<String>,
+            headers: HeaderMap,
+            body: Bytes,
+        ) -> Response {
+            let content_type = headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok());
+            if content_type != Some("application/offset+octet-stream") {
+                return StatusCode::UNSUPPORTED_MEDIA_TYPE.into_response();
+            }
+            let Some(offset) = headers
+                .get("Upload-Offset")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok())
+            else {
+                return StatusCode::BAD_REQUEST.into_response();
+            };
+
+            let mut uploads = UPLOADS.lock().unwrap();
+            let Some(upload) = uploads.get_mut(&uid) else {
+                return StatusCode::NOT_FOUND.into_response();
+            };
+            if offset != upload.offset {
+                return StatusCode::CONFLICT.into_response();
+            }
+            let new_offset = offset + body.len() as u64;
+            if new_offset > upload.length {
+                return StatusCode::PAYLOAD_TOO_LARGE.into_response();
+            }
+            upload.offset = new_offset;
+
+            (StatusCode::NO_CONTENT, tus_headers(upload)).into_response()
+        }
+
+        /// Routes of the mock server, to be nested under `/mock-tus`
+        pub fn router<S: Clone + Send + Sync + 'static>() -> Router<S> {
+            Router::new()
+                .route("/:uid", head(upload_status).patch(upload_chunk))
+                .layer(DefaultBodyLimit::max(2 * TUS_CHUNK_SIZE as usize))
+        }
+    }
 }
@@ -0,0 +1,511 @@
+//! Uploaded videos that are not published yet, kept in the server KV store
+//!
+//! A draft can be scheduled, at the chosen time [publish_scheduled_draft] is called
+//! through QStash (or a local timer without the `qstash` feature) and publishes it
+//! with a delegation of the creator's identity stored alongside the draft.
+//! That delegation can only call the creator's canister, expires shortly after the
+//! publish time and is kept sealed with the cookie key.
+use candid::Principal;
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use crate::state::canisters::CanistersAuthWire;
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub enum DraftStatus {
+    Draft,
+    Scheduled { at_secs: u64 },
+    Publishing,
+    Published { post_id: u64 },
+    Failed { reason: String },
+}
+
+impl DraftStatus {
+    /// Metadata can be edited and the draft deleted or (re)scheduled
+    pub fn is_editable(&self) -> bool {
+        matches!(
+            self,
+            Self::Draft | Self::Scheduled { .. } | Self::Failed { .. }
+        )
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub struct Draft {
+    /// Cloudflare stream uid of the uploaded video
+    pub uid: String,
+    pub hashtags: Vec<String>,
+    pub description: String,
+    pub enable_hot_or_not: bool,
+    pub is_nsfw: bool,
+    pub created_at_secs: u64,
+    pub status: DraftStatus,
+}
+
+/// Save a draft for an uploaded video, updating its metadata if it already exists
+#[server(
+    input = Cbor
+)]
+pub async fn save_draft(
+    cans_wire: CanistersAuthWire,
+    uid: String,
+    hashtags: Vec<String>,
+    description: String,
+    enable_hot_or_not: bool,
+    is_nsfw: bool,
+) -> Result<Draft, ServerFnError> {
+    server_impl::save_draft(
+        cans_wire,
+        uid,
+        hashtags,
+        description,
+        enable_hot_or_not,
+        is_nsfw,
+    )
+    .await
+}
+
+/// Drafts of the authenticated user, newest first
+#[server(
+    input = Cbor
+)]
+pub async fn list_drafts(cans_wire: CanistersAuthWire) -> Result<Vec<Draft>, ServerFnError> {
+    server_impl::list_drafts(cans_wire).await
+}
+
+/// Delete a draft, cancelling its scheduled publish if any
+#[server(
+    input = Cbor
+)]
+pub async fn delete_draft(cans_wire: CanistersAuthWire, uid: String) -> Result<(), ServerFnError> {
+    server_impl::delete_draft(cans_wire, uid).await
+}
+
+/// Schedule a draft to be published at `publish_at_secs` (unix seconds)
+/// `None` turns a scheduled draft back into a plain draft
+#[server(
+    input = Cbor
+)]
+pub async fn schedule_draft(
+    cans_wire: CanistersAuthWire,
+    uid: String,
+    publish_at_secs: Option<u64>,
+) -> Result<Draft, ServerFnError> {
+    server_impl::schedule_draft(cans_wire, uid, publish_at_secs).await
+}
+
+/// Called by QStash once a scheduled draft is due
+///
+/// The endpoint is fixed so that deliveries enqueued before a deploy still resolve.
+/// It is unauthenticated: a draft is only published once its stored schedule is due,
+/// so an early or repeated call is a no-op
+#[server(endpoint = "publish_scheduled_draft", input = Json)]
+pub async fn publish_scheduled_draft(owner: Principal, uid: String) -> Result<(), ServerFnError> {
+    server_impl::publish_scheduled_draft(owner, uid).await
+}
+
+#[cfg(feature = "ssr")]
+mod server_impl {
+    use axum_extra::extract::cookie::Key;
+    use candid::Principal;
+    use leptos::{expect_context, ServerFnError};
+    use serde::{Deserialize, Serialize};
+    use web_time::Duration;
+
+    use super::{Draft, DraftStatus};
+    use crate::{
+        auth::{
+            delegate_scoped_identity,
+            server_impl::{
+                seal_with_cookie_key,
+                store::{KVStore, KVStoreImpl},
+                unseal_with_cookie_key,
+            },
+        },
+        page::upload::{cf_upload::publish_video, validators::description_validator},
+        state::canisters::{Canisters, CanistersAuthWire},
+        utils::{
+            hashtags::{merge_hashtags, server_impl::record_post_hashtags},
+            notifications::inbox::server_impl::notify_followers,
+            time::current_epoch,
+        },
+    };
+
+    /// How far ahead a draft can be scheduled, well within the login session
+    const MAX_SCHEDULE_AHEAD: Duration = Duration::from_secs(3 * 24 * 60 * 60);
+    /// Concurrent edits of a draft may race, give up after a few attempts
+    const MAX_UPDATE_ATTEMPTS: usize = 3;
+    /// Validity of the publishing delegation past the publish time,
+    /// covers delayed QStash deliveries
+    const PUBLISH_GRACE: Duration = Duration::from_secs(30 * 60);
+
+    /// A draft as persisted, with the identity used for its scheduled publish
+    #[derive(Serialize, Deserialize)]
+    struct StoredDraft {
+        draft: Draft,
+        /// [CanistersAuthWire] of a delegation scoped to the user canister,
+        /// sealed with the cookie key
+        #[serde(default)]
+        sealed_publisher: Option<String>,
+    }
+
+    /// Delegation that can only publish to the user's canister until shortly after `at_secs`
+    fn scoped_publisher(cans: &Canisters<true>, at_secs: u64) -> CanistersAuthWire {
+        let max_age =
+            Duration::from_secs(at_secs.saturating_sub(current_epoch().as_secs())) + PUBLISH_GRACE;
+        let id = delegate_scoped_identity(cans.identity(), vec![cans.user_canister()], max_age);
+        cans.wire_with_identity(id)
+    }
+
+    fn seal_publisher(publisher: &CanistersAuthWire) -> Result<String, ServerFnError> {
+        let key: Key = expect_context();
+        seal_with_cookie_key(&key, serde_json::to_string(publisher)?)
+    }
+
+    fn unseal_publisher(sealed: String) -> Result<CanistersAuthWire, ServerFnError> {
+        let key: Key = expect_context();
+        let raw = unseal_with_cookie_key(&key, sealed)
+            .ok_or_else(|| ServerFnError::new("Invalid sealed publisher"))?;
+        Ok(serde_json::from_str(&raw)?)
+    }
+
+    /// Uids of `owner`'s drafts
+    fn drafts_index(owner: Principal) -> String {
+        format!("drafts:{owner}")
+    }
+
+    fn draft_key(owner: Principal, uid: &str) -> String {
+        format!("draft:{owner}:{uid}")
+    }
+
+    /// An empty value is a deleted draft, see [delete_draft]
+    fn parse_draft(raw: &str) -> Result<Option<StoredDraft>, ServerFnError> {
+        if raw.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(raw)?))
+    }
+
+    /// Returns the stored draft along with its raw value, for compare and swap
+    async fn load_draft(
+        kv: &KVStoreImpl,
+        owner: Principal,
+        uid: &str,
+    ) -> Result<Option<(String, StoredDraft)>, ServerFnError> {
+        let Some(raw) = kv.read(draft_key(owner, uid)).await? else {
+            return Ok(None);
+        };
+        Ok(parse_draft(&raw)?.map(|stored| (raw, stored)))
+    }
+
+    /// Read-modify-write a draft with compare and swap, so that a concurrent change
+    /// (i.e the draft being published) is never overwritten with a stale copy
+    /// `update` gets the current draft, it is called again if the draft changed meanwhile
+    async fn update_draft(
+        kv: &KVStoreImpl,
+        owner: Principal,
+        uid: &str,
+        mut update: impl FnMut(Option<StoredDraft>) -> Result<StoredDraft, ServerFnError>,
+    ) -> Result<StoredDraft, ServerFnError> {
+        let key = draft_key(owner, uid);
+        for _ in 0..MAX_UPDATE_ATTEMPTS {
+            let raw = kv.read(key.clone()).await?;
+            let current = raw.as_deref().map(parse_draft).transpose()?.flatten();
+            let stored = update(current)?;
+            if kv
+                .compare_and_swap(key.clone(), raw, serde_json::to_string(&stored)?)
+                .await?
+            {
+                kv.index_add(drafts_index(owner), uid.to_string()).await?;
+                return Ok(stored);
+            }
+        }
+        Err(ServerFnError::new("Draft was changed meanwhile, try again"))
+    }
+
+    fn editable(current: Option<StoredDraft>) -> Result<StoredDraft, ServerFnError> {
+        let stored = current.ok_or_else(|| ServerFnError::new("Draft not found"))?;
+        if !stored.draft.status.is_editable() {
+            return Err(ServerFnError::new("Draft is already published"));
+        }
+        Ok(stored)
+    }
+
+    pub async fn save_draft(
+        cans_wire: CanistersAuthWire,
+        uid: String,
+        hashtags: Vec<String>,
+        description: String,
+        enable_hot_or_not: bool,
+        is_nsfw: bool,
+    ) -> Result<Draft, ServerFnError> {
+        description_validator(description.clone()).map_err(ServerFnError::Args)?;
+        let hashtags = merge_hashtags(&hashtags, &description)
+            .map_err(|e| ServerFnError::Args(e.to_string()))?;
+
+        let owner = cans_wire.canisters()?.user_principal();
+        let kv: KVStoreImpl = expect_context();
+        let stored = update_draft(&kv, owner, &uid, |current| match current {
+            Some(stored) if !stored.draft.status.is_editable() => {
+                Err(ServerFnError::new("Draft is already published"))
+            }
+            Some(stored) => Ok(StoredDraft {
+                draft: Draft {
+                    hashtags: hashtags.clone(),
+                    description: description.clone(),
+                    enable_hot_or_not,
+                    is_nsfw,
+                    ..stored.draft
+                },
+                sealed_publisher: stored.sealed_publisher,
+            }),
+            None => Ok(StoredDraft {
+                draft: Draft {
+                    uid: uid.clone(),
+                    hashtags: hashtags.clone(),
+                    description: description.clone(),
+                    enable_hot_or_not,
+                    is_nsfw,
+                    created_at_secs: current_epoch().as_secs(),
+                    status: DraftStatus::Draft,
+                },
+                sealed_publisher: None,
+            }),
+        })
+        .await?;
+
+        Ok(stored.draft)
+    }
+
+    pub async fn list_drafts(cans_wire: CanistersAuthWire) -> Result<Vec<Draft>, ServerFnError> {
+        let owner = cans_wire.canisters()?.user_principal();
+        let kv: KVStoreImpl = expect_context();
+        let mut drafts = Vec::new();
+        for uid in kv.index_members(drafts_index(owner)).await? {
+            let Some(raw) = kv.read(draft_key(owner, &uid)).await? else {
+                // Deleted after being indexed
+                kv.index_remove(drafts_index(owner), uid).await?;
+                continue;
+            };
+            match parse_draft(&raw) {
+                Ok(Some(stored)) => drafts.push(stored.draft),
+                // Being deleted
+                Ok(None) => (),
+                Err(e) => log::warn!("skipping malformed draft {uid}: {e}"),
+            }
+        }
+        drafts.sort_by(|a, b| b.created_at_secs.cmp(&a.created_at_secs));
+
+        Ok(drafts)
+    }
+
+    pub async fn delete_draft(
+        cans_wire: CanistersAuthWire,
+        uid: String,
+    ) -> Result<(), ServerFnError> {
+        let owner = cans_wire.canisters()?.user_principal();
+        let kv: KVStoreImpl = expect_context();
+        if let Some((raw, stored)) = load_draft(&kv, owner, &uid).await? {
+            if stored.draft.status == DraftStatus::Publishing {
+                return Err(ServerFnError::new("Draft is being published"));
+            }
+            // Emptied first so that the draft can't be claimed for publishing meanwhile,
+            // a pending scheduled publish then finds no draft and does nothing
+            let emptied = kv
+                .compare_and_swap(draft_key(owner, &uid), Some(raw), String::new())
+                .await?;
+            if !emptied {
+                return Err(ServerFnError::new("Draft was changed meanwhile, try again"));
+            }
+        }
+        kv.delete(draft_key(owner, &uid)).await?;
+        kv.index_remove(drafts_index(owner), uid).await?;
+
+        Ok(())
+    }
+
+    pub async fn schedule_draft(
+        cans_wire: CanistersAuthWire,
+        uid: String,
+        publish_at_secs: Option<u64>,
+    ) -> Result<Draft, ServerFnError> {
+        let cans = cans_wire.canisters()?;
+        let owner = cans.user_principal();
+        let kv: KVStoreImpl = expect_context();
+
+        let Some(at_secs) = publish_at_secs else {
+            let stored = update_draft(&kv, owner, &uid, |current| {
+                let mut stored = editable(current)?;
+                stored.draft.status = DraftStatus::Draft;
+                stored.sealed_publisher = None;
+                Ok(stored)
+            })
+            .await?;
+            return Ok(stored.draft);
+        };
+
+        if at_secs <= current_epoch().as_secs() {
+            return Err(ServerFnError::Args(
+                "Publish time must be in the future".into(),
+            ));
+        }
+        if at_secs > (current_epoch() + MAX_SCHEDULE_AHEAD).as_secs() {
+            return Err(ServerFnError::Args(
+                "Drafts can be scheduled at most 3 days ahead".into(),
+            ));
+        }
+        // The publishing delegation is bounded by the login session
+        let publish_deadline = Duration::from_secs(at_secs) + PUBLISH_GRACE;
+        if publish_deadline.as_nanos() >= u128::from(cans.expiry_ns()) {
+            return Err(ServerFnError::Args(
+                "Publish time is past your login session, pick an earlier time".into(),
+            ));
+        }
+
+        let publisher = scoped_publisher(&cans, at_secs);
+        let sealed_publisher = seal_publisher(&publisher)?;
+        let stored = update_draft(&kv, owner, &uid, |current| {
+            let mut stored = editable(current)?;
+            stored.draft.status = DraftStatus::Scheduled { at_secs };
+            stored.sealed_publisher = Some(sealed_publisher.clone());
+            Ok(stored)
+        })
+        .await?;
+        enqueue_publish(owner, uid, at_secs, publisher.canisters()?).await?;
+
+        Ok(stored.draft)
+    }
+
+    /// Publish the draft if its schedule is due
+    /// the status is moved to `Publishing` atomically, so concurrent calls publish once
+    async fn publish_if_due(
+        kv: &KVStoreImpl,
+        owner: Principal,
+        uid: &str,
+        cans: Canisters<true>,
+    ) -> Result<(), ServerFnError> {
+        let Some((raw, mut stored)) = load_draft(kv, owner, uid).await? else {
+            return Ok(());
+        };
+        let DraftStatus::Scheduled { at_secs } = stored.draft.status else {
+            return Ok(());
+        };
+        if at_secs > current_epoch().as_secs() {
+            return Ok(());
+        }
+
+        stored.draft.status = DraftStatus::Publishing;
+        let claimed_raw = serde_json::to_string(&stored)?;
+        let claimed = kv
+            .compare_and_swap(draft_key(owner, uid), Some(raw), claimed_raw.clone())
+            .await?;
+        if !claimed {
+            return Ok(());
+        }
+
+        let draft = stored.draft.clone();
+        let res = publish_video(
+            cans.clone(),
+            draft.hashtags,
+            draft.description,
+            draft.uid,
+            draft.enable_hot_or_not,
+            draft.is_nsfw,
+        )
+        .await;
+        stored.draft.status = match &res {
+            Ok(post_id) => DraftStatus::Published { post_id: *post_id },
+            Err(e) => DraftStatus::Failed {
+                reason: e.to_string(),
+            },
+        };
+        // The identity is no longer needed once the publish ran
+        stored.sealed_publisher = None;
+        // A `Publishing` draft can't be edited or deleted, nothing else writes it meanwhile
+        let stored_result = kv
+            .compare_and_swap(
+                draft_key(owner, uid),
+                Some(claimed_raw),
+                serde_json::to_string(&stored)?,
+            )
+            .await?;
+        if !stored_result {
+            log::warn!("draft {uid} changed while being published");
+        }
+
+        let post_id = res?;
+        if let Err(e) = record_post_hashtags(kv, &cans, post_id).await {
+            log::warn!("failed to count hashtags of post {post_id}: {e}");
+        }
+        if let Err(e) = notify_followers(kv, &cans, post_id).await {
+            log::warn!("failed to notify followers of post {post_id}: {e}");
+        }
+
+        Ok(())
+    }
+
+    pub async fn publish_scheduled_draft(
+        owner: Principal,
+        uid: String,
+    ) -> Result<(), ServerFnError> {
+        let kv: KVStoreImpl = expect_context();
+        let Some((_, stored)) = load_draft(&kv, owner, &uid).await? else {
+            return Ok(());
+        };
+        let Some(sealed) = stored.sealed_publisher else {
+            return Ok(());
+        };
+        let publisher = unseal_publisher(sealed)?;
+
+        publish_if_due(&kv, owner, &uid, publisher.canisters()?).await
+    }
+
+    #[cfg(feature = "qstash")]
+    async fn enqueue_publish(
+        owner: Principal,
+        uid: String,
+        at_secs: u64,
+        _cans: Canisters<true>,
+    ) -> Result<(), ServerFnError> {
+        use leptos::server_fn::ServerFn;
+        use reqwest::Url;
+
+        use crate::{
+            page::upload::drafts::PublishScheduledDraft,
+            utils::{host::get_host, qstash::QStashClient},
+        };
+
+        let callback = Url::parse(&format!(
+            "https://{}{}",
+            get_host(),
+            PublishScheduledDraft::PATH
+        ))?;
+        let client: QStashClient = expect_context();
+        client
+            .enqueue_draft_publish(callback, &PublishScheduledDraft { owner, uid }, at_secs)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Without QStash the publish runs from a timer in this process
+    /// and is lost if the server restarts before it is due
+    #[cfg(not(feature = "qstash"))]
+    async fn enqueue_publish(
+        owner: Principal,
+        uid: String,
+        at_secs: u64,
+        cans: Canisters<true>,
+    ) -> Result<(), ServerFnError> {
+        let kv: KVStoreImpl = expect_context();
+        let delay = Duration::from_secs(at_secs.saturating_sub(current_epoch().as_secs()));
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            if let Err(e) = publish_if_due(&kv, owner, &uid, cans).await {
+                log::error!("scheduled publish of draft {uid} failed: {e:?}");
+            }
+        });
+
+        Ok(())
+    }
+}
@@ -1,13 +1,20 @@
 mod cf_upload;
+pub mod drafts;
+pub mod tus;
 mod validators;
 mod video_upload;
 
+#[cfg(all(not(feature = "cloudflare"), feature = "ssr"))]
+pub use cf_upload::mock_tus_router;
+
 use crate::{
     component::toggle::ToggleWithLabel,
     state::canisters::auth_canisters_store,
     utils::{
         event_streaming::events::{VideoUploadInitiated, VideoUploadUploadButtonClicked},
+        hashtags::{complete_hashtag, merge_hashtags, partial_hashtag, suggest_hashtags},
         host::show_cdao_page,
+        time::parse_local_datetime_to_secs,
         web::FileWithUrl,
     },
 };
@@ -18,37 +25,65 @@ use leptos::{
 };
 
 use leptos_router::Redirect;
-use validators::{description_validator, hashtags_validator};
+use leptos_use::signal_debounced;
+use validators::{description_validator, hashtags_validator, VideoMetadata};
 use video_upload::{PreVideoUpload, VideoUploader};
 
+/// What happens once the upload is processed
+#[derive(Clone, Copy, PartialEq)]
+enum PublishMode {
+    Now,
+    Draft,
+    /// Publish at the given unix seconds
+    At(u64),
+}
+
 #[derive(Clone)]
 struct UploadParams {
     file_blob: FileWithUrl,
+    video_meta: VideoMetadata,
     hashtags: Vec<String>,
     description: String,
     enable_hot_or_not: bool,
     is_nsfw: bool,
+    publish_mode: PublishMode,
 }
 
 #[component]
 fn PreUploadView(trigger_upload: WriteSignal<Option<UploadParams>>) -> impl IntoView {
     let description_err = create_rw_signal(String::new());
     let desc_err_memo = create_memo(move |_| description_err());
+    let description = create_rw_signal(String::new());
     let hashtags = create_rw_signal(Vec::new());
     let hashtags_err = create_rw_signal(String::new());
-    let hashtags_err_memo = create_memo(move |_| hashtags_err());
+    // Typed tags followed by the inline tags of the description
+    let post_tags =
+        create_memo(move |_| with!(|hashtags, description| merge_hashtags(hashtags, description)));
+    let hashtags_err_memo = create_memo(move |_| {
+        let typed_err = hashtags_err();
+        if !typed_err.is_empty() {
+            return typed_err;
+        }
+        post_tags.with(|tags| {
+            tags.as_ref()
+                .err()
+                .map(|e| e.to_string())
+                .unwrap_or_default()
+        })
+    });
     let file_blob = create_rw_signal(None::<FileWithUrl>);
+    let video_meta = create_rw_signal(None::<VideoMetadata>);
     let desc = create_node_ref::<Textarea>();
     let invalid_form = create_memo(move |_| {
-        with!(|desc_err_memo, hashtags_err_memo, file_blob, hashtags| {
+        with!(|desc_err_memo, hashtags_err_memo, file_blob, post_tags| {
             // Description error
             !desc_err_memo.is_empty()
                 // Hashtags error
                 || !hashtags_err_memo.is_empty()
                 // File is not uploaded
                 || file_blob.is_none()
-                // Hashtags are empty
-                || hashtags.is_empty()
+                // No valid hashtags, typed or inline
+                || !post_tags.as_ref().is_ok_and(|tags| !tags.is_empty())
                 // Description is empty
                 || desc().map(|d| d.value().is_empty()).unwrap_or(true)
         })
@@ -56,6 +91,13 @@ fn PreUploadView(trigger_upload: WriteSignal<Option<UploadParams>>) -> impl Into
     let hashtag_inp = create_node_ref::<Input>();
     let enable_hot_or_not = create_node_ref::<Input>();
     let is_nsfw = create_node_ref::<Input>();
+    let save_as_draft = create_rw_signal(false);
+    let publish_at = create_rw_signal(None::<u64>);
+    let submit_text = move || match (save_as_draft(), publish_at()) {
+        (false, _) => "Upload Video",
+        (true, None) => "Save Draft",
+        (true, Some(_)) => "Schedule Video",
+    };
     let canister_store = auth_canisters_store();
 
     VideoUploadInitiated.send_event();
@@ -69,12 +111,23 @@ fn PreUploadView(trigger_upload: WriteSignal<Option<UploadParams>>) -> impl Into
         );
 
         let description = desc.get_untracked().unwrap().value();
-        let hashtags = hashtags.get_untracked();
+        let Ok(hashtags) = post_tags.get_untracked() else {
+            return;
+        };
         let Some(file_blob) = file_blob.get_untracked() else {
             return;
         };
+        let Some(video_meta) = video_meta.get_untracked() else {
+            return;
+        };
+        let publish_mode = match (save_as_draft.get_untracked(), publish_at.get_untracked()) {
+            (false, _) => PublishMode::Now,
+            (true, None) => PublishMode::Draft,
+            (true, Some(at)) => PublishMode::At(at),
+        };
         trigger_upload.set(Some(UploadParams {
             file_blob,
+            video_meta,
             hashtags,
             description,
             enable_hot_or_not: false,
@@ -82,6 +135,7 @@ fn PreUploadView(trigger_upload: WriteSignal<Option<UploadParams>>) -> impl Into
                 .get_untracked()
                 .map(|v| v.checked())
                 .unwrap_or_default(),
+            publish_mode,
         }));
     };
 
@@ -93,6 +147,29 @@ fn PreUploadView(trigger_upload: WriteSignal<Option<UploadParams>>) -> impl Into
         Err(e) => hashtags_err.set(e),
     };
 
+    let typed_hashtags = create_rw_signal(String::new());
+    let partial_tag = create_memo(move |_| typed_hashtags.with(|hts| partial_hashtag(hts)));
+    let partial_tag = signal_debounced(partial_tag, 300.0);
+    let suggestions = create_local_resource(
+        move || partial_tag.get(),
+        |partial| async move {
+            let Some(partial) = partial else {
+                return vec![];
+            };
+            suggest_hashtags(partial).await.unwrap_or_default()
+        },
+    );
+    let pick_suggestion = move |tag: String| {
+        let Some(inp) = hashtag_inp.get_untracked() else {
+            return;
+        };
+        let hts = complete_hashtag(&inp.value(), &tag);
+        inp.set_value(&hts);
+        typed_hashtags.set(hts.clone());
+        hashtag_on_input(hts);
+        _ = inp.focus();
+    };
+
     create_effect(move |_| {
         let Some(hashtag_inp) = hashtag_inp() else {
             return;
@@ -105,7 +182,7 @@ fn PreUploadView(trigger_upload: WriteSignal<Option<UploadParams>>) -> impl Into
     });
 
     view! {
-        <PreVideoUpload file_blob=file_blob.write_only() />
+        <PreVideoUpload file_blob=file_blob.write_only() video_meta=video_meta.write_only() />
         <div class="flex flex-col gap-4 lg:basis-7/12">
             <div class="flex flex-col gap-y-2">
                 <Show when=move || { with!(| description_err | ! description_err.is_empty()) }>
@@ -115,6 +192,7 @@ fn PreUploadView(trigger_upload: WriteSignal<Option<UploadParams>>) -> impl Into
                     _ref=desc
                     on:input=move |ev| {
                         let desc = event_target_value(&ev);
+                        description.set(desc.clone());
                         description_err.set(description_validator(desc).err().unwrap_or_default());
                     }
 
@@ -125,18 +203,19 @@ fn PreUploadView(trigger_upload: WriteSignal<Option<UploadParams>>) -> impl Into
             </div>
             <div class="flex flex-col gap-y-2">
                 <Show
-                    when=move || { with!(| hashtags_err | ! hashtags_err.is_empty()) }
+                    when=move || { with!(| hashtags_err_memo | ! hashtags_err_memo.is_empty()) }
                     fallback=|| {
                         view! { <h3 class="font-semibold text-neutral-600">Add Hashtags</h3> }
                     }
                 >
 
-                    <h3 class="text-red-500 font-semibold">{hashtags_err_memo()}</h3>
+                    <h3 class="text-red-500 font-semibold">{hashtags_err_memo}</h3>
                 </Show>
                 <input
                     _ref=hashtag_inp
                     on:input=move |ev| {
                         let hts = event_target_value(&ev);
+                        typed_hashtags.set(hts.clone());
                         hashtag_on_input(hts);
                     }
 
@@ -144,17 +223,59 @@ fn PreUploadView(trigger_upload: WriteSignal<Option<UploadParams>>) -> impl Into
                     type="text"
                     placeholder="#hashtag1,#hashtag2,#hashtag3..."
                 />
+                <Suspense>
+                    <div class="flex flex-row flex-wrap gap-2">
+                        {move || {
+                            suggestions()
+                                .unwrap_or_default()
+                                .into_iter()
+                                .filter(|tag| with!(| hashtags | ! hashtags.contains(tag)))
+                                .map(|tag| {
+                                    let label = format!("#{tag}");
+                                    view! {
+                                        <button
+                                            class="px-3 py-1 rounded-full bg-neutral-800 text-sm text-neutral-300"
+                                            on:click=move |_| pick_suggestion(tag.clone())
+                                        >
+                                            {label}
+                                        </button>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                    </div>
+                </Suspense>
             </div>
             <div class="flex flex-col gap-y-2">
                 // <ToggleWithLabel node_ref=enable_hot_or_not lab="Participate in Hot or Not"/>
                 <ToggleWithLabel lab="NSFW" />
+                <label class="flex flex-row items-center gap-2 text-md font-medium text-gray-300">
+                    <input
+                        type="checkbox"
+                        class="accent-primary-600"
+                        on:change=move |ev| save_as_draft.set(event_target_checked(&ev))
+                    />
+                    Save as draft
+                </label>
+                <Show when=save_as_draft>
+                    <label class="flex flex-col gap-1 text-sm text-neutral-400">
+                        "Publish at, up to 3 days ahead (optional)"
+                        <input
+                            type="datetime-local"
+                            class="p-3 bg-neutral-800 rounded-md text-white"
+                            on:input=move |ev| {
+                                publish_at.set(parse_local_datetime_to_secs(&event_target_value(&ev)))
+                            }
+                        />
+                    </label>
+                </Show>
             </div>
             <button
                 on:click=move |_| on_submit()
                 disabled=invalid_form
                 class="py-3 w-5/6 md:w-4/6 my-8 self-center disabled:bg-primary-400 disabled:text-white/80 bg-primary-600 rounded-full font-bold text-md md:text-lg lg:text-xl"
             >
-                Upload Video
+                {submit_text}
             </button>
         </div>
     }
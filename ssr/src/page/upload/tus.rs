@@ -0,0 +1,191 @@
+//! Minimal tus 1.0 client for resumable, chunked uploads
+//! <https://tus.io/protocols/resumable-upload>
+//!
+//! The upload is created by the server (see [super::cf_upload::get_upload_info]),
+//! the browser only resolves the current offset with `HEAD` and sends chunks with `PATCH`.
+
+use serde::{Deserialize, Serialize};
+
+use super::cf_upload::UploadInfo;
+
+/// Cloudflare requires chunks to be a multiple of 256KiB and at least 5MiB
+pub const TUS_CHUNK_SIZE: u64 = 5 * 1024 * 1024;
+
+#[derive(Debug, thiserror::Error)]
+pub enum TusError {
+    #[error("network error: {0}")]
+    Network(#[from] gloo::net::Error),
+    #[error("upload server responded with status {0}")]
+    Status(u16),
+    #[error("upload no longer exists on the server")]
+    Gone,
+    #[error("upload server did not report a valid offset")]
+    InvalidOffset,
+    /// The upload was created for a file of another size, the server would refuse the rest
+    #[error("the file is {file} bytes but the upload expects {upload} bytes")]
+    SizeMismatch { file: u64, upload: u64 },
+}
+
+/// Identifies a local file across page reloads
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub struct FileFingerprint {
+    pub name: String,
+    pub size: u64,
+    pub last_modified_ms: u64,
+}
+
+impl FileFingerprint {
+    pub fn new(file: &gloo::file::File) -> Self {
+        let last_modified_ms = file
+            .last_modified_time()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+        Self {
+            name: file.name(),
+            size: file.size(),
+            last_modified_ms,
+        }
+    }
+}
+
+/// An upload which was started but not completed, persisted in local storage
+/// so that selecting the same file after a reload resumes it
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct PendingUpload {
+    pub file: FileFingerprint,
+    pub upload: UploadInfo,
+}
+
+#[cfg(feature = "hydrate")]
+mod client {
+    use gloo::{
+        net::http::{Method, RequestBuilder, Response},
+        timers::future::TimeoutFuture,
+    };
+
+    use super::*;
+
+    const TUS_VERSION: &str = "1.0.0";
+    /// Attempts per chunk before the upload is reported as failed
+    const MAX_CHUNK_ATTEMPTS: u32 = 5;
+
+    impl TusError {
+        fn is_retryable(&self) -> bool {
+            match self {
+                Self::Network(_) | Self::InvalidOffset => true,
+                Self::Status(status) => *status >= 500 || *status == 409 || *status == 423,
+                Self::Gone | Self::SizeMismatch { .. } => false,
+            }
+        }
+    }
+
+    fn check_status(res: &Response) -> Result<(), TusError> {
+        match res.status() {
+            200..=299 => Ok(()),
+            404 | 410 => Err(TusError::Gone),
+            status => Err(TusError::Status(status)),
+        }
+    }
+
+    fn upload_offset(res: &Response) -> Result<u64, TusError> {
+        res.headers()
+            .get("Upload-Offset")
+            .and_then(|o| o.parse().ok())
+            .ok_or(TusError::InvalidOffset)
+    }
+
+    async fn head(upload_url: &str) -> Result<Response, TusError> {
+        let res = RequestBuilder::new(upload_url)
+            .method(Method::HEAD)
+            .header("Tus-Resumable", TUS_VERSION)
+            .send()
+            .await?;
+        check_status(&res)?;
+        Ok(res)
+    }
+
+    pub async fn current_offset(upload_url: &str) -> Result<u64, TusError> {
+        upload_offset(&head(upload_url).await?)
+    }
+
+    async fn send_chunk(
+        upload_url: &str,
+        file: &gloo::file::File,
+        offset: u64,
+    ) -> Result<u64, TusError> {
+        let end = (offset + TUS_CHUNK_SIZE).min(file.size());
+        let chunk = web_sys::Blob::from(file.slice(offset, end));
+        let res = RequestBuilder::new(upload_url)
+            .method(Method::PATCH)
+            .header("Tus-Resumable", TUS_VERSION)
+            .header("Upload-Offset", &offset.to_string())
+            .header("Content-Type", "application/offset+octet-stream")
+            .body(chunk)?
+            .send()
+            .await?;
+        check_status(&res)?;
+        upload_offset(&res)
+    }
+
+    pub async fn upload_file(
+        upload_url: &str,
+        file: &gloo::file::File,
+        on_progress: impl Fn(u64, u64),
+    ) -> Result<(), TusError> {
+        let size = file.size();
+        let status = head(upload_url).await?;
+        let upload_length = status
+            .headers()
+            .get("Upload-Length")
+            .and_then(|l| l.parse().ok());
+        if let Some(upload) = upload_length.filter(|&upload| upload != size) {
+            return Err(TusError::SizeMismatch { file: size, upload });
+        }
+        let mut offset = upload_offset(&status)?;
+        on_progress(offset, size);
+
+        let mut attempt = 0;
+        while offset < size {
+            match send_chunk(upload_url, file, offset).await {
+                Ok(new_offset) => {
+                    attempt = 0;
+                    offset = new_offset;
+                    on_progress(offset, size);
+                }
+                Err(e) if e.is_retryable() && attempt + 1 < MAX_CHUNK_ATTEMPTS => {
+                    attempt += 1;
+                    log::warn!("tus chunk at {offset} failed (attempt {attempt}): {e}");
+                    TimeoutFuture::new(1000 * 2u32.pow(attempt)).await;
+                    // The chunk may have been partially accepted, resync with the server
+                    if let Ok(server_offset) = current_offset(upload_url).await {
+                        offset = server_offset;
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Upload (or resume uploading) `file` to the tus endpoint in `upload_info`
+/// `on_progress` is called with `(uploaded_bytes, total_bytes)` after every chunk
+pub async fn upload_video_stream(
+    upload_info: &UploadInfo,
+    file: &gloo::file::File,
+    on_progress: impl Fn(u64, u64),
+) -> Result<(), TusError> {
+    #[cfg(feature = "hydrate")]
+    {
+        client::upload_file(&upload_info.upload_url, file, on_progress).await
+    }
+    #[cfg(not(feature = "hydrate"))]
+    {
+        _ = upload_info;
+        _ = file;
+        _ = on_progress;
+        Ok(())
+    }
+}
@@ -1,3 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::hashtags::{parse_hashtags, HashtagError, MAX_HASHTAGS};
+
 pub fn description_validator(desc: String) -> Result<(), String> {
     if desc.is_empty() {
         return Err("Description is required".into());
@@ -8,26 +12,121 @@ pub fn description_validator(desc: String) -> Result<(), String> {
     Ok(())
 }
 
+/// Tags typed in the hashtag input, it may be empty
+/// as the description can carry inline `#tags`
 pub fn hashtags_validator(hashtags: String) -> Result<Vec<String>, String> {
-    if hashtags.is_empty() {
-        return Err("Hashtags are required".into());
+    let hashtags = parse_hashtags(&hashtags).map_err(|e| e.to_string())?;
+    if hashtags.len() > MAX_HASHTAGS {
+        return Err(HashtagError::TooMany.to_string());
+    }
+
+    Ok(hashtags)
+}
+
+/// Longest accepted video
+pub const MAX_VIDEO_DURATION_SECS: u64 = 60;
+/// Largest accepted video file
+pub const MAX_VIDEO_SIZE_BYTES: u64 = 500 * 1024 * 1024;
+/// Shortest accepted side of the video frame, in pixels
+pub const MIN_VIDEO_SIDE_PX: u32 = 240;
+/// Longest accepted side of the video frame, in pixels (4K)
+pub const MAX_VIDEO_SIDE_PX: u32 = 4096;
+/// Width / height above which a video is not portrait and will be letterboxed in the feed
+pub const MAX_PORTRAIT_ASPECT_RATIO: f64 = 0.8;
+/// Containers accepted by Cloudflare Stream
+/// codecs are not inspected, Cloudflare fails the videos it can't transcode
+pub const ALLOWED_VIDEO_MIME_TYPES: &[&str] = &[
+    "video/mp4",
+    "video/quicktime",
+    "video/webm",
+    "video/x-matroska",
+    "video/x-m4v",
+];
+
+/// Guess the mime type of a video from its file name
+/// used when the browser does not report one
+pub fn video_mime_from_file_name(name: &str) -> Option<&'static str> {
+    let (_, ext) = name.rsplit_once('.')?;
+    Some(match ext.to_ascii_lowercase().as_str() {
+        "mp4" => "video/mp4",
+        "mov" => "video/quicktime",
+        "webm" => "video/webm",
+        "mkv" => "video/x-matroska",
+        "m4v" => "video/x-m4v",
+        _ => return None,
+    })
+}
+
+/// Metadata of the selected video, as probed by the browser
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub struct VideoMetadata {
+    pub mime_type: String,
+    pub size: u64,
+    /// None if the browser could not determine the duration
+    pub duration_secs: Option<f64>,
+    /// 0 if the browser could not decode the video track
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Check a video against the upload limits
+/// returns warnings for videos that are accepted but may not look as expected
+///
+/// `video` is reported by the client, this only rejects videos early.
+/// The size and duration limits are enforced by Cloudflare, see `get_upload_info_impl`
+pub fn video_preflight(video: &VideoMetadata) -> Result<Vec<String>, String> {
+    let mut warnings = vec![];
+
+    if !ALLOWED_VIDEO_MIME_TYPES.contains(&video.mime_type.as_str()) {
+        return Err("Unsupported video format, please upload an MP4, MOV, WebM or MKV file".into());
+    }
+    if video.size == 0 {
+        return Err("The selected file is empty".into());
+    }
+    if video.size > MAX_VIDEO_SIZE_BYTES {
+        return Err(format!(
+            "Video must be smaller than {}MB",
+            MAX_VIDEO_SIZE_BYTES / (1024 * 1024)
+        ));
     }
 
-    let hashtags: Vec<_> = hashtags
-        .split(',')
-        .filter_map(|s| {
-            let ht = s.trim().replace('#', "");
-            if ht.is_empty() {
-                None
-            } else {
-                Some(ht)
-            }
-        })
-        .collect();
+    match video.duration_secs {
+        Some(duration) if duration > MAX_VIDEO_DURATION_SECS as f64 => {
+            return Err(format!(
+                "Please ensure that the video is shorter than {MAX_VIDEO_DURATION_SECS} seconds"
+            ));
+        }
+        Some(_) => (),
+        None => warnings.push(format!(
+            "Could not determine the video duration, videos longer than {MAX_VIDEO_DURATION_SECS} seconds will be rejected"
+        )),
+    }
 
-    if hashtags.len() > 8 {
-        return Err("Only a maximum of 8 hashtags are allowed".into());
+    if video.width == 0 || video.height == 0 {
+        warnings.push(
+            "Your browser can't play this video, it may use a codec that is not widely supported"
+                .into(),
+        );
+        return Ok(warnings);
+    }
+    let (short_side, long_side) = if video.width < video.height {
+        (video.width, video.height)
+    } else {
+        (video.height, video.width)
+    };
+    if short_side < MIN_VIDEO_SIDE_PX {
+        return Err(format!(
+            "Video resolution is too low, the shorter side must be at least {MIN_VIDEO_SIDE_PX}px"
+        ));
+    }
+    if long_side > MAX_VIDEO_SIDE_PX {
+        return Err(format!(
+            "Video resolution is too high, the longer side must be at most {MAX_VIDEO_SIDE_PX}px"
+        ));
+    }
+    if video.width as f64 / video.height as f64 > MAX_PORTRAIT_ASPECT_RATIO {
+        warnings.push("Video is not in portrait, it will be letterboxed in the feed".into());
     }
 
-    Ok(hashtags)
+    Ok(warnings)
 }
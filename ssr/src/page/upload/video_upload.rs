@@ -1,30 +1,39 @@
 use super::{
-    cf_upload::{get_upload_info, get_video_status, publish_video, upload_video_stream},
-    UploadParams,
+    cf_upload::{get_upload_info, get_video_status, publish_video},
+    drafts::{save_draft, schedule_draft},
+    tus::{upload_video_stream, FileFingerprint, PendingUpload, TusError},
+    validators::{
+        video_mime_from_file_name, video_preflight, VideoMetadata, MAX_VIDEO_DURATION_SECS,
+    },
+    PublishMode, UploadParams,
 };
 use crate::{
     component::modal::Modal,
-    state::canisters::{auth_canisters_store, authenticated_canisters, Canisters},
+    consts::PENDING_VIDEO_UPLOAD_STORE,
+    state::canisters::{auth_canisters_store, authenticated_canisters, CanistersAuthWire},
     try_or_redirect_opt,
     utils::{
         event_streaming::events::{
             VideoUploadSuccessful, VideoUploadUnsuccessful, VideoUploadVideoSelected,
         },
+        hashtags::record_post_hashtags,
+        notifications::inbox::notify_followers_of_post,
         route::go_to_root,
         web::FileWithUrl,
         MockPartialEq,
     },
 };
+use codee::string::JsonSerdeCodec;
 use futures::StreamExt;
 use gloo::timers::future::IntervalStream;
 use ic_agent::Identity;
 use leptos::{
-    ev::durationchange,
+    ev::{error, loadedmetadata},
     html::{Input, Video},
     *,
 };
 use leptos_icons::*;
-use leptos_use::use_event_listener;
+use leptos_use::{storage::use_local_storage, use_event_listener};
 use web_time::SystemTime;
 
 #[component]
@@ -36,17 +45,28 @@ pub fn DropBox() -> impl IntoView {
                 <span class="font-semibold">Click to upload</span>
                 or drag and drop
             </p>
-            <p class="text-xs text-gray-400">Video File (Max 60s)</p>
+            <p class="text-xs text-gray-400">
+                {format!("Video File (Max {MAX_VIDEO_DURATION_SECS}s)")}
+            </p>
         </div>
     }
 }
 
+fn default_upload_hint() -> String {
+    format!("Please ensure that the video is shorter than {MAX_VIDEO_DURATION_SECS} seconds")
+}
+
 #[component]
-pub fn PreVideoUpload(file_blob: WriteSignal<Option<FileWithUrl>>) -> impl IntoView {
+pub fn PreVideoUpload(
+    file_blob: WriteSignal<Option<FileWithUrl>>,
+    video_meta: WriteSignal<Option<VideoMetadata>>,
+) -> impl IntoView {
     let file_ref = create_node_ref::<Input>();
     let file = create_rw_signal(None::<FileWithUrl>);
     let video_ref = create_node_ref::<Video>();
     let modal_show = create_rw_signal(false);
+    let modal_text = create_rw_signal(default_upload_hint());
+    let preflight_warnings = create_rw_signal(Vec::<String>::new());
     let canister_store = auth_canisters_store();
 
     #[cfg(feature = "hydrate")]
@@ -66,29 +86,55 @@ pub fn PreVideoUpload(file_blob: WriteSignal<Option<FileWithUrl>>) -> impl IntoV
         });
     }
 
-    _ = use_event_listener(video_ref, durationchange, move |_| {
-        let duration = video_ref
-            .get_untracked()
-            .map(|v| v.duration())
-            .unwrap_or_default();
+    // Probe the selected file once the browser has loaded its metadata (or failed to)
+    let preflight = move || {
         let Some(vid_file) = file.get_untracked() else {
             return;
         };
-        if duration <= 60.0 || duration.is_nan() {
-            modal_show.set(false);
-            file_blob.set(Some(vid_file));
-            return;
-        }
+        let (duration, width, height) = video_ref
+            .get_untracked()
+            .map(|v| (v.duration(), v.video_width(), v.video_height()))
+            .unwrap_or_default();
+        let raw_mime = vid_file.file.raw_mime_type();
+        let mime_type = if raw_mime.is_empty() {
+            video_mime_from_file_name(&vid_file.file.name())
+                .unwrap_or_default()
+                .to_string()
+        } else {
+            raw_mime
+        };
+        let meta = VideoMetadata {
+            mime_type,
+            size: vid_file.file.size(),
+            duration_secs: duration.is_finite().then_some(duration),
+            width,
+            height,
+        };
 
-        batch(|| {
-            modal_show.set(true);
-            file.set(None);
-            file_blob.set(None);
-        });
-        if let Some(f) = file_ref.get_untracked() {
-            f.set_value("");
+        match video_preflight(&meta) {
+            Ok(warnings) => batch(|| {
+                modal_show.set(false);
+                preflight_warnings.set(warnings);
+                video_meta.set(Some(meta));
+                file_blob.set(Some(vid_file));
+            }),
+            Err(e) => {
+                batch(|| {
+                    modal_text.set(e);
+                    modal_show.set(true);
+                    preflight_warnings.set(vec![]);
+                    file.set(None);
+                    file_blob.set(None);
+                    video_meta.set(None);
+                });
+                if let Some(f) = file_ref.get_untracked() {
+                    f.set_value("");
+                }
+            }
         }
-    });
+    };
+    _ = use_event_listener(video_ref, loadedmetadata, move |_| preflight());
+    _ = use_event_listener(video_ref, error, move |_| preflight());
 
     view! {
         <div class="flex items-center self-center justify-center w-3/4 mb-8 lg:mb-0 lg:pb-12 lg:w-1/2 lg:max-h-full lg:px-8">
@@ -112,8 +158,16 @@ pub fn PreVideoUpload(file_blob: WriteSignal<Option<FileWithUrl>>) -> impl IntoV
                         with!(| file | file.as_ref().map(| _ | "block").unwrap_or("none"))
                     }
                 ></video>
+                <For
+                    each=preflight_warnings
+                    key=|w| w.clone()
+                    children=|w| view! { <span class="text-yellow-500 text-sm py-1">{w}</span> }
+                />
                 <input
-                    on:click=move |_| modal_show.set(true)
+                    on:click=move |_| {
+                        modal_text.set(default_upload_hint());
+                        modal_show.set(true);
+                    }
                     id="dropzone-file"
                     _ref=file_ref
                     type="file"
@@ -124,7 +178,7 @@ pub fn PreVideoUpload(file_blob: WriteSignal<Option<FileWithUrl>>) -> impl IntoV
         </div>
         <Modal show=modal_show>
             <span class="text-lg md:text-xl text-white h-full items-center py-10 text-center w-full flex flex-col justify-center">
-                Please ensure that the video is shorter than 60 seconds
+                {modal_text}
             </span>
         </Modal>
     }
@@ -135,7 +189,15 @@ pub fn ProgressItem(
     #[prop(into)] initial_text: String,
     #[prop(into)] done_text: String,
     #[prop(into)] loading: Signal<bool>,
+    /// fraction of work done, shown next to `initial_text` while loading
+    #[prop(optional, into)]
+    progress: Option<Signal<f64>>,
 ) -> impl IntoView {
+    let loading_text = move || match progress {
+        Some(progress) => format!("{initial_text} {:.0}%", progress() * 100.0),
+        None => initial_text.clone(),
+    };
+
     view! {
         <Show
             when=loading
@@ -148,7 +210,7 @@ pub fn ProgressItem(
         >
 
             <Icon class="w-10 h-10 text-primary-600 animate-spin" icon=icondata::CgSpinnerTwo />
-            <span class="text-white text-lg font-semibold">{initial_text.clone()}</span>
+            <span class="text-white text-lg font-semibold">{loading_text.clone()}</span>
         </Show>
     }
 }
@@ -160,6 +222,7 @@ pub fn VideoUploader(params: UploadParams) -> impl IntoView {
     let description = params.description;
 
     let uploading = create_rw_signal(true);
+    let upload_progress = create_rw_signal(0.0);
     let processing = create_rw_signal(true);
     let publishing = create_rw_signal(true);
     let video_url = file_blob.url;
@@ -172,6 +235,9 @@ pub fn VideoUploader(params: UploadParams) -> impl IntoView {
     let canister_store = auth_canisters_store();
 
     let up_desc = description.clone();
+    let video_meta = params.video_meta;
+    let (pending_upload, set_pending_upload, _) =
+        use_local_storage::<Option<PendingUpload>, JsonSerdeCodec>(PENDING_VIDEO_UPLOAD_STORE);
 
     let upload_action = create_local_resource(
         move || canister_store().map(MockPartialEq),
@@ -179,6 +245,7 @@ pub fn VideoUploader(params: UploadParams) -> impl IntoView {
             let hashtags = up_hashtags.clone();
             let description = up_desc.clone();
             let file_blob = file_blob.clone();
+            let video_meta = video_meta.clone();
             async move {
                 let cans = cans?.0;
                 let creator_principal = cans.identity().sender().unwrap();
@@ -187,30 +254,54 @@ pub fn VideoUploader(params: UploadParams) -> impl IntoView {
                     .unwrap()
                     .as_millis();
 
-                // TODO: authenticated call
-                let res = get_upload_info(
-                    creator_principal,
-                    hashtags,
-                    description,
-                    time_ms.to_string(),
-                )
-                .await;
+                let fingerprint = FileFingerprint::new(&file_blob);
+                // Resume an interrupted upload of the same file
+                let resumable = pending_upload
+                    .get_untracked()
+                    .filter(|pending| pending.file == fingerprint);
+                let upload_info = if let Some(pending) = resumable {
+                    pending.upload
+                } else {
+                    // TODO: authenticated call
+                    let res = get_upload_info(
+                        creator_principal,
+                        hashtags,
+                        description,
+                        time_ms.to_string(),
+                        video_meta.clone(),
+                    )
+                    .await;
 
-                if res.is_err() {
-                    let e = res.as_ref().err().unwrap().to_string();
-                    VideoUploadUnsuccessful.send_event(
-                        e,
-                        hashtags_len,
-                        is_nsfw,
-                        enable_hot_or_not,
-                        canister_store,
-                    );
-                }
-
-                let upload_info = try_or_redirect_opt!(res);
+                    if res.is_err() {
+                        let e = res.as_ref().err().unwrap().to_string();
+                        VideoUploadUnsuccessful.send_event(
+                            e,
+                            hashtags_len,
+                            is_nsfw,
+                            enable_hot_or_not,
+                            canister_store,
+                        );
+                    }
 
-                let res = upload_video_stream(&upload_info, &file_blob).await;
+                    let upload_info = try_or_redirect_opt!(res);
+                    set_pending_upload(Some(PendingUpload {
+                        file: fingerprint,
+                        upload: upload_info.clone(),
+                    }));
+                    upload_info
+                };
+
+                let res = upload_video_stream(&upload_info, &file_blob, |uploaded, total| {
+                    if total > 0 {
+                        upload_progress.set(uploaded as f64 / total as f64);
+                    }
+                })
+                .await;
 
+                if let Err(TusError::Gone) = res {
+                    // The upload expired, the next attempt must start over
+                    set_pending_upload(None);
+                }
                 if res.is_err() {
                     let e = res.as_ref().err().unwrap().to_string();
                     VideoUploadUnsuccessful.send_event(
@@ -224,6 +315,7 @@ pub fn VideoUploader(params: UploadParams) -> impl IntoView {
 
                 try_or_redirect_opt!(res);
 
+                set_pending_upload(None);
                 uploading.set(false);
 
                 let mut check_status = IntervalStream::new(4000);
@@ -254,13 +346,50 @@ pub fn VideoUploader(params: UploadParams) -> impl IntoView {
         },
     );
 
-    let publish_action = create_action(move |(canisters, uid): &(Canisters<true>, String)| {
-        let canisters = canisters.clone();
+    let publish_mode = params.publish_mode;
+    let (publish_text, published_text) = match publish_mode {
+        PublishMode::Now => ("Publishing", "Published"),
+        PublishMode::Draft => ("Saving draft", "Saved as draft"),
+        PublishMode::At(_) => ("Scheduling", "Scheduled"),
+    };
+
+    let draft_hashtags = hashtags.clone();
+    let draft_desc = description.clone();
+    let save_draft_action = create_action(move |(cans_wire, uid): &(CanistersAuthWire, String)| {
+        let cans_wire = cans_wire.clone();
+        let hashtags = draft_hashtags.clone();
+        let description = draft_desc.clone();
+        let uid = uid.clone();
+        async move {
+            let res = save_draft(
+                cans_wire.clone(),
+                uid.clone(),
+                hashtags,
+                description,
+                enable_hot_or_not,
+                is_nsfw,
+            )
+            .await;
+            try_or_redirect_opt!(res);
+
+            if let PublishMode::At(at_secs) = publish_mode {
+                let res = schedule_draft(cans_wire, uid, Some(at_secs)).await;
+                try_or_redirect_opt!(res);
+            }
+            publishing.set(false);
+
+            Some(())
+        }
+    });
+
+    let publish_action = create_action(move |(cans_wire, uid): &(CanistersAuthWire, String)| {
+        let cans_wire = cans_wire.clone();
         let hashtags = hashtags.clone();
         let hashtags_len = hashtags.len();
         let description = description.clone();
         let uid = uid.clone();
         async move {
+            let canisters = try_or_redirect_opt!(cans_wire.canisters());
             let res = publish_video(
                 canisters,
                 hashtags,
@@ -295,6 +424,12 @@ pub fn VideoUploader(params: UploadParams) -> impl IntoView {
                 post_id,
                 canister_store,
             );
+            if let Err(e) = record_post_hashtags(cans_wire.clone(), post_id).await {
+                log::warn!("failed to count hashtags of post {post_id}: {e}");
+            }
+            if let Err(e) = notify_followers_of_post(cans_wire, post_id).await {
+                log::warn!("failed to notify followers of post {post_id}: {e}");
+            }
 
             Some(())
         }
@@ -315,18 +450,31 @@ pub fn VideoUploader(params: UploadParams) -> impl IntoView {
         </div>
         <div class="flex flex-col basis-full lg:basis-7/12 gap-4 px-4">
             <div class="flex flex-row gap-4">
-                <ProgressItem initial_text="Uploading" done_text="Uploaded" loading=uploading />
+                <ProgressItem
+                    initial_text="Uploading"
+                    done_text="Uploaded"
+                    loading=uploading
+                    progress=upload_progress
+                />
             </div>
             <div class="flex flex-row gap-4">
                 <ProgressItem initial_text="Processing" done_text="Processed" loading=processing />
             </div>
             <div class="flex flex-row gap-4">
-                <ProgressItem initial_text="Publishing" done_text="Published" loading=publishing />
+                <ProgressItem
+                    initial_text=publish_text
+                    done_text=published_text
+                    loading=publishing
+                />
                 <Suspense>
                     {move || {
                         let uid = upload_action().flatten()?;
-                        let canisters = (cans_res.0)()?.ok()?.canisters().ok()?;
-                        publish_action.dispatch((canisters, uid));
+                        let cans_wire = (cans_res.0)()?.ok()?;
+                        if publish_mode == PublishMode::Now {
+                            publish_action.dispatch((cans_wire, uid));
+                        } else {
+                            save_draft_action.dispatch((cans_wire, uid));
+                        }
                         Some(())
                     }}
 
@@ -0,0 +1,219 @@
+//! Verification of wallet history against the certified ICRC-3 block log of the ledger
+//!
+//! The ledger certifies the hash of its latest block, every block carries the hash of its
+//! parent (`phash`). Walking the chain back from the certified tip proves each visited block
+//! was produced by the ledger, which is then compared with the transaction we display.
+use std::{collections::BTreeMap, io::Cursor};
+
+use candid::{Nat, Principal};
+use ic_certification::{Certificate, HashTree, LookupResult};
+use icrc_ledger_types::icrc::generic_value::Value;
+use leptos::ServerFnError;
+
+use super::txn::{TxnInfoType, TxnInfoWallet};
+use crate::{
+    state::canisters::Canisters,
+    utils::token::icrc::{value_account, value_field, value_nat, IcrcAccount},
+};
+
+/// Blocks behind the tip that are walked for verification
+/// transactions older than this are left unchecked
+const MAX_VERIFIED_DEPTH: u64 = 2000;
+const BLOCKS_PER_CALL: u64 = 500;
+
+struct CertifiedTip {
+    last_block_index: u64,
+    last_block_hash: [u8; 32],
+}
+
+fn lookup<'a>(tree: &'a HashTree, path: &[&[u8]]) -> Result<&'a [u8], ServerFnError> {
+    match tree.lookup_path(path) {
+        LookupResult::Found(v) => Ok(v),
+        _ => Err(ServerFnError::new(format!(
+            "Path {} not found in HashTree",
+            String::from_utf8_lossy(path.last().copied().unwrap_or_default())
+        ))),
+    }
+}
+
+async fn certified_tip<const A: bool>(
+    cans: &Canisters<A>,
+    ledger: Principal,
+) -> Result<CertifiedTip, ServerFnError> {
+    let ledger_can = cans.sns_ledger(ledger).await;
+    let tip = ledger_can
+        .icrc_3_get_tip_certificate()
+        .await?
+        .ok_or(ServerFnError::new(
+            "Failed to get tip certificate from ledger canister",
+        ))?;
+
+    let cert: Certificate = ciborium::from_reader(Cursor::new(&tip.certificate))
+        .map_err(|e| ServerFnError::new(format!("Failed to parse Certificate: {}", e)))?;
+    cans.verify_certificate(&cert, ledger).await?;
+
+    let hash_tree: HashTree = ciborium::from_reader(Cursor::new(&tip.hash_tree))
+        .map_err(|e| ServerFnError::new(format!("Failed to parse HashTree: {}", e)))?;
+    let certified_data = lookup(
+        &cert.tree,
+        &[
+            b"canister".as_slice(),
+            ledger.as_slice(),
+            b"certified_data".as_slice(),
+        ],
+    )?;
+    if certified_data != hash_tree.digest() {
+        return Err(ServerFnError::new(
+            "Ledger hash tree does not match certified data",
+        ));
+    }
+
+    let last_block_index =
+        u64::from_be_bytes(lookup(&hash_tree, &[b"last_block_index".as_slice()])?.try_into()?);
+    let last_block_hash = lookup(&hash_tree, &[b"last_block_hash".as_slice()])?.try_into()?;
+
+    Ok(CertifiedTip {
+        last_block_index,
+        last_block_hash,
+    })
+}
+
+struct CertifiedBlocks {
+    /// Lowest block returned by the ledger, older blocks are archived and unchecked
+    lowest_fetched: u64,
+    blocks: BTreeMap<u64, Value>,
+}
+
+/// Fetch blocks `from..=tip` and keep the ones reachable from the certified tip hash
+async fn certified_blocks<const A: bool>(
+    cans: &Canisters<A>,
+    ledger: Principal,
+    from: u64,
+    tip: &CertifiedTip,
+) -> Result<CertifiedBlocks, ServerFnError> {
+    let mut blocks = BTreeMap::new();
+    let mut start = from;
+    while start <= tip.last_block_index {
+        let length = (tip.last_block_index + 1 - start).min(BLOCKS_PER_CALL);
+        let res = cans.icrc3_blocks(ledger, start, length).await?;
+        for block in res.blocks {
+            let Ok(id) = u64::try_from(block.id.0) else {
+                continue;
+            };
+            blocks.insert(id, block.block);
+        }
+        // Archived blocks are not returned by the ledger, they are skipped
+        start += length;
+    }
+    let lowest_fetched = blocks
+        .keys()
+        .next()
+        .copied()
+        .unwrap_or(tip.last_block_index + 1);
+
+    let mut verified = BTreeMap::new();
+    let mut expected = tip.last_block_hash;
+    for idx in (from..=tip.last_block_index).rev() {
+        let Some(block) = blocks.remove(&idx) else {
+            break;
+        };
+        if block.clone().hash() != expected {
+            log::warn!("block {idx} of ledger {ledger} does not match the certified chain");
+            break;
+        }
+        match value_field(&block, "phash") {
+            Some(Value::Blob(phash)) => {
+                let Ok(phash) = phash.as_slice().try_into() else {
+                    break;
+                };
+                expected = phash;
+            }
+            // Only the genesis block has no parent
+            _ if idx == 0 => (),
+            _ => break,
+        }
+        verified.insert(idx, block);
+    }
+
+    Ok(CertifiedBlocks {
+        lowest_fetched,
+        blocks: verified,
+    })
+}
+
+fn account_matches(tx: &Value, key: &str, expected: Option<IcrcAccount>) -> bool {
+    let Some(expected) = expected else {
+        return true;
+    };
+    value_field(tx, key).and_then(value_account) == Some(expected)
+}
+
+/// `key_account` is the account the history was fetched for, `None` for the whole ledger
+fn block_matches(txn: &TxnInfoWallet, key_account: Option<IcrcAccount>, block: &Value) -> bool {
+    let Some(tx) = value_field(block, "tx") else {
+        return false;
+    };
+    let op = match txn.tag {
+        TxnInfoType::Mint { .. } => "mint",
+        TxnInfoType::Burn { .. } => "burn",
+        TxnInfoType::Sent { .. } | TxnInfoType::Received { .. } | TxnInfoType::Transfer { .. } => {
+            "xfer"
+        }
+    };
+    let (from, to) = match txn.tag {
+        TxnInfoType::Mint { to } => (None, Some(to)),
+        TxnInfoType::Burn { from } => (Some(from), None),
+        TxnInfoType::Sent { to } => (key_account, Some(to)),
+        TxnInfoType::Received { from } => (Some(from), key_account),
+        TxnInfoType::Transfer { from, to } => (Some(from), Some(to)),
+    };
+    let op_matches = matches!(value_field(tx, "op"), Some(Value::Text(o)) if o == op);
+    let amount_matches = value_field(tx, "amt").and_then(value_nat) == Some(txn.amount.e8s.clone());
+    let ts_matches = value_field(block, "ts").and_then(value_nat) == Some(Nat::from(txn.timestamp));
+
+    op_matches
+        && amount_matches
+        && ts_matches
+        && account_matches(tx, "from", from)
+        && account_matches(tx, "to", to)
+}
+
+/// Mark transactions of `key_account` as verified against the certified block log of `ledger`
+/// `None` for the public history of the ledger
+/// transactions outside the verifiable window are left unchecked
+pub async fn mark_certified<const A: bool>(
+    cans: &Canisters<A>,
+    ledger: Principal,
+    key_account: Option<IcrcAccount>,
+    txns: &mut [TxnInfoWallet],
+) {
+    let Some(oldest) = txns.iter().map(|t| t.id).min() else {
+        return;
+    };
+
+    let verified = async {
+        let tip = certified_tip(cans, ledger).await?;
+        let from = oldest.max(tip.last_block_index.saturating_sub(MAX_VERIFIED_DEPTH));
+        let certified = certified_blocks(cans, ledger, from, &tip).await?;
+        Ok::<_, ServerFnError>((from.max(certified.lowest_fetched), certified.blocks))
+    }
+    .await;
+
+    match verified {
+        Ok((from, blocks)) => {
+            for txn in txns.iter_mut().filter(|t| t.id >= from) {
+                txn.verified = Some(
+                    blocks
+                        .get(&txn.id)
+                        .is_some_and(|block| block_matches(txn, key_account, block)),
+                );
+            }
+        }
+        Err(e) => {
+            log::warn!("failed to verify history against ledger {ledger}: {e}");
+            for txn in txns {
+                txn.verified = Some(false);
+            }
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use super::txn::IndexOrLedger;
+use crate::utils::web::download_text_file;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Debug)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv;charset=utf-8",
+            Self::Json => "application/json",
+        }
+    }
+}
+
+/// Export the complete transaction history of a token
+/// walks every page of the history provider, not just the ones visible in the UI
+#[server(
+    input = Cbor
+)]
+pub async fn export_txn_history(
+    source: IndexOrLedger,
+    symbol: String,
+    decimals: u8,
+    format: ExportFormat,
+) -> Result<String, ServerFnError> {
+    server_impl::export_txn_history(source, symbol, decimals, format).await
+}
+
+#[component]
+pub fn ExportTxnHistory(source: IndexOrLedger, symbol: String, decimals: u8) -> impl IntoView {
+    let export_action = create_action(move |&format: &ExportFormat| {
+        let source = source.clone();
+        let symbol = symbol.clone();
+        async move {
+            let content = export_txn_history(source, symbol.clone(), decimals, format).await?;
+            let file_name = format!(
+                "{}-transactions.{}",
+                symbol.to_lowercase(),
+                format.extension()
+            );
+            download_text_file(&file_name, format.mime_type(), &content);
+            Ok::<_, ServerFnError>(())
+        }
+    });
+    let exporting = export_action.pending();
+    let export_err = move || {
+        export_action.value().with(|v| {
+            v.as_ref()
+                .and_then(|r| r.as_ref().err().map(|e| e.to_string()))
+        })
+    };
+
+    view! {
+        <div class="flex flex-col w-full gap-1">
+            <div class="flex flex-row items-center gap-2 text-sm">
+                <span class="text-white/50">Export history</span>
+                <button
+                    class="px-3 py-1 rounded-full border border-white/15 text-white disabled:text-white/30"
+                    disabled=exporting
+                    on:click=move |_| export_action.dispatch(ExportFormat::Csv)
+                >
+                    CSV
+                </button>
+                <button
+                    class="px-3 py-1 rounded-full border border-white/15 text-white disabled:text-white/30"
+                    disabled=exporting
+                    on:click=move |_| export_action.dispatch(ExportFormat::Json)
+                >
+                    JSON
+                </button>
+                <Show when=exporting>
+                    <span class="text-white/50">Preparing...</span>
+                </Show>
+            </div>
+            {move || export_err().map(|e| view! { <span class="text-sm text-red-500">{e}</span> })}
+        </div>
+    }
+}
+
+#[cfg(feature = "ssr")]
+mod server_impl {
+    use leptos::ServerFnError;
+    use serde::Serialize;
+
+    use super::{
+        super::txn::{provider::get_history_provider, IndexOrLedger, TxnInfoType, TxnInfoWallet},
+        ExportFormat,
+    };
+    use crate::{
+        component::infinite_scroller::{CursoredDataProvider, PageEntry},
+        state::canisters::unauth_canisters,
+        utils::{time::parse_ns_to_iso8601, token::icrc::IcrcAccount},
+    };
+
+    /// Upper bound on exported transactions, guards against providers that never end
+    const MAX_EXPORT_TXNS: usize = 100_000;
+    const EXPORT_PAGE_SIZE: usize = 500;
+
+    /// A single row of the exported history
+    #[derive(Serialize, Debug)]
+    struct ExportRecord {
+        id: u64,
+        /// ISO 8601, UTC
+        timestamp: String,
+        timestamp_ns: u64,
+        #[serde(rename = "type")]
+        kind: String,
+        from: Option<String>,
+        to: Option<String>,
+        amount: String,
+        symbol: String,
+    }
+
+    impl ExportRecord {
+        fn new(txn: &TxnInfoWallet, key_account: Option<IcrcAccount>, symbol: &str) -> Self {
+            let (from, to) = match txn.tag {
+                TxnInfoType::Mint { to } => (None, Some(to)),
+                TxnInfoType::Burn { from } => (Some(from), None),
+                TxnInfoType::Sent { to } => (key_account, Some(to)),
+                TxnInfoType::Received { from } => (Some(from), key_account),
+                TxnInfoType::Transfer { from, to } => (Some(from), Some(to)),
+            };
+            Self {
+                id: txn.id,
+                timestamp: parse_ns_to_iso8601(txn.timestamp).unwrap_or_default(),
+                timestamp_ns: txn.timestamp,
+                kind: txn.tag.to_string(),
+                from: from.map(|a| a.to_string()),
+                to: to.map(|a| a.to_string()),
+                amount: txn.amount.humanize_float(),
+                symbol: symbol.to_string(),
+            }
+        }
+    }
+
+    fn csv_field(field: &str) -> String {
+        if field.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    }
+
+    fn to_csv(records: &[ExportRecord]) -> String {
+        let mut out = String::from("id,timestamp,timestamp_ns,type,from,to,amount,symbol\n");
+        for r in records {
+            let row = [
+                r.id.to_string(),
+                r.timestamp.clone(),
+                r.timestamp_ns.to_string(),
+                r.kind.clone(),
+                r.from.clone().unwrap_or_default(),
+                r.to.clone().unwrap_or_default(),
+                r.amount.clone(),
+                r.symbol.clone(),
+            ];
+            let row: Vec<_> = row.iter().map(|f| csv_field(f.as_str())).collect();
+            out.push_str(&row.join(","));
+            out.push('\n');
+        }
+        out
+    }
+
+    pub async fn export_txn_history(
+        source: IndexOrLedger,
+        symbol: String,
+        decimals: u8,
+        format: ExportFormat,
+    ) -> Result<String, ServerFnError> {
+        let key_account = match &source {
+            IndexOrLedger::Index { key_account, .. } => Some(*key_account),
+            IndexOrLedger::Ledger(_) => None,
+        };
+        let provider = get_history_provider(unauth_canisters(), source, decimals, false);
+
+        let mut txns = Vec::<TxnInfoWallet>::new();
+        let mut cursor = 0;
+        loop {
+            if cursor >= MAX_EXPORT_TXNS {
+                return Err(ServerFnError::new(format!(
+                    "History exceeds {MAX_EXPORT_TXNS} transactions, too large to export"
+                )));
+            }
+            let PageEntry { data, end } = provider
+                .get_by_cursor(cursor, cursor + EXPORT_PAGE_SIZE)
+                .await
+                .map_err(|e| ServerFnError::new(e.to_string()))?;
+            txns.extend(data);
+            if end {
+                break;
+            }
+            cursor += EXPORT_PAGE_SIZE;
+        }
+
+        let records: Vec<_> = txns
+            .iter()
+            .map(|txn| ExportRecord::new(txn, key_account, &symbol))
+            .collect();
+
+        match format {
+            ExportFormat::Csv => Ok(to_csv(&records)),
+            ExportFormat::Json => serde_json::to_string_pretty(&records)
+                .map_err(|e| ServerFnError::new(e.to_string())),
+        }
+    }
+}
@@ -1,3 +1,6 @@
+#[cfg(not(feature = "mock-wallet-history"))]
+mod certified;
+pub mod export;
 pub mod tokens;
 pub mod transactions;
 pub mod txn;
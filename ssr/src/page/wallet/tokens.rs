@@ -5,7 +5,11 @@ use ic_agent::AgentError;
 
 use crate::page::token::RootType;
 use crate::page::wallet::ShareButtonWithFallbackPopup;
-use crate::utils::token::{get_ck_metadata, TokenBalanceOrClaiming};
+use crate::utils::token::{
+    get_ck_metadata,
+    icrc::{to_ledger_account, IcrcAccount},
+    TokenBalanceOrClaiming,
+};
 use crate::{
     component::infinite_scroller::{CursoredDataProvider, InfiniteScroller, KeyedData, PageEntry},
     state::canisters::{unauth_canisters, Canisters},
@@ -17,7 +21,7 @@ use crate::{
 use futures::stream::{self, StreamExt};
 use leptos::*;
 use yral_canisters_client::individual_user_template::Result14;
-use yral_canisters_client::sns_ledger::{Account, SnsLedger};
+use yral_canisters_client::sns_ledger::SnsLedger;
 #[derive(Clone)]
 pub struct TokenRootList {
     pub canisters: Canisters<false>,
@@ -40,12 +44,9 @@ impl KeyedData for RootType {
         self.clone()
     }
 }
-async fn get_balance<'a>(user_principal: Principal, ledger: &SnsLedger<'a>) -> Option<Nat> {
+async fn get_balance<'a>(account: IcrcAccount, ledger: &SnsLedger<'a>) -> Option<Nat> {
     ledger
-        .icrc_1_balance_of(Account {
-            owner: user_principal,
-            subaccount: None,
-        })
+        .icrc_1_balance_of(to_ledger_account(account))
         .await
         .ok()
 }
@@ -82,7 +83,7 @@ impl CursoredDataProvider for TokenRootList {
                 match root_type {
                     RootType::BTC { ledger, .. } => {
                         let ledger = cans.sns_ledger(ledger).await;
-                        let bal = get_balance(self.user_principal, &ledger).await?;
+                        let bal = get_balance(self.user_principal.into(), &ledger).await?;
 
                         if bal != 0u64 {
                             Some(root_type)
@@ -92,7 +93,7 @@ impl CursoredDataProvider for TokenRootList {
                     }
                     RootType::USDC { ledger, .. } => {
                         let ledger = cans.sns_ledger(ledger).await;
-                        let bal = get_balance(self.user_principal, &ledger).await?;
+                        let bal = get_balance(self.user_principal.into(), &ledger).await?;
 
                         if bal != 0u64 {
                             Some(root_type)
@@ -119,7 +120,7 @@ async fn token_metadata_or_fallback(
     user_principal: Principal,
     token_root: Principal,
 ) -> TokenMetadata {
-    let metadata = token_metadata_by_root(&cans, Some(user_principal), token_root)
+    let metadata = token_metadata_by_root(&cans, Some(user_principal.into()), token_root)
         .await
         .ok()
         .flatten();
@@ -157,13 +158,13 @@ pub fn TokenView(
 
             match token_root {
                 RootType::BTC { ledger, index } => {
-                    get_ck_metadata(cans, Some(user_principal), ledger, index)
+                    get_ck_metadata(cans, Some(user_principal.into()), ledger, index)
                         .await
                         .unwrap()
                         .unwrap()
                 }
                 RootType::USDC { ledger, index } => {
-                    get_ck_metadata(cans, Some(user_principal), ledger, index)
+                    get_ck_metadata(cans, Some(user_principal.into()), ledger, index)
                         .await
                         .unwrap()
                         .unwrap()
@@ -1,14 +1,24 @@
 use leptos::*;
 
-use super::txn::{provider::get_history_provider, TxnView};
+use super::{
+    export::ExportTxnHistory,
+    txn::{provider::get_history_provider, TxnView},
+};
 use crate::page::wallet::txn::IndexOrLedger;
 use crate::{component::infinite_scroller::InfiniteScroller, state::canisters::unauth_canisters};
 
 const FETCH_CNT: usize = 15;
 
 #[component]
-pub fn TransactionList(source: IndexOrLedger, symbol: String, decimals: u8) -> impl IntoView {
-    let provider = get_history_provider(unauth_canisters(), source, decimals);
+pub fn TransactionList(
+    source: IndexOrLedger,
+    symbol: String,
+    decimals: u8,
+    /// verify transactions against the certified ledger blocks
+    #[prop(optional)]
+    verify: bool,
+) -> impl IntoView {
+    let provider = get_history_provider(unauth_canisters(), source, decimals, verify);
     view! {
         <div class="flex flex-col w-full justify-between items-stretch">
             <InfiniteScroller
@@ -24,13 +34,34 @@ pub fn TransactionList(source: IndexOrLedger, symbol: String, decimals: u8) -> i
 
 #[component]
 pub fn Transactions(source: IndexOrLedger, symbol: String, decimals: u8) -> impl IntoView {
+    let verify = create_rw_signal(false);
+    let list_source = source.clone();
+    let list_symbol = symbol.clone();
+
     view! {
 
     <span class="text-xl w-full text-white font-bold">Transactions</span>
+        <ExportTxnHistory source symbol decimals/>
+        <label class="flex flex-row items-center gap-2 w-full text-sm text-white/50">
+            <input
+                type="checkbox"
+                class="accent-primary-600"
+                prop:checked=verify
+                on:change=move |ev| verify.set(event_target_checked(&ev))
+            />
+            Verify against certified ledger
+        </label>
 
         <div class="flex items-center flex-col gap- pb-12 w-full">
             <div class="flex flex-col divide-y divide-white/10 w-full">
-                <TransactionList source=source symbol decimals/>
+                {move || view! {
+                    <TransactionList
+                        source=list_source.clone()
+                        symbol=list_symbol.clone()
+                        decimals
+                        verify=verify()
+                    />
+                }}
             </div>
         </div>
     }
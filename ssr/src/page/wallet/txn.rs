@@ -9,7 +9,10 @@ use serde::{Deserialize, Serialize};
 use crate::{
     component::infinite_scroller::KeyedData,
     page::token::info::TokenKeyParam,
-    utils::{time::parse_ns_to_datetime, token::TokenBalance},
+    utils::{
+        time::parse_ns_to_datetime,
+        token::{icrc::IcrcAccount, TokenBalance},
+    },
 };
 
 #[derive(Clone, Copy)]
@@ -18,11 +21,13 @@ pub enum TxnDirection {
     Added,
     Deducted,
 }
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub enum IndexOrLedger {
     Index {
-        key_principal: Principal,
+        key_account: IcrcAccount,
         index: Principal,
+        /// required for verifying the index against the ledger
+        ledger: Principal,
     },
     Ledger(Principal),
 }
@@ -39,11 +44,11 @@ impl From<TxnDirection> for &'static icondata_core::IconData {
 
 #[derive(Clone, Copy, Serialize, Deserialize, Debug)]
 pub enum TxnInfoType {
-    Mint { to: Principal },
-    Sent { to: Principal }, // only for keyed
-    Burn { from: Principal },
-    Received { from: Principal },                // only for keyed
-    Transfer { from: Principal, to: Principal }, // only for public transaction
+    Mint { to: IcrcAccount },
+    Sent { to: IcrcAccount }, // only for keyed
+    Burn { from: IcrcAccount },
+    Received { from: IcrcAccount },                  // only for keyed
+    Transfer { from: IcrcAccount, to: IcrcAccount }, // only for public transaction
 }
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct TxnInfoWallet {
@@ -51,6 +56,10 @@ pub struct TxnInfoWallet {
     pub timestamp: u64,
     pub amount: TokenBalance,
     pub id: u64,
+    /// Whether the transaction matches the certified ledger block
+    /// None if verification was not requested or the block is out of reach
+    #[serde(default)]
+    pub verified: Option<bool>,
 }
 
 impl KeyedData for TxnInfoWallet {
@@ -139,9 +148,24 @@ pub fn TxnView(
                     }
                 }
                 <div class="flex flex-col">
-                    <span class="text-md md:text-lg font-semibold text-white">
-                        {info.tag.to_text()}
-                    </span>
+                    <div class="flex flex-row items-center gap-1">
+                        <span class="text-md md:text-lg font-semibold text-white">
+                            {info.tag.to_text()}
+                        </span>
+                        {info.verified.map(|verified| if verified {
+                            view! {
+                                <span title="Verified against the certified ledger" class="text-green-600">
+                                    <Icon icon=icondata::LuShieldCheck />
+                                </span>
+                            }
+                        } else {
+                            view! {
+                                <span title="Does not match the certified ledger" class="text-yellow-500">
+                                    <Icon icon=icondata::LuShieldAlert />
+                                </span>
+                            }
+                        })}
+                    </div>
                     {
                         move || {
                             match info.tag{
@@ -197,12 +221,14 @@ pub mod provider {
         canisters: Canisters<false>,
         source: IndexOrLedger,
         decimals: u8,
+        verify: bool,
     ) -> impl CursoredDataProvider<Data = TxnInfoWallet> + Clone {
         #[cfg(feature = "mock-wallet-history")]
         {
             _ = canisters;
             _ = source;
             _ = decimals;
+            _ = verify;
             mock::MockHistoryProvider
         }
         #[cfg(not(feature = "mock-wallet-history"))]
@@ -211,26 +237,36 @@ pub mod provider {
                 canisters,
                 source,
                 decimals,
+                verify,
+                page_cursors: Default::default(),
             }
         }
     }
 
     #[cfg(not(feature = "mock-wallet-history"))]
     mod canister {
-        use std::io::Cursor;
+        use std::{
+            collections::BTreeMap,
+            io::Cursor,
+            sync::{Arc, Mutex},
+        };
 
         use super::{
-            Canisters, CursoredDataProvider, IndexOrLedger, TokenBalance, TxnInfoType,
+            Canisters, CursoredDataProvider, IcrcAccount, IndexOrLedger, TokenBalance, TxnInfoType,
             TxnInfoWallet,
         };
-        use crate::component::infinite_scroller::PageEntry;
+        use crate::{
+            component::infinite_scroller::PageEntry,
+            page::wallet::certified::mark_certified,
+            utils::token::icrc::{from_index_account, from_ledger_account, to_index_account},
+        };
         use candid::{Nat, Principal};
         use ic_agent::AgentError;
         use ic_certification::{HashTree, LookupResult};
         use leptos::ServerFnError;
         use yral_canisters_client::{
             sns_index::{
-                Account, GetAccountTransactionsArgs, GetTransactionsResult, Transaction,
+                GetAccountTransactionsArgs, GetTransactionsResult, SnsIndex, Transaction,
                 TransactionWithId,
             },
             sns_ledger::{GetTransactionsRequest, SnsLedger},
@@ -238,7 +274,7 @@ pub mod provider {
 
         fn parse_transactions(
             txn: TransactionWithId,
-            user_principal: Principal,
+            user_account: IcrcAccount,
             decimals: u8,
         ) -> Result<TxnInfoWallet, ServerFnError> {
             let timestamp = txn.transaction.timestamp;
@@ -248,48 +284,50 @@ pub mod provider {
                 Transaction {
                     mint: Some(mint), ..
                 } => Ok(TxnInfoWallet {
-                    tag: TxnInfoType::Mint { to: mint.to.owner },
+                    tag: TxnInfoType::Mint {
+                        to: from_index_account(mint.to),
+                    },
                     timestamp,
                     amount: TokenBalance::new(mint.amount, decimals),
                     id,
+                    verified: None,
                 }),
                 Transaction {
                     burn: Some(burn), ..
                 } => Ok(TxnInfoWallet {
-                    tag: TxnInfoType::Burn {
-                        from: user_principal,
-                    },
+                    tag: TxnInfoType::Burn { from: user_account },
                     timestamp,
                     amount: TokenBalance::new(burn.amount, decimals),
                     id,
+                    verified: None,
                 }),
                 Transaction {
                     transfer: Some(transfer),
                     ..
                 } => {
-                    if user_principal == transfer.from.owner {
+                    let from = from_index_account(transfer.from);
+                    let to = from_index_account(transfer.to);
+                    if user_account == from {
                         // User is sending funds
                         Ok(TxnInfoWallet {
-                            tag: TxnInfoType::Sent {
-                                to: transfer.to.owner,
-                            },
+                            tag: TxnInfoType::Sent { to },
                             timestamp,
                             amount: TokenBalance::new(transfer.amount, decimals),
                             id,
+                            verified: None,
                         })
-                    } else if user_principal == transfer.to.owner {
+                    } else if user_account == to {
                         // User is receiving funds
                         Ok(TxnInfoWallet {
-                            tag: TxnInfoType::Received {
-                                from: transfer.from.owner,
-                            },
+                            tag: TxnInfoType::Received { from },
                             timestamp,
                             amount: TokenBalance::new(transfer.amount, decimals),
                             id,
+                            verified: None,
                         })
                     } else {
                         Err(ServerFnError::new(
-                            "Transfer details do not match the user principal",
+                            "Transfer details do not match the user account",
                         ))
                     }
                 }
@@ -308,32 +346,37 @@ pub mod provider {
                 yral_canisters_client::sns_ledger::Transaction {
                     mint: Some(mint), ..
                 } => Ok(TxnInfoWallet {
-                    tag: TxnInfoType::Mint { to: mint.to.owner },
+                    tag: TxnInfoType::Mint {
+                        to: from_ledger_account(mint.to),
+                    },
                     timestamp,
                     amount: TokenBalance::new(mint.amount, decimals),
                     id,
+                    verified: None,
                 }),
                 yral_canisters_client::sns_ledger::Transaction {
                     burn: Some(burn), ..
                 } => Ok(TxnInfoWallet {
                     tag: TxnInfoType::Burn {
-                        from: burn.from.owner,
+                        from: from_ledger_account(burn.from),
                     },
                     timestamp,
                     amount: TokenBalance::new(burn.amount, decimals),
                     id,
+                    verified: None,
                 }),
                 yral_canisters_client::sns_ledger::Transaction {
                     transfer: Some(transfer),
                     ..
                 } => Ok(TxnInfoWallet {
                     tag: TxnInfoType::Transfer {
-                        from: transfer.from.owner,
-                        to: transfer.to.owner,
+                        from: from_ledger_account(transfer.from),
+                        to: from_ledger_account(transfer.to),
                     },
                     timestamp,
                     amount: TokenBalance::new(transfer.amount, decimals),
                     id,
+                    verified: None,
                 }),
                 _ => Err(ServerFnError::new("Unable to parse transaction details")),
             }
@@ -364,11 +407,148 @@ pub mod provider {
             Err(ServerFnError::new("Path not found in HashTree"))
         }
 
+        fn nat_to_u64(n: &Nat) -> Option<u64> {
+            u64::try_from(n.0.clone()).ok()
+        }
+
         #[derive(Clone)]
         pub struct TxnHistory {
             pub canisters: Canisters<false>,
             pub source: IndexOrLedger,
             pub decimals: u8,
+            /// verify every transaction against the certified ledger blocks
+            pub verify: bool,
+            /// Index pages are fetched by transaction id, not position.
+            /// Maps a position in the history to the id of the transaction before it
+            pub page_cursors: Arc<Mutex<BTreeMap<usize, Nat>>>,
+        }
+
+        impl TxnHistory {
+            async fn index_page(
+                index: &SnsIndex<'_>,
+                account: IcrcAccount,
+                after: Option<Nat>,
+                max_results: usize,
+            ) -> Result<Vec<TransactionWithId>, AgentError> {
+                let history = index
+                    .get_account_transactions(GetAccountTransactionsArgs {
+                        max_results: Nat::from(max_results),
+                        // Newest first, starting after the oldest transaction already seen
+                        start: after,
+                        account: to_index_account(account),
+                    })
+                    .await?;
+                match history {
+                    GetTransactionsResult::Ok(v) => Ok(v.transactions),
+                    GetTransactionsResult::Err(_) => Err(AgentError::PrincipalError(
+                        ic_agent::export::PrincipalError::CheckSequenceNotMatch(),
+                    )),
+                }
+            }
+
+            /// Transactions `start..end` of `account`'s history in the index, newest first
+            /// the list ends once the index returns no transactions
+            async fn index_transactions(
+                &self,
+                index: Principal,
+                account: IcrcAccount,
+                start: usize,
+                end: usize,
+            ) -> Result<Option<Vec<TransactionWithId>>, AgentError> {
+                let index = self.canisters.sns_index(index).await;
+                let (mut pos, mut after) = self
+                    .page_cursors
+                    .lock()
+                    .unwrap()
+                    .range(..=start)
+                    .next_back()
+                    .map(|(pos, id)| (*pos, Some(id.clone())))
+                    .unwrap_or_default();
+                // Walk up to `start` if it was not reached by a previous page
+                while pos < start {
+                    let skipped = Self::index_page(&index, account, after, start - pos).await?;
+                    let Some(oldest) = skipped.last() else {
+                        return Ok(None);
+                    };
+                    pos += skipped.len();
+                    after = Some(oldest.id.clone());
+                    self.page_cursors
+                        .lock()
+                        .unwrap()
+                        .insert(pos, oldest.id.clone());
+                }
+
+                let transactions = Self::index_page(&index, account, after, end - start).await?;
+                let Some(oldest) = transactions.last() else {
+                    return Ok(None);
+                };
+                self.page_cursors
+                    .lock()
+                    .unwrap()
+                    .insert(start + transactions.len(), oldest.id.clone());
+                Ok(Some(transactions))
+            }
+
+            /// Ledger transactions `start..start + length` with their ids,
+            /// archived ones are fetched from the archive canisters
+            async fn ledger_transactions(
+                &self,
+                ledger: &SnsLedger<'_>,
+                start: u64,
+                length: u64,
+            ) -> Result<Vec<(u64, yral_canisters_client::sns_ledger::Transaction)>, AgentError>
+            {
+                let history = ledger
+                    .get_transactions(GetTransactionsRequest {
+                        start: start.into(),
+                        length: length.into(),
+                    })
+                    .await?;
+
+                let mut txns = vec![];
+                for range in history.archived_transactions {
+                    let (Some(range_start), Some(range_len)) =
+                        (nat_to_u64(&range.start), nat_to_u64(&range.length))
+                    else {
+                        return Err(AgentError::MessageError(
+                            "Invalid archived transaction range".into(),
+                        ));
+                    };
+                    // Archives may return less than requested
+                    let mut fetched = 0;
+                    while fetched < range_len {
+                        let archived = self
+                            .canisters
+                            .archived_transactions(
+                                &range.callback.0,
+                                range_start + fetched,
+                                range_len - fetched,
+                            )
+                            .await?;
+                        if archived.is_empty() {
+                            return Err(AgentError::MessageError(format!(
+                                "Archive returned no transactions from {}",
+                                range_start + fetched
+                            )));
+                        }
+                        for txn in archived {
+                            txns.push((range_start + fetched, txn));
+                            fetched += 1;
+                        }
+                    }
+                }
+
+                let first_index = nat_to_u64(&history.first_index)
+                    .ok_or_else(|| AgentError::MessageError("Invalid ledger first index".into()))?;
+                txns.extend(
+                    history
+                        .transactions
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, txn)| (first_index + i as u64, txn)),
+                );
+                Ok(txns)
+            }
         }
 
         impl CursoredDataProvider for TxnHistory {
@@ -383,91 +563,57 @@ pub mod provider {
                 match &self.source {
                     IndexOrLedger::Index {
                         index,
-                        key_principal,
+                        key_account,
+                        ledger,
                     } => {
-                        let index_canister = self.canisters.sns_index(*index).await;
-
-                        // Fetch transactions up to the 'end' index
-                        let max_results = end; // Fetch enough transactions to cover 'end'
-
-                        let history = index_canister
-                            .get_account_transactions(GetAccountTransactionsArgs {
-                                max_results: Nat::from(max_results),
-                                start: None, // No cursor, fetch the latest transactions
-                                account: Account {
-                                    owner: *key_principal,
-                                    subaccount: None,
-                                },
-                            })
-                            .await?;
-
-                        let transactions = match history {
-                            GetTransactionsResult::Ok(v) => v.transactions,
-                            GetTransactionsResult::Err(_) => {
-                                return Err(AgentError::PrincipalError(
-                                    ic_agent::export::PrincipalError::CheckSequenceNotMatch(),
-                                ));
-                            }
+                        let Some(transactions) = self
+                            .index_transactions(*index, *key_account, start, end)
+                            .await?
+                        else {
+                            return Ok(PageEntry {
+                                data: vec![],
+                                end: true,
+                            });
                         };
 
-                        let transactions = transactions.into_iter().skip(start).take(end - start);
-                        let txns_len = transactions.len();
-                        let data: Vec<TxnInfoWallet> = transactions
+                        let mut data: Vec<TxnInfoWallet> = transactions
+                            .into_iter()
                             .filter_map(|txn| {
-                                parse_transactions(txn, *key_principal, self.decimals).ok()
+                                parse_transactions(txn, *key_account, self.decimals).ok()
                             })
                             .collect();
+                        if self.verify {
+                            mark_certified(&self.canisters, *ledger, Some(*key_account), &mut data)
+                                .await;
+                        }
 
-                        let is_end = txns_len < (end - start);
-
-                        Ok(PageEntry { data, end: is_end })
+                        Ok(PageEntry { data, end: false })
                     }
-                    IndexOrLedger::Ledger(ledger) => {
-                        let ledger = self.canisters.sns_ledger(*ledger).await;
+                    IndexOrLedger::Ledger(ledger_id) => {
+                        let ledger = self.canisters.sns_ledger(*ledger_id).await;
 
-                        let total_transactions = get_latest_ledger_transaction(&ledger)
+                        let last_block = get_latest_ledger_transaction(&ledger)
                             .await
                             .map_err(|e| AgentError::MessageError(e.to_string()))?;
+                        // Newest first, position 0 is the last block
+                        let log_length = last_block + 1;
+                        let hi = log_length.saturating_sub(start as u64);
+                        let lo = log_length.saturating_sub(end as u64);
 
-                        let start_index = if total_transactions > end as u64 {
-                            total_transactions - end as u64
-                        } else {
-                            0
-                        };
+                        let history = self.ledger_transactions(&ledger, lo, hi - lo).await?;
 
-                        let length = if total_transactions > start as u64 {
-                            total_transactions - start_index - start as u64
-                        } else {
-                            total_transactions - start_index
-                        };
-
-                        let history = ledger
-                            .get_transactions(GetTransactionsRequest {
-                                start: start_index.into(),
-                                length: length.into(),
+                        let mut data: Vec<TxnInfoWallet> = history
+                            .into_iter()
+                            .filter_map(|(idx, txn)| {
+                                parse_transactions_ledger(txn, idx, self.decimals).ok()
                             })
-                            .await
-                            .map_err(|e| AgentError::MessageError(e.to_string()))?;
+                            .rev()
+                            .collect();
+                        if self.verify {
+                            mark_certified(&self.canisters, *ledger_id, None, &mut data).await;
+                        }
 
-                        let list_end = start_index == 0;
-
-                        Ok(PageEntry {
-                            data: history
-                                .transactions
-                                .into_iter()
-                                .enumerate()
-                                .filter_map(|(i, txn)| {
-                                    let idx = (history.first_index.clone() + i).0.to_u64_digits();
-                                    if idx.is_empty() {
-                                        None
-                                    } else {
-                                        parse_transactions_ledger(txn, idx[0], self.decimals).ok()
-                                    }
-                                })
-                                .rev()
-                                .collect(),
-                            end: list_end,
-                        })
+                        Ok(PageEntry { data, end: lo == 0 })
                     }
                 }
             }
@@ -493,20 +639,20 @@ pub mod provider {
         fn tag_from_u32(v: u32) -> TxnInfoType {
             match v % 4 {
                 0 => TxnInfoType::Mint {
-                    to: Principal::anonymous(),
+                    to: Principal::anonymous().into(),
                 },
                 1 => TxnInfoType::Burn {
-                    from: Principal::anonymous(),
+                    from: Principal::anonymous().into(),
                 },
                 2 => TxnInfoType::Received {
-                    from: Principal::anonymous(),
+                    from: Principal::anonymous().into(),
                 },
                 3 => TxnInfoType::Sent {
-                    to: Principal::anonymous(),
+                    to: Principal::anonymous().into(),
                 },
                 4 => TxnInfoType::Transfer {
-                    from: Principal::anonymous(),
-                    to: Principal::anonymous(),
+                    from: Principal::anonymous().into(),
+                    to: Principal::anonymous().into(),
                 },
                 _ => unreachable!(),
             }
@@ -527,6 +673,7 @@ pub mod provider {
                         timestamp: rand_gen.next_u64(),
                         tag: tag_from_u32(rand_gen.next_u32()),
                         id: rand_gen.next_u64(),
+                        verified: None,
                     })
                     .collect();
                 Ok(PageEntry { data, end: false })
@@ -1,7 +1,9 @@
-use std::sync::Arc;
+use std::{future::Future, sync::Arc};
 
-use candid::{Decode, Principal};
+use candid::{CandidType, Decode, Encode, Func, Principal};
 use ic_agent::{identity::DelegatedIdentity, AgentError, Identity};
+use ic_certification::Certificate;
+use icrc_ledger_types::icrc3::blocks::{GetBlocksRequest, GetBlocksResult};
 use leptos::*;
 use serde::{Deserialize, Serialize};
 use sns_validation::pbs::sns_pb::SnsInitPayload;
@@ -14,7 +16,7 @@ use yral_canisters_client::{
     post_cache::PostCache,
     sns_governance::SnsGovernance,
     sns_index::SnsIndex,
-    sns_ledger::SnsLedger,
+    sns_ledger::{GetTransactionsRequest, SnsLedger, Transaction},
     sns_root::SnsRoot,
     user_index::{Result1, UserIndex},
 };
@@ -102,6 +104,23 @@ impl Canisters<true> {
         self.expiry
     }
 
+    /// Wire for the same user with `id`, a delegation of this identity
+    pub fn wire_with_identity(&self, id: DelegatedIdentityWire) -> CanistersAuthWire {
+        let expiry = id
+            .delegation_chain
+            .iter()
+            .map(|del| del.delegation.expiration)
+            .min()
+            .unwrap_or(self.expiry);
+
+        CanistersAuthWire {
+            id,
+            user_canister: self.user_canister,
+            expiry,
+            profile_details: self.profile_details(),
+        }
+    }
+
     pub fn identity(&self) -> &DelegatedIdentity {
         self.id
             .as_ref()
@@ -144,6 +163,16 @@ impl Canisters<true> {
 }
 
 impl<const A: bool> Canisters<A> {
+    /// Run a canister call with bounded retries on transient agent errors
+    /// see [AgentWrapper::with_retry]
+    pub async fn with_retry<T, Fut: Future<Output = Result<T, AgentError>>>(
+        &self,
+        call: &'static str,
+        f: impl FnMut() -> Fut,
+    ) -> Result<T, AgentError> {
+        self.agent.with_retry(call, f).await
+    }
+
     pub async fn post_cache(&self) -> PostCache<'_> {
         let agent = self.agent.get_agent().await;
         PostCache(POST_CACHE_ID, agent)
@@ -211,6 +240,64 @@ impl<const A: bool> Canisters<A> {
         SnsRoot(canister_id, agent)
     }
 
+    /// ICRC-3 blocks of a ledger as generic values, required for computing block hashes
+    pub async fn icrc3_blocks(
+        &self,
+        ledger: Principal,
+        start: u64,
+        length: u64,
+    ) -> Result<GetBlocksResult, AgentError> {
+        let agent = self.agent.get_agent().await;
+        let args = Encode!(&vec![GetBlocksRequest {
+            start: start.into(),
+            length: length.into(),
+        }])
+        .unwrap();
+        let bytes = agent
+            .query(&ledger, "icrc3_get_blocks")
+            .with_arg(args)
+            .call()
+            .await?;
+        Ok(Decode!(&bytes, GetBlocksResult)?)
+    }
+
+    /// Transactions `start..start + length` from the ledger archive behind `callback`,
+    /// as returned in `archived_transactions` of the ledger's `get_transactions`
+    pub async fn archived_transactions(
+        &self,
+        callback: &Func,
+        start: u64,
+        length: u64,
+    ) -> Result<Vec<Transaction>, AgentError> {
+        #[derive(CandidType, Deserialize)]
+        struct TransactionRange {
+            transactions: Vec<Transaction>,
+        }
+
+        let agent = self.agent.get_agent().await;
+        let args = Encode!(&GetTransactionsRequest {
+            start: start.into(),
+            length: length.into(),
+        })
+        .unwrap();
+        let bytes = agent
+            .query(&callback.principal, &callback.method)
+            .with_arg(args)
+            .call()
+            .await?;
+        Ok(Decode!(&bytes, TransactionRange)?.transactions)
+    }
+
+    /// Verify a certificate against the root key, `canister_id` must be covered by its delegation
+    pub async fn verify_certificate(
+        &self,
+        cert: &Certificate,
+        canister_id: Principal,
+    ) -> Result<(), AgentError> {
+        let agent = self.agent.get_agent().await;
+        agent.verify(cert, canister_id)
+    }
+
     async fn subnet_indexes(&self) -> Result<Vec<Principal>, AgentError> {
         #[cfg(any(feature = "local-bin", feature = "local-lib"))]
         {
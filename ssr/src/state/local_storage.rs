@@ -3,7 +3,10 @@ use codee::string::JsonSerdeCodec;
 use leptos::{Signal, WriteSignal};
 use leptos_use::storage::use_local_storage;
 
-use crate::consts::REFERRER_STORE;
+use crate::{
+    consts::{REFERRER_STORE, TRACKING_CONSENT_STORE},
+    utils::event_streaming::consent::TrackingConsent,
+};
 
 pub fn use_referrer_store() -> (
     Signal<Option<Principal>>,
@@ -12,3 +15,11 @@ pub fn use_referrer_store() -> (
 ) {
     use_local_storage::<Option<Principal>, JsonSerdeCodec>(REFERRER_STORE)
 }
+
+pub fn use_tracking_consent_store() -> (
+    Signal<TrackingConsent>,
+    WriteSignal<TrackingConsent>,
+    impl Fn() + Clone,
+) {
+    use_local_storage::<TrackingConsent, JsonSerdeCodec>(TRACKING_CONSENT_STORE)
+}
@@ -6,7 +6,6 @@ pub mod canisters;
 pub mod content_seed_client;
 pub mod history;
 pub mod local_storage;
-
 #[cfg(feature = "ssr")]
 pub mod server {
 
@@ -27,6 +26,8 @@ pub mod server {
         pub admin_canisters: super::admin_canisters::AdminCanisters,
         #[cfg(feature = "cloudflare")]
         pub cloudflare: gob_cloudflare::CloudflareAuth,
+        #[cfg(feature = "cloudflare")]
+        pub cf_tus: crate::utils::cf_tus::CfTusClient,
         pub kv: KVStoreImpl,
         pub routes: Vec<RouteListing>,
         pub cookie_key: Key,
@@ -39,5 +40,8 @@ pub mod server {
         #[cfg(feature = "qstash")]
         pub qstash: crate::utils::qstash::QStashClient,
         pub grpc_icpump_search_channel: ICPumpSearchGrpcChannel,
+        pub ml_feed: crate::utils::ml_feed::ml_feed_grpc::MLFeed,
+        pub analytics_sinks: crate::utils::event_streaming::sinks::AnalyticsSinks,
+        pub leaderboard: crate::utils::leaderboard::server_impl::LeaderboardCache,
     }
 }
@@ -0,0 +1,111 @@
+//! Cloudflare Stream resumable uploads over the tus protocol
+//! <https://developers.cloudflare.com/stream/uploading-videos/resumable-uploads/>
+
+use std::{collections::HashMap, sync::Arc};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use http::{header::AUTHORIZATION, HeaderMap, HeaderValue};
+use reqwest::{header::LOCATION, Client, Url};
+use serde_json::json;
+
+use crate::consts::CF_BASE_URL;
+
+pub const TUS_VERSION: &str = "1.0.0";
+
+#[derive(Debug, thiserror::Error)]
+pub enum CfTusError {
+    #[error("request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("missing header {0} in cloudflare response")]
+    MissingHeader(&'static str),
+}
+
+/// A tus upload created for a creator, the url is used directly by the browser
+pub struct TusUploadCreated {
+    pub uid: String,
+    pub upload_url: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct CfTusClient {
+    client: Client,
+    /// `accounts/{account_id}/stream/`
+    stream_url: Arc<Url>,
+}
+
+impl CfTusClient {
+    pub fn new(account_id: &str, token: &str) -> Self {
+        let mut bearer: HeaderValue = format!("Bearer {}", token)
+            .parse()
+            .expect("Invalid Cloudflare token");
+        bearer.set_sensitive(true);
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, bearer);
+
+        let client = Client::builder()
+            .default_headers(headers)
+            .build()
+            .expect("Failed to create Cloudflare tus client");
+        let stream_url = CF_BASE_URL
+            .join(&format!("accounts/{account_id}/stream/"))
+            .unwrap();
+
+        Self {
+            client,
+            stream_url: Arc::new(stream_url),
+        }
+    }
+
+    /// Create a direct creator upload of `upload_length` bytes
+    /// `metadata` is sent as tus `Upload-Metadata`, only keys known to Cloudflare are honored
+    pub async fn create_upload(
+        &self,
+        creator: &str,
+        upload_length: u64,
+        metadata: &[(&str, String)],
+    ) -> Result<TusUploadCreated, CfTusError> {
+        let mut ep = (*self.stream_url).clone();
+        ep.set_query(Some("direct_user=true"));
+        let upload_metadata = metadata
+            .iter()
+            .map(|(k, v)| format!("{k} {}", STANDARD.encode(v)))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let res = self
+            .client
+            .post(ep)
+            .header("Tus-Resumable", TUS_VERSION)
+            .header("Upload-Length", upload_length.to_string())
+            .header("Upload-Creator", creator)
+            .header("Upload-Metadata", upload_metadata)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let header = |name: &'static str| {
+            res.headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+                .ok_or(CfTusError::MissingHeader(name))
+        };
+
+        Ok(TusUploadCreated {
+            uid: header("stream-media-id")?,
+            upload_url: header(LOCATION.as_str())?,
+        })
+    }
+
+    /// Set user defined metadata on a video, tus uploads can't carry it on creation
+    pub async fn set_meta(&self, uid: &str, meta: HashMap<&str, String>) -> Result<(), CfTusError> {
+        let ep = self.stream_url.join(uid).unwrap();
+        self.client
+            .post(ep)
+            .json(&json!({ "meta": meta }))
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,305 @@
+//! Tracking consent
+//!
+//! Until the user opts in, events are sent without the params identifying them
+//! and GA4 is not loaded. The choice is kept in local storage and,
+//! for server side events, in the KV store keyed by principal.
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use super::{queue::EventQueue, schema::WireEvent};
+use crate::state::{canisters::CanistersAuthWire, local_storage::use_tracking_consent_store};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum TrackingConsent {
+    /// No choice made yet, the banner is shown
+    #[default]
+    Unset,
+    Granted,
+    Denied,
+}
+
+impl TrackingConsent {
+    pub fn allows_pii(self) -> bool {
+        self == Self::Granted
+    }
+}
+
+/// Params identifying the acting user,
+/// the publisher of a post is public and is kept
+const PII_PARAMS: &[&str] = &[
+    "user_id",
+    "user_id_viewer",
+    "display_name",
+    "canister_id",
+    "to",
+];
+
+/// Strip the params identifying the user unless `consent` allows them
+pub fn apply_consent(event: &mut WireEvent, consent: TrackingConsent) {
+    if consent.allows_pii() {
+        return;
+    }
+    event.client_id = None;
+    if let Some(params) = event.params.as_object_mut() {
+        for key in PII_PARAMS {
+            params.remove(*key);
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct ConsentCtx {
+    pub consent: Signal<TrackingConsent>,
+    set_consent: WriteSignal<TrackingConsent>,
+}
+
+impl Default for ConsentCtx {
+    fn default() -> Self {
+        let (consent, set_consent, _) = use_tracking_consent_store();
+        Self {
+            consent,
+            set_consent,
+        }
+    }
+}
+
+/// Record a choice locally and for the signed in principal,
+/// must be created under `BaseRoute`
+pub fn use_set_consent() -> Action<TrackingConsent, ()> {
+    let ctx: ConsentCtx = expect_context();
+    let cans_wire_res = crate::state::canisters::authenticated_canisters();
+
+    let queue: EventQueue = expect_context();
+
+    create_action(move |&consent: &TrackingConsent| {
+        ctx.set_consent.set(consent);
+        if consent == TrackingConsent::Denied {
+            // queued events may still identify the user, consent was checked when queued
+            queue.clear();
+        }
+        let cans_wire_res = cans_wire_res.clone();
+        async move {
+            let res = match cans_wire_res.wait_untracked().await {
+                Ok(cans_wire) => save_tracking_consent(cans_wire, consent).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = res {
+                log::warn!("failed to save tracking consent: {e}");
+            }
+        }
+    })
+}
+
+/// Adopt the choice stored for the signed in principal, i.e made on another device
+pub fn use_restore_consent() {
+    let ctx: ConsentCtx = expect_context();
+    let cans_wire_res = crate::state::canisters::authenticated_canisters();
+
+    create_local_resource(
+        move || ctx.consent.get() == TrackingConsent::Unset,
+        move |unset| {
+            let cans_wire_res = cans_wire_res.clone();
+            async move {
+                if !unset {
+                    return;
+                }
+                let Ok(cans_wire) = cans_wire_res.wait_untracked().await else {
+                    return;
+                };
+                match get_tracking_consent(cans_wire).await {
+                    Ok(TrackingConsent::Unset) => (),
+                    Ok(consent) => ctx.set_consent.set(consent),
+                    Err(e) => log::warn!("failed to fetch tracking consent: {e}"),
+                }
+            }
+        },
+    );
+}
+
+#[server(
+    input = Cbor
+)]
+pub async fn get_tracking_consent(
+    cans_wire: CanistersAuthWire,
+) -> Result<TrackingConsent, ServerFnError> {
+    let user = cans_wire.canisters()?.user_principal();
+    server_impl::read_consent(&user.to_text()).await
+}
+
+#[server(
+    input = Cbor
+)]
+pub async fn save_tracking_consent(
+    cans_wire: CanistersAuthWire,
+    consent: TrackingConsent,
+) -> Result<(), ServerFnError> {
+    let user = cans_wire.canisters()?.user_principal();
+    server_impl::write_consent(&user.to_text(), consent).await
+}
+
+/// Withdraw consent and queue the analytics history of the user for deletion,
+/// the sinks can't delete it themselves so an operator processes the queue
+#[server(
+    input = Cbor
+)]
+pub async fn request_analytics_deletion(cans_wire: CanistersAuthWire) -> Result<(), ServerFnError> {
+    let user = cans_wire.canisters()?.user_principal();
+    server_impl::request_deletion(user).await
+}
+
+#[cfg(feature = "ssr")]
+pub mod server_impl {
+    use std::collections::HashMap;
+
+    use axum::{
+        extract::{Path, State},
+        http::{HeaderMap, StatusCode},
+        response::{IntoResponse, Response},
+        routing::{delete, get},
+        Json, Router,
+    };
+    use candid::Principal;
+    use leptos::{expect_context, ServerFnError};
+
+    use super::{apply_consent, TrackingConsent};
+    use crate::{
+        auth::server_impl::store::{KVStore, KVStoreImpl},
+        state::server::AppState,
+        utils::{event_streaming::schema::WireEvent, time::current_epoch},
+    };
+
+    /// Principals with a pending deletion request
+    const DELETION_INDEX: &str = "analytics-deletions";
+
+    fn consent_key(user: &str) -> String {
+        format!("tracking-consent:{user}")
+    }
+
+    fn deletion_key(user: &str) -> String {
+        format!("analytics-deletion:{user}")
+    }
+
+    pub async fn read_consent(user: &str) -> Result<TrackingConsent, ServerFnError> {
+        let kv: KVStoreImpl = expect_context();
+        let Some(raw) = kv.read(consent_key(user)).await? else {
+            return Ok(TrackingConsent::Unset);
+        };
+        Ok(serde_json::from_str(&raw)?)
+    }
+
+    pub async fn write_consent(user: &str, consent: TrackingConsent) -> Result<(), ServerFnError> {
+        let kv: KVStoreImpl = expect_context();
+        kv.write(consent_key(user), serde_json::to_string(&consent)?)
+            .await?;
+        Ok(())
+    }
+
+    /// Principal an event identifies, if any
+    fn event_user(event: &WireEvent) -> Option<&str> {
+        ["user_id", "user_id_viewer"]
+            .into_iter()
+            .find_map(|key| event.params[key].as_str())
+    }
+
+    /// Consent of the user an event was raised for, `Unset` if unknown
+    pub async fn event_consent(event: &WireEvent) -> TrackingConsent {
+        let Some(user) = event_user(event) else {
+            return TrackingConsent::Unset;
+        };
+        read_consent(user).await.unwrap_or_else(|e| {
+            log::warn!("failed to read tracking consent of {user}: {e}");
+            TrackingConsent::Unset
+        })
+    }
+
+    /// Strip the params identifying users who didn't consent, whatever the client did
+    pub async fn enforce_consent(events: &mut [WireEvent]) {
+        let mut consents = HashMap::<String, TrackingConsent>::new();
+        for event in events {
+            let user = event_user(event).map(str::to_string);
+            let consent = match user {
+                Some(user) => match consents.get(&user) {
+                    Some(&consent) => consent,
+                    None => {
+                        let consent = event_consent(event).await;
+                        consents.insert(user, consent);
+                        consent
+                    }
+                },
+                None => TrackingConsent::Unset,
+            };
+            apply_consent(event, consent);
+        }
+    }
+
+    pub async fn request_deletion(user: Principal) -> Result<(), ServerFnError> {
+        let user = user.to_text();
+        write_consent(&user, TrackingConsent::Denied).await?;
+        let kv: KVStoreImpl = expect_context();
+        kv.write(deletion_key(&user), current_epoch().as_secs().to_string())
+            .await?;
+        kv.index_add(DELETION_INDEX.into(), user).await?;
+        Ok(())
+    }
+
+    /// Operator endpoints for the deletion queue, authenticated with `ANALYTICS_ADMIN_TOKEN`
+    ///
+    /// * `GET /` lists the pending requests as `[(principal, requested at secs)]`
+    /// * `DELETE /:principal` marks a request as processed
+    pub fn deletion_router() -> Router<AppState> {
+        Router::new()
+            .route("/", get(pending_deletions))
+            .route("/:user", delete(complete_deletion))
+    }
+
+    fn is_operator(headers: &HeaderMap) -> bool {
+        let Ok(token) = std::env::var("ANALYTICS_ADMIN_TOKEN") else {
+            return false;
+        };
+        headers
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .is_some_and(|v| v == token.trim())
+    }
+
+    async fn pending_deletions(State(kv): State<KVStoreImpl>, headers: HeaderMap) -> Response {
+        if !is_operator(&headers) {
+            return StatusCode::UNAUTHORIZED.into_response();
+        }
+        let res: Result<Vec<(String, u64)>, ServerFnError> = async {
+            let mut pending = vec![];
+            for user in kv.index_members(DELETION_INDEX.into()).await? {
+                let Some(requested_at) = kv.read(deletion_key(&user)).await? else {
+                    continue;
+                };
+                pending.push((user, requested_at.parse()?));
+            }
+            Ok(pending)
+        }
+        .await;
+        match res {
+            Ok(pending) => Json(pending).into_response(),
+            Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        }
+    }
+
+    async fn complete_deletion(
+        State(kv): State<KVStoreImpl>,
+        Path(user): Path<String>,
+        headers: HeaderMap,
+    ) -> Response {
+        if !is_operator(&headers) {
+            return StatusCode::UNAUTHORIZED.into_response();
+        }
+        let res = async {
+            kv.delete(deletion_key(&user)).await?;
+            kv.index_remove(DELETION_INDEX.into(), user).await
+        }
+        .await;
+        match res {
+            Ok(_) => StatusCode::NO_CONTENT.into_response(),
+            Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        }
+    }
+}
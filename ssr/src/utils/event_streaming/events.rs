@@ -4,49 +4,28 @@ use leptos::html::Input;
 use leptos::{create_effect, MaybeSignal, ReadSignal, RwSignal, SignalGetUntracked};
 use leptos::{create_signal, ev, expect_context, html::Video, NodeRef, SignalGet, SignalSet};
 use leptos_use::use_event_listener;
-use serde_json::json;
 use sns_validation::pbs::sns_pb::SnsInitPayload;
 use wasm_bindgen::JsCast;
 
+use super::schema::{
+    AnalyticsEvent, ErrorEventProps, LoginCtaProps, LoginJoinOverlayViewedProps,
+    LoginMethodSelectedProps, LoginSuccessfulProps, LogoutProps, ProfileViewVideoProps, ReferProps,
+    TokenCreationProps, TokensClaimedProps, TokensTransferredProps, UploadProps, VideoProps,
+    VideoWatchedProps,
+};
 use super::EventHistory;
 use crate::component::auth_providers::ProviderKind;
 use crate::state::auth::account_connected_reader;
 use crate::state::canisters::{auth_canisters_store, Canisters};
 use crate::state::history::HistoryCtx;
 #[cfg(feature = "ga4")]
-use crate::utils::event_streaming::{
-    send_event, send_event_warehouse, send_event_warehouse_ssr, send_user_id,
-};
+use crate::utils::event_streaming::send_user_id;
+use crate::utils::event_streaming::{send_event, send_event_warehouse, send_event_warehouse_ssr};
+use crate::utils::hashtags::parse_hashtags;
 use crate::utils::posts::PostDetails;
 use crate::utils::profile::ProfileDetails;
 use crate::utils::user::{user_details_can_store_or_ret, user_details_or_ret};
 
-pub enum AnalyticsEvent {
-    VideoWatched(VideoWatched),
-    LikeVideo(LikeVideo),
-    ShareVideo(ShareVideo),
-    VideoUploadInitiated(VideoUploadInitiated),
-    VideoUploadUploadButtonClicked(VideoUploadUploadButtonClicked),
-    VideoUploadVideoSelected(VideoUploadVideoSelected),
-    VideoUploadUnsuccessful(VideoUploadUnsuccessful),
-    VideoUploadSuccessful(VideoUploadSuccessful),
-    Refer(Refer),
-    ReferShareLink(ReferShareLink),
-    LoginSuccessful(LoginSuccessful),
-    LoginMethodSelected(LoginMethodSelected),
-    LoginJoinOverlayViewed(LoginJoinOverlayViewed),
-    LoginCta(LoginCta),
-    LogoutClicked(LogoutClicked),
-    LogoutConfirmation(LogoutConfirmation),
-    ErrorEvent(ErrorEvent),
-    ProfileViewVideo(ProfileViewVideo),
-    TokenCreationStarted(TokenCreationStarted),
-    TokenCreationCompleted(TokenCreationCompleted),
-    TokenCreationFailed(TokenCreationFailed),
-    TokensClaimedFromNeuron(TokensClaimedFromNeuron),
-    TokensTransferred(TokensTransferred),
-}
-
 #[derive(Default)]
 pub struct VideoWatched;
 
@@ -56,7 +35,7 @@ impl VideoWatched {
         vid_details: MaybeSignal<Option<PostDetails>>,
         container_ref: NodeRef<Video>,
     ) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
             let (is_connected, _) = account_connected_reader();
 
@@ -83,31 +62,12 @@ impl VideoWatched {
 
                 // send bigquery event when video is watched > 95%
                 if current_time >= 0.95 * duration && !full_video_watched.get() {
-                    send_event_warehouse(
-                        "video_duration_watched",
-                        &json!({
-                            "publisher_user_id": post.map(|p| p.poster_principal),
-                            "user_id": user.details.principal,
-                            "is_loggedIn": is_connected(),
-                            "display_name": user.details.display_name.clone(),
-                            "canister_id": user.canister_id,
-                            "video_id": post.map(|p| p.uid.clone()),
-                            "video_category": "NA",
-                            "creator_category": "NApublisher_canister_id(",
-                            "hashtag_count": post.map(|p| p.hastags.len()),
-                            "is_NSFW": post.map(|p| p.is_nsfw),
-                            "is_hotorNot": post.map(|p| p.is_hot_or_not()),
-                            "feed_type": "NA",
-                            "view_count": post.map(|p| p.views),
-                            "like_count": post.map(|p| p.likes),
-                            "share_count": 0,
-                            "percentage_watched": 100.0,
-                            "absolute_watched": duration,
-                            "video_duration": duration,
-                            "post_id": post.map(|p| p.post_id),
-                            "publisher_canister_id": post.map(|p| p.canister_id),
-                        }),
-                    );
+                    send_event_warehouse(AnalyticsEvent::VideoDurationWatched(VideoWatchedProps {
+                        video: VideoProps::new(user.clone(), is_connected(), post),
+                        percentage_watched: 100.0,
+                        absolute_watched: duration,
+                        video_duration: duration,
+                    }));
 
                     set_full_video_watched.set(true);
                 }
@@ -117,28 +77,11 @@ impl VideoWatched {
                 }
 
                 if current_time >= 3.0 {
-                    send_event(
-                        "video_viewed",
-                        &json!({
-                            "publisher_user_id": post.map(|p| p.poster_principal),
-                            "user_id": user.details.principal,
-                            "is_loggedIn": is_connected(),
-                            "display_name": user.details.display_name,
-                            "canister_id": user.canister_id,
-                            "video_id": post.map(|p| p.uid.clone()),
-                            "video_category": "NA",
-                            "creator_category": "NA",
-                            "hashtag_count": post.map(|p| p.hastags.len()),
-                            "is_NSFW": post.map(|p| p.is_nsfw),
-                            "is_hotorNot": post.map(|p| p.is_hot_or_not()),
-                            "feed_type": "NA",
-                            "view_count": post.map(|p| p.views),
-                            "like_count": post.map(|p| p.likes),
-                            "share_count": 0,
-                            "post_id": post.map(|p| p.post_id),
-                            "publisher_canister_id": post.map(|p| p.canister_id),
-                        }),
-                    );
+                    send_event(AnalyticsEvent::VideoViewed(VideoProps::new(
+                        user,
+                        is_connected(),
+                        post,
+                    )));
                     set_video_watched.set(true);
                 }
             });
@@ -160,31 +103,12 @@ impl VideoWatched {
 
                 let percentage_watched = (current_time / duration) * 100.0;
 
-                send_event_warehouse(
-                    "video_duration_watched",
-                    &json!({
-                        "publisher_user_id": post.map(|p| p.poster_principal),
-                        "user_id": user.details.principal,
-                        "is_loggedIn": is_connected(),
-                        "display_name": user.details.display_name.clone(),
-                        "canister_id": user.canister_id,
-                        "video_id": post.map(|p| p.uid.clone()),
-                        "video_category": "NA",
-                        "creator_category": "NA",
-                        "hashtag_count": post.map(|p| p.hastags.len()),
-                        "is_NSFW": post.map(|p| p.is_nsfw),
-                        "is_hotorNot": post.map(|p| p.is_hot_or_not()),
-                        "feed_type": "NA",
-                        "view_count": post.map(|p| p.views),
-                        "like_count": post.map(|p| p.likes),
-                        "share_count": 0,
-                        "percentage_watched": percentage_watched,
-                        "absolute_watched": current_time,
-                        "video_duration": duration,
-                        "post_id": post.map(|p| p.post_id),
-                        "publisher_canister_id": post.map(|p| p.canister_id),
-                    }),
-                );
+                send_event_warehouse(AnalyticsEvent::VideoDurationWatched(VideoWatchedProps {
+                    video: VideoProps::new(user, is_connected(), post),
+                    percentage_watched,
+                    absolute_watched: current_time,
+                    video_duration: duration,
+                }));
             });
         }
     }
@@ -200,44 +124,16 @@ impl LikeVideo {
         likes: RwSignal<u64>,
         cans_store: RwSignal<Option<Canisters<true>>>,
     ) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
-            let publisher_user_id = post_details.poster_principal;
-            let video_id = post_details.uid.clone();
-            let hastag_count = post_details.hastags.len();
-            let is_nsfw = post_details.is_nsfw;
-            let is_hotornot = post_details.hot_or_not_feed_ranking_score.is_some();
-            let view_count = post_details.views;
-            let post_id = post_details.post_id;
-            let publisher_canister_id = post_details.canister_id;
-
             let (is_connected, _) = account_connected_reader();
             // like_video - analytics
 
             let user = user_details_can_store_or_ret!(cans_store);
 
-            send_event(
-                "like_video",
-                &json!({
-                    "publisher_user_id":publisher_user_id,
-                    "user_id": user.details.principal,
-                    "is_loggedIn": is_connected(),
-                    "display_name": user.details.display_name,
-                    "canister_id": user.canister_id,
-                    "video_id": video_id,
-                    "video_category": "NA",
-                    "creator_category": "NA",
-                    "hashtag_count": hastag_count,
-                    "is_NSFW": is_nsfw,
-                    "is_hotorNot": is_hotornot,
-                    "feed_type": "NA",
-                    "view_count": view_count,
-                    "like_count": likes.get(),
-                    "share_count": 0,
-                    "post_id": post_id,
-                    "publisher_canister_id": publisher_canister_id,
-                }),
-            );
+            let mut props = VideoProps::new(user, is_connected(), Some(&post_details));
+            props.like_count = Some(likes.get());
+            send_event(AnalyticsEvent::LikeVideo(props));
         }
     }
 }
@@ -251,41 +147,18 @@ impl ShareVideo {
         post_details: PostDetails,
         cans_store: RwSignal<Option<Canisters<true>>>,
     ) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
-            let publisher_user_id = post_details.poster_principal;
-            let video_id = post_details.uid.clone();
-            let hastag_count = post_details.hastags.len();
-            let is_nsfw = post_details.is_nsfw;
-            let is_hotornot = post_details.hot_or_not_feed_ranking_score.is_some();
-            let view_count = post_details.views;
-            let like_count = post_details.likes;
-
             let (is_connected, _) = account_connected_reader();
 
             let user = user_details_can_store_or_ret!(cans_store);
 
             // share_video - analytics
-            send_event(
-                "share_video",
-                &json!({
-                    "publisher_user_id":publisher_user_id,
-                    "user_id": user.details.principal,
-                    "is_loggedIn": is_connected.get(),
-                    "display_name": user.details.display_name,
-                    "canister_id": user.canister_id,
-                    "video_id": video_id,
-                    "video_category": "NA",
-                    "creator_category": "NA",
-                    "hashtag_count": hastag_count,
-                    "is_NSFW": is_nsfw,
-                    "is_hotorNot": is_hotornot,
-                    "feed_type": "NA",
-                    "view_count": view_count,
-                    "like_count": like_count,
-                    "share_count": 0,
-                }),
-            );
+            send_event(AnalyticsEvent::ShareVideo(VideoProps::new(
+                user,
+                is_connected.get(),
+                Some(&post_details),
+            )));
         }
     }
 }
@@ -295,19 +168,11 @@ pub struct VideoUploadInitiated;
 
 impl VideoUploadInitiated {
     pub fn send_event(&self) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
             // video_upload_initiated - analytics
             let user = user_details_or_ret!();
-            send_event(
-                "video_upload_initiated",
-                &json!({
-                    "user_id": user.details.principal,
-                    "display_name": user.details.display_name,
-                    "canister_id": user.canister_id,
-                    "creator_category": "NA",
-                }),
-            );
+            send_event(AnalyticsEvent::VideoUploadInitiated(UploadProps::new(user)));
         }
     }
 }
@@ -323,12 +188,13 @@ impl VideoUploadUploadButtonClicked {
         enable_hot_or_not: NodeRef<Input>,
         cans_store: RwSignal<Option<Canisters<true>>>,
     ) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
             // video_upload_upload_button_clicked - analytics
             let user = user_details_can_store_or_ret!(cans_store);
 
-            let hashtag_count = hashtag_inp.get_untracked().unwrap().value().len();
+            let hashtag_count = parse_hashtags(&hashtag_inp.get_untracked().unwrap().value())
+                .map_or(0, |tags| tags.len());
             let is_nsfw_val = is_nsfw
                 .get_untracked()
                 .map(|v| v.checked())
@@ -339,18 +205,13 @@ impl VideoUploadUploadButtonClicked {
                 .unwrap_or_default();
 
             create_effect(move |_| {
-                send_event(
-                    "video_upload_upload_button_clicked",
-                    &json!({
-                        "user_id": user.details.principal,
-                        "display_name": user.details.display_name.clone().unwrap_or_default(),
-                        "canister_id": user.canister_id,
-                        "creator_category": "NA",
-                        "hashtag_count": hashtag_count,
-                        "is_NSFW": is_nsfw_val,
-                        "is_hotorNot": is_hotornot_val,
-                    }),
-                );
+                send_event(AnalyticsEvent::VideoUploadUploadButtonClicked(
+                    UploadProps::new(user.clone()).with_post_settings(
+                        hashtag_count,
+                        is_nsfw_val,
+                        is_hotornot_val,
+                    ),
+                ));
             });
         }
     }
@@ -361,20 +222,14 @@ pub struct VideoUploadVideoSelected;
 
 impl VideoUploadVideoSelected {
     pub fn send_event(&self, cans_store: RwSignal<Option<Canisters<true>>>) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
             // video_upload_video_selected - analytics
             let user = user_details_can_store_or_ret!(cans_store);
 
-            send_event(
-                "video_upload_video_selected",
-                &json!({
-                    "user_id": user.details.principal,
-                    "display_name": user.details.display_name.unwrap_or_default(),
-                    "canister_id": user.canister_id,
-                    "creator_category": "NA",
-                }),
-            )
+            send_event(AnalyticsEvent::VideoUploadVideoSelected(UploadProps::new(
+                user,
+            )))
         }
     }
 }
@@ -392,24 +247,20 @@ impl VideoUploadUnsuccessful {
         enable_hot_or_not: bool,
         cans_store: RwSignal<Option<Canisters<true>>>,
     ) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
             // video_upload_unsuccessful - analytics
             let user = user_details_can_store_or_ret!(cans_store);
 
-            send_event(
-                "video_upload_unsuccessful",
-                &json!({
-                    "user_id": user.details.principal,
-                    "display_name": user.details.display_name.unwrap_or_default(),
-                    "canister_id": user.canister_id,
-                    "creator_category": "NA",
-                    "hashtag_count": hashtags_len,
-                    "is_NSFW": is_nsfw,
-                    "is_hotorNot": enable_hot_or_not,
-                    "fail_reason": error,
-                }),
-            );
+            let props = UploadProps {
+                fail_reason: Some(error),
+                ..UploadProps::new(user).with_post_settings(
+                    hashtags_len,
+                    is_nsfw,
+                    enable_hot_or_not,
+                )
+            };
+            send_event(AnalyticsEvent::VideoUploadUnsuccessful(props));
         }
     }
 }
@@ -427,26 +278,22 @@ impl VideoUploadSuccessful {
         post_id: u64,
         cans_store: RwSignal<Option<Canisters<true>>>,
     ) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
             // video_upload_successful - analytics
             let user = user_details_can_store_or_ret!(cans_store);
-            send_event(
-                "video_upload_successful",
-                &json!({
-                    "user_id": user.details.principal,
-                    "publisher_user_id": user.details.principal,
-                    "display_name": user.details.display_name,
-                    "canister_id": user.canister_id,
-                    "creator_category": "NA",
-                    "hashtag_count": hashtags_len,
-                    "is_NSFW": is_nsfw,
-                    "is_hotorNot": enable_hot_or_not,
-                    "is_filter_used": false,
-                    "video_id": video_id,
-                    "post_id": post_id,
-                }),
-            );
+            let props = UploadProps {
+                publisher_user_id: Some(user.details.principal),
+                is_filter_used: Some(false),
+                video_id: Some(video_id),
+                post_id: Some(post_id),
+                ..UploadProps::new(user).with_post_settings(
+                    hashtags_len,
+                    is_nsfw,
+                    enable_hot_or_not,
+                )
+            };
+            send_event(AnalyticsEvent::VideoUploadSuccessful(props));
         }
     }
 }
@@ -456,7 +303,7 @@ pub struct Refer;
 
 impl Refer {
     pub fn send_event(&self, logged_in: ReadSignal<bool>) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
             // refer - analytics
 
@@ -470,16 +317,13 @@ impl Refer {
             let prev_site = history_ctx.prev_url_untracked();
 
             // refer - analytics
-            send_event(
-                "refer",
-                &json!({
-                    "user_id":user_id,
-                    "is_loggedIn": logged_in.get_untracked(),
-                    "display_name": display_name,
-                    "canister_id": canister_id,
-                    "refer_location": prev_site,
-                }),
-            );
+            send_event(AnalyticsEvent::Refer(ReferProps {
+                user_id,
+                is_logged_in: logged_in.get_untracked(),
+                display_name,
+                canister_id,
+                refer_location: prev_site,
+            }));
         }
     }
 }
@@ -493,7 +337,7 @@ impl ReferShareLink {
         logged_in: ReadSignal<bool>,
         cans_store: RwSignal<Option<Canisters<true>>>,
     ) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
             // refer_share_link - analytics
             let user = user_details_can_store_or_ret!(cans_store);
@@ -507,16 +351,13 @@ impl ReferShareLink {
             let prev_site = history_ctx.prev_url_untracked();
 
             // refer_share_link - analytics
-            send_event(
-                "refer_share_link",
-                &json!({
-                    "user_id":user_id,
-                    "is_loggedIn": logged_in.get_untracked(),
-                    "display_name": display_name,
-                    "canister_id": canister_id,
-                    "refer_location": prev_site,
-                }),
-            );
+            send_event(AnalyticsEvent::ReferShareLink(ReferProps {
+                user_id,
+                is_logged_in: logged_in.get_untracked(),
+                display_name,
+                canister_id,
+                refer_location: prev_site,
+            }));
         }
     }
 }
@@ -526,25 +367,23 @@ pub struct LoginSuccessful;
 
 impl LoginSuccessful {
     pub fn send_event(&self, canisters: Canisters<true>) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
             // login_successful - analytics
 
             let user_id = canisters.identity().sender().unwrap();
             let canister_id = canisters.user_canister();
 
+            #[cfg(feature = "ga4")]
             send_user_id(user_id.to_string());
 
             // login_successful - analytics
-            send_event(
-                "login_successful",
-                &json!({
-                    "login_method": "google", // TODO: change this when more providers are added
-                    "user_id": user_id.to_string(),
-                    "canister_id": canister_id.to_string(),
-                    "is_new_user": false,                   // TODO: add this info
-                }),
-            );
+            send_event(AnalyticsEvent::LoginSuccessful(LoginSuccessfulProps {
+                login_method: "google", // TODO: change this when more providers are added
+                user_id,
+                canister_id,
+                is_new_user: false, // TODO: add this info
+            }));
         }
     }
 }
@@ -554,21 +393,20 @@ pub struct LoginMethodSelected;
 
 impl LoginMethodSelected {
     pub fn send_event(&self, prov: ProviderKind) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
             // login_method_selected - analytics
-            send_event(
-                "login_method_selected",
-                &json!({
-                    "login_method": match prov {
+            send_event(AnalyticsEvent::LoginMethodSelected(
+                LoginMethodSelectedProps {
+                    login_method: match prov {
                         #[cfg(feature = "local-auth")]
                         ProviderKind::LocalStorage => "local_storage",
                         #[cfg(any(feature = "oauth-ssr", feature = "oauth-hydrate"))]
                         ProviderKind::Google => "google",
                     },
-                    "attempt_count": 1,
-                }),
-            );
+                    attempt_count: 1,
+                },
+            ));
         }
     }
 }
@@ -578,7 +416,7 @@ pub struct LoginJoinOverlayViewed;
 
 impl LoginJoinOverlayViewed {
     pub fn send_event(&self) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
             // login_join_overlay_viewed - analytics
             let user = user_details_or_ret!();
@@ -586,14 +424,14 @@ impl LoginJoinOverlayViewed {
 
             let user_id = user.details.principal;
 
-            send_event(
-                "login_join_overlay_viewed",
-                &json!({
-                    "user_id_viewer": user_id,
-                    "previous_event": event_history.event_name.get_untracked(),
-                }),
-            );
+            send_event(AnalyticsEvent::LoginJoinOverlayViewed(
+                LoginJoinOverlayViewedProps {
+                    user_id_viewer: user_id,
+                    previous_event: event_history.event_name.get_untracked(),
+                },
+            ));
 
+            #[cfg(feature = "ga4")]
             send_user_id(user_id.to_string());
         }
     }
@@ -604,19 +442,16 @@ pub struct LoginCta;
 
 impl LoginCta {
     pub fn send_event(&self, cta_location: String) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
             // login_cta - analytics
 
             let event_history: EventHistory = expect_context();
 
-            send_event(
-                "login_cta",
-                &json!({
-                    "previous_event": event_history.event_name.get_untracked(),
-                    "cta_location": cta_location,
-                }),
-            );
+            send_event(AnalyticsEvent::LoginCta(LoginCtaProps {
+                previous_event: event_history.event_name.get_untracked(),
+                cta_location,
+            }));
         }
     }
 }
@@ -626,7 +461,7 @@ pub struct LogoutClicked;
 
 impl LogoutClicked {
     pub fn send_event(&self, cans_store: RwSignal<Option<Canisters<true>>>) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
             let user = user_details_can_store_or_ret!(cans_store);
             let details = user.details;
@@ -636,14 +471,11 @@ impl LogoutClicked {
             let display_name = details.display_name;
             let canister_id = user.canister_id;
 
-            send_event(
-                "logout_clicked",
-                &json!({
-                    "user_id_viewer": user_id,
-                    "display_name": display_name,
-                    "canister_id": canister_id,
-                }),
-            );
+            send_event(AnalyticsEvent::LogoutClicked(LogoutProps {
+                user_id_viewer: user_id,
+                display_name,
+                canister_id,
+            }));
         }
     }
 }
@@ -653,7 +485,7 @@ pub struct LogoutConfirmation;
 
 impl LogoutConfirmation {
     pub fn send_event(&self, cans_store: RwSignal<Option<Canisters<true>>>) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
             let user = user_details_can_store_or_ret!(cans_store);
             let details = user.details;
@@ -663,14 +495,11 @@ impl LogoutConfirmation {
             let canister_id = user.canister_id;
             // logout_confirmation - analytics
 
-            send_event(
-                "logout_confirmation",
-                &json!({
-                    "user_id_viewer": user_id,
-                    "display_name": display_name,
-                    "canister_id": canister_id,
-                }),
-            );
+            send_event(AnalyticsEvent::LogoutConfirmation(LogoutProps {
+                user_id_viewer: user_id,
+                display_name,
+                canister_id,
+            }));
         }
     }
 }
@@ -680,7 +509,7 @@ pub struct ErrorEvent;
 
 impl ErrorEvent {
     pub fn send_event(&self, error_str: String, cans_store: RwSignal<Option<Canisters<true>>>) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
             let event_history: EventHistory = expect_context();
             let user = user_details_can_store_or_ret!(cans_store);
@@ -690,15 +519,12 @@ impl ErrorEvent {
             let canister_id = user.canister_id;
 
             // error_event - analytics
-            send_event(
-                "error_event",
-                &json!({
-                    "user_id": user_id,
-                    "canister_id": canister_id,
-                    "description": error_str,
-                    "previous_event": event_history.event_name.get_untracked(),
-                }),
-            );
+            send_event(AnalyticsEvent::ErrorEvent(ErrorEventProps {
+                user_id,
+                canister_id,
+                description: error_str,
+                previous_event: event_history.event_name.get_untracked(),
+            }));
         }
     }
 }
@@ -712,7 +538,7 @@ impl ProfileViewVideo {
         post_details: PostDetails,
         cans_store: RwSignal<Option<Canisters<true>>>,
     ) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
             let publisher_user_id = post_details.poster_principal;
             let video_id = post_details.uid.clone();
@@ -721,18 +547,15 @@ impl ProfileViewVideo {
 
             let user = user_details_can_store_or_ret!(cans_store);
 
-            send_event(
-                "profile_view_video",
-                &json!({
-                    "publisher_user_id":publisher_user_id,
-                    "user_id": user.details.principal,
-                    "is_loggedIn": is_connected(),
-                    "display_name": user.details.display_name,
-                    "canister_id": user.canister_id,
-                    "video_id": video_id,
-                    "profile_feed": "main",
-                }),
-            );
+            send_event(AnalyticsEvent::ProfileViewVideo(ProfileViewVideoProps {
+                publisher_user_id,
+                user_id: user.details.principal,
+                is_logged_in: is_connected(),
+                display_name: user.details.display_name,
+                canister_id: user.canister_id,
+                video_id,
+                profile_feed: "main",
+            }));
         }
     }
 }
@@ -746,7 +569,7 @@ impl TokenCreationStarted {
         sns_init_payload: SnsInitPayload,
         cans_store: RwSignal<Option<Canisters<true>>>,
     ) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
             let user = user_details_can_store_or_ret!(cans_store);
             let details = user.details;
@@ -755,16 +578,17 @@ impl TokenCreationStarted {
             let canister_id = user.canister_id;
 
             // token_creation_started - analytics
-            send_event(
-                "token_creation_started",
-                &json!({
-                    "user_id": user_id,
-                    "canister_id": canister_id,
-                    "token_name": sns_init_payload.token_name,
-                    "token_symbol": sns_init_payload.token_symbol,
-                    "name": sns_init_payload.name
-                }),
-            );
+            send_event(AnalyticsEvent::TokenCreationStarted(TokenCreationProps {
+                user_id,
+                canister_id,
+                token_name: sns_init_payload.token_name,
+                token_symbol: sns_init_payload.token_symbol,
+                name: sns_init_payload.name,
+                description: None,
+                logo: None,
+                link: None,
+                error: None,
+            }));
         }
     }
 }
@@ -780,28 +604,23 @@ impl TokenCreationCompleted {
         profile_details: ProfileDetails,
         canister_id: Principal,
     ) {
-        #[cfg(feature = "ga4")]
-        {
-            let user_id = profile_details.principal;
-
-            let link = format!("/token/info/{token_root}");
-
-            // token_creation_completed - analytics
-            send_event_warehouse_ssr(
-                "token_creation_completed",
-                &json!({
-                    "user_id": user_id,
-                    "canister_id": canister_id,
-                    "token_name": sns_init_payload.token_name,
-                    "token_symbol": sns_init_payload.token_symbol,
-                    "name": sns_init_payload.name,
-                    "description": sns_init_payload.description,
-                    "logo": sns_init_payload.logo,
-                    "link": link,
-                }),
-            )
-            .await;
-        }
+        let user_id = profile_details.principal;
+
+        let link = format!("/token/info/{token_root}");
+
+        // token_creation_completed - analytics
+        send_event_warehouse_ssr(AnalyticsEvent::TokenCreationCompleted(TokenCreationProps {
+            user_id,
+            canister_id,
+            token_name: sns_init_payload.token_name,
+            token_symbol: sns_init_payload.token_symbol,
+            name: sns_init_payload.name,
+            description: sns_init_payload.description,
+            logo: sns_init_payload.logo,
+            link: Some(link),
+            error: None,
+        }))
+        .await;
     }
 }
 
@@ -816,25 +635,21 @@ impl TokenCreationFailed {
         profile_details: ProfileDetails,
         canister_id: Principal,
     ) {
-        #[cfg(feature = "ga4")]
-        {
-            let user_id = profile_details.principal;
-
-            // token_creation_failed - analytics
-            send_event_warehouse_ssr(
-                "token_creation_failed",
-                &json!({
-                    "user_id": user_id,
-                    "canister_id": canister_id,
-                    "token_name": sns_init_payload.token_name,
-                    "token_symbol": sns_init_payload.token_symbol,
-                    "name": sns_init_payload.name,
-                    "description": sns_init_payload.description,
-                    "error": error_str
-                }),
-            )
-            .await;
-        }
+        let user_id = profile_details.principal;
+
+        // token_creation_failed - analytics
+        send_event_warehouse_ssr(AnalyticsEvent::TokenCreationFailed(TokenCreationProps {
+            user_id,
+            canister_id,
+            token_name: sns_init_payload.token_name,
+            token_symbol: sns_init_payload.token_symbol,
+            name: sns_init_payload.name,
+            description: sns_init_payload.description,
+            logo: None,
+            link: None,
+            error: Some(error_str),
+        }))
+        .await;
     }
 }
 
@@ -843,7 +658,7 @@ pub struct TokensClaimedFromNeuron;
 
 impl TokensClaimedFromNeuron {
     pub fn send_event(&self, amount: u64, cans_store: Canisters<true>) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
             let details = cans_store.profile_details();
 
@@ -851,14 +666,13 @@ impl TokensClaimedFromNeuron {
             let canister_id = cans_store.user_canister();
 
             // tokens_claimed_from_neuron - analytics
-            send_event(
-                "tokens_claimed_from_neuron",
-                &json!({
-                    "user_id": user_id,
-                    "canister_id": canister_id,
-                    "amount": amount
-                }),
-            );
+            send_event(AnalyticsEvent::TokensClaimedFromNeuron(
+                TokensClaimedProps {
+                    user_id,
+                    canister_id,
+                    amount,
+                },
+            ));
         }
     }
 }
@@ -868,7 +682,7 @@ pub struct TokensTransferred;
 
 impl TokensTransferred {
     pub fn send_event(&self, amount: String, to: Principal, cans_store: Canisters<true>) {
-        #[cfg(all(feature = "hydrate", feature = "ga4"))]
+        #[cfg(feature = "hydrate")]
         {
             let details = cans_store.profile_details();
 
@@ -876,15 +690,12 @@ impl TokensTransferred {
             let canister_id = cans_store.user_canister();
 
             // tokens_transferred - analytics
-            send_event(
-                "tokens_transferred",
-                &json!({
-                    "user_id": user_id,
-                    "canister_id": canister_id,
-                    "amount": amount,
-                    "to": to
-                }),
-            );
+            send_event(AnalyticsEvent::TokensTransferred(TokensTransferredProps {
+                user_id,
+                canister_id,
+                amount,
+                to,
+            }));
         }
     }
 }
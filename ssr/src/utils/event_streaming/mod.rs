@@ -1,13 +1,15 @@
-use std::env;
-
-use gloo_utils::format::JsValueSerdeExt;
 use leptos::*;
-use serde_json::json;
 use wasm_bindgen::prelude::*;
 
-use crate::consts::GTAG_MEASUREMENT_ID;
-
+pub mod consent;
 pub mod events;
+pub mod queue;
+pub mod schema;
+#[cfg(feature = "ssr")]
+pub mod sinks;
+use consent::{apply_consent, ConsentCtx, TrackingConsent};
+use queue::EventQueue;
+use schema::{AnalyticsEvent, WireEvent};
 
 #[cfg(feature = "ssr")]
 pub mod warehouse_events {
@@ -25,27 +27,80 @@ pub struct EventHistory {
     pub event_name: RwSignal<String>,
 }
 
-#[cfg(feature = "ga4")]
-pub fn send_event(event_name: &str, params: &serde_json::Value) {
-    use super::host::get_host;
+/// Consent given in this browser, `Unset` outside of the app
+fn local_consent() -> TrackingConsent {
+    use_context::<ConsentCtx>()
+        .map(|ctx| ctx.consent.get_untracked())
+        .unwrap_or_default()
+}
 
-    let event_history: EventHistory = expect_context();
+/// Client id gtag assigned to this browser, from its `_ga` cookie (`GA1.1.<client id>`)
+#[cfg(feature = "hydrate")]
+pub fn ga_client_id() -> Option<String> {
+    use web_sys::HtmlDocument;
+
+    let cookies = document().dyn_into::<HtmlDocument>().ok()?.cookie().ok()?;
+    let ga = cookies
+        .split(';')
+        .find_map(|cookie| cookie.trim().strip_prefix("_ga="))?;
+    let mut parts = ga.splitn(3, '.');
+    parts.next()?;
+    parts.next()?;
+    parts.next().map(str::to_string)
+}
+
+#[cfg(not(feature = "hydrate"))]
+pub fn ga_client_id() -> Option<String> {
+    None
+}
 
-    event_history.event_name.set(event_name.to_string());
+/// Random id for an event queued in this browser, events sent by the server are never retried
+#[cfg(feature = "hydrate")]
+pub fn new_event_id() -> Option<String> {
+    let random = || (js_sys::Math::random() * u32::MAX as f64) as u32;
+    Some(format!(
+        "{:x}-{:08x}{:08x}",
+        js_sys::Date::now() as u64,
+        random(),
+        random()
+    ))
+}
+
+#[cfg(not(feature = "hydrate"))]
+pub fn new_event_id() -> Option<String> {
+    None
+}
+
+fn client_wire_event(event: AnalyticsEvent) -> WireEvent {
+    use super::host::get_host;
 
-    let host_str = get_host();
-    let mut params = params.clone();
-    params["host"] = json!(host_str);
+    let mut event = event.into_wire(&get_host());
+    event.client_id = ga_client_id();
+    event.event_id = new_event_id();
+    apply_consent(&mut event, local_consent());
+    event
+}
+
+/// Queue an event for the sinks configured on the server
+pub fn send_event(event: AnalyticsEvent) {
+    let event = client_wire_event(event);
 
-    // Warehouse
-    send_event_warehouse(event_name, &params);
+    let event_history: EventHistory = expect_context();
+    event_history.event_name.set(event.name.clone());
 
-    // gtag GA4
-    gtag("event", event_name, &JsValue::from_serde(&params).unwrap());
+    expect_context::<EventQueue>().push(event);
 }
 
 #[cfg(feature = "ga4")]
 pub fn send_user_id(user_id: String) {
+    use gloo_utils::format::JsValueSerdeExt;
+    use serde_json::json;
+
+    use crate::consts::GTAG_MEASUREMENT_ID;
+
+    if !local_consent().allows_pii() {
+        return;
+    }
     let gtag_measurement_id = GTAG_MEASUREMENT_ID.as_ref();
 
     gtag(
@@ -58,73 +113,41 @@ pub fn send_user_id(user_id: String) {
     );
 }
 
-#[cfg(feature = "ga4")]
-pub fn send_event_warehouse(event_name: &str, params: &serde_json::Value) {
-    use super::host::get_host;
-
-    let event_name = event_name.to_string();
-
-    let mut params = params.clone();
-    if params["host"].is_null() {
-        let host_str = get_host();
-        params["host"] = json!(host_str);
-    }
-
-    let params_str = params.to_string();
-
-    spawn_local(async move {
-        stream_to_offchain_agent(event_name, params_str)
-            .await
-            .unwrap();
-    });
+/// Queue an event without recording it as the previous event
+pub fn send_event_warehouse(event: AnalyticsEvent) {
+    expect_context::<EventQueue>().push(client_wire_event(event));
 }
 
-#[cfg(feature = "ga4")]
-pub async fn send_event_warehouse_ssr(event_name: &str, params: &serde_json::Value) {
+/// Send an event right away, for events raised by server functions
+pub async fn send_event_warehouse_ssr(event: AnalyticsEvent) {
     use super::host::get_host;
 
-    let event_name = event_name.to_string();
-
-    let mut params = params.clone();
-    if params["host"].is_null() {
-        let host_str = get_host();
-        params["host"] = json!(host_str);
+    // `stream_event_batch` applies the consent of the user on the server
+    #[cfg_attr(feature = "ssr", allow(unused_mut))]
+    let mut event = event.into_wire(&get_host());
+    #[cfg(not(feature = "ssr"))]
+    apply_consent(&mut event, local_consent());
+    if let Err(e) = stream_event_batch(vec![event]).await {
+        log::warn!("failed to send analytics event: {e}");
     }
-
-    let params_str = params.to_string();
-
-    stream_to_offchain_agent(event_name, params_str)
-        .await
-        .unwrap();
 }
 
-#[cfg(feature = "ga4")]
-#[server]
-pub async fn stream_to_offchain_agent(event: String, params: String) -> Result<(), ServerFnError> {
-    use tonic::metadata::MetadataValue;
-    use tonic::transport::Channel;
-    use tonic::Request;
-
-    let channel: Channel = expect_context();
-
-    let mut off_chain_agent_grpc_auth_token = env::var("GRPC_AUTH_TOKEN").expect("GRPC_AUTH_TOKEN");
-    // removing whitespaces and new lines for proper parsing
-    off_chain_agent_grpc_auth_token.retain(|c| !c.is_whitespace());
+#[server(
+    input = Json
+)]
+pub async fn stream_event_batch(mut events: Vec<WireEvent>) -> Result<(), ServerFnError> {
+    use crate::auth::server_impl::store::KVStoreImpl;
+    use queue::FLUSH_BATCH_SIZE;
+    use sinks::AnalyticsSinks;
 
-    let token: MetadataValue<_> = format!("Bearer {}", off_chain_agent_grpc_auth_token).parse()?;
-
-    let mut client =
-        warehouse_events::warehouse_events_client::WarehouseEventsClient::with_interceptor(
-            channel,
-            move |mut req: Request<()>| {
-                req.metadata_mut().insert("authorization", token.clone());
-                Ok(req)
-            },
-        );
-
-    let request = tonic::Request::new(warehouse_events::WarehouseEvent { event, params });
-
-    client.send_event(request).await?;
+    if events.len() > FLUSH_BATCH_SIZE {
+        return Err(ServerFnError::Args("Too many events".into()));
+    }
+    // Clients may be outdated or modified, or have queued the events before consent was revoked
+    consent::server_impl::enforce_consent(&mut events).await;
+    let sinks: AnalyticsSinks = expect_context();
+    let kv: KVStoreImpl = expect_context();
+    sinks.send_batch(&kv, &events).await?;
 
     Ok(())
 }
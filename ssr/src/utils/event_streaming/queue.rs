@@ -0,0 +1,113 @@
+//! Client side batching of warehouse events
+//!
+//! Events stay in local storage until the server acknowledges them, so they survive
+//! reloads, closed tabs and network failures. Delivery is at least once,
+//! batches the server rejects as malformed are dropped rather than retried.
+use codee::string::JsonSerdeCodec;
+use leptos::*;
+use leptos_use::{storage::use_local_storage, use_document, use_event_listener, use_interval_fn};
+
+use super::schema::WireEvent;
+
+const EVENT_QUEUE_STORE: &str = "analytics-event-queue";
+/// Flush as soon as a full batch is queued, also the upper bound of a single request
+pub const FLUSH_BATCH_SIZE: usize = 20;
+const FLUSH_INTERVAL_MS: u64 = 10_000;
+/// Oldest events are dropped past this, i.e after being offline for long
+const MAX_QUEUED_EVENTS: usize = 500;
+
+/// Network and server failures, anything else means the batch itself is bad
+fn is_retryable(e: &ServerFnError) -> bool {
+    matches!(
+        e,
+        ServerFnError::Request(_)
+            | ServerFnError::Response(_)
+            | ServerFnError::ServerError(_)
+            | ServerFnError::WrappedServerError(_)
+    )
+}
+
+#[derive(Clone, Copy)]
+pub struct EventQueue {
+    queue: Signal<Vec<WireEvent>>,
+    set_queue: WriteSignal<Vec<WireEvent>>,
+    flushing: StoredValue<bool>,
+}
+
+impl Default for EventQueue {
+    /// Should only be created once, at the root of the app
+    fn default() -> Self {
+        let (queue, set_queue, _) =
+            use_local_storage::<Vec<WireEvent>, JsonSerdeCodec>(EVENT_QUEUE_STORE);
+        let this = Self {
+            queue,
+            set_queue,
+            flushing: store_value(false),
+        };
+
+        // Also picks up events left over from a previous visit
+        _ = use_interval_fn(move || this.flush(), FLUSH_INTERVAL_MS);
+        _ = use_event_listener(use_document(), ev::visibilitychange, move |_| {
+            if document().hidden() {
+                this.flush();
+            }
+        });
+
+        this
+    }
+}
+
+impl EventQueue {
+    pub fn push(&self, event: WireEvent) {
+        self.set_queue.update(|q| {
+            q.push(event);
+            let overflow = q.len().saturating_sub(MAX_QUEUED_EVENTS);
+            q.drain(..overflow);
+        });
+        if self.queue.with_untracked(Vec::len) >= FLUSH_BATCH_SIZE {
+            self.flush();
+        }
+    }
+
+    /// Drop every queued event
+    pub fn clear(&self) {
+        self.set_queue.set(vec![]);
+    }
+
+    /// Send the oldest batch, no-op if a flush is already in flight
+    pub fn flush(&self) {
+        if self.flushing.get_value() {
+            return;
+        }
+        let batch: Vec<_> = self
+            .queue
+            .with_untracked(|q| q.iter().take(FLUSH_BATCH_SIZE).cloned().collect());
+        if batch.is_empty() {
+            return;
+        }
+
+        self.flushing.set_value(true);
+        let this = *self;
+        spawn_local(async move {
+            let res = super::stream_event_batch(batch.clone()).await;
+            this.flushing.set_value(false);
+            match res {
+                Ok(()) => (),
+                Err(e) if is_retryable(&e) => {
+                    log::warn!("failed to flush analytics events, retrying later: {e}");
+                    return;
+                }
+                // The server would reject it again, keeping it would block every later batch
+                Err(e) => log::warn!("dropping {} rejected analytics events: {e}", batch.len()),
+            }
+            // Events may have been pushed (or dropped) while the batch was in flight
+            this.set_queue.update(|q| {
+                let sent = q.iter().zip(&batch).take_while(|(a, b)| a == b).count();
+                q.drain(..sent);
+            });
+            if this.queue.with_untracked(Vec::len) >= FLUSH_BATCH_SIZE {
+                this.flush();
+            }
+        });
+    }
+}
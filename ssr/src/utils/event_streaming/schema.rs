@@ -0,0 +1,282 @@
+//! Typed analytics events
+//!
+//! Param names match the ones already stored in the warehouse and GA4,
+//! bump [EVENT_SCHEMA_VERSION] whenever a payload changes in a breaking way.
+use candid::Principal;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{posts::PostDetails, user::UserDetails};
+
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+const NOT_AVAILABLE: &str = "NA";
+
+/// The viewer and the post an event is about
+#[derive(Serialize, Clone, Debug)]
+pub struct VideoProps {
+    pub publisher_user_id: Option<Principal>,
+    pub user_id: Principal,
+    #[serde(rename = "is_loggedIn")]
+    pub is_logged_in: bool,
+    pub display_name: Option<String>,
+    pub canister_id: Principal,
+    pub video_id: Option<String>,
+    pub video_category: &'static str,
+    pub creator_category: &'static str,
+    pub hashtag_count: Option<usize>,
+    #[serde(rename = "is_NSFW")]
+    pub is_nsfw: Option<bool>,
+    #[serde(rename = "is_hotorNot")]
+    pub is_hot_or_not: Option<bool>,
+    pub feed_type: &'static str,
+    pub view_count: Option<u64>,
+    pub like_count: Option<u64>,
+    pub share_count: u64,
+    pub post_id: Option<u64>,
+    pub publisher_canister_id: Option<Principal>,
+}
+
+impl VideoProps {
+    pub fn new(user: UserDetails, is_logged_in: bool, post: Option<&PostDetails>) -> Self {
+        Self {
+            publisher_user_id: post.map(|p| p.poster_principal),
+            user_id: user.details.principal,
+            is_logged_in,
+            display_name: user.details.display_name,
+            canister_id: user.canister_id,
+            video_id: post.map(|p| p.uid.clone()),
+            video_category: NOT_AVAILABLE,
+            creator_category: NOT_AVAILABLE,
+            hashtag_count: post.map(|p| p.hastags.len()),
+            is_nsfw: post.map(|p| p.is_nsfw),
+            is_hot_or_not: post.map(|p| p.is_hot_or_not()),
+            feed_type: NOT_AVAILABLE,
+            view_count: post.map(|p| p.views),
+            like_count: post.map(|p| p.likes),
+            share_count: 0,
+            post_id: post.map(|p| p.post_id),
+            publisher_canister_id: post.map(|p| p.canister_id),
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct VideoWatchedProps {
+    #[serde(flatten)]
+    pub video: VideoProps,
+    pub percentage_watched: f64,
+    pub absolute_watched: f64,
+    pub video_duration: f64,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ProfileViewVideoProps {
+    pub publisher_user_id: Principal,
+    pub user_id: Principal,
+    #[serde(rename = "is_loggedIn")]
+    pub is_logged_in: bool,
+    pub display_name: Option<String>,
+    pub canister_id: Principal,
+    pub video_id: String,
+    pub profile_feed: &'static str,
+}
+
+/// The uploader and, once known, the upload
+#[derive(Serialize, Clone, Debug)]
+pub struct UploadProps {
+    pub user_id: Principal,
+    pub display_name: Option<String>,
+    pub canister_id: Principal,
+    pub creator_category: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hashtag_count: Option<usize>,
+    #[serde(rename = "is_NSFW", skip_serializing_if = "Option::is_none")]
+    pub is_nsfw: Option<bool>,
+    #[serde(rename = "is_hotorNot", skip_serializing_if = "Option::is_none")]
+    pub is_hot_or_not: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fail_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publisher_user_id: Option<Principal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_filter_used: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_id: Option<u64>,
+}
+
+impl UploadProps {
+    pub fn new(user: UserDetails) -> Self {
+        Self {
+            user_id: user.details.principal,
+            display_name: user.details.display_name,
+            canister_id: user.canister_id,
+            creator_category: NOT_AVAILABLE,
+            hashtag_count: None,
+            is_nsfw: None,
+            is_hot_or_not: None,
+            fail_reason: None,
+            publisher_user_id: None,
+            is_filter_used: None,
+            video_id: None,
+            post_id: None,
+        }
+    }
+
+    pub fn with_post_settings(
+        mut self,
+        hashtag_count: usize,
+        is_nsfw: bool,
+        is_hot_or_not: bool,
+    ) -> Self {
+        self.hashtag_count = Some(hashtag_count);
+        self.is_nsfw = Some(is_nsfw);
+        self.is_hot_or_not = Some(is_hot_or_not);
+        self
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ReferProps {
+    pub user_id: Principal,
+    #[serde(rename = "is_loggedIn")]
+    pub is_logged_in: bool,
+    pub display_name: Option<String>,
+    pub canister_id: Principal,
+    pub refer_location: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct LoginSuccessfulProps {
+    pub login_method: &'static str,
+    pub user_id: Principal,
+    pub canister_id: Principal,
+    pub is_new_user: bool,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct LoginMethodSelectedProps {
+    pub login_method: &'static str,
+    pub attempt_count: u32,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct LoginJoinOverlayViewedProps {
+    pub user_id_viewer: Principal,
+    pub previous_event: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct LoginCtaProps {
+    pub previous_event: String,
+    pub cta_location: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct LogoutProps {
+    pub user_id_viewer: Principal,
+    pub display_name: Option<String>,
+    pub canister_id: Principal,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ErrorEventProps {
+    pub user_id: Principal,
+    pub canister_id: Principal,
+    pub description: String,
+    pub previous_event: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct TokenCreationProps {
+    pub user_id: Principal,
+    pub canister_id: Principal,
+    pub token_name: Option<String>,
+    pub token_symbol: Option<String>,
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logo: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct TokensClaimedProps {
+    pub user_id: Principal,
+    pub canister_id: Principal,
+    pub amount: u64,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct TokensTransferredProps {
+    pub user_id: Principal,
+    pub canister_id: Principal,
+    pub amount: String,
+    pub to: Principal,
+}
+
+/// Every event sent to GA4 and the warehouse, the variant name is the event name
+#[derive(Serialize, Clone, Debug)]
+#[serde(tag = "event", content = "params", rename_all = "snake_case")]
+pub enum AnalyticsEvent {
+    VideoViewed(VideoProps),
+    VideoDurationWatched(VideoWatchedProps),
+    LikeVideo(VideoProps),
+    ShareVideo(VideoProps),
+    VideoUploadInitiated(UploadProps),
+    VideoUploadUploadButtonClicked(UploadProps),
+    VideoUploadVideoSelected(UploadProps),
+    VideoUploadUnsuccessful(UploadProps),
+    VideoUploadSuccessful(UploadProps),
+    Refer(ReferProps),
+    ReferShareLink(ReferProps),
+    LoginSuccessful(LoginSuccessfulProps),
+    LoginMethodSelected(LoginMethodSelectedProps),
+    LoginJoinOverlayViewed(LoginJoinOverlayViewedProps),
+    LoginCta(LoginCtaProps),
+    LogoutClicked(LogoutProps),
+    LogoutConfirmation(LogoutProps),
+    ErrorEvent(ErrorEventProps),
+    ProfileViewVideo(ProfileViewVideoProps),
+    TokenCreationStarted(TokenCreationProps),
+    TokenCreationCompleted(TokenCreationProps),
+    TokenCreationFailed(TokenCreationProps),
+    TokensClaimedFromNeuron(TokensClaimedProps),
+    TokensTransferred(TokensTransferredProps),
+}
+
+/// An event as queued and sent to the server
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WireEvent {
+    pub name: String,
+    /// JSON object of params, including `host` and `schema_version`
+    pub params: serde_json::Value,
+    /// GA4 client id of the browser that raised the event, see [super::ga_client_id]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    /// Unique per event, lets sinks drop the copies of a retried batch, see [super::new_event_id]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
+}
+
+impl AnalyticsEvent {
+    pub fn into_wire(self, host: &str) -> WireEvent {
+        let mut tagged = serde_json::to_value(self).expect("analytics events are serializable");
+        let name = tagged["event"].as_str().unwrap_or_default().to_string();
+        let mut params = tagged["params"].take();
+        params["host"] = host.into();
+        params["schema_version"] = EVENT_SCHEMA_VERSION.into();
+
+        WireEvent {
+            name,
+            params,
+            client_id: None,
+            event_id: None,
+        }
+    }
+}
@@ -0,0 +1,323 @@
+//! Destinations of analytics events, selected at startup by `init::AppStateBuilder`
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::Write,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use enum_dispatch::enum_dispatch;
+use futures::future::join_all;
+use reqwest::Url;
+use serde_json::json;
+use thiserror::Error;
+use tonic::{
+    metadata::AsciiMetadataValue,
+    transport::{Channel, ClientTlsConfig},
+    Request,
+};
+
+use super::{schema::WireEvent, warehouse_events};
+use crate::auth::server_impl::store::{KVStore, KVStoreImpl};
+
+const DELIVERED_PREFIX: &str = "analytics-delivered";
+/// How long a sink remembers the events it accepted, well past the client's retries
+const DELIVERED_TTL: Duration = Duration::from_secs(2 * 24 * 60 * 60);
+
+#[derive(Error, Debug)]
+pub enum AnalyticsError {
+    #[error("grpc: {0}")]
+    Grpc(#[from] tonic::Status),
+    #[error("http: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[enum_dispatch]
+pub(crate) trait AnalyticsSink: Send {
+    fn name(&self) -> &'static str;
+    async fn send_batch(&self, events: &[WireEvent]) -> Result<(), AnalyticsError>;
+}
+
+/// GA4 Measurement Protocol
+#[derive(Clone)]
+pub struct Ga4Sink {
+    client: reqwest::Client,
+    collect_url: Url,
+}
+
+impl Ga4Sink {
+    pub fn new(measurement_id: &str, api_secret: &str) -> Self {
+        let mut collect_url = Url::parse("https://www.google-analytics.com/mp/collect").unwrap();
+        collect_url
+            .query_pairs_mut()
+            .append_pair("measurement_id", measurement_id)
+            .append_pair("api_secret", api_secret);
+        Self {
+            client: Default::default(),
+            collect_url,
+        }
+    }
+}
+
+impl AnalyticsSink for Ga4Sink {
+    fn name(&self) -> &'static str {
+        "ga4"
+    }
+
+    async fn send_batch(&self, events: &[WireEvent]) -> Result<(), AnalyticsError> {
+        // GA4 attributes events to the browser's gtag client, events raised
+        // without one (server side or without consent) are not sent to GA4
+        let mut by_client = HashMap::<String, Vec<_>>::new();
+        for event in events {
+            let Some(client_id) = &event.client_id else {
+                continue;
+            };
+            by_client.entry(client_id.clone()).or_default().push(json!({
+                "name": event.name,
+                "params": event.params,
+            }));
+        }
+
+        for (client_id, events) in by_client {
+            self.client
+                .post(self.collect_url.clone())
+                .json(&json!({
+                    "client_id": client_id,
+                    "events": events,
+                }))
+                .send()
+                .await?
+                .error_for_status()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The off-chain agent's `warehouse_events` gRPC service
+#[derive(Clone)]
+pub struct WarehouseSink {
+    channel: Channel,
+    auth_token: AsciiMetadataValue,
+}
+
+impl WarehouseSink {
+    pub fn new(url: &Url, auth_token: &str) -> Self {
+        let tls_config = ClientTlsConfig::new().with_webpki_roots();
+        let channel = Channel::from_shared(url.to_string())
+            .expect("Invalid warehouse url")
+            .tls_config(tls_config)
+            .expect("Couldn't update TLS config for off-chain agent")
+            .connect_lazy();
+        // removing whitespaces and new lines for proper parsing
+        let auth_token: String = auth_token.chars().filter(|c| !c.is_whitespace()).collect();
+
+        Self {
+            channel,
+            auth_token: format!("Bearer {auth_token}")
+                .parse()
+                .expect("Invalid warehouse auth token"),
+        }
+    }
+}
+
+impl AnalyticsSink for WarehouseSink {
+    fn name(&self) -> &'static str {
+        "warehouse"
+    }
+
+    async fn send_batch(&self, events: &[WireEvent]) -> Result<(), AnalyticsError> {
+        let token = self.auth_token.clone();
+        let mut client =
+            warehouse_events::warehouse_events_client::WarehouseEventsClient::with_interceptor(
+                self.channel.clone(),
+                move |mut req: Request<()>| {
+                    req.metadata_mut().insert("authorization", token.clone());
+                    Ok(req)
+                },
+            );
+
+        for event in events {
+            // lets the warehouse drop events resent after a partial failure of this loop
+            let mut params = event.params.clone();
+            if let Some(event_id) = &event.event_id {
+                params["event_id"] = event_id.as_str().into();
+            }
+            let request = Request::new(warehouse_events::WarehouseEvent {
+                event: event.name.clone(),
+                params: params.to_string(),
+            });
+            client.send_event(request).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Generic collector, receives `{"events": [{"name", "params"}]}` as a JSON POST
+#[derive(Clone)]
+pub struct HttpSink {
+    client: reqwest::Client,
+    url: Url,
+    auth_token: Option<String>,
+}
+
+impl HttpSink {
+    pub fn new(url: Url, auth_token: Option<String>) -> Self {
+        Self {
+            client: Default::default(),
+            url,
+            auth_token,
+        }
+    }
+}
+
+impl AnalyticsSink for HttpSink {
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    async fn send_batch(&self, events: &[WireEvent]) -> Result<(), AnalyticsError> {
+        let mut req = self
+            .client
+            .post(self.url.clone())
+            .json(&json!({ "events": events }));
+        if let Some(token) = &self.auth_token {
+            req = req.bearer_auth(token);
+        }
+        req.send().await?.error_for_status()?;
+
+        Ok(())
+    }
+}
+
+/// JSON lines written to a file or stdout, for local development
+#[derive(Clone, Default)]
+pub struct LogSink {
+    file: Option<Arc<Mutex<File>>>,
+}
+
+impl LogSink {
+    pub fn to_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            file: Some(Arc::new(Mutex::new(file))),
+        })
+    }
+}
+
+impl AnalyticsSink for LogSink {
+    fn name(&self) -> &'static str {
+        "log"
+    }
+
+    async fn send_batch(&self, events: &[WireEvent]) -> Result<(), AnalyticsError> {
+        let mut lines = String::new();
+        for event in events {
+            lines.push_str(&serde_json::to_string(event).expect("events are serializable"));
+            lines.push('\n');
+        }
+        match &self.file {
+            Some(file) => file.lock().unwrap().write_all(lines.as_bytes())?,
+            None => print!("{lines}"),
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+#[enum_dispatch(AnalyticsSink)]
+pub enum AnalyticsSinkImpl {
+    Ga4(Ga4Sink),
+    Warehouse(WarehouseSink),
+    Http(HttpSink),
+    Log(LogSink),
+}
+
+fn delivered_key(sink: &str, event_id: &str) -> String {
+    format!("{DELIVERED_PREFIX}:{sink}:{event_id}")
+}
+
+/// All configured sinks, events are fanned out to each of them
+#[derive(Clone, Default)]
+pub struct AnalyticsSinks(pub Vec<AnalyticsSinkImpl>);
+
+impl AnalyticsSinks {
+    /// Events of the batch that `sink` has not accepted yet
+    async fn pending_for(
+        kv: &KVStoreImpl,
+        sink: &AnalyticsSinkImpl,
+        events: &[WireEvent],
+    ) -> Vec<WireEvent> {
+        let delivered = join_all(events.iter().map(|event| async move {
+            let event_id = event.event_id.as_ref()?;
+            match kv.read(delivered_key(sink.name(), event_id)).await {
+                Ok(res) => res.map(|_| ()),
+                Err(e) => {
+                    log::warn!("failed to read analytics delivery: {e}");
+                    None
+                }
+            }
+        }))
+        .await;
+
+        events
+            .iter()
+            .zip(delivered)
+            .filter(|(_, delivered)| delivered.is_none())
+            .map(|(event, _)| event.clone())
+            .collect()
+    }
+
+    async fn send_to(
+        kv: &KVStoreImpl,
+        sink: &AnalyticsSinkImpl,
+        events: &[WireEvent],
+    ) -> Result<(), AnalyticsError> {
+        let pending = Self::pending_for(kv, sink, events).await;
+        if pending.is_empty() {
+            return Ok(());
+        }
+        sink.send_batch(&pending).await?;
+
+        for event_id in pending.iter().filter_map(|e| e.event_id.clone()) {
+            let res = kv
+                .write_with_ttl(
+                    delivered_key(sink.name(), &event_id),
+                    String::new(),
+                    DELIVERED_TTL,
+                )
+                .await;
+            if let Err(e) = res {
+                log::warn!("failed to record analytics delivery: {e}");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fails if any sink failed, the client then retries the batch later.
+    /// Delivery is tracked per sink and event id,
+    /// so the retry only reaches the sinks that haven't accepted it yet
+    pub async fn send_batch(
+        &self,
+        kv: &KVStoreImpl,
+        events: &[WireEvent],
+    ) -> Result<(), AnalyticsError> {
+        let results = join_all(self.0.iter().map(|sink| async move {
+            let res = Self::send_to(kv, sink, events).await;
+            if let Err(e) = &res {
+                log::warn!("analytics sink {} failed: {e}", sink.name());
+            }
+            res
+        }))
+        .await;
+
+        results.into_iter().collect()
+    }
+}
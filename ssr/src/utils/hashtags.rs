@@ -0,0 +1,424 @@
+//! Hashtag parsing and normalization, shared by the upload form and the server
+//!
+//! Tags are stored normalized: NFKC, lowercase, without the leading `#`.
+//! Only letters, numbers (in any script) and `_` are allowed, plus the combining
+//! marks and joiners that scripts like Devanagari need inside a word.
+use leptos::{server, ServerFnError};
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+use crate::state::canisters::CanistersAuthWire;
+
+pub const MAX_HASHTAGS: usize = 8;
+/// Length bounds of a single tag, in characters
+pub const MIN_HASHTAG_LEN: usize = 2;
+pub const MAX_HASHTAG_LEN: usize = 30;
+pub const MAX_HASHTAG_SUGGESTIONS: usize = 6;
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum HashtagError {
+    #[error("Hashtags are required")]
+    Empty,
+    #[error("Only a maximum of {MAX_HASHTAGS} hashtags are allowed")]
+    TooMany,
+    #[error("#{0} is too short, hashtags need at least {MIN_HASHTAG_LEN} characters")]
+    TooShort(String),
+    #[error("#{0} is too long, hashtags can have at most {MAX_HASHTAG_LEN} characters")]
+    TooLong(String),
+    #[error("#{0} can only contain letters, numbers and _")]
+    InvalidChars(String),
+}
+
+fn starts_hashtag(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Enclosing marks (Me) are combining marks too, but only decorate symbols
+fn is_enclosing_mark(c: char) -> bool {
+    matches!(c, '\u{0488}'..='\u{0489}' | '\u{1ABE}' | '\u{20DD}'..='\u{20E0}' | '\u{20E2}'..='\u{20E4}' | '\u{A670}'..='\u{A672}')
+}
+
+/// Nonspacing and spacing marks (Mn, Mc) i.e the virama in `नमस्ते`, and ZWJ/ZWNJ
+fn is_joining_char(c: char) -> bool {
+    c == '\u{200C}' || c == '\u{200D}' || (is_combining_mark(c) && !is_enclosing_mark(c))
+}
+
+fn is_hashtag_char(c: char) -> bool {
+    starts_hashtag(c) || is_joining_char(c)
+}
+
+fn is_separator(c: char) -> bool {
+    c == ',' || c == '#' || c.is_whitespace()
+}
+
+fn canonical(raw: &str) -> String {
+    let tag = raw.nfkc().collect::<String>().to_lowercase();
+    tag.trim().trim_start_matches('#').trim().to_string()
+}
+
+/// Marks and joiners are only valid inside a tag, never at its start
+fn has_valid_chars(tag: &str) -> bool {
+    tag.chars().next().is_some_and(starts_hashtag)
+        && tag.chars().all(is_hashtag_char)
+        && tag.chars().any(char::is_alphanumeric)
+}
+
+/// Normalize a single tag, `None` if nothing is left after stripping `#` and whitespace
+pub fn normalize_hashtag(raw: &str) -> Result<Option<String>, HashtagError> {
+    let tag = canonical(raw);
+    if tag.is_empty() {
+        return Ok(None);
+    }
+
+    if !has_valid_chars(&tag) {
+        return Err(HashtagError::InvalidChars(tag));
+    }
+    let len = tag.chars().count();
+    if len < MIN_HASHTAG_LEN {
+        return Err(HashtagError::TooShort(tag));
+    }
+    if len > MAX_HASHTAG_LEN {
+        return Err(HashtagError::TooLong(tag));
+    }
+
+    Ok(Some(tag))
+}
+
+fn push_unique(tags: &mut Vec<String>, tag: String) {
+    if !tags.contains(&tag) {
+        tags.push(tag);
+    }
+}
+
+/// Parse tags typed by the user, separated by commas, whitespace or `#`
+/// i.e `#travel, #Food #travel` -> `["travel", "food"]`
+pub fn parse_hashtags(input: &str) -> Result<Vec<String>, HashtagError> {
+    let mut tags = Vec::new();
+    for raw in input.split(is_separator) {
+        if let Some(tag) = normalize_hashtag(raw)? {
+            push_unique(&mut tags, tag);
+        }
+    }
+
+    Ok(tags)
+}
+
+/// Inline `#tags` in free text, invalid ones are ignored
+/// a `#` only starts a tag at the beginning of the text or after a non tag character,
+/// so fragments like `page#section` are not picked up
+pub fn extract_inline_hashtags(text: &str) -> Vec<String> {
+    let text: String = text.nfkc().collect();
+    let mut tags = Vec::new();
+    let mut prev = None::<char>;
+    let mut chars = text.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        let starts_tag = c == '#' && !prev.is_some_and(is_hashtag_char);
+        prev = Some(c);
+        if !starts_tag {
+            continue;
+        }
+        let start = idx + c.len_utf8();
+        let mut end = start;
+        while let Some(&(i, c)) = chars.peek() {
+            if !is_hashtag_char(c) {
+                break;
+            }
+            end = i + c.len_utf8();
+            prev = Some(c);
+            chars.next();
+        }
+        if let Ok(Some(tag)) = normalize_hashtag(&text[start..end]) {
+            push_unique(&mut tags, tag);
+        }
+    }
+
+    tags
+}
+
+/// Tags of a post: the explicit ones followed by the inline tags of its description
+pub fn merge_hashtags(hashtags: &[String], description: &str) -> Result<Vec<String>, HashtagError> {
+    let mut tags = validate_hashtags(hashtags)?;
+    for tag in extract_inline_hashtags(description) {
+        push_unique(&mut tags, tag);
+    }
+    if tags.len() > MAX_HASHTAGS {
+        return Err(HashtagError::TooMany);
+    }
+
+    Ok(tags)
+}
+
+/// Normalize and check tags received from a client
+pub fn validate_hashtags(hashtags: &[String]) -> Result<Vec<String>, HashtagError> {
+    let mut tags = Vec::new();
+    for raw in hashtags {
+        if let Some(tag) = normalize_hashtag(raw)? {
+            push_unique(&mut tags, tag);
+        }
+    }
+    if tags.len() > MAX_HASHTAGS {
+        return Err(HashtagError::TooMany);
+    }
+
+    Ok(tags)
+}
+
+/// The start of a tag, normalized but without length rules
+fn hashtag_prefix(raw: &str) -> Option<String> {
+    let prefix = canonical(raw);
+    (!prefix.is_empty() && has_valid_chars(&prefix)).then_some(prefix)
+}
+
+/// The tag being typed at the end of `input`
+pub fn partial_hashtag(input: &str) -> Option<String> {
+    hashtag_prefix(input.rsplit(is_separator).next()?)
+}
+
+/// Replace the tag being typed at the end of `input` with `tag`
+/// i.e `#food, #tra` + `travel` -> `#food, #travel, `
+pub fn complete_hashtag(input: &str, tag: &str) -> String {
+    let partial_len = input.rsplit(is_separator).next().map_or(0, str::len);
+    let head = input[..input.len() - partial_len].trim_end_matches('#');
+    format!("{head}#{tag}, ")
+}
+
+/// Popular tags starting with `prefix`, most used first
+#[server]
+pub async fn suggest_hashtags(prefix: String) -> Result<Vec<String>, ServerFnError> {
+    server_impl::suggest_hashtags(prefix).await
+}
+
+/// Most used tags overall, most used first
+#[server]
+pub async fn trending_hashtags() -> Result<Vec<String>, ServerFnError> {
+    server_impl::trending_hashtags().await
+}
+
+/// Called by the publisher once a post is live, counts the tags of the post
+#[server(input = Cbor)]
+pub async fn record_post_hashtags(
+    cans_wire: CanistersAuthWire,
+    post_id: u64,
+) -> Result<(), ServerFnError> {
+    use crate::auth::server_impl::store::KVStoreImpl;
+    use leptos::expect_context;
+
+    let cans = cans_wire.canisters()?;
+    let kv: KVStoreImpl = expect_context();
+    server_impl::record_post_hashtags(&kv, &cans, post_id).await
+}
+
+#[cfg(feature = "ssr")]
+pub mod server_impl {
+    use leptos::{expect_context, ServerFnError};
+
+    use super::{hashtag_prefix, merge_hashtags, MAX_HASHTAG_SUGGESTIONS};
+    use crate::{
+        auth::server_impl::store::{KVError, KVStore, KVStoreImpl},
+        state::canisters::Canisters,
+    };
+
+    const HASHTAG_COUNT_PREFIX: &str = "hashtag-count:";
+    /// Marks a post whose tags were counted, so a post is counted once
+    const HASHTAG_COUNTED_PREFIX: &str = "hashtag-counted:";
+    /// Most used tags starting with a prefix, kept for every prefix of every
+    /// used tag so that suggesting is a single read
+    /// the empty prefix holds the most used tags overall
+    const HASHTAG_TOP_PREFIX: &str = "hashtag-top:";
+    /// Concurrent uploads may race on a counter, give up after a few attempts
+    const MAX_INCREMENT_ATTEMPTS: usize = 3;
+
+    /// (tag, uses), most used first
+    type TopTags = Vec<(String, u64)>;
+
+    /// Read-modify-write `key`, `update` returns `None` to leave the value as is
+    /// Returns the new value, `None` if unchanged or concurrent writes won every attempt
+    async fn update_value(
+        kv: &KVStoreImpl,
+        key: String,
+        update: impl Fn(Option<&str>) -> Option<String>,
+    ) -> Result<Option<String>, KVError> {
+        for _ in 0..MAX_INCREMENT_ATTEMPTS {
+            let current = kv.read(key.clone()).await?;
+            let Some(new) = update(current.as_deref()) else {
+                return Ok(None);
+            };
+            if kv
+                .compare_and_swap(key.clone(), current, new.clone())
+                .await?
+            {
+                return Ok(Some(new));
+            }
+        }
+        Ok(None)
+    }
+
+    fn insert_top(raw: Option<&str>, tag: &str, uses: u64) -> Option<String> {
+        let mut top: TopTags = raw
+            .and_then(|raw| serde_json::from_str(raw).ok())
+            .unwrap_or_default();
+        top.retain(|(t, _)| t != tag);
+        top.push((tag.to_string(), uses));
+        top.sort_by(|(a_tag, a), (b_tag, b)| b.cmp(a).then_with(|| a_tag.cmp(b_tag)));
+        top.truncate(MAX_HASHTAG_SUGGESTIONS);
+        // Uses only grow, a tag not making the cut leaves the list unchanged
+        top.iter()
+            .any(|(t, _)| t == tag)
+            .then(|| serde_json::to_string(&top).ok())
+            .flatten()
+    }
+
+    async fn record_tag(kv: &KVStoreImpl, tag: &str) -> Result<(), KVError> {
+        let uses = update_value(kv, format!("{HASHTAG_COUNT_PREFIX}{tag}"), |current| {
+            let count = current
+                .and_then(|c| c.parse::<u64>().ok())
+                .unwrap_or_default();
+            Some((count + 1).to_string())
+        })
+        .await?;
+        let Some(uses) = uses.and_then(|u| u.parse::<u64>().ok()) else {
+            return Ok(());
+        };
+
+        let prefix_ends = tag.char_indices().map(|(end, c)| end + c.len_utf8());
+        for end in std::iter::once(0).chain(prefix_ends) {
+            let prefix = &tag[..end];
+            update_value(kv, format!("{HASHTAG_TOP_PREFIX}{prefix}"), |raw| {
+                insert_top(raw, tag, uses)
+            })
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Count a use of each tag, feeding [super::suggest_hashtags]
+    /// failures are logged, popularity is best effort
+    pub async fn record_hashtag_use(kv: &KVStoreImpl, tags: &[String]) {
+        for tag in tags {
+            if let Err(e) = record_tag(kv, tag).await {
+                log::warn!("failed to count #{tag}: {e}");
+            }
+        }
+    }
+
+    /// Count the tags of a published post of the caller
+    /// the tags are read back from the canister, a post is counted at most once
+    pub async fn record_post_hashtags(
+        kv: &KVStoreImpl,
+        cans: &Canisters<true>,
+        post_id: u64,
+    ) -> Result<(), ServerFnError> {
+        let user = cans.authenticated_user().await;
+        let post = user.get_individual_post_details_by_id(post_id).await?;
+        let tags = merge_hashtags(&post.hashtags, &post.description)
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let counted_key = format!("{HASHTAG_COUNTED_PREFIX}{}:{post_id}", cans.user_canister());
+        if !kv.compare_and_swap(counted_key, None, "1".into()).await? {
+            return Ok(());
+        }
+        record_hashtag_use(kv, &tags).await;
+
+        Ok(())
+    }
+
+    async fn top_tags(prefix: &str) -> Result<Vec<String>, ServerFnError> {
+        let kv: KVStoreImpl = expect_context();
+        let Some(raw) = kv.read(format!("{HASHTAG_TOP_PREFIX}{prefix}")).await? else {
+            return Ok(vec![]);
+        };
+        let top: TopTags = serde_json::from_str(&raw)?;
+
+        Ok(top.into_iter().map(|(tag, _)| tag).collect())
+    }
+
+    pub async fn suggest_hashtags(prefix: String) -> Result<Vec<String>, ServerFnError> {
+        let Some(prefix) = hashtag_prefix(&prefix) else {
+            return Ok(vec![]);
+        };
+        top_tags(&prefix).await
+    }
+
+    pub async fn trending_hashtags() -> Result<Vec<String>, ServerFnError> {
+        top_tags("").await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_tags() {
+        assert_eq!(normalize_hashtag(" #Travel "), Ok(Some("travel".into())));
+        // NFKC folds the fullwidth form
+        assert_eq!(normalize_hashtag("#ＦＯＯＤ"), Ok(Some("food".into())));
+        assert_eq!(normalize_hashtag("#"), Ok(None));
+        assert_eq!(
+            normalize_hashtag("#a"),
+            Err(HashtagError::TooShort("a".into()))
+        );
+        assert_eq!(
+            normalize_hashtag(&"a".repeat(MAX_HASHTAG_LEN + 1)),
+            Err(HashtagError::TooLong("a".repeat(MAX_HASHTAG_LEN + 1)))
+        );
+        assert_eq!(
+            normalize_hashtag("#foo-bar"),
+            Err(HashtagError::InvalidChars("foo-bar".into()))
+        );
+    }
+
+    #[test]
+    fn parses_and_dedupes_tags() {
+        assert_eq!(
+            parse_hashtags("#travel, #Food #travel"),
+            Ok(vec!["travel".to_string(), "food".to_string()])
+        );
+        assert_eq!(parse_hashtags("  ,, # "), Ok(vec![]));
+        let too_many = (0..=MAX_HASHTAGS)
+            .map(|i| format!("tag{i}"))
+            .collect::<Vec<_>>();
+        assert_eq!(validate_hashtags(&too_many), Err(HashtagError::TooMany));
+    }
+
+    #[test]
+    fn extracts_inline_tags() {
+        assert_eq!(
+            extract_inline_hashtags("#Beach day, see page#section and #beach #sun!"),
+            vec!["beach".to_string(), "sun".to_string()]
+        );
+        assert_eq!(
+            merge_hashtags(&["sun".into()], "hello #beach #sun"),
+            Ok(vec!["sun".to_string(), "beach".to_string()])
+        );
+    }
+
+    #[test]
+    fn accepts_indic_scripts() {
+        assert_eq!(parse_hashtags("#नमस्ते"), Ok(vec!["नमस्ते".to_string()]));
+        assert_eq!(
+            extract_inline_hashtags("बोलो #नमस्ते दोस्तों"),
+            vec!["नमस्ते".to_string()]
+        );
+        // ZWJ and ZWNJ shape conjuncts inside a word
+        assert_eq!(
+            parse_hashtags("#क्\u{200D}ष #क्\u{200C}ष"),
+            Ok(vec!["क्\u{200D}ष".to_string(), "क्\u{200C}ष".to_string()])
+        );
+        assert_eq!(
+            parse_hashtags("#\u{094D}नम"),
+            Err(HashtagError::InvalidChars("\u{094D}नम".into()))
+        );
+    }
+
+    #[test]
+    fn completes_partial_tag() {
+        assert_eq!(partial_hashtag("#food, #Tra"), Some("tra".into()));
+        assert_eq!(partial_hashtag("#food, "), None);
+        assert_eq!(partial_hashtag("#food, #tr-"), None);
+        assert_eq!(
+            complete_hashtag("#food, #tra", "travel"),
+            "#food, #travel, "
+        );
+        assert_eq!(complete_hashtag("", "travel"), "#travel, ");
+    }
+}
@@ -1,18 +1,115 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+};
 
 use candid::Principal;
-use ic_agent::{agent::AgentBuilder, Agent, Identity};
+use ic_agent::{
+    agent::{AgentBuilder, RejectCode},
+    Agent, AgentError, Identity,
+};
+use once_cell::sync::Lazy;
+use web_time::Duration;
 
-use crate::consts::AGENT_URL;
+use crate::{consts::AGENT_URL, utils::time::sleep};
+
+/// Bounded exponential backoff for canister calls
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(2),
+        }
+    }
+}
+
+impl RetryPolicy {
+    fn delay(&self, attempt: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(1 << attempt.min(16))
+            .min(self.max_delay)
+    }
+}
+
+/// Whether the call may succeed if retried
+/// (transport failures, timeouts, replica overload & transient rejects)
+pub fn is_retriable(err: &AgentError) -> bool {
+    match err {
+        AgentError::TransportError(_)
+        | AgentError::TimeoutWaitingForResponse()
+        | AgentError::CertificateOutdated(_) => true,
+        AgentError::HttpError(payload) => payload.status == 429 || payload.status >= 500,
+        AgentError::CertifiedReject(reject) | AgentError::UncertifiedReject(reject) => {
+            reject.reject_code == RejectCode::SysTransient
+        }
+        _ => false,
+    }
+}
+
+/// Per call counters, keyed by the call name passed to [AgentWrapper::with_retry]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CallMetrics {
+    pub calls: u64,
+    pub retries: u64,
+    pub failures: u64,
+}
+
+static CALL_METRICS: Lazy<Mutex<HashMap<&'static str, CallMetrics>>> = Lazy::new(Default::default);
+
+fn record_call(call: &'static str, update: impl FnOnce(&mut CallMetrics)) {
+    let Ok(mut metrics) = CALL_METRICS.lock() else {
+        return;
+    };
+    update(metrics.entry(call).or_default());
+}
+
+/// Snapshot of the canister call metrics recorded so far
+pub fn call_metrics() -> HashMap<&'static str, CallMetrics> {
+    CALL_METRICS
+        .lock()
+        .map(|metrics| metrics.clone())
+        .unwrap_or_default()
+}
+
+/// Log [call_metrics] every `period` for the lifetime of the server
+#[cfg(feature = "ssr")]
+pub fn spawn_call_metrics_log(period: Duration) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            let mut metrics: Vec<_> = call_metrics().into_iter().collect();
+            metrics.sort_by_key(|(call, _)| *call);
+            for (call, m) in metrics {
+                log::info!(
+                    "canister call {call}: {} calls, {} retries, {} failures",
+                    m.calls,
+                    m.retries,
+                    m.failures
+                );
+            }
+        }
+    });
+}
 
 #[derive(Clone)]
-pub struct AgentWrapper(Agent);
+pub struct AgentWrapper(Agent, RetryPolicy);
 
 impl AgentWrapper {
     pub fn build(builder_func: impl FnOnce(AgentBuilder) -> AgentBuilder) -> Self {
         let mut builder = Agent::builder().with_url(AGENT_URL);
         builder = builder_func(builder);
-        Self(builder.build().unwrap())
+        Self(builder.build().unwrap(), RetryPolicy::default())
     }
 
     pub async fn get_agent(&self) -> &Agent {
@@ -34,4 +131,31 @@ impl AgentWrapper {
     pub fn principal(&self) -> Result<Principal, String> {
         self.0.get_principal()
     }
+
+    /// Run a canister call, retrying retriable errors with exponential backoff
+    /// `call` identifies the call in [call_metrics]
+    pub async fn with_retry<T, Fut: Future<Output = Result<T, AgentError>>>(
+        &self,
+        call: &'static str,
+        mut f: impl FnMut() -> Fut,
+    ) -> Result<T, AgentError> {
+        let policy = self.1;
+        record_call(call, |m| m.calls += 1);
+        let mut attempt = 0;
+        loop {
+            match f().await {
+                Ok(res) => return Ok(res),
+                Err(e) if attempt < policy.max_retries && is_retriable(&e) => {
+                    log::warn!("canister call {call} failed, retrying: {e}");
+                    record_call(call, |m| m.retries += 1);
+                    sleep(policy.delay(attempt)).await;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    record_call(call, |m| m.failures += 1);
+                    return Err(e);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,564 @@
+//! Hot or Not leaderboard
+//!
+//! Canisters can't be enumerated, so players are registered in the KV store when they
+//! bet (or open the leaderboard). Every [REFRESH_INTERVAL_SECS] one replica fetches
+//! their new bets, folds them into per player aggregates and publishes the boards
+//! to the KV store, which every replica then loads.
+use candid::Principal;
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use web_time::Duration;
+
+use super::profile::{BetDetails, BetOutcome, ProfileDetails};
+use crate::{
+    component::infinite_scroller::{CursoredDataProvider, KeyedData, PageEntry},
+    state::canisters::CanistersAuthWire,
+};
+
+pub const REFRESH_INTERVAL_SECS: u64 = 5 * 60;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum LeaderboardPeriod {
+    Daily,
+    Weekly,
+    #[default]
+    AllTime,
+}
+
+impl LeaderboardPeriod {
+    pub const ALL: [Self; 3] = [Self::Daily, Self::Weekly, Self::AllTime];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Daily => "Today",
+            Self::Weekly => "This Week",
+            Self::AllTime => "All Time",
+        }
+    }
+
+    /// Only bets placed after this count towards the period
+    pub fn since(self, now: Duration) -> Duration {
+        let window = match self {
+            Self::Daily => Duration::from_secs(24 * 60 * 60),
+            Self::Weekly => Duration::from_secs(7 * 24 * 60 * 60),
+            Self::AllTime => return Duration::ZERO,
+        };
+        now.saturating_sub(window)
+    }
+}
+
+/// Results of a player's settled bets
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct PlayerStats {
+    /// COYNs received minus COYNs bet
+    pub net_earnings: i64,
+    pub bets: u64,
+    pub wins: u64,
+    /// Longest run of consecutive wins
+    pub best_streak: u64,
+}
+
+impl PlayerStats {
+    /// Settled bets placed after `since`, oldest first
+    fn settled_since(bets: &[BetDetails], since: Duration) -> Vec<&BetDetails> {
+        let mut settled: Vec<_> = bets
+            .iter()
+            .filter(|bet| bet.placed_at() >= since)
+            .filter(|bet| !matches!(bet.outcome, BetOutcome::AwaitingResult))
+            .collect();
+        settled.sort_by_key(|bet| bet.placed_at());
+        settled
+    }
+
+    /// Count settled bets, oldest first, continuing a run of `streak` wins
+    fn add_bets<'a>(&mut self, streak: &mut u64, bets: impl IntoIterator<Item = &'a BetDetails>) {
+        for bet in bets {
+            let amount = bet.bet_amount as i64;
+            self.bets += 1;
+            match bet.outcome {
+                BetOutcome::Won(reward) => {
+                    self.net_earnings += reward as i64 - amount;
+                    self.wins += 1;
+                    *streak += 1;
+                    self.best_streak = self.best_streak.max(*streak);
+                }
+                BetOutcome::Draw(reward) => {
+                    self.net_earnings += reward as i64 - amount;
+                    *streak = 0;
+                }
+                BetOutcome::Lost => {
+                    self.net_earnings -= amount;
+                    *streak = 0;
+                }
+                BetOutcome::AwaitingResult => unreachable!(),
+            }
+        }
+    }
+
+    /// Settled bets placed after `since`, `None` if there are none
+    pub fn from_bets(bets: &[BetDetails], since: Duration) -> Option<Self> {
+        let settled = Self::settled_since(bets, since);
+        if settled.is_empty() {
+            return None;
+        }
+        let mut stats = Self::default();
+        stats.add_bets(&mut 0, settled);
+
+        Some(stats)
+    }
+
+    /// Percentage of settled bets won
+    pub fn win_rate(&self) -> f64 {
+        if self.bets == 0 {
+            return 0.0;
+        }
+        (self.wins as f64 * 100.0) / self.bets as f64
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LeaderboardEntry {
+    /// 1 based
+    pub rank: usize,
+    pub user_canister: Principal,
+    pub profile: ProfileDetails,
+    pub stats: PlayerStats,
+}
+
+impl KeyedData for LeaderboardEntry {
+    type Key = Principal;
+
+    fn key(&self) -> Self::Key {
+        self.user_canister
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct LeaderboardProvider {
+    period: LeaderboardPeriod,
+}
+
+impl LeaderboardProvider {
+    pub fn new(period: LeaderboardPeriod) -> Self {
+        Self { period }
+    }
+}
+
+impl CursoredDataProvider for LeaderboardProvider {
+    type Data = LeaderboardEntry;
+    type Error = ServerFnError;
+
+    async fn get_by_cursor(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> Result<PageEntry<LeaderboardEntry>, ServerFnError> {
+        let data = get_leaderboard(self.period, start, end).await?;
+        let list_end = data.len() < (end - start);
+        Ok(PageEntry {
+            data,
+            end: list_end,
+        })
+    }
+}
+
+#[server]
+pub async fn get_leaderboard(
+    period: LeaderboardPeriod,
+    start: usize,
+    end: usize,
+) -> Result<Vec<LeaderboardEntry>, ServerFnError> {
+    let cache: server_impl::LeaderboardCache = expect_context();
+    Ok(cache.page(period, start, end))
+}
+
+/// The viewer's entry, also registers them so they show up after the next refresh
+#[server(
+    input = Cbor
+)]
+pub async fn get_own_leaderboard_entry(
+    cans_wire: CanistersAuthWire,
+    period: LeaderboardPeriod,
+) -> Result<Option<LeaderboardEntry>, ServerFnError> {
+    use crate::auth::server_impl::store::KVStoreImpl;
+
+    let user_canister = cans_wire.canisters()?.user_canister();
+    let kv: KVStoreImpl = expect_context();
+    server_impl::register_player(&kv, user_canister).await?;
+
+    let cache: server_impl::LeaderboardCache = expect_context();
+    Ok(cache.entry(period, user_canister))
+}
+
+/// Called after a bet is placed so the bettor is ranked
+#[server(
+    input = Cbor
+)]
+pub async fn register_player(cans_wire: CanistersAuthWire) -> Result<(), ServerFnError> {
+    use crate::auth::server_impl::store::KVStoreImpl;
+
+    let user_canister = cans_wire.canisters()?.user_canister();
+    let kv: KVStoreImpl = expect_context();
+    server_impl::register_player(&kv, user_canister).await?;
+
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub mod server_impl {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, RwLock},
+    };
+
+    use candid::Principal;
+    use futures::{stream, StreamExt};
+    use leptos::ServerFnError;
+    use serde::{Deserialize, Serialize};
+    use web_time::Duration;
+
+    use super::{LeaderboardEntry, LeaderboardPeriod, PlayerStats, REFRESH_INTERVAL_SECS};
+    use crate::{
+        auth::server_impl::store::{KVError, KVStore, KVStoreImpl},
+        component::infinite_scroller::{CursoredDataProvider, PageEntry},
+        state::canisters::Canisters,
+        utils::{
+            notifications::inbox::{server_impl::notify_once, NotificationKind},
+            profile::{BetDetails, BetOutcome, BetsProvider, ProfileDetails, PROFILE_CHUNK_SZ},
+            time::current_epoch,
+        },
+    };
+
+    /// Index of the user canisters of registered players
+    const PLAYERS_INDEX: &str = "leaderboard-players";
+    /// Held by the replica refreshing the leaderboard, see [acquire_refresh_lease]
+    const REFRESH_LEASE_KEY: &str = "leaderboard-refresh-lease";
+    /// Bets fetched per player and refresh, older bets are not counted on a player's first refresh
+    const MAX_BETS_PER_PLAYER: usize = 1000;
+    const CONCURRENT_PLAYER_FETCHES: usize = 8;
+    /// Results of bets placed before this are not notified, i.e when first deployed.
+    /// Shorter than [crate::utils::notifications::inbox::server_impl::NOTIFY_DEDUP_TTL] so a result is notified once
+    const BET_NOTIFY_WINDOW: Duration = Duration::from_secs(2 * 24 * 60 * 60);
+
+    fn aggregate_key(user_canister: Principal) -> String {
+        format!("leaderboard-aggregate:{user_canister}")
+    }
+
+    fn board_key(period: LeaderboardPeriod) -> String {
+        format!("leaderboard-board:{period:?}")
+    }
+
+    /// All time stats of a player's bets placed before `folded_until_secs`,
+    /// so a refresh only fetches the bets placed since
+    #[derive(Serialize, Deserialize, Clone, Default)]
+    struct PlayerAggregate {
+        stats: PlayerStats,
+        /// Wins in a row at the end of the folded bets
+        streak: u64,
+        folded_until_secs: u64,
+    }
+
+    struct PlayerBets {
+        profile: ProfileDetails,
+        /// Bets within the longest period other than all time
+        recent: Vec<BetDetails>,
+        all_time: Option<PlayerStats>,
+    }
+
+    impl PlayerBets {
+        fn stats(&self, period: LeaderboardPeriod, now: Duration) -> Option<PlayerStats> {
+            match period {
+                LeaderboardPeriod::AllTime => self.all_time,
+                _ => PlayerStats::from_bets(&self.recent, period.since(now)),
+            }
+        }
+    }
+
+    /// Rank by net earnings, ties broken by win rate and then wins
+    fn rank_players(players: &mut [(Principal, ProfileDetails, PlayerStats)]) {
+        players.sort_by(|(_, _, a), (_, _, b)| {
+            b.net_earnings
+                .cmp(&a.net_earnings)
+                .then_with(|| b.win_rate().total_cmp(&a.win_rate()))
+                .then_with(|| b.wins.cmp(&a.wins))
+        });
+    }
+
+    pub async fn register_player(
+        kv: &KVStoreImpl,
+        user_canister: Principal,
+    ) -> Result<(), KVError> {
+        kv.index_add(PLAYERS_INDEX.to_string(), user_canister.to_text())
+            .await
+    }
+
+    /// Bets placed after `since`, bets are listed newest first
+    /// so paging stops at the first page placed entirely before it
+    async fn bets_since(
+        canisters: &Canisters<false>,
+        user_canister: Principal,
+        since: Duration,
+    ) -> Result<Vec<BetDetails>, ServerFnError> {
+        let provider = BetsProvider::new(canisters.clone(), user_canister);
+        let mut bets = vec![];
+        while bets.len() < MAX_BETS_PER_PLAYER {
+            let start = bets.len();
+            let PageEntry { data, end } = provider
+                .get_by_cursor(start, start + PROFILE_CHUNK_SZ)
+                .await?;
+            let older = data.iter().all(|bet| bet.placed_at() < since);
+            bets.extend(data);
+            if end || older {
+                break;
+            }
+        }
+        bets.retain(|bet| bet.placed_at() >= since);
+
+        Ok(bets)
+    }
+
+    async fn player_bets(
+        canisters: &Canisters<false>,
+        kv: &KVStoreImpl,
+        user_canister: Principal,
+        now: Duration,
+    ) -> Result<PlayerBets, ServerFnError> {
+        let user = canisters.individual_user(user_canister).await;
+        let profile = ProfileDetails::from(user.get_profile_details().await?);
+
+        let mut aggregate: PlayerAggregate = match kv.read(aggregate_key(user_canister)).await? {
+            Some(raw) => serde_json::from_str(&raw)?,
+            None => PlayerAggregate::default(),
+        };
+        let folded_until = Duration::from_secs(aggregate.folded_until_secs);
+        let mut bets = bets_since(canisters, user_canister, folded_until).await?;
+
+        // Bets outside every other period are settled and only count towards all time
+        let fold_until = Duration::from_secs(LeaderboardPeriod::Weekly.since(now).as_secs());
+        if fold_until > folded_until {
+            let (to_fold, recent): (Vec<_>, Vec<_>) = bets
+                .into_iter()
+                .partition(|bet| bet.placed_at() < fold_until);
+            aggregate.stats.add_bets(
+                &mut aggregate.streak,
+                PlayerStats::settled_since(&to_fold, Duration::ZERO),
+            );
+            aggregate.folded_until_secs = fold_until.as_secs();
+            kv.write(
+                aggregate_key(user_canister),
+                serde_json::to_string(&aggregate)?,
+            )
+            .await?;
+            bets = recent;
+        }
+
+        let mut all_time = aggregate.stats;
+        all_time.add_bets(
+            &mut aggregate.streak,
+            PlayerStats::settled_since(&bets, Duration::ZERO),
+        );
+        Ok(PlayerBets {
+            profile,
+            recent: bets,
+            all_time: (all_time.bets > 0).then_some(all_time),
+        })
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct RefreshLease {
+        worker: String,
+        expires_at_secs: u64,
+    }
+
+    /// Take or renew the lease on refreshing the leaderboard, so a single replica refreshes it.
+    /// The lease lapses after a couple of missed refreshes and another replica takes over
+    async fn acquire_refresh_lease(kv: &KVStoreImpl, worker: &str) -> Result<bool, ServerFnError> {
+        let now = current_epoch().as_secs();
+        let current = kv.read(REFRESH_LEASE_KEY.to_string()).await?;
+        if let Some(raw) = &current {
+            let lease: RefreshLease = serde_json::from_str(raw)?;
+            if lease.worker != worker && lease.expires_at_secs > now {
+                return Ok(false);
+            }
+        }
+        let lease = RefreshLease {
+            worker: worker.to_string(),
+            expires_at_secs: now + 2 * REFRESH_INTERVAL_SECS,
+        };
+        let swapped = kv
+            .compare_and_swap(
+                REFRESH_LEASE_KEY.to_string(),
+                current,
+                serde_json::to_string(&lease)?,
+            )
+            .await?;
+        Ok(swapped)
+    }
+
+    /// The refresh already fetches every bet, so it also notifies players of new results
+    ///
+    /// Only registered players (who bet or opened the leaderboard since the leaderboard
+    /// was deployed) are notified, and only if their result is settled within
+    /// [BET_NOTIFY_WINDOW] of the bet. With the ReDB store every replica refreshes
+    /// its own leaderboard, so players are notified once per replica
+    async fn notify_bet_results(
+        kv: &KVStoreImpl,
+        user_canister: Principal,
+        user: Principal,
+        bets: &[BetDetails],
+    ) {
+        let since = current_epoch().saturating_sub(BET_NOTIFY_WINDOW);
+        for bet in bets.iter().filter(|bet| bet.placed_at() >= since) {
+            let kind = match bet.outcome {
+                BetOutcome::Won(amount) => NotificationKind::BetWon {
+                    post_canister: bet.canister_id,
+                    post_id: bet.post_id,
+                    amount,
+                },
+                BetOutcome::Lost => NotificationKind::BetLost {
+                    post_canister: bet.canister_id,
+                    post_id: bet.post_id,
+                    amount: bet.bet_amount,
+                },
+                BetOutcome::Draw(_) | BetOutcome::AwaitingResult => continue,
+            };
+            let dedup_key = format!("bet:{user_canister}:{}:{}", bet.canister_id, bet.post_id);
+            if let Err(e) = notify_once(kv, dedup_key, user, kind).await {
+                log::warn!("failed to notify {user} of a bet result: {e}");
+            }
+        }
+    }
+
+    /// Rankings of every period, replaced as a whole on each refresh
+    #[derive(Clone, Default)]
+    pub struct LeaderboardCache(Arc<RwLock<HashMap<LeaderboardPeriod, Vec<LeaderboardEntry>>>>);
+
+    impl LeaderboardCache {
+        pub fn page(
+            &self,
+            period: LeaderboardPeriod,
+            start: usize,
+            end: usize,
+        ) -> Vec<LeaderboardEntry> {
+            let boards = self.0.read().unwrap();
+            let Some(board) = boards.get(&period) else {
+                return vec![];
+            };
+            board
+                .iter()
+                .skip(start)
+                .take(end.saturating_sub(start))
+                .cloned()
+                .collect()
+        }
+
+        pub fn entry(
+            &self,
+            period: LeaderboardPeriod,
+            user_canister: Principal,
+        ) -> Option<LeaderboardEntry> {
+            let boards = self.0.read().unwrap();
+            boards
+                .get(&period)?
+                .iter()
+                .find(|entry| entry.user_canister == user_canister)
+                .cloned()
+        }
+
+        /// Rank every registered player and publish the boards to the KV store
+        async fn refresh(
+            canisters: &Canisters<false>,
+            kv: &KVStoreImpl,
+        ) -> Result<(), ServerFnError> {
+            let players: Vec<Principal> = kv
+                .index_members(PLAYERS_INDEX.to_string())
+                .await?
+                .into_iter()
+                .filter_map(|member| Principal::from_text(member).ok())
+                .collect();
+
+            let now = current_epoch();
+            let players: Vec<_> = stream::iter(players)
+                .map(|user_canister| async move {
+                    let res = player_bets(canisters, kv, user_canister, now).await;
+                    if let Err(e) = &res {
+                        log::warn!("failed to fetch leaderboard stats of {user_canister}: {e}");
+                    }
+                    res.ok().map(|player| (user_canister, player))
+                })
+                .buffer_unordered(CONCURRENT_PLAYER_FETCHES)
+                .filter_map(|player| async move { player })
+                .collect()
+                .await;
+
+            for (user_canister, player) in &players {
+                notify_bet_results(kv, *user_canister, player.profile.principal, &player.recent)
+                    .await;
+            }
+
+            for period in LeaderboardPeriod::ALL {
+                let mut ranked: Vec<_> = players
+                    .iter()
+                    .filter_map(|(user_canister, player)| {
+                        let stats = player.stats(period, now)?;
+                        Some((*user_canister, player.profile.clone(), stats))
+                    })
+                    .collect();
+                rank_players(&mut ranked);
+                let board: Vec<_> = ranked
+                    .into_iter()
+                    .enumerate()
+                    .map(|(idx, (user_canister, profile, stats))| LeaderboardEntry {
+                        rank: idx + 1,
+                        user_canister,
+                        profile,
+                        stats,
+                    })
+                    .collect();
+                kv.write(board_key(period), serde_json::to_string(&board)?)
+                    .await?;
+            }
+
+            Ok(())
+        }
+
+        /// Replace the cached boards with the ones last published
+        async fn load(&self, kv: &KVStoreImpl) -> Result<(), ServerFnError> {
+            let mut boards = HashMap::new();
+            for period in LeaderboardPeriod::ALL {
+                let Some(raw) = kv.read(board_key(period)).await? else {
+                    continue;
+                };
+                boards.insert(period, serde_json::from_str(&raw)?);
+            }
+            *self.0.write().unwrap() = boards;
+            Ok(())
+        }
+
+        /// Every [REFRESH_INTERVAL_SECS] for the lifetime of the server, refresh the
+        /// leaderboard if this replica holds the lease and load the published boards
+        pub fn spawn_refresh(&self, canisters: Canisters<false>, kv: KVStoreImpl) {
+            let cache = self.clone();
+            let worker = format!("{}-{}", std::process::id(), current_epoch().as_nanos());
+            tokio::spawn(async move {
+                let mut interval =
+                    tokio::time::interval(Duration::from_secs(REFRESH_INTERVAL_SECS));
+                loop {
+                    interval.tick().await;
+                    match acquire_refresh_lease(&kv, &worker).await {
+                        Ok(true) => {
+                            if let Err(e) = Self::refresh(&canisters, &kv).await {
+                                log::error!("failed to refresh leaderboard: {e}");
+                            }
+                        }
+                        Ok(false) => (),
+                        Err(e) => log::error!("failed to acquire leaderboard refresh lease: {e}"),
+                    }
+                    if let Err(e) = cache.load(&kv).await {
+                        log::error!("failed to load leaderboard: {e}");
+                    }
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::{HashSet, VecDeque};
+
+use axum_extra::extract::{cookie::Key, SignedCookieJar};
+use candid::Principal;
+use leptos::{expect_context, ServerFnError};
+use leptos_axum::extract_with_state;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    auth::server_impl::{
+        extract_principal_from_cookie,
+        store::{KVStore, KVStoreImpl},
+    },
+    state::canisters::unauth_canisters,
+    utils::types::PostId,
+};
+
+use super::{is_transient, ml_feed_grpc::MLFeed, SeenPost, ML_FEED_UNREACHABLE};
+
+/// Most recent posts remembered per user
+const MAX_SEEN_POSTS: usize = 2000;
+/// Seen posts sent to the ML feed server as a filter hint
+/// the full seen set is always applied on our side
+const FILTER_HINT_POSTS: usize = 200;
+/// Extra posts fetched on every refill, served on subsequent calls
+const PREFETCH_POSTS: u32 = 30;
+/// Attempts at updating the user's cache when their requests race
+const MAX_UPDATE_ATTEMPTS: usize = 5;
+
+fn seen_key(user_canister: Principal) -> String {
+    format!("ml-feed-seen:{user_canister}")
+}
+
+fn next_batch_key(user_canister: Principal) -> String {
+    format!("ml-feed-next:{user_canister}")
+}
+
+/// Current raw value of `key`, for [KVStore::compare_and_swap], and its parsed value
+async fn read_json<T: DeserializeOwned + Default>(
+    kv: &KVStoreImpl,
+    key: String,
+) -> Result<(Option<String>, T), ServerFnError> {
+    let Some(raw) = kv.read(key).await? else {
+        return Ok((None, T::default()));
+    };
+    let value = serde_json::from_str(&raw)?;
+    Ok((Some(raw), value))
+}
+
+/// Write `value` unless `key` changed since it read as `expected`
+async fn swap_json<T: Serialize>(
+    kv: &KVStoreImpl,
+    key: String,
+    expected: Option<String>,
+    value: &T,
+) -> Result<bool, ServerFnError> {
+    let swapped = kv
+        .compare_and_swap(key, expected, serde_json::to_string(value)?)
+        .await?;
+    Ok(swapped)
+}
+
+async fn user_canister_from_cookie() -> Result<Principal, ServerFnError> {
+    let key: Key = expect_context();
+    let jar: SignedCookieJar = extract_with_state(&key).await?;
+    let Some(principal) = extract_principal_from_cookie(&jar)? else {
+        return Err(ServerFnError::new("user is not logged in"));
+    };
+
+    let canisters = unauth_canisters();
+    canisters
+        .get_individual_canister_by_user_principal(principal)
+        .await?
+        .ok_or_else(|| ServerFnError::new("user canister not found"))
+}
+
+pub async fn get_ml_feed_cached_impl(
+    limit: u32,
+    recently_seen: Vec<SeenPost>,
+) -> Result<Vec<PostId>, ServerFnError> {
+    let kv: KVStoreImpl = expect_context();
+    let ml_feed: MLFeed = expect_context();
+    let user_canister = user_canister_from_cookie().await?;
+
+    // Concurrent requests of the same user retry on conflict, the ML feed is only queried once
+    let mut fetched: Option<Vec<PostId>> = None;
+    for _ in 0..MAX_UPDATE_ATTEMPTS {
+        let (seen_raw, mut seen): (_, VecDeque<SeenPost>) =
+            read_json(&kv, seen_key(user_canister)).await?;
+        let mut seen_ids = seen.iter().map(SeenPost::id).collect::<HashSet<_>>();
+        for post in &recently_seen {
+            if seen_ids.insert(post.id()) {
+                seen.push_back(post.clone());
+            }
+        }
+        while seen.len() > MAX_SEEN_POSTS {
+            if let Some(evicted) = seen.pop_front() {
+                seen_ids.remove(&evicted.id());
+            }
+        }
+
+        let (next_raw, mut next_batch): (_, Vec<PostId>) =
+            read_json(&kv, next_batch_key(user_canister)).await?;
+        next_batch.retain(|post| !seen_ids.contains(post));
+
+        if next_batch.len() < limit as usize {
+            if fetched.is_none() {
+                let filter_hint = seen.iter().rev().take(FILTER_HINT_POSTS).cloned().collect();
+                let posts = ml_feed
+                    .get_start_feed(&user_canister, limit + PREFETCH_POSTS, filter_hint)
+                    .await
+                    .map_err(|e| {
+                        if is_transient(&e) {
+                            ServerFnError::new(ML_FEED_UNREACHABLE)
+                        } else {
+                            e.into()
+                        }
+                    })?;
+                fetched = Some(posts);
+            }
+            for post in fetched.iter().flatten() {
+                if !seen_ids.contains(post) && !next_batch.contains(post) {
+                    next_batch.push(*post);
+                }
+            }
+        }
+
+        let served = next_batch
+            .drain(..next_batch.len().min(limit as usize))
+            .collect::<Vec<_>>();
+
+        // Merging `recently_seen` again after a conflict is a no-op
+        if !swap_json(&kv, seen_key(user_canister), seen_raw, &seen).await? {
+            continue;
+        }
+        if !swap_json(&kv, next_batch_key(user_canister), next_raw, &next_batch).await? {
+            continue;
+        }
+
+        return Ok(served);
+    }
+
+    Err(ServerFnError::new("ml feed cache is busy, try again"))
+}
@@ -1,7 +1,155 @@
+#[cfg(feature = "ssr")]
+pub mod feed_cache;
 use crate::consts::ML_FEED_GRPC_URL;
 use candid::Principal;
+use leptos::{server, ServerFnError};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use web_time::Duration;
+
+use super::{posts::PostDetails, time::sleep, types::PostId};
+
+/// Configuration shared by the SSR (tonic) and hydrate (grpc-web) ML feed clients
+#[derive(Clone, Debug, PartialEq)]
+pub struct MlFeedConfig {
+    pub url: String,
+    pub connect_timeout: Duration,
+    pub request_timeout: Duration,
+    /// Number of retries after the first attempt
+    pub max_retries: u32,
+    /// Backoff before the first retry, doubled on every subsequent retry
+    pub retry_backoff: Duration,
+}
+
+impl Default for MlFeedConfig {
+    fn default() -> Self {
+        Self {
+            url: ML_FEED_GRPC_URL.to_string(),
+            // The feed falls back to the post cache, so a single short attempt
+            // keeps the first video from waiting on an unreachable server
+            connect_timeout: Duration::from_secs(2),
+            request_timeout: Duration::from_secs(3),
+            max_retries: 0,
+            retry_backoff: Duration::from_millis(200),
+        }
+    }
+}
+
+impl MlFeedConfig {
+    /// Read the config from the environment, falling back to defaults
+    /// for missing or malformed values
+    /// on the server, the variables are read at runtime
+    /// on the client, they are read at compile time
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let millis = |key: &str, fallback: Duration| {
+            env_var(key)
+                .and_then(|v| v.parse().ok())
+                .map(Duration::from_millis)
+                .unwrap_or(fallback)
+        };
+
+        Self {
+            url: env_var("ML_FEED_GRPC_URL").unwrap_or(default.url),
+            connect_timeout: millis("ML_FEED_CONNECT_TIMEOUT_MS", default.connect_timeout),
+            request_timeout: millis("ML_FEED_REQUEST_TIMEOUT_MS", default.request_timeout),
+            max_retries: env_var("ML_FEED_MAX_RETRIES")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.max_retries),
+            retry_backoff: millis("ML_FEED_RETRY_BACKOFF_MS", default.retry_backoff),
+        }
+    }
+
+    /// Run `f`, retrying with exponential backoff on transient errors
+    pub async fn with_retries<T, Fut: Future<Output = Result<T, tonic::Status>>>(
+        &self,
+        mut f: impl FnMut() -> Fut,
+    ) -> Result<T, tonic::Status> {
+        let mut backoff = self.retry_backoff;
+        let mut attempt = 0;
+        loop {
+            match f().await {
+                Ok(res) => return Ok(res),
+                Err(e) if attempt < self.max_retries && is_transient(&e) => {
+                    log::warn!("ml feed request failed, retrying: {e}");
+                    sleep(backoff).await;
+                    backoff *= 2;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn env_var(key: &str) -> Option<String> {
+    std::env::var(key).ok()
+}
+
+#[cfg(not(feature = "ssr"))]
+fn env_var(key: &str) -> Option<String> {
+    let val = match key {
+        "ML_FEED_GRPC_URL" => option_env!("ML_FEED_GRPC_URL"),
+        "ML_FEED_CONNECT_TIMEOUT_MS" => option_env!("ML_FEED_CONNECT_TIMEOUT_MS"),
+        "ML_FEED_REQUEST_TIMEOUT_MS" => option_env!("ML_FEED_REQUEST_TIMEOUT_MS"),
+        "ML_FEED_MAX_RETRIES" => option_env!("ML_FEED_MAX_RETRIES"),
+        "ML_FEED_RETRY_BACKOFF_MS" => option_env!("ML_FEED_RETRY_BACKOFF_MS"),
+        _ => None,
+    };
+    val.map(str::to_string)
+}
+
+/// Errors for which the ML feed server is considered unreachable
+/// callers should fall back to the canister backed feed for these
+pub fn is_transient(status: &tonic::Status) -> bool {
+    use tonic::Code;
+    matches!(
+        status.code(),
+        Code::Unavailable | Code::DeadlineExceeded | Code::Unknown | Code::ResourceExhausted
+    )
+}
+
+/// Error of [get_ml_feed_cached] when the ML feed server is unreachable,
+/// see [is_transient]
+pub const ML_FEED_UNREACHABLE: &str = "ML feed server is unreachable";
+
+/// A post the user has already been served
+/// sent to the ML feed server so it can filter them out
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeenPost {
+    pub canister_id: Principal,
+    pub post_id: u64,
+    pub video_uid: String,
+}
+
+impl SeenPost {
+    pub fn id(&self) -> PostId {
+        (self.canister_id, self.post_id)
+    }
+}
+
+impl From<&PostDetails> for SeenPost {
+    fn from(post: &PostDetails) -> Self {
+        Self {
+            canister_id: post.canister_id,
+            post_id: post.post_id,
+            video_uid: post.uid.clone(),
+        }
+    }
+}
 
-use super::types::PostId;
+/// Get the next batch of the ML feed for the logged in user
+/// through the server side feed cache
+/// `recently_seen` only needs to contain posts queued since the last call,
+/// the server keeps track of everything the user has seen before
+#[server]
+pub async fn get_ml_feed_cached(
+    limit: u32,
+    recently_seen: Vec<SeenPost>,
+) -> Result<Vec<PostId>, ServerFnError> {
+    feed_cache::get_ml_feed_cached_impl(limit, recently_seen).await
+}
 
 #[cfg(feature = "hydrate")]
 pub mod ml_feed_grpcweb {
@@ -9,7 +157,6 @@ pub mod ml_feed_grpcweb {
     use crate::utils::ml_feed::ml_feed_grpcweb::ml_feed_proto::{
         ml_feed_client::MlFeedClient, FeedRequest, PostItem,
     };
-    use crate::utils::posts::PostDetails;
     use tonic_web_wasm_client::Client;
 
     pub mod ml_feed_proto {
@@ -19,26 +166,32 @@ pub mod ml_feed_grpcweb {
     #[derive(Clone)]
     pub struct MLFeed {
         pub client: MlFeedClient<Client>,
+        pub config: MlFeedConfig,
     }
 
     impl Default for MLFeed {
         fn default() -> Self {
-            let client = Client::new(ML_FEED_GRPC_URL.to_string());
+            Self::new(MlFeedConfig::from_env())
+        }
+    }
+
+    impl MLFeed {
+        pub fn new(config: MlFeedConfig) -> Self {
+            let client = Client::new(config.url.clone());
 
             Self {
                 client: MlFeedClient::new(client),
+                config,
             }
         }
-    }
 
-    impl MLFeed {
         pub async fn get_next_feed(
-            mut self,
+            self,
             canister_id: &Principal,
             limit: u32,
             filter_list: Vec<PostDetails>,
         ) -> Result<Vec<PostId>, tonic::Status> {
-            let request = FeedRequest {
+            let feed_req = FeedRequest {
                 canister_id: canister_id.to_string(),
                 filter_posts: filter_list
                     .iter()
@@ -51,12 +204,15 @@ pub mod ml_feed_grpcweb {
                 num_results: limit,
             };
 
-            let response = self.client.get_feed(request).await.map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::Internal,
-                    format!("Error fetching posts: {:?}", e),
-                )
-            })?;
+            let response = self
+                .config
+                .with_retries(|| {
+                    let mut client = self.client.clone();
+                    let mut request = tonic::Request::new(feed_req.clone());
+                    request.set_timeout(self.config.request_timeout);
+                    async move { client.get_feed(request).await }
+                })
+                .await?;
 
             let feed_res = response.into_inner().feed;
 
@@ -76,61 +232,81 @@ pub mod ml_feed_grpcweb {
 #[cfg(feature = "ssr")]
 pub mod ml_feed_grpc {
     use super::*;
-    use crate::utils::posts::PostDetails;
+    use crate::utils::ml_feed::ml_feed_grpc::ml_feed_proto::{
+        ml_feed_client::MlFeedClient, FeedRequest, PostItem,
+    };
+    use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
 
     pub mod ml_feed_proto {
         tonic::include_proto!("ml_feed");
     }
 
-    pub async fn get_start_feed(
-        canister_id: &Principal,
-        limit: u32,
-        filter_list: Vec<PostDetails>,
-    ) -> Result<Vec<PostId>, tonic::Status> {
-        use crate::utils::ml_feed::ml_feed_grpc::ml_feed_proto::{
-            ml_feed_client::MlFeedClient, FeedRequest, PostItem,
-        };
-        use tonic::transport::Channel;
+    /// Client of the ML feed server, its channel connects lazily
+    /// and is shared by every request
+    #[derive(Clone)]
+    pub struct MLFeed {
+        pub client: MlFeedClient<Channel>,
+        pub config: MlFeedConfig,
+    }
 
-        let channel = Channel::from_static("https://yral-ml-feed-server-staging.fly.dev:443")
-            .connect()
-            .await
-            .expect("Couldn't connect to ML feed server");
+    impl MLFeed {
+        pub fn new(config: MlFeedConfig) -> Self {
+            let mut endpoint = Endpoint::from_shared(config.url.clone())
+                .expect("Invalid ML feed url")
+                .connect_timeout(config.connect_timeout)
+                .timeout(config.request_timeout);
+            // allow plaintext for local mock servers
+            if config.url.starts_with("https") {
+                endpoint = endpoint
+                    .tls_config(ClientTlsConfig::new().with_webpki_roots())
+                    .expect("Couldn't update TLS config for ML feed");
+            }
 
-        // let mut client = MlFeedClient::connect(ML_FEED_GRPC_URL).await.unwrap();
+            Self {
+                client: MlFeedClient::new(endpoint.connect_lazy()),
+                config,
+            }
+        }
 
-        let mut client = MlFeedClient::new(channel);
+        pub async fn get_start_feed(
+            &self,
+            canister_id: &Principal,
+            limit: u32,
+            filter_list: Vec<SeenPost>,
+        ) -> Result<Vec<PostId>, tonic::Status> {
+            let feed_req = FeedRequest {
+                canister_id: canister_id.to_string(),
+                filter_posts: filter_list
+                    .into_iter()
+                    .map(|item| PostItem {
+                        post_id: item.post_id as u32,
+                        canister_id: item.canister_id.to_string(),
+                        video_id: item.video_uid,
+                    })
+                    .collect(),
+                num_results: limit,
+            };
 
-        let request = tonic::Request::new(FeedRequest {
-            canister_id: canister_id.to_string(),
-            filter_posts: filter_list
+            let response = self
+                .config
+                .with_retries(|| {
+                    let mut client = self.client.clone();
+                    let request = tonic::Request::new(feed_req.clone());
+                    async move { client.get_feed(request).await }
+                })
+                .await?;
+
+            let feed_res = response.into_inner().feed;
+
+            Ok(feed_res
                 .iter()
-                .map(|item| PostItem {
-                    post_id: item.post_id as u32,
-                    canister_id: item.canister_id.to_string(),
-                    video_id: item.uid.clone(),
+                .map(|item| {
+                    (
+                        Principal::from_text(&item.canister_id).unwrap(),
+                        item.post_id as u64,
+                    )
                 })
-                .collect(),
-            num_results: limit,
-        });
-
-        let response = client.get_feed(request).await.map_err(|e| {
-            tonic::Status::new(
-                tonic::Code::Internal,
-                format!("error fetching posts: {:?}", e),
-            )
-        })?;
-
-        let feed_res = response.into_inner().feed;
-
-        Ok(feed_res
-            .iter()
-            .map(|item| {
-                (
-                    Principal::from_text(&item.canister_id).unwrap(),
-                    item.post_id as u64,
-                )
-            })
-            .collect())
+                .collect())
+        }
     }
 }
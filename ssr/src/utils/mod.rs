@@ -3,12 +3,17 @@ use leptos::{create_memo, Resource, Serializable, Signal, SignalStream, SignalWi
 use serde::{Deserialize, Serialize};
 
 pub mod ab_testing;
+#[cfg(all(feature = "cloudflare", feature = "ssr"))]
+pub mod cf_tus;
 pub mod event_streaming;
+pub mod hashtags;
 pub mod host;
 pub mod ic;
 pub mod icon;
+pub mod leaderboard;
 pub mod ml_feed;
 pub mod notifications;
+pub mod post_edits;
 pub mod posts;
 pub mod profile;
 #[cfg(feature = "qstash")]
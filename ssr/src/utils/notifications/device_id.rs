@@ -38,3 +38,36 @@ pub async fn send_principal_and_token_offchain(
 
     Ok(())
 }
+
+/// Push to every device bound to `principal_id` by [send_principal_and_token_offchain],
+/// delivered by the off-chain agent
+#[cfg(all(feature = "ssr", feature = "ga4"))]
+pub async fn push_to_principal(
+    principal_id: String,
+    title: String,
+    body: String,
+    url: String,
+) -> Result<(), ServerFnError> {
+    use crate::consts::OFF_CHAIN_AGENT_URL;
+    use serde_json::json;
+
+    let mut off_chain_agent_grpc_auth_token = env::var("GRPC_AUTH_TOKEN").expect("GRPC_AUTH_TOKEN");
+    // removing whitespaces and new lines for proper parsing
+    off_chain_agent_grpc_auth_token.retain(|c| !c.is_whitespace());
+
+    let push_ep = OFF_CHAIN_AGENT_URL.join("notifications/send").unwrap();
+    reqwest::Client::new()
+        .post(push_ep)
+        .bearer_auth(off_chain_agent_grpc_auth_token)
+        .json(&json!({
+            "principal_id": principal_id,
+            "title": title,
+            "body": body,
+            "url": url,
+        }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(())
+}
@@ -0,0 +1,634 @@
+//! In-app notification inbox
+//!
+//! Notifications are fanned out on the server by [server_impl::notify]: stored in the
+//! recipient's inbox in the KV store and pushed to their devices, unless the category is muted.
+use candid::{Nat, Principal};
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    component::infinite_scroller::{CursoredDataProvider, KeyedData, PageEntry},
+    state::canisters::CanistersAuthWire,
+};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum NotificationCategory {
+    BetResults,
+    Referrals,
+    TokensReceived,
+    TokensClaimed,
+    FollowedPosts,
+}
+
+impl NotificationCategory {
+    pub const ALL: [Self; 5] = [
+        Self::BetResults,
+        Self::Referrals,
+        Self::TokensReceived,
+        Self::TokensClaimed,
+        Self::FollowedPosts,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::BetResults => "Bet Results",
+            Self::Referrals => "Referral Rewards",
+            Self::TokensReceived => "Tokens Received",
+            Self::TokensClaimed => "Tokens Claimed",
+            Self::FollowedPosts => "New Posts",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum NotificationKind {
+    BetWon {
+        post_canister: Principal,
+        post_id: u64,
+        amount: u64,
+    },
+    BetLost {
+        post_canister: Principal,
+        post_id: u64,
+        amount: u64,
+    },
+    ReferralReward {
+        referee: Principal,
+    },
+    TokenReceived {
+        from: Principal,
+        symbol: String,
+        amount: String,
+    },
+    TokensClaimed {
+        token_root: Principal,
+        amount: u64,
+    },
+    FollowedPost {
+        publisher: Principal,
+        post_canister: Principal,
+        post_id: u64,
+    },
+}
+
+impl NotificationKind {
+    pub fn category(&self) -> NotificationCategory {
+        match self {
+            Self::BetWon { .. } | Self::BetLost { .. } => NotificationCategory::BetResults,
+            Self::ReferralReward { .. } => NotificationCategory::Referrals,
+            Self::TokenReceived { .. } => NotificationCategory::TokensReceived,
+            Self::TokensClaimed { .. } => NotificationCategory::TokensClaimed,
+            Self::FollowedPost { .. } => NotificationCategory::FollowedPosts,
+        }
+    }
+
+    pub fn title(&self) -> String {
+        match self {
+            Self::BetWon { .. } => "You won a bet!".into(),
+            Self::BetLost { .. } => "You lost a bet".into(),
+            Self::ReferralReward { .. } => "Referral reward credited".into(),
+            Self::TokenReceived { symbol, .. } => format!("You received {symbol}"),
+            Self::TokensClaimed { .. } => "Tokens claimed".into(),
+            Self::FollowedPost { .. } => "New post".into(),
+        }
+    }
+
+    pub fn body(&self) -> String {
+        match self {
+            Self::BetWon { amount, .. } => format!("You received {amount} COYNs"),
+            Self::BetLost { amount, .. } => format!("You lost {amount} COYNs"),
+            Self::ReferralReward { referee } => format!("{referee} joined with your link"),
+            Self::TokenReceived {
+                from,
+                symbol,
+                amount,
+            } => format!("{amount} {symbol} from {from}"),
+            Self::TokensClaimed { amount, .. } => {
+                format!("{amount} tokens were claimed from your neuron")
+            }
+            Self::FollowedPost { publisher, .. } => format!("{publisher} posted a new video"),
+        }
+    }
+
+    /// Page opened when the notification is clicked
+    pub fn href(&self) -> String {
+        match self {
+            Self::BetWon {
+                post_canister,
+                post_id,
+                ..
+            }
+            | Self::BetLost {
+                post_canister,
+                post_id,
+                ..
+            }
+            | Self::FollowedPost {
+                post_canister,
+                post_id,
+                ..
+            } => format!("/hot-or-not/{post_canister}/{post_id}"),
+            Self::ReferralReward { .. } => "/refer-earn".into(),
+            Self::TokenReceived { .. } => "/wallet".into(),
+            Self::TokensClaimed { token_root, .. } => format!("/token/info/{token_root}"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Notification {
+    /// Zero padded creation time in nanoseconds, sorts chronologically
+    pub id: String,
+    pub created_at_secs: u64,
+    pub kind: NotificationKind,
+    pub read: bool,
+}
+
+impl KeyedData for Notification {
+    type Key = String;
+
+    fn key(&self) -> Self::Key {
+        self.id.clone()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct NotificationPrefs {
+    /// Mirrors `NOTIFICATIONS_ENABLED_STORE` of the user's devices
+    pub push_enabled: bool,
+    /// Not pushed and stored as already read
+    pub muted: Vec<NotificationCategory>,
+}
+
+impl NotificationPrefs {
+    pub fn is_muted(&self, category: NotificationCategory) -> bool {
+        self.muted.contains(&category)
+    }
+
+    pub fn set_muted(&mut self, category: NotificationCategory, muted: bool) {
+        self.muted.retain(|c| *c != category);
+        if muted {
+            self.muted.push(category);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct NotificationsProvider {
+    cans_wire: CanistersAuthWire,
+}
+
+impl NotificationsProvider {
+    pub fn new(cans_wire: CanistersAuthWire) -> Self {
+        Self { cans_wire }
+    }
+}
+
+impl CursoredDataProvider for NotificationsProvider {
+    type Data = Notification;
+    type Error = ServerFnError;
+
+    async fn get_by_cursor(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> Result<PageEntry<Notification>, ServerFnError> {
+        let data = get_notifications(self.cans_wire.clone(), start, end).await?;
+        let list_end = data.len() < (end - start);
+        Ok(PageEntry {
+            data,
+            end: list_end,
+        })
+    }
+}
+
+/// Newest first
+#[server(
+    input = Cbor
+)]
+pub async fn get_notifications(
+    cans_wire: CanistersAuthWire,
+    start: usize,
+    end: usize,
+) -> Result<Vec<Notification>, ServerFnError> {
+    use crate::auth::server_impl::store::KVStoreImpl;
+
+    let user = cans_wire.canisters()?.user_principal();
+    let kv: KVStoreImpl = expect_context();
+    server_impl::inbox(&kv, user, start, end).await
+}
+
+#[server(
+    input = Cbor
+)]
+pub async fn unread_notification_count(
+    cans_wire: CanistersAuthWire,
+) -> Result<usize, ServerFnError> {
+    use crate::auth::server_impl::store::KVStoreImpl;
+
+    let user = cans_wire.canisters()?.user_principal();
+    let kv: KVStoreImpl = expect_context();
+    server_impl::unread_count(&kv, user).await
+}
+
+/// Mark `ids` as read, every notification if empty
+#[server(
+    input = Cbor
+)]
+pub async fn mark_notifications_read(
+    cans_wire: CanistersAuthWire,
+    ids: Vec<String>,
+) -> Result<(), ServerFnError> {
+    use crate::auth::server_impl::store::KVStoreImpl;
+
+    let user = cans_wire.canisters()?.user_principal();
+    let kv: KVStoreImpl = expect_context();
+    server_impl::mark_read(&kv, user, ids).await
+}
+
+#[server(
+    input = Cbor
+)]
+pub async fn get_notification_prefs(
+    cans_wire: CanistersAuthWire,
+) -> Result<NotificationPrefs, ServerFnError> {
+    use crate::auth::server_impl::store::KVStoreImpl;
+
+    let user = cans_wire.canisters()?.user_principal();
+    let kv: KVStoreImpl = expect_context();
+    server_impl::prefs(&kv, user).await
+}
+
+#[server(
+    input = Cbor
+)]
+pub async fn set_notification_prefs(
+    cans_wire: CanistersAuthWire,
+    prefs: NotificationPrefs,
+) -> Result<(), ServerFnError> {
+    use crate::auth::server_impl::store::KVStoreImpl;
+
+    let user = cans_wire.canisters()?.user_principal();
+    let kv: KVStoreImpl = expect_context();
+    server_impl::set_prefs(&kv, user, &prefs).await
+}
+
+/// Called by the sender once a transfer settled, the recipient is notified
+/// of transfer `block_index` on `ledger` as recorded by the ledger
+#[server(
+    input = Cbor
+)]
+pub async fn notify_token_received(
+    cans_wire: CanistersAuthWire,
+    ledger: Principal,
+    block_index: Nat,
+) -> Result<(), ServerFnError> {
+    use crate::auth::server_impl::store::KVStoreImpl;
+
+    let cans = cans_wire.canisters()?;
+    let kv: KVStoreImpl = expect_context();
+    server_impl::notify_transfer(&kv, &cans, ledger, block_index).await
+}
+
+/// Called by the publisher once a post is live
+#[server(
+    input = Cbor
+)]
+pub async fn notify_followers_of_post(
+    cans_wire: CanistersAuthWire,
+    post_id: u64,
+) -> Result<(), ServerFnError> {
+    use crate::auth::server_impl::store::KVStoreImpl;
+
+    let cans = cans_wire.canisters()?;
+    let kv: KVStoreImpl = expect_context();
+    server_impl::notify_followers(&kv, &cans, post_id).await
+}
+
+#[cfg(feature = "ssr")]
+pub mod server_impl {
+    use candid::{Nat, Principal};
+    use icrc_ledger_types::icrc::generic_value::Value;
+    use leptos::ServerFnError;
+    use web_time::Duration;
+
+    use super::{Notification, NotificationKind, NotificationPrefs};
+    use crate::{
+        auth::server_impl::store::{KVStore, KVStoreImpl},
+        state::canisters::Canisters,
+        utils::{
+            time::current_epoch,
+            token::{
+                icrc::{value_account, value_field, value_nat},
+                TokenBalance,
+            },
+        },
+    };
+
+    /// Notifications are dropped from the inbox after this
+    const INBOX_TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+    /// Followers beyond this are not notified of a new post
+    const MAX_NOTIFIED_FOLLOWERS: usize = 1000;
+    /// Older notifications are dropped from the inbox
+    const MAX_INBOX_SIZE: usize = 200;
+    /// [notify_once] forgets a notification was sent after this,
+    /// callers only notify of events more recent than it
+    pub const NOTIFY_DEDUP_TTL: Duration = INBOX_TTL;
+
+    /// Ids of the notifications in `user`'s inbox
+    fn inbox_index(user: Principal) -> String {
+        format!("notif-inbox:{user}")
+    }
+
+    fn notif_key(user: Principal, id: &str) -> String {
+        format!("notif:{user}:{id}")
+    }
+
+    fn prefs_key(user: Principal) -> String {
+        format!("notif-prefs:{user}")
+    }
+
+    pub async fn prefs(
+        kv: &KVStoreImpl,
+        user: Principal,
+    ) -> Result<NotificationPrefs, ServerFnError> {
+        let Some(raw) = kv.read(prefs_key(user)).await? else {
+            return Ok(NotificationPrefs::default());
+        };
+        Ok(serde_json::from_str(&raw)?)
+    }
+
+    pub async fn set_prefs(
+        kv: &KVStoreImpl,
+        user: Principal,
+        prefs: &NotificationPrefs,
+    ) -> Result<(), ServerFnError> {
+        kv.write(prefs_key(user), serde_json::to_string(prefs)?)
+            .await?;
+        Ok(())
+    }
+
+    async fn store(
+        kv: &KVStoreImpl,
+        user: Principal,
+        notif: &Notification,
+    ) -> Result<(), ServerFnError> {
+        let age = current_epoch().saturating_sub(Duration::from_secs(notif.created_at_secs));
+        let ttl = INBOX_TTL.saturating_sub(age);
+        kv.write_with_ttl(
+            notif_key(user, &notif.id),
+            serde_json::to_string(notif)?,
+            ttl,
+        )
+        .await?;
+        kv.index_add(inbox_index(user), notif.id.clone()).await?;
+        Ok(())
+    }
+
+    /// Ids in `user`'s inbox, newest first
+    /// notifications beyond [MAX_INBOX_SIZE] are dropped
+    async fn inbox_ids(kv: &KVStoreImpl, user: Principal) -> Result<Vec<String>, ServerFnError> {
+        let mut ids = kv.index_members(inbox_index(user)).await?;
+        // Ids are zero padded timestamps
+        ids.sort_by(|a, b| b.cmp(a));
+        let dropped = ids.split_off(ids.len().min(MAX_INBOX_SIZE));
+        for id in dropped {
+            kv.delete(notif_key(user, &id)).await?;
+            kv.index_remove(inbox_index(user), id).await?;
+        }
+        Ok(ids)
+    }
+
+    async fn read_notifs(
+        kv: &KVStoreImpl,
+        user: Principal,
+        ids: impl IntoIterator<Item = String>,
+    ) -> Result<Vec<Notification>, ServerFnError> {
+        let mut notifs = vec![];
+        for id in ids {
+            let Some(raw) = kv.read(notif_key(user, &id)).await? else {
+                // Expired
+                kv.index_remove(inbox_index(user), id).await?;
+                continue;
+            };
+            match serde_json::from_str(&raw) {
+                Ok(notif) => notifs.push(notif),
+                Err(e) => log::warn!("skipping malformed notification {id}: {e}"),
+            }
+        }
+        Ok(notifs)
+    }
+
+    /// Notifications `start..end` of `user`'s inbox, newest first
+    pub async fn inbox(
+        kv: &KVStoreImpl,
+        user: Principal,
+        start: usize,
+        end: usize,
+    ) -> Result<Vec<Notification>, ServerFnError> {
+        let ids = inbox_ids(kv, user).await?;
+        read_notifs(
+            kv,
+            user,
+            ids.into_iter().skip(start).take(end.saturating_sub(start)),
+        )
+        .await
+    }
+
+    pub async fn unread_count(kv: &KVStoreImpl, user: Principal) -> Result<usize, ServerFnError> {
+        let ids = inbox_ids(kv, user).await?;
+        let notifs = read_notifs(kv, user, ids).await?;
+        Ok(notifs.iter().filter(|n| !n.read).count())
+    }
+
+    pub async fn mark_read(
+        kv: &KVStoreImpl,
+        user: Principal,
+        ids: Vec<String>,
+    ) -> Result<(), ServerFnError> {
+        let ids = if ids.is_empty() {
+            inbox_ids(kv, user).await?
+        } else {
+            ids.into_iter().take(MAX_INBOX_SIZE).collect()
+        };
+        for mut notif in read_notifs(kv, user, ids).await? {
+            if notif.read {
+                continue;
+            }
+            notif.read = true;
+            store(kv, user, &notif).await?;
+        }
+        Ok(())
+    }
+
+    /// Store `kind` in `user`'s inbox and push it to their devices,
+    /// muted categories are stored as read and not pushed
+    pub async fn notify(
+        kv: &KVStoreImpl,
+        user: Principal,
+        kind: NotificationKind,
+    ) -> Result<(), ServerFnError> {
+        let prefs = prefs(kv, user).await?;
+        let muted = prefs.is_muted(kind.category());
+        let now = current_epoch();
+        let notif = Notification {
+            id: format!("{:020}", now.as_nanos()),
+            created_at_secs: now.as_secs(),
+            kind,
+            read: muted,
+        };
+        store(kv, user, &notif).await?;
+
+        // Devices are only bound to principals with the `ga4` feature
+        #[cfg(feature = "ga4")]
+        {
+            use crate::utils::notifications::device_id::push_to_principal;
+
+            let kind = &notif.kind;
+            if prefs.push_enabled && !muted {
+                if let Err(e) =
+                    push_to_principal(user.to_text(), kind.title(), kind.body(), kind.href()).await
+                {
+                    log::warn!("failed to push notification to {user}: {e}");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// [notify] unless a notification was sent for `dedup_key` in the last [NOTIFY_DEDUP_TTL]
+    pub async fn notify_once(
+        kv: &KVStoreImpl,
+        dedup_key: String,
+        user: Principal,
+        kind: NotificationKind,
+    ) -> Result<(), ServerFnError> {
+        let key = format!("notif-sent:{dedup_key}");
+        let first = kv
+            .compare_and_swap(key.clone(), None, String::new())
+            .await?;
+        if !first {
+            return Ok(());
+        }
+        kv.write_with_ttl(key.clone(), String::new(), NOTIFY_DEDUP_TTL)
+            .await?;
+
+        // Release the claim so the notification is retried
+        if let Err(e) = notify(kv, user, kind).await {
+            kv.delete(key).await?;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Notify the recipient of transfer `block_index` on `ledger`, which must be sent by
+    /// `cans`' user. The notification is built from the ledger block, not client input
+    pub async fn notify_transfer(
+        kv: &KVStoreImpl,
+        cans: &Canisters<true>,
+        ledger: Principal,
+        block_index: Nat,
+    ) -> Result<(), ServerFnError> {
+        let idx = u64::try_from(block_index.0.clone())
+            .map_err(|_| ServerFnError::new("Invalid block index"))?;
+        let block = cans
+            .icrc3_blocks(ledger, idx, 1)
+            .await?
+            .blocks
+            .into_iter()
+            .find(|block| block.id == block_index)
+            .ok_or_else(|| ServerFnError::new("Transfer block not found"))?
+            .block;
+
+        let tx = value_field(&block, "tx")
+            .filter(|tx| matches!(value_field(tx, "op"), Some(Value::Text(op)) if op == "xfer"))
+            .ok_or_else(|| ServerFnError::new("Block is not a transfer"))?;
+        let from = value_field(tx, "from").and_then(value_account);
+        let to = value_field(tx, "to").and_then(value_account);
+        let amount = value_field(tx, "amt").and_then(value_nat);
+        let (Some(from), Some(to), Some(amount)) = (from, to, amount) else {
+            return Err(ServerFnError::new("Malformed transfer block"));
+        };
+        if from.owner != cans.user_principal() {
+            return Err(ServerFnError::new("Transfer was not sent by the caller"));
+        }
+
+        let ledger_can = cans.sns_ledger(ledger).await;
+        let symbol = ledger_can.icrc_1_symbol().await?;
+        let decimals = ledger_can.icrc_1_decimals().await?;
+        notify_once(
+            kv,
+            format!("transfer:{ledger}:{idx}"),
+            to.owner,
+            NotificationKind::TokenReceived {
+                from: from.owner,
+                symbol,
+                amount: TokenBalance::new(amount, decimals).humanize_float(),
+            },
+        )
+        .await
+    }
+
+    /// Notify the followers of `cans`' user of their new post `post_id`
+    /// the post must exist in the user's canister, the followers are notified in the
+    /// background and at most once per post
+    pub async fn notify_followers(
+        kv: &KVStoreImpl,
+        cans: &Canisters<true>,
+        post_id: u64,
+    ) -> Result<(), ServerFnError> {
+        cans.authenticated_user()
+            .await
+            .get_individual_post_details_by_id(post_id)
+            .await
+            .map_err(|_| ServerFnError::new("Post not found"))?;
+
+        let kv = kv.clone();
+        let cans = cans.clone();
+        tokio::spawn(async move {
+            if let Err(e) = notify_followers_of(&kv, &cans, post_id).await {
+                log::warn!("failed to notify followers of post {post_id}: {e}");
+            }
+        });
+
+        Ok(())
+    }
+
+    async fn notify_followers_of(
+        kv: &KVStoreImpl,
+        cans: &Canisters<true>,
+        post_id: u64,
+    ) -> Result<(), ServerFnError> {
+        let publisher = cans.user_principal();
+        let post_canister = cans.user_canister();
+        let user = cans.individual_user(post_canister).await;
+
+        let mut followers = vec![];
+        let mut last_index = None;
+        while followers.len() < MAX_NOTIFIED_FOLLOWERS {
+            let page = user
+                .get_principals_that_follow_this_profile_paginated(last_index)
+                .await?;
+            let Some(&(idx, _)) = page.last() else {
+                break;
+            };
+            last_index = Some(idx);
+            followers.extend(page.into_iter().map(|(_, follower)| follower));
+        }
+
+        for follower in followers {
+            let kind = NotificationKind::FollowedPost {
+                publisher,
+                post_canister,
+                post_id,
+            };
+            let dedup_key = format!("post:{post_canister}:{post_id}:{follower}");
+            if let Err(e) = notify_once(kv, dedup_key, follower, kind).await {
+                log::warn!("failed to notify follower {follower} of post {post_id}: {e}");
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
 
 pub mod device_id;
+pub mod inbox;
 
 #[wasm_bindgen(module = "/src/utils/notifications/setup-firebase-messaging.js")]
 extern "C" {
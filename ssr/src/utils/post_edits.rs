@@ -0,0 +1,229 @@
+//! Owner edits of published posts, and hiding them
+//!
+//! The user canister offers no way to change a post once `add_post_v_2` succeeded,
+//! so changes are kept in the server KV store and applied over the canister data
+//! by [get_post_uid](super::posts::get_post_uid) and the feed sources.
+//! There is no owner delete or status update either, only the platform sets
+//! `PostStatus::Deleted`, so owners can hide a post from the app but not delete it:
+//! the canister keeps serving it to anyone asking for it by id.
+//! Bets placed directly against the canister bypass this overlay, only the app
+//! stops offering them once hot or not consent is withdrawn.
+use std::collections::HashMap;
+
+use leptos::{server, ServerFnError};
+use serde::{Deserialize, Serialize};
+
+use super::{posts::PostDetails, types::PostId};
+use crate::state::canisters::CanistersAuthWire;
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub struct PostEdit {
+    pub description: String,
+    pub hashtags: Vec<String>,
+    pub is_nsfw: bool,
+    pub enable_hot_or_not: bool,
+}
+
+impl PostEdit {
+    pub fn from_post(post: &PostDetails) -> Self {
+        Self {
+            description: post.description.clone(),
+            hashtags: post.hastags.clone(),
+            is_nsfw: post.is_nsfw,
+            enable_hot_or_not: post.is_hot_or_not(),
+        }
+    }
+
+    pub fn apply(&self, post: &mut PostDetails) {
+        post.description.clone_from(&self.description);
+        post.hastags.clone_from(&self.hashtags);
+        post.is_nsfw = self.is_nsfw;
+        // Consent can only be withdrawn, the ranking score is assigned by the canister
+        if !self.enable_hot_or_not {
+            post.hot_or_not_feed_ranking_score = None;
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct PostOverride {
+    pub edit: Option<PostEdit>,
+    #[serde(alias = "deleted")]
+    pub hidden: bool,
+}
+
+/// Apply `overrides` to `posts`, dropping hidden ones
+pub fn apply_overrides(
+    posts: Vec<PostDetails>,
+    overrides: &HashMap<PostId, PostOverride>,
+) -> Vec<PostDetails> {
+    posts
+        .into_iter()
+        .filter_map(|mut post| {
+            let Some(over) = overrides.get(&(post.canister_id, post.post_id)) else {
+                return Some(post);
+            };
+            if over.hidden {
+                return None;
+            }
+            if let Some(edit) = &over.edit {
+                edit.apply(&mut post);
+            }
+            Some(post)
+        })
+        .collect()
+}
+
+/// Overrides of the given posts, looked up in batches
+/// failures are logged and treated as no overrides
+pub async fn fetch_post_overrides(post_ids: Vec<PostId>) -> HashMap<PostId, PostOverride> {
+    let mut overrides = HashMap::new();
+    for batch in post_ids.chunks(MAX_OVERRIDE_LOOKUP) {
+        match get_post_overrides(batch.to_vec()).await {
+            Ok(res) => overrides.extend(res),
+            Err(e) => log::warn!("failed to fetch post overrides: {e}"),
+        }
+    }
+    overrides
+}
+
+/// Apply the owners' edits to posts loaded from canisters, dropping hidden ones
+pub async fn apply_post_overrides(posts: Vec<PostDetails>) -> Vec<PostDetails> {
+    if posts.is_empty() {
+        return posts;
+    }
+    let overrides =
+        fetch_post_overrides(posts.iter().map(|p| (p.canister_id, p.post_id)).collect()).await;
+    apply_overrides(posts, &overrides)
+}
+
+/// Upper bound on posts per lookup
+pub const MAX_OVERRIDE_LOOKUP: usize = 50;
+
+#[server(
+    input = Cbor
+)]
+pub async fn get_post_overrides(
+    post_ids: Vec<PostId>,
+) -> Result<Vec<(PostId, PostOverride)>, ServerFnError> {
+    server_impl::get_post_overrides(post_ids).await
+}
+
+/// Edit a post of the authenticated user, returns the normalized edit
+#[server(
+    input = Cbor
+)]
+pub async fn edit_post(
+    cans_wire: CanistersAuthWire,
+    post_id: u64,
+    edit: PostEdit,
+) -> Result<PostEdit, ServerFnError> {
+    server_impl::edit_post(cans_wire, post_id, edit).await
+}
+
+/// Hide a post of the authenticated user from the app, see the module docs
+#[server(
+    input = Cbor
+)]
+pub async fn hide_post(cans_wire: CanistersAuthWire, post_id: u64) -> Result<(), ServerFnError> {
+    server_impl::hide_post(cans_wire, post_id).await
+}
+
+#[cfg(feature = "ssr")]
+mod server_impl {
+    use candid::Principal;
+    use futures::future::try_join_all;
+    use leptos::{expect_context, ServerFnError};
+
+    use super::{PostEdit, PostOverride, MAX_OVERRIDE_LOOKUP};
+    use crate::{
+        auth::server_impl::store::{KVStore, KVStoreImpl},
+        state::canisters::CanistersAuthWire,
+        utils::{hashtags::merge_hashtags, types::PostId},
+    };
+
+    fn override_key(canister_id: Principal, post_id: u64) -> String {
+        format!("post-override:{canister_id}:{post_id}")
+    }
+
+    async fn read_override(
+        kv: &KVStoreImpl,
+        canister_id: Principal,
+        post_id: u64,
+    ) -> Result<Option<PostOverride>, ServerFnError> {
+        let Some(raw) = kv.read(override_key(canister_id, post_id)).await? else {
+            return Ok(None);
+        };
+        Ok(Some(serde_json::from_str(&raw)?))
+    }
+
+    async fn update_override(
+        cans_wire: CanistersAuthWire,
+        post_id: u64,
+        update: impl FnOnce(&mut PostOverride),
+    ) -> Result<(), ServerFnError> {
+        // Posts live in the user canister, owning the canister means owning the post
+        let canister_id = cans_wire.canisters()?.user_canister();
+        let kv: KVStoreImpl = expect_context();
+        let mut over = read_override(&kv, canister_id, post_id)
+            .await?
+            .unwrap_or_default();
+        if over.hidden {
+            return Err(ServerFnError::new("Post was hidden"));
+        }
+        update(&mut over);
+        kv.write(
+            override_key(canister_id, post_id),
+            serde_json::to_string(&over)?,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_post_overrides(
+        post_ids: Vec<PostId>,
+    ) -> Result<Vec<(PostId, PostOverride)>, ServerFnError> {
+        if post_ids.len() > MAX_OVERRIDE_LOOKUP {
+            return Err(ServerFnError::Args("Too many posts".into()));
+        }
+        let kv: KVStoreImpl = expect_context();
+        let overrides = try_join_all(post_ids.into_iter().map(|(canister_id, post_id)| {
+            let kv = &kv;
+            async move {
+                let over = read_override(kv, canister_id, post_id).await?;
+                Ok::<_, ServerFnError>(over.map(|over| ((canister_id, post_id), over)))
+            }
+        }))
+        .await?;
+
+        Ok(overrides.into_iter().flatten().collect())
+    }
+
+    pub async fn edit_post(
+        cans_wire: CanistersAuthWire,
+        post_id: u64,
+        edit: PostEdit,
+    ) -> Result<PostEdit, ServerFnError> {
+        if edit.description.len() < 10 {
+            return Err(ServerFnError::Args(
+                "Description must be at least 10 characters".into(),
+            ));
+        }
+        let hashtags = merge_hashtags(&edit.hashtags, &edit.description)
+            .map_err(|e| ServerFnError::Args(e.to_string()))?;
+        let edit = PostEdit { hashtags, ..edit };
+
+        let saved = edit.clone();
+        update_override(cans_wire, post_id, move |over| over.edit = Some(saved)).await?;
+
+        Ok(edit)
+    }
+
+    pub async fn hide_post(
+        cans_wire: CanistersAuthWire,
+        post_id: u64,
+    ) -> Result<(), ServerFnError> {
+        update_override(cans_wire, post_id, |over| over.hidden = true).await
+    }
+}
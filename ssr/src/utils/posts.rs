@@ -6,7 +6,7 @@ use yral_canisters_client::individual_user_template::PostDetailsForFrontend;
 
 use crate::state::canisters::Canisters;
 
-use super::{profile::propic_from_principal, types::PostStatus};
+use super::{post_edits::apply_post_overrides, profile::propic_from_principal, types::PostStatus};
 
 use ic_agent::AgentError;
 use thiserror::Error;
@@ -111,14 +111,33 @@ impl PostDetails {
     }
 }
 
+/// Post as published by its owner, with their later edits and deletion applied
 pub async fn get_post_uid<const AUTH: bool>(
     canisters: &Canisters<AUTH>,
     user_canister: Principal,
     post_id: u64,
 ) -> Result<Option<PostDetails>, PostViewError> {
-    let post_creator_can = canisters.individual_user(user_canister).await;
-    let post_details = match post_creator_can
-        .get_individual_post_details_by_id(post_id)
+    let Some(post) = get_post_uid_raw(canisters, user_canister, post_id).await? else {
+        return Ok(None);
+    };
+    Ok(apply_post_overrides(vec![post]).await.pop())
+}
+
+/// Post as stored in the canister
+/// callers resolving many posts use this and apply
+/// [apply_post_overrides] once per batch
+pub async fn get_post_uid_raw<const AUTH: bool>(
+    canisters: &Canisters<AUTH>,
+    user_canister: Principal,
+    post_id: u64,
+) -> Result<Option<PostDetails>, PostViewError> {
+    let post_details = match canisters
+        .with_retry("individual_user.get_post_details", || async move {
+            let post_creator_can = canisters.individual_user(user_canister).await;
+            post_creator_can
+                .get_individual_post_details_by_id(post_id)
+                .await
+        })
         .await
     {
         Ok(p) => p,
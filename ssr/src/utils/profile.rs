@@ -15,7 +15,7 @@ use crate::{
     state::canisters::Canisters,
 };
 
-use super::{posts::PostDetails, time::current_epoch};
+use super::{post_edits::apply_post_overrides, posts::PostDetails, time::current_epoch};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct ProfileDetails {
@@ -127,6 +127,10 @@ impl BetDetails {
         }
     }
 
+    pub fn placed_at(&self) -> Duration {
+        self.placed_at
+    }
+
     pub fn bet_duration(&self) -> Duration {
         // Bet duration + 5 minute overhead
         Duration::from_secs(((self.slot_id as u64) * 60 * 60) + 5 * 60)
@@ -220,10 +224,18 @@ impl CursoredDataProvider for PostsProvider {
         start: usize,
         end: usize,
     ) -> Result<PageEntry<PostDetails>, AgentError> {
-        let user = self.canisters.individual_user(self.user).await;
+        let canisters = &self.canisters;
+        let user_canister = self.user;
         let limit = end - start;
-        let posts = user
-            .get_posts_of_this_user_profile_with_pagination_cursor(start as u64, limit as u64)
+        let posts = canisters
+            .with_retry("individual_user.get_posts", || async move {
+                let user = canisters.individual_user(user_canister).await;
+                user.get_posts_of_this_user_profile_with_pagination_cursor(
+                    start as u64,
+                    limit as u64,
+                )
+                .await
+            })
             .await?;
         let posts = match posts {
             Result11::Ok(v) => v,
@@ -241,6 +253,7 @@ impl CursoredDataProvider for PostsProvider {
             .into_iter()
             .map(|details| PostDetails::from_canister_post(false, self.user, details))
             .collect();
+        let post_details = apply_post_overrides(post_details).await;
         self.video_queue.update_untracked(|vq| {
             vq.extend_from_slice(&post_details);
         });
@@ -272,10 +285,15 @@ impl CursoredDataProvider for BetsProvider {
         start: usize,
         end: usize,
     ) -> Result<PageEntry<BetDetails>, AgentError> {
-        let user = self.canisters.individual_user(self.user).await;
+        let canisters = &self.canisters;
+        let user_canister = self.user;
         assert_eq!(end - start, 10);
-        let bets = user
-            .get_hot_or_not_bets_placed_by_this_profile_with_pagination(start as u64)
+        let bets = canisters
+            .with_retry("individual_user.get_bets", || async move {
+                let user = canisters.individual_user(user_canister).await;
+                user.get_hot_or_not_bets_placed_by_this_profile_with_pagination(start as u64)
+                    .await
+            })
             .await?;
         let list_end = bets.len() < (end - start);
         Ok(PageEntry {
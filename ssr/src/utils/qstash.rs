@@ -5,6 +5,7 @@ use http::{
     HeaderMap, HeaderValue,
 };
 use reqwest::{Client, Url};
+use serde::Serialize;
 use yral_qstash_types::ClaimTokensRequest;
 
 use crate::consts::{CDAO_SWAP_TIME_SECS, OFF_CHAIN_AGENT_URL};
@@ -52,4 +53,26 @@ impl QStashClient {
             .await?;
         Ok(())
     }
+
+    /// Deliver `req` as JSON to `callback` no earlier than `at_secs` (unix seconds)
+    pub async fn enqueue_draft_publish(
+        &self,
+        callback: Url,
+        req: &impl Serialize,
+        at_secs: u64,
+    ) -> Result<(), reqwest::Error> {
+        let path = format!("publish/{callback}");
+        let ep = self.base_url.join(&path).unwrap();
+
+        self.client
+            .post(ep)
+            .json(req)
+            .header(CONTENT_TYPE, "application/json")
+            .header("upstash-method", "POST")
+            .header("upstash-not-before", at_secs.to_string())
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
 }
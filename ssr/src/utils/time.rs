@@ -88,3 +88,39 @@ pub fn parse_ns_to_datetime(timestamp: u64) -> Result<String, ParseError> {
         )
     })
 }
+
+/// Format a timestamp in nanoseconds as an ISO 8601 UTC datetime
+/// i.e 2024-08-14T09:30:00Z
+pub fn parse_ns_to_iso8601(timestamp: u64) -> Result<String, ParseError> {
+    DateTime::from_timestamp(
+        (timestamp / 1_000_000_000) as i64,
+        ((timestamp % 1_000_000_000) / 1_000) as u32,
+    )
+    .map(|dt| {
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            dt.date.year, dt.date.month, dt.date.day, dt.time.hour, dt.time.minute, dt.time.second,
+        )
+    })
+}
+
+/// Parse the value of a `datetime-local` input, in the browser's timezone, to unix seconds
+/// returns None outside the browser
+pub fn parse_local_datetime_to_secs(value: &str) -> Option<u64> {
+    #[cfg(feature = "hydrate")]
+    {
+        use wasm_bindgen::JsValue;
+        use web_sys::js_sys::Date;
+
+        let ms = Date::new(&JsValue::from_str(value)).get_time();
+        if ms.is_nan() || ms < 0.0 {
+            return None;
+        }
+        Some((ms / 1000.0) as u64)
+    }
+    #[cfg(not(feature = "hydrate"))]
+    {
+        _ = value;
+        None
+    }
+}
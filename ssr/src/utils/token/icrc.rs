@@ -0,0 +1,86 @@
+//! ICRC-1 accounts, i.e. a principal with an optional 32 byte subaccount
+//!
+//! The textual encoding is `<principal>-<checksum>.<subaccount hex>`,
+//! or just the principal for the default subaccount
+
+use candid::{Nat, Principal};
+use icrc_ledger_types::icrc::generic_value::Value;
+use serde_bytes::ByteBuf;
+use yral_canisters_client::{sns_index, sns_ledger};
+
+pub use icrc_ledger_types::icrc1::account::{Account as IcrcAccount, Subaccount};
+
+pub fn to_ledger_account(account: IcrcAccount) -> sns_ledger::Account {
+    sns_ledger::Account {
+        owner: account.owner,
+        subaccount: account.subaccount.map(|s| ByteBuf::from(s.to_vec())),
+    }
+}
+
+pub fn to_index_account(account: IcrcAccount) -> sns_index::Account {
+    sns_index::Account {
+        owner: account.owner,
+        subaccount: account.subaccount.map(|s| ByteBuf::from(s.to_vec())),
+    }
+}
+
+/// Subaccounts of invalid length are treated as the default subaccount
+pub fn subaccount_from_bytes(bytes: Option<ByteBuf>) -> Option<Subaccount> {
+    bytes.and_then(|b| b.into_vec().try_into().ok())
+}
+
+pub fn from_ledger_account(account: sns_ledger::Account) -> IcrcAccount {
+    IcrcAccount {
+        owner: account.owner,
+        subaccount: subaccount_from_bytes(account.subaccount),
+    }
+}
+
+pub fn from_index_account(account: sns_index::Account) -> IcrcAccount {
+    IcrcAccount {
+        owner: account.owner,
+        subaccount: subaccount_from_bytes(account.subaccount),
+    }
+}
+
+/// Hex encoded subaccount, as used in query params
+pub fn subaccount_to_hex(subaccount: &Subaccount) -> String {
+    hex::encode(subaccount)
+}
+
+pub fn subaccount_from_hex(subaccount: &str) -> Option<Subaccount> {
+    hex::decode(subaccount).ok()?.try_into().ok()
+}
+
+/// Field of an ICRC-3 map value, e.g. a block or its `tx`
+pub fn value_field<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    match value {
+        Value::Map(map) => map.get(key),
+        _ => None,
+    }
+}
+
+pub fn value_nat(value: &Value) -> Option<Nat> {
+    match value {
+        Value::Nat(n) => Some(n.clone()),
+        Value::Nat64(n) => Some(Nat::from(*n)),
+        _ => None,
+    }
+}
+
+/// ICRC-3 account value, the owner followed by an optional subaccount
+pub fn value_account(value: &Value) -> Option<IcrcAccount> {
+    let Value::Array(parts) = value else {
+        return None;
+    };
+    let owner = match parts.first()? {
+        Value::Blob(owner) => Principal::try_from_slice(owner).ok()?,
+        _ => return None,
+    };
+    let subaccount = match parts.get(1) {
+        Some(Value::Blob(subaccount)) => Some(subaccount.as_slice().try_into().ok()?),
+        Some(_) => return None,
+        None => None,
+    };
+    Some(IcrcAccount { owner, subaccount })
+}
@@ -1,5 +1,7 @@
 pub mod firestore;
 pub mod icpump;
+pub mod icrc;
+pub mod sns_config;
 
 use std::{
     cmp::Ordering,
@@ -16,12 +18,14 @@ use serde::{Deserialize, Serialize};
 
 use yral_canisters_client::{
     sns_governance::{DissolveState, GetMetadataArg, ListNeurons},
-    sns_ledger::{Account as LedgerAccount, MetadataValue},
+    sns_ledger::MetadataValue,
     sns_root::ListSnsCanistersArg,
 };
 
 use crate::state::canisters::Canisters;
 
+use self::icrc::{to_ledger_account, IcrcAccount};
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct TokenBalance {
     pub e8s: Nat,
@@ -270,7 +274,7 @@ pub struct TokenCans {
 
 pub async fn token_metadata_by_root<const A: bool>(
     cans: &Canisters<A>,
-    user_principal: Option<Principal>,
+    user_account: Option<IcrcAccount>,
     token_root: Principal,
 ) -> Result<Option<TokenMetadata>, ServerFnError> {
     // let user_principal = cans
@@ -286,14 +290,14 @@ pub async fn token_metadata_by_root<const A: bool>(
         return Ok(None);
     };
     let metadata =
-        get_token_metadata(cans, user_principal, token_root, governance, ledger, index).await?;
+        get_token_metadata(cans, user_account, token_root, governance, ledger, index).await?;
 
     Ok(Some(metadata))
 }
 
 pub async fn get_token_metadata<const A: bool>(
     cans: &Canisters<A>,
-    user_principal: Option<Principal>,
+    user_account: Option<IcrcAccount>,
     root: Principal,
     governance: Principal,
     ledger: Principal,
@@ -320,8 +324,8 @@ pub async fn get_token_metadata<const A: bool>(
         decimals,
     };
 
-    if let Some(user_principal) = user_principal {
-        let balance = get_token_balance(cans, user_principal, governance, ledger).await?;
+    if let Some(user_account) = user_account {
+        let balance = get_token_balance(cans, user_account, governance, ledger).await?;
         token_metadata.balance = Some(balance);
     }
 
@@ -330,7 +334,7 @@ pub async fn get_token_metadata<const A: bool>(
 
 pub async fn get_ck_metadata<const A: bool>(
     cans: Canisters<A>,
-    user_principal: Option<Principal>,
+    user_account: Option<IcrcAccount>,
     ledger: Principal,
     index: Principal,
 ) -> Result<Option<TokenMetadata>, AgentError> {
@@ -390,13 +394,10 @@ pub async fn get_ck_metadata<const A: bool>(
         decimals: decimals.clone().unwrap().0.to_u64_digits()[0] as u8,
     };
 
-    // If a user principal is provided, try to get the balance
-    if let Some(user_principal) = user_principal {
+    // If a user account is provided, try to get the balance
+    if let Some(user_account) = user_account {
         let balance = match ledger_can
-            .icrc_1_balance_of(yral_canisters_client::sns_ledger::Account {
-                owner: user_principal,
-                subaccount: None,
-            })
+            .icrc_1_balance_of(to_ledger_account(user_account))
             .await
         {
             Ok(balance) => balance,
@@ -415,21 +416,21 @@ pub async fn get_ck_metadata<const A: bool>(
 /// returns TokenBalanceOrClaiming::Claiming if the token creation is in progress
 async fn get_token_balance<const A: bool>(
     cans: &Canisters<A>,
-    user_principal: Principal,
+    user_account: IcrcAccount,
     governance: Principal,
     ledger: Principal,
 ) -> Result<TokenBalanceOrClaiming, AgentError> {
     let ledger = cans.sns_ledger(ledger).await;
-    let acc = LedgerAccount {
-        owner: user_principal,
-        subaccount: None,
-    };
     // Balance > 0 -> Token is already claimed
-    let balance_e8s = ledger.icrc_1_balance_of(acc).await?;
+    let balance_e8s = ledger
+        .icrc_1_balance_of(to_ledger_account(user_account))
+        .await?;
     let ready_balance = |e8s| Ok(TokenBalanceOrClaiming::new(TokenBalance::new_cdao(e8s)));
-    if balance_e8s > 0u8 {
+    // Claiming only credits the default subaccount
+    if balance_e8s > 0u8 || user_account.subaccount.is_some_and(|s| s != [0; 32]) {
         return ready_balance(balance_e8s);
     }
+    let user_principal = user_account.owner;
 
     // if balance is 0 we may not have completed claiming
     let governance = cans.sns_governance(governance).await;
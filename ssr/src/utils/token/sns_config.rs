@@ -0,0 +1,88 @@
+//! `sns_init.yaml` of tokens deployed through the app
+//!
+//! The config can't be recovered from the SNS canisters alone,
+//! so the one exported at creation is kept for its creator to download again.
+use candid::Principal;
+use leptos::{server, ServerFnError};
+
+use crate::state::canisters::CanistersAuthWire;
+
+/// Keep the config of a token the authenticated user just deployed
+#[server(
+    input = Cbor
+)]
+pub async fn save_token_config(
+    cans_wire: CanistersAuthWire,
+    root: Principal,
+    yaml: String,
+) -> Result<(), ServerFnError> {
+    server_impl::save_token_config(cans_wire, root, yaml).await
+}
+
+/// Config of a token, `None` if the authenticated user didn't deploy it or none was kept
+#[server(
+    input = Cbor
+)]
+pub async fn get_token_config(
+    cans_wire: CanistersAuthWire,
+    root: Principal,
+) -> Result<Option<String>, ServerFnError> {
+    server_impl::get_token_config(cans_wire, root).await
+}
+
+#[cfg(feature = "ssr")]
+mod server_impl {
+    use candid::Principal;
+    use leptos::{expect_context, ServerFnError};
+    use sns_validation::config::SnsConfigurationFile;
+
+    use crate::{
+        auth::server_impl::store::{KVStore, KVStoreImpl},
+        state::canisters::{Canisters, CanistersAuthWire},
+    };
+
+    fn config_key(root: Principal) -> String {
+        format!("token-config:{root}")
+    }
+
+    async fn is_creator(cans: &Canisters<true>, root: Principal) -> Result<bool, ServerFnError> {
+        let deployed = cans
+            .authenticated_user()
+            .await
+            .deployed_cdao_canisters()
+            .await?;
+
+        Ok(deployed.iter().any(|c| c.root == root))
+    }
+
+    pub async fn save_token_config(
+        cans_wire: CanistersAuthWire,
+        root: Principal,
+        yaml: String,
+    ) -> Result<(), ServerFnError> {
+        SnsConfigurationFile::from_yaml(&yaml)
+            .map_err(|e| ServerFnError::Args(format!("Invalid token config: {e}")))?;
+        let cans = cans_wire.canisters()?;
+        if !is_creator(&cans, root).await? {
+            return Err(ServerFnError::new("Token was not created by you"));
+        }
+
+        let kv: KVStoreImpl = expect_context();
+        kv.write(config_key(root), yaml).await?;
+
+        Ok(())
+    }
+
+    pub async fn get_token_config(
+        cans_wire: CanistersAuthWire,
+        root: Principal,
+    ) -> Result<Option<String>, ServerFnError> {
+        let cans = cans_wire.canisters()?;
+        if !is_creator(&cans, root).await? {
+            return Ok(None);
+        }
+
+        let kv: KVStoreImpl = expect_context();
+        Ok(kv.read(config_key(root)).await?)
+    }
+}
@@ -66,3 +66,29 @@ pub async fn paste_from_clipboard() -> Option<String> {
         None
     }
 }
+
+/// Save text content as a file download
+/// does nothing outside the browser
+pub fn download_text_file(file_name: &str, mime_type: &str, content: &str) {
+    #[cfg(not(feature = "hydrate"))]
+    {
+        _ = file_name;
+        _ = mime_type;
+        _ = content;
+    }
+    #[cfg(feature = "hydrate")]
+    {
+        use gloo::{
+            file::{Blob, ObjectUrl},
+            timers::callback::Timeout,
+        };
+
+        let url = ObjectUrl::from(Blob::new_with_options(content, Some(mime_type)));
+        let anchor = leptos::html::a();
+        anchor.set_href(&url);
+        anchor.set_download(file_name);
+        anchor.click();
+        // Revoking the url right after the click races the download in some browsers
+        Timeout::new(60_000, move || drop(url)).forget();
+    }
+}